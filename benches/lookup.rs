@@ -0,0 +1,56 @@
+//! Lexicon lookups and whole-document G2P on a long text.
+//!
+//! Run with `cargo bench --bench lookup`.
+
+use misaki_rs::lexicon::{Lexicon, TokenContext};
+use misaki_rs::{G2P, Language};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const PARAGRAPH: &str = "The committee met on Tuesday to review the proposal. It was used to \
+    estimate the costs of running the NASA program, and an iPhone app reported the results. \
+    Members of the House said they'd read the report, but the FBI declined to comment. \
+    Walking through the park, Dr. Smith noticed that the leaves had turned and the children \
+    were playing happily in the warm afternoon sun.";
+
+/// Runs `f` for at least a second and returns the mean time per run.
+fn time(mut f: impl FnMut()) -> Duration {
+    f();
+    let start = Instant::now();
+    let mut runs = 0u32;
+    while start.elapsed() < Duration::from_secs(1) {
+        f();
+        runs += 1;
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    let lexicon = Lexicon::new(Language::EnglishUS);
+    let words: Vec<&str> = PARAGRAPH
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\''))
+        .collect();
+    let ctx = TokenContext { future_vowel: Some(false), ..Default::default() };
+
+    let per_pass = time(|| {
+        for word in &words {
+            black_box(lexicon.is_known(black_box(word), "NN"));
+        }
+    });
+    println!("is_known   {:>8.1} ns/word", per_pass.as_nanos() as f64 / words.len() as f64);
+
+    let per_pass = time(|| {
+        for word in &words {
+            black_box(lexicon.get_word(black_box(word), "NN", None, Some(&ctx)));
+        }
+    });
+    println!("get_word   {:>8.1} ns/word", per_pass.as_nanos() as f64 / words.len() as f64);
+
+    let g2p = G2P::new(Language::EnglishUS);
+    let document = PARAGRAPH.repeat(20);
+    let per_doc = time(|| {
+        black_box(g2p.g2p(black_box(&document)).unwrap());
+    });
+    println!("g2p        {:>8.1} µs/word", per_doc.as_secs_f64() * 1e6 / (words.len() * 20) as f64);
+}
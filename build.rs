@@ -0,0 +1,50 @@
+//! Converts the bundled tagger model from JSON to the compact binary form
+//! `PerceptronTagger::english` loads, and with `capi` generates the C header
+//! for `capi`.
+
+#[allow(dead_code)]
+#[path = "src/tagger_format.rs"]
+mod tagger_format;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::{env, fs};
+
+const TAGGER_DIR: &str = "src/resources/tagger";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", TAGGER_DIR);
+    println!("cargo:rerun-if-changed=src/tagger_format.rs");
+
+    let read = |name: &str| fs::read_to_string(format!("{}/{}", TAGGER_DIR, name)).expect("tagger resource");
+    let weights: tagger_format::Weights = serde_json::from_str(&read("weights.json")).expect("weights.json");
+    let classes: Vec<String> = read("classes.txt").lines().map(|s| s.trim().to_string()).collect();
+    let tags: HashMap<String, String> = serde_json::from_str(&read("tags.json")).expect("tags.json");
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("tagger.bin");
+    fs::write(out, tagger_format::encode(&weights, &classes, &tags)).expect("write tagger.bin");
+
+    #[cfg(feature = "capi")]
+    write_header();
+}
+
+/// Writes `misaki.h` for `src/capi.rs` to OUT_DIR; `capi`'s tests check that
+/// `include/misaki.h` matches it.
+#[cfg(feature = "capi")]
+fn write_header() {
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    let config = cbindgen::Config {
+        language: cbindgen::Language::C,
+        include_guard: Some("MISAKI_H".to_string()),
+        cpp_compat: true,
+        autogen_warning: Some("/* Generated by cbindgen from src/capi.rs; do not edit. */".to_string()),
+        ..Default::default()
+    };
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("misaki.h");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src("src/capi.rs")
+        .generate()
+        .expect("generate misaki.h")
+        .write_to_file(out);
+}
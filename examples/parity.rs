@@ -0,0 +1,39 @@
+use misaki_rs::parity;
+use misaki_rs::{G2P, G2POptions, Language, PauseMarkers, TokenizerKind};
+use std::process::ExitCode;
+
+// Usage: cargo run --example parity --features parity -- corpus.jsonl [--gb]
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(path) = args.iter().find(|a| !a.starts_with("--")) else {
+        eprintln!("usage: parity <corpus.jsonl> [--gb]");
+        return ExitCode::FAILURE;
+    };
+    let lang = if args.iter().any(|a| a == "--gb") { Language::EnglishGB } else { Language::EnglishUS };
+
+    // Closest to Python misaki: spaCy tokenization and punctuation kept
+    let options = G2POptions {
+        tokenizer: TokenizerKind::SpacyCompat,
+        pause_markers: Some(PauseMarkers::default()),
+        ..Default::default()
+    };
+    let g2p = G2P::with_options(lang, options);
+
+    let cases = match parity::load_corpus(path) {
+        Ok(cases) => cases,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match parity::run(&g2p, &cases) {
+        Ok(report) => {
+            println!("{}", report);
+            if report.mismatches.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,108 @@
+#ifndef MISAKI_H
+#define MISAKI_H
+
+/* Generated by cbindgen from src/capi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A G2P for one language.
+ */
+typedef struct MisakiG2P MisakiG2P;
+
+/**
+ * The outcome of `misaki_g2p_phonemize`: phonemes and JSON, or an error.
+ */
+typedef struct MisakiResult MisakiResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * A G2P for a language code such as "en-us" or "en-gb", or null if the
+ * code is unknown or loading the language failed.
+ *
+ * # Safety
+ *
+ * `language` must be null or a NUL-terminated string.
+ */
+struct MisakiG2P *misaki_g2p_new(const char *language);
+
+/**
+ * Frees a G2P. Null is ignored.
+ *
+ * # Safety
+ *
+ * `g2p` must be null or come from `misaki_g2p_new`, and not be used after.
+ */
+void misaki_g2p_free(struct MisakiG2P *g2p);
+
+/**
+ * Adds the entries of a JSON dictionary to the G2P's user layer (see
+ * `Lexicon::load_user_dictionary`). Returns 0, or -1 if `json` isn't a
+ * valid dictionary.
+ *
+ * # Safety
+ *
+ * `g2p` must come from `misaki_g2p_new`, and `json` be null or a
+ * NUL-terminated string.
+ */
+int32_t misaki_g2p_load_dictionary(struct MisakiG2P *g2p, const char *json);
+
+/**
+ * Phonemizes `text`. Never null: check `misaki_result_error` first.
+ *
+ * # Safety
+ *
+ * `g2p` must come from `misaki_g2p_new`, and `text` be null or a
+ * NUL-terminated string.
+ */
+struct MisakiResult *misaki_g2p_phonemize(const struct MisakiG2P *g2p, const char *text);
+
+/**
+ * The phonemes, or null if phonemizing failed.
+ *
+ * # Safety
+ *
+ * `result` must come from `misaki_g2p_phonemize`.
+ */
+const char *misaki_result_phonemes(const struct MisakiResult *result);
+
+/**
+ * The whole `G2PResult` as JSON (tokens, chunks, warnings), or null if
+ * phonemizing failed.
+ *
+ * # Safety
+ *
+ * `result` must come from `misaki_g2p_phonemize`.
+ */
+const char *misaki_result_json(const struct MisakiResult *result);
+
+/**
+ * Why phonemizing failed, or null if it didn't.
+ *
+ * # Safety
+ *
+ * `result` must come from `misaki_g2p_phonemize`.
+ */
+const char *misaki_result_error(const struct MisakiResult *result);
+
+/**
+ * Frees a result and its strings. Null is ignored.
+ *
+ * # Safety
+ *
+ * `result` must be null or come from `misaki_g2p_phonemize`, and neither
+ * it nor its strings be used after.
+ */
+void misaki_result_free(struct MisakiResult *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MISAKI_H */
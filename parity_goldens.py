@@ -0,0 +1,28 @@
+import argparse
+import json
+import sys
+
+# Writes a parity corpus for `cargo run --example parity --features parity`:
+# one {"text", "phonemes"} JSON object per input line, phonemized by Python
+# misaki. Needs `pip install misaki[en]`.
+
+def main():
+    parser = argparse.ArgumentParser(description="Generate misaki parity goldens")
+    parser.add_argument("texts", help="file with one text per line")
+    parser.add_argument("--gb", action="store_true", help="British English")
+    args = parser.parse_args()
+
+    from misaki import en
+    # No fallback, so words missing from the lexicons come out as ❓ and stand out in the report
+    g2p = en.G2P(trf=False, british=args.gb, fallback=None)
+
+    with open(args.texts, encoding="utf-8") as f:
+        for line in f:
+            text = line.strip()
+            if not text:
+                continue
+            phonemes, _ = g2p(text)
+            print(json.dumps({"text": text, "phonemes": phonemes}, ensure_ascii=False))
+
+if __name__ == "__main__":
+    sys.exit(main())
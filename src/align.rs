@@ -0,0 +1,148 @@
+use crate::token::MToken;
+use serde::Serialize;
+
+/// One phoneme of G2P output and the source characters it was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PhonemeAlignment {
+    pub phoneme: char,
+    /// Char index of the phoneme in the phoneme string.
+    pub index: usize,
+    /// Char offsets `(start, end)` in the preprocessed text, within the span
+    /// of the phoneme's token.
+    pub char_span: (usize, usize),
+}
+
+/// Maps each phoneme of `tokens` (as joined by `G2P::g2p`, with `unk` for
+/// tokens left without phonemes) to the characters it was read from.
+///
+/// Best-effort: a token's phonemes are spread evenly over its characters.
+/// Stress marks go with the phoneme after them, and length marks and ties
+/// with the one before. Whitespace gets no entry.
+pub fn align(tokens: &[MToken], unk: &str) -> Vec<PhonemeAlignment> {
+    let mut alignment = Vec::new();
+    let mut index = 0;
+    for tk in tokens {
+        let phonemes = tk.phonemes.as_deref().unwrap_or(unk);
+        let segments = phonemes.chars().filter(|&c| is_segment(c)).count();
+        let (start, end) = tk.char_span;
+        let len = end - start;
+        let mut seen = 0;
+        for c in phonemes.chars() {
+            let ordinal = if is_segment(c) {
+                seen += 1;
+                seen - 1
+            } else if matches!(c, 'ˈ' | 'ˌ') {
+                seen.min(segments.saturating_sub(1))
+            } else {
+                seen.saturating_sub(1)
+            };
+            if !c.is_whitespace() && segments > 0 {
+                let from = start + ordinal * len / segments;
+                let to = (start + (ordinal + 1) * len / segments).max(from + 1).min(end);
+                alignment.push(PhonemeAlignment {
+                    phoneme: c,
+                    index,
+                    char_span: (from, to),
+                });
+            }
+            index += 1;
+        }
+        index += tk.phoneme_space().len();
+    }
+    alignment
+}
+
+/// Copies phoneme timings computed elsewhere (TTS attention, a forced
+/// aligner) onto `tokens`, filling `start_ts`/`end_ts`. `timings` are
+/// `(phonemes, start, end)` in order, in the same symbols as the tokens'
+/// phonemes; each may hold one or several characters, and whitespace entries
+/// are skipped. A token spans the entries covering its phoneme characters;
+/// tokens without phonemes get a zero-length span where they fall, and tokens
+/// past the end of `timings` are left unset.
+pub fn apply_alignment(tokens: &mut [MToken], timings: &[(String, f64, f64)]) {
+    let mut timings = timings.iter().filter(|(ps, _, _)| !ps.trim().is_empty()).peekable();
+    let mut last_end = None;
+    for tk in tokens {
+        let mut needed = tk.phonemes.as_deref().unwrap_or("").chars().filter(|c| !c.is_whitespace()).count();
+        if needed == 0 {
+            tk.start_ts = last_end.or(timings.peek().map(|t| t.1));
+            tk.end_ts = tk.start_ts;
+            continue;
+        }
+        let (mut start, mut end) = (None, None);
+        while needed > 0 {
+            let Some((ps, s, e)) = timings.next() else {
+                break;
+            };
+            start = start.or(Some(*s));
+            end = Some(*e);
+            needed = needed.saturating_sub(ps.chars().filter(|c| !c.is_whitespace()).count());
+        }
+        tk.start_ts = start;
+        tk.end_ts = end;
+        last_end = end.or(last_end);
+    }
+}
+
+/// Whether `c` is a sound of its own rather than a mark on a neighbouring one.
+fn is_segment(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, 'ˈ' | 'ˌ' | 'ː' | '\u{200D}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, phonemes: &str, char_span: (usize, usize)) -> MToken {
+        MToken { char_span, ..MToken::read_as(text, phonemes) }
+    }
+
+    #[test]
+    fn test_align() {
+        let tokens = [token("cat", "kˈæt", (0, 3)), token("sat", "sˈæt", (4, 7))];
+        let alignment = align(&tokens, "❓");
+        let spans: Vec<(char, usize, (usize, usize))> =
+            alignment.iter().map(|a| (a.phoneme, a.index, a.char_span)).collect();
+        assert_eq!(
+            spans,
+            [
+                ('k', 0, (0, 1)),
+                ('ˈ', 1, (1, 2)),
+                ('æ', 2, (1, 2)),
+                ('t', 3, (2, 3)),
+                ('s', 5, (4, 5)),
+                ('ˈ', 6, (5, 6)),
+                ('æ', 7, (5, 6)),
+                ('t', 8, (6, 7)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_alignment() {
+        let mut tokens = [token("cat", "kˈæt", (0, 3)), token(",", " ", (3, 4)), token("sat", "sˈæt", (5, 8))];
+        let timings: Vec<(String, f64, f64)> = [("k", 0.0, 0.1), ("ˈæ", 0.1, 0.2), ("t", 0.2, 0.3), (" ", 0.3, 0.5)]
+            .into_iter()
+            .chain([("s", 0.5, 0.6), ("ˈ", 0.6, 0.6), ("æ", 0.6, 0.7), ("t", 0.7, 0.8)])
+            .map(|(p, s, e)| (p.to_string(), s, e))
+            .collect();
+        apply_alignment(&mut tokens, &timings);
+        let spans: Vec<_> = tokens.iter().map(|t| (t.start_ts, t.end_ts)).collect();
+        assert_eq!(
+            spans,
+            [(Some(0.0), Some(0.3)), (Some(0.3), Some(0.3)), (Some(0.5), Some(0.8))]
+        );
+
+        // Timings that stop early leave the rest unset
+        apply_alignment(&mut tokens, &timings[..3]);
+        assert_eq!((tokens[2].start_ts, tokens[2].end_ts), (None, None));
+    }
+
+    #[test]
+    fn test_align_uneven() {
+        // More phonemes than letters: each still points into the token
+        let alignment = align(&[token("I", "a‍ɪ", (0, 1))], "❓");
+        assert_eq!(alignment.len(), 3);
+        assert!(alignment.iter().all(|a| a.char_span == (0, 1)));
+    }
+}
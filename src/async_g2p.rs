@@ -0,0 +1,121 @@
+//! G2P for tokio services: calls run on the runtime's blocking threads,
+//! at most a fixed number at a time, so a large input never stalls the
+//! async workers.
+
+use crate::g2p::{G2P, G2PError};
+use crate::options::G2POptions;
+use crate::result::G2PResult;
+use crate::token::MToken;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// A `G2P` shared by a pool of blocking workers. Calls beyond the pool's
+/// size wait for a worker to come free, which is the backpressure: a
+/// handler that would rather refuse work than queue it checks `available`
+/// first. Cloning shares the G2P and the pool.
+#[derive(Clone)]
+pub struct AsyncG2P {
+    g2p: Arc<G2P>,
+    workers: Arc<Semaphore>,
+}
+
+impl AsyncG2P {
+    /// Runs `g2p` on up to `workers` blocking threads at once.
+    ///
+    /// Panics if `workers` is 0.
+    pub fn new(g2p: G2P, workers: usize) -> Self {
+        assert!(workers > 0, "AsyncG2P needs at least one worker");
+        Self { g2p: Arc::new(g2p), workers: Arc::new(Semaphore::new(workers)) }
+    }
+
+    /// Runs `g2p` on this one's workers, so that the two together never
+    /// run more calls at once than it alone would.
+    pub fn sharing_workers(&self, g2p: G2P) -> Self {
+        Self { g2p: Arc::new(g2p), workers: Arc::clone(&self.workers) }
+    }
+
+    /// Builds a G2P on one of this one's workers, which loading a lexicon
+    /// keeps busy as a call would, then runs it on them as `sharing_workers`.
+    pub async fn build_sharing_workers(&self, build: impl FnOnce() -> G2P + Send + 'static) -> Self {
+        let g2p = self.run(move |_| build()).await;
+        self.sharing_workers(g2p)
+    }
+
+    /// The shared G2P, for its lexicon and options.
+    pub fn inner(&self) -> &G2P {
+        &self.g2p
+    }
+
+    /// Workers free to start a call right away.
+    pub fn available(&self) -> usize {
+        self.workers.available_permits()
+    }
+
+    /// `G2P::g2p` on a worker.
+    pub async fn g2p(&self, text: impl Into<String>) -> Result<(String, Vec<MToken>), G2PError> {
+        let text = text.into();
+        self.run(move |g2p| g2p.g2p(&text)).await
+    }
+
+    /// `G2P::analyze` on a worker.
+    pub async fn analyze(&self, text: impl Into<String>) -> Result<G2PResult, G2PError> {
+        let text = text.into();
+        self.run(move |g2p| g2p.analyze(&text)).await
+    }
+
+    /// `G2P::analyze_with` on a worker, so that one G2P serves callers
+    /// wanting different options.
+    pub async fn analyze_with(&self, text: impl Into<String>, options: G2POptions) -> Result<G2PResult, G2PError> {
+        let text = text.into();
+        self.run(move |g2p| g2p.analyze_with(&text, &options)).await
+    }
+
+    async fn run<T: Send + 'static>(&self, f: impl FnOnce(&G2P) -> T + Send + 'static) -> T {
+        let permit = Arc::clone(&self.workers).acquire_owned().await.expect("the semaphore is never closed");
+        let g2p = Arc::clone(&self.g2p);
+        let job = tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            f(&g2p)
+        });
+        match job.await {
+            Ok(result) => result,
+            // A panic in G2P is the caller's, as it would be called directly
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language;
+
+    #[test]
+    fn test_async_g2p() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let g2p = AsyncG2P::new(G2P::new(Language::EnglishUS), 2);
+        let (expected, _) = g2p.inner().g2p("The cat sat on the mat.").unwrap();
+
+        runtime.block_on(async {
+            let jobs: Vec<_> = (0..4)
+                .map(|_| {
+                    let g2p = g2p.clone();
+                    tokio::spawn(async move { g2p.g2p("The cat sat on the mat.").await })
+                })
+                .collect();
+            for job in jobs {
+                assert_eq!(job.await.unwrap().unwrap().0, expected);
+            }
+            let result = g2p.analyze("Hello world").await.unwrap();
+            assert_eq!(result.text, "Hello world");
+            let options = G2POptions { output_format: crate::options::OutputFormat::XSampa, ..G2POptions::default() };
+            let result = g2p.analyze_with("cat", options.clone()).await.unwrap();
+            assert_eq!(result.phonemes, g2p.inner().g2p_with("cat", &options).unwrap().0);
+
+            let gb = g2p.build_sharing_workers(|| G2P::new(Language::EnglishGB)).await;
+            assert_eq!(gb.inner().lexicon.lang, Language::EnglishGB);
+            assert!(Arc::ptr_eq(&gb.workers, &g2p.workers));
+        });
+        assert_eq!(g2p.available(), 2);
+    }
+}
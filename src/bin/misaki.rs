@@ -0,0 +1,197 @@
+//! The pipeline from the shell: phonemes, tokens and tags of the files given
+//! or of stdin, and lexicon lookups.
+//!
+//! ```text
+//! misaki phonemize [FILE...]    phonemes, a sentence per line
+//! misaki tokenize [FILE...]     tokens, an input line per line
+//! misaki tag [FILE...]          word/TAG pairs, a sentence per line
+//! misaki lookup WORD            the lexicon's reading of WORD
+//! misaki serve [ADDR]           the HTTP service (feature `server`), on
+//!                               127.0.0.1:8080 unless ADDR is given
+//! ```
+//!
+//! `--lang CODE` picks the language ("en-us", "en-gb", ...), `--tag TAG` the
+//! tag `lookup` reads the word as, and `--json` or `--jsonl` print the
+//! records (with every token's metadata) as one JSON array or a line each.
+
+use misaki_rs::{G2P, Language, MToken, Pronunciation};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::ExitCode;
+
+const USAGE: &str =
+    "usage: misaki <phonemize|tokenize|tag|lookup WORD|serve [ADDR]> [FILE...] [--lang CODE] [--tag TAG] [--json|--jsonl]";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Jsonl,
+}
+
+struct Args {
+    command: String,
+    /// Files, or for `lookup` the word.
+    operands: Vec<String>,
+    lang: Language,
+    tag: String,
+    format: Format,
+}
+
+/// A line of `tokenize` output.
+#[derive(Serialize)]
+struct Line {
+    text: String,
+    tokens: Vec<MToken>,
+}
+
+/// The output of `lookup`.
+#[derive(Serialize)]
+struct Lookup<'a> {
+    word: &'a str,
+    tag: &'a str,
+    pronunciation: Option<Pronunciation<'a>>,
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = args.next().ok_or(USAGE)?;
+    let mut parsed = Args {
+        command,
+        operands: Vec::new(),
+        lang: Language::EnglishUS,
+        tag: "NN".to_string(),
+        format: Format::Text,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => parsed.format = Format::Json,
+            "--jsonl" => parsed.format = Format::Jsonl,
+            "--lang" => {
+                let code = args.next().ok_or("--lang needs a language code")?;
+                parsed.lang = Language::from_code(&code).ok_or(format!("unknown language '{}'", code))?;
+            }
+            "--tag" => parsed.tag = args.next().ok_or("--tag needs a tag")?,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option '{}'\n{}", arg, USAGE)),
+            _ => parsed.operands.push(arg),
+        }
+    }
+    Ok(parsed)
+}
+
+/// The files named, or stdin if none are.
+fn inputs(files: &[String]) -> io::Result<Vec<Box<dyn BufRead>>> {
+    if files.is_empty() {
+        return Ok(vec![Box::new(io::stdin().lock())]);
+    }
+    files.iter().map(|path| Ok(Box::new(BufReader::new(File::open(path)?)) as Box<dyn BufRead>)).collect()
+}
+
+/// Prints records as `format` says, `text` giving the plain form.
+struct Printer<W: Write> {
+    out: W,
+    format: Format,
+    json: Vec<serde_json::Value>,
+}
+
+impl<W: Write> Printer<W> {
+    fn print<T: Serialize>(&mut self, record: &T, text: impl FnOnce() -> String) -> Result<(), String> {
+        let result = match self.format {
+            Format::Text => writeln!(self.out, "{}", text()),
+            Format::Jsonl => writeln!(self.out, "{}", serde_json::to_string(record).map_err(|e| e.to_string())?),
+            Format::Json => {
+                self.json.push(serde_json::to_value(record).map_err(|e| e.to_string())?);
+                Ok(())
+            }
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    fn finish(mut self) -> Result<(), String> {
+        if self.format == Format::Json {
+            let json = serde_json::to_string_pretty(&self.json).map_err(|e| e.to_string())?;
+            writeln!(self.out, "{}", json).map_err(|e| e.to_string())?;
+        }
+        self.out.flush().map_err(|e| e.to_string())
+    }
+}
+
+fn run(args: Args) -> Result<ExitCode, String> {
+    #[cfg(feature = "server")]
+    if args.command == "serve" {
+        let addr = args.operands.first().map_or("127.0.0.1:8080", String::as_str);
+        serve(addr, args.lang).map_err(|e| e.to_string())?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let g2p = G2P::new(args.lang.clone());
+    let mut printer = Printer { out: io::stdout().lock(), format: args.format, json: Vec::new() };
+    let mut code = ExitCode::SUCCESS;
+
+    match args.command.as_str() {
+        "phonemize" | "tag" => {
+            let tag = args.command == "tag";
+            for input in inputs(&args.operands).map_err(|e| e.to_string())? {
+                for sentence in g2p.g2p_stream(input) {
+                    let sentence = sentence.map_err(|e| e.to_string())?;
+                    printer.print(&sentence, || {
+                        if tag {
+                            sentence.tokens.iter().map(|tk| format!("{}/{}", tk.text, tk.tag)).collect::<Vec<_>>().join(" ")
+                        } else {
+                            sentence.phonemes.trim_end().to_string()
+                        }
+                    })?;
+                }
+            }
+        }
+        "tokenize" => {
+            for input in inputs(&args.operands).map_err(|e| e.to_string())? {
+                for text in input.lines() {
+                    let text = text.map_err(|e| e.to_string())?;
+                    let line = Line { tokens: g2p.tokenize(&text), text };
+                    printer.print(&line, || line.tokens.iter().map(|tk| tk.text.as_str()).collect::<Vec<_>>().join(" "))?;
+                }
+            }
+        }
+        "lookup" => {
+            let [word] = args.operands.as_slice() else {
+                return Err(format!("lookup takes one word\n{}", USAGE));
+            };
+            let pronunciation = g2p.lexicon.get_word(word, &args.tag, None, None);
+            if pronunciation.is_none() {
+                code = ExitCode::FAILURE;
+            }
+            let lookup = Lookup { word, tag: &args.tag, pronunciation };
+            printer.print(&lookup, || match &lookup.pronunciation {
+                Some(p) => format!("{}\t{:?}\t{:?}", p.phonemes, p.rating, p.source),
+                None => format!("'{}' is not in the lexicon", word),
+            })?;
+        }
+        command => return Err(format!("unknown command '{}'\n{}", command, USAGE)),
+    }
+    printer.finish()?;
+    Ok(code)
+}
+
+/// Serves `server::router` on `addr` with a worker per CPU.
+#[cfg(feature = "server")]
+fn serve(addr: &str, lang: Language) -> io::Result<()> {
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_io().build()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        eprintln!("misaki: serving on http://{}", listener.local_addr()?);
+        axum::serve(listener, misaki_rs::server::router(lang, workers)).await
+    })
+}
+
+fn main() -> ExitCode {
+    match parse(std::env::args().skip(1)).and_then(run) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("misaki: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
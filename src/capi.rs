@@ -0,0 +1,236 @@
+//! A C interface, for engines and TTS integrations outside Rust. The
+//! header is `include/misaki.h`, generated from this file by cbindgen; link
+//! the library built with `cargo rustc --release --lib --crate-type cdylib
+//! --features capi`.
+//!
+//! Strings in and out are NUL-terminated UTF-8. Every `MisakiG2P` and
+//! `MisakiResult` returned must be freed with its `_free` function, and the
+//! strings a result hands out live as long as it does. A panic never
+//! unwinds into C: it comes back as the function's failure value.
+
+use crate::g2p::G2P;
+use crate::language::Language;
+use std::any::Any;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// A G2P for one language.
+pub struct MisakiG2P(G2P);
+
+/// The outcome of `misaki_g2p_phonemize`: phonemes and JSON, or an error.
+pub struct MisakiResult {
+    phonemes: Option<CString>,
+    json: Option<CString>,
+    error: Option<CString>,
+}
+
+impl MisakiResult {
+    fn error(message: impl Into<String>) -> *mut MisakiResult {
+        let error = CString::new(message.into().replace('\0', "")).ok();
+        Box::into_raw(Box::new(MisakiResult { phonemes: None, json: None, error }))
+    }
+}
+
+/// `ptr` as a `&str`, if it is non-null UTF-8.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string.
+unsafe fn str_arg<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(ptr) }.to_str().ok()
+}
+
+/// The message of a caught panic.
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    let message = match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic.downcast_ref::<&str>().map_or("unknown panic", |s| s).to_string(),
+    };
+    format!("panicked: {}", message)
+}
+
+fn as_ptr(s: &Option<CString>) -> *const c_char {
+    s.as_ref().map_or(ptr::null(), |s| s.as_ptr())
+}
+
+/// A G2P for a language code such as "en-us" or "en-gb", or null if the
+/// code is unknown or loading the language failed.
+///
+/// # Safety
+///
+/// `language` must be null or a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_g2p_new(language: *const c_char) -> *mut MisakiG2P {
+    let lang = match unsafe { str_arg(language) }.and_then(Language::from_code) {
+        Some(lang) => lang,
+        None => return ptr::null_mut(),
+    };
+    match panic::catch_unwind(|| G2P::new(lang)) {
+        Ok(g2p) => Box::into_raw(Box::new(MisakiG2P(g2p))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Frees a G2P. Null is ignored.
+///
+/// # Safety
+///
+/// `g2p` must be null or come from `misaki_g2p_new`, and not be used after.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_g2p_free(g2p: *mut MisakiG2P) {
+    if !g2p.is_null() {
+        drop(unsafe { Box::from_raw(g2p) });
+    }
+}
+
+/// Adds the entries of a JSON dictionary to the G2P's user layer (see
+/// `Lexicon::load_user_dictionary`). Returns 0, or -1 if `json` isn't a
+/// valid dictionary.
+///
+/// # Safety
+///
+/// `g2p` must come from `misaki_g2p_new`, and `json` be null or a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_g2p_load_dictionary(g2p: *mut MisakiG2P, json: *const c_char) -> i32 {
+    let (Some(g2p), Some(json)) = (unsafe { g2p.as_mut() }, unsafe { str_arg(json) }) else {
+        return -1;
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| g2p.0.lexicon.load_user_dictionary_json(json))) {
+        Ok(Ok(())) => 0,
+        _ => -1,
+    }
+}
+
+/// Phonemizes `text`. Never null: check `misaki_result_error` first.
+///
+/// # Safety
+///
+/// `g2p` must come from `misaki_g2p_new`, and `text` be null or a
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_g2p_phonemize(g2p: *const MisakiG2P, text: *const c_char) -> *mut MisakiResult {
+    let Some(g2p) = (unsafe { g2p.as_ref() }) else {
+        return MisakiResult::error("g2p is null");
+    };
+    let Some(text) = (unsafe { str_arg(text) }) else {
+        return MisakiResult::error("text is null or not UTF-8");
+    };
+    let result = match panic::catch_unwind(AssertUnwindSafe(|| g2p.0.analyze(text))) {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => return MisakiResult::error(e.to_string()),
+        Err(panic) => return MisakiResult::error(panic_message(panic)),
+    };
+    let json = serde_json::to_string(&result).ok().and_then(|json| CString::new(json).ok());
+    let phonemes = CString::new(result.phonemes).ok();
+    Box::into_raw(Box::new(MisakiResult { phonemes, json, error: None }))
+}
+
+/// The phonemes, or null if phonemizing failed.
+///
+/// # Safety
+///
+/// `result` must come from `misaki_g2p_phonemize`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_result_phonemes(result: *const MisakiResult) -> *const c_char {
+    unsafe { result.as_ref() }.map_or(ptr::null(), |r| as_ptr(&r.phonemes))
+}
+
+/// The whole `G2PResult` as JSON (tokens, chunks, warnings), or null if
+/// phonemizing failed.
+///
+/// # Safety
+///
+/// `result` must come from `misaki_g2p_phonemize`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_result_json(result: *const MisakiResult) -> *const c_char {
+    unsafe { result.as_ref() }.map_or(ptr::null(), |r| as_ptr(&r.json))
+}
+
+/// Why phonemizing failed, or null if it didn't.
+///
+/// # Safety
+///
+/// `result` must come from `misaki_g2p_phonemize`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_result_error(result: *const MisakiResult) -> *const c_char {
+    unsafe { result.as_ref() }.map_or(ptr::null(), |r| as_ptr(&r.error))
+}
+
+/// Frees a result and its strings. Null is ignored.
+///
+/// # Safety
+///
+/// `result` must be null or come from `misaki_g2p_phonemize`, and neither
+/// it nor its strings be used after.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn misaki_result_free(result: *mut MisakiResult) {
+    if !result.is_null() {
+        drop(unsafe { Box::from_raw(result) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capi() {
+        let read = |ptr: *const c_char| unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        unsafe {
+            assert!(misaki_g2p_new(c"xx".as_ptr()).is_null());
+            let g2p = misaki_g2p_new(c"en-us".as_ptr());
+            assert!(!g2p.is_null());
+
+            let result = misaki_g2p_phonemize(g2p, c"The cat.".as_ptr());
+            assert!(misaki_result_error(result).is_null());
+            assert_eq!(read(misaki_result_phonemes(result)), G2P::new(Language::EnglishUS).g2p("The cat.").unwrap().0);
+            assert!(read(misaki_result_json(result)).contains("\"tokens\""));
+            misaki_result_free(result);
+
+            assert_eq!(misaki_g2p_load_dictionary(g2p, c"{\"zorblat\": \"zˈɔɹblæt\"}".as_ptr()), 0);
+            assert_eq!(misaki_g2p_load_dictionary(g2p, c"not json".as_ptr()), -1);
+            let result = misaki_g2p_phonemize(g2p, c"zorblat".as_ptr());
+            assert_eq!(read(misaki_result_phonemes(result)), "zˈɔɹblæt");
+            misaki_result_free(result);
+
+            let result = misaki_g2p_phonemize(g2p, ptr::null());
+            assert!(misaki_result_phonemes(result).is_null());
+            assert_eq!(read(misaki_result_error(result)), "text is null or not UTF-8");
+            misaki_result_free(result);
+            misaki_g2p_free(g2p);
+        }
+    }
+
+    #[test]
+    fn test_capi_panic() {
+        use crate::pipeline::{BuiltinStage, PipelineCtx};
+        use crate::token::MToken;
+        let g2p = G2P::builder(Language::EnglishUS)
+            .stage(BuiltinStage::Tag, |_: &mut Vec<MToken>, _: &PipelineCtx| panic!("bad stage"))
+            .build();
+        let g2p = Box::into_raw(Box::new(MisakiG2P(g2p)));
+        unsafe {
+            let result = misaki_g2p_phonemize(g2p, c"The cat.".as_ptr());
+            assert!(misaki_result_phonemes(result).is_null());
+            let error = CStr::from_ptr(misaki_result_error(result)).to_str().unwrap();
+            assert_eq!(error, "panicked: bad stage");
+            misaki_result_free(result);
+            misaki_g2p_free(g2p);
+        }
+    }
+
+    #[test]
+    fn test_header_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/misaki.h"));
+        assert_eq!(
+            include_str!("../include/misaki.h"),
+            generated,
+            "include/misaki.h is stale: copy the one build.rs wrote to OUT_DIR over it"
+        );
+    }
+}
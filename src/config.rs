@@ -0,0 +1,234 @@
+//! G2Ps described by a TOML file (see `G2P::from_config`), so a deployment
+//! can be tuned without rebuilding it:
+//!
+//! ```toml
+//! language = "en-gb"
+//! dialect = "australian"
+//! # Tried in order; [] spells unknown words out
+//! fallback = ["espeak", "lts"]
+//! # User dictionaries, relative to this file
+//! lexicons = ["names.json"]
+//!
+//! [options]
+//! unicode_form = "nfkc"
+//! read_addresses = false
+//! oov_policy = { mark = "<unk>" }
+//! ```
+//!
+//! `[options]` holds any `G2POptions` field, by name; those not given keep
+//! their defaults.
+
+use crate::fallback::{Fallback, FallbackChain};
+use crate::g2p::{G2P, G2PBuilder};
+use crate::language::{Dialect, Language};
+use crate::lexicon::LexiconError;
+use crate::lts::LtsFallback;
+use crate::options::G2POptions;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("failed to read config: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid config: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("unknown language '{0}'")]
+    UnknownLanguage(String),
+    #[error("unknown dialect '{0}'")]
+    UnknownDialect(String),
+    #[error("fallback needs the '{0}' feature")]
+    MissingFeature(&'static str),
+    #[error("fallback '{name}' failed to load: {error}")]
+    Fallback { name: &'static str, error: String },
+    #[error("failed to load lexicon '{}': {error}", path.display())]
+    Lexicon { path: PathBuf, error: LexiconError },
+}
+
+/// A fallback named in `fallback`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackConfig {
+    /// `LtsFallback`, in the dialect's British or American phonemes.
+    Lts,
+    /// `EspeakFallback` in the dialect's voice (feature `espeak`).
+    Espeak,
+    /// `neural::NeuralFallback` (feature `neural-fallback`):
+    /// `{ neural = { model = "g2p.onnx", config = "g2p.json" } }`.
+    Neural { model: PathBuf, config: PathBuf },
+}
+
+/// The contents of a config file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct G2PConfig {
+    /// A code `Language::from_code` reads; US English if unset.
+    pub language: Option<String>,
+    /// "us", "gb", "scottish", "caribbean", "australian", "indian", "irish",
+    /// or an espeak-ng voice; the language's if unset. Anything else is
+    /// `ConfigError::UnknownDialect`.
+    pub dialect: Option<String>,
+    /// Fallbacks for out-of-vocabulary words, tried in order. The dialect's
+    /// if unset.
+    pub fallback: Option<Vec<FallbackConfig>>,
+    /// User dictionaries, loaded in order (see `Lexicon::load_user_dictionary`).
+    pub lexicons: Vec<PathBuf>,
+    pub options: G2POptions,
+}
+
+impl G2PConfig {
+    pub fn from_toml(toml: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(toml)?)
+    }
+
+    /// Reads `path`, resolving the relative paths in it against its
+    /// directory.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let mut config = Self::from_toml(&fs::read_to_string(path)?)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        for lexicon in &mut config.lexicons {
+            *lexicon = dir.join(&*lexicon);
+        }
+        for fallback in config.fallback.iter_mut().flatten() {
+            if let FallbackConfig::Neural { model, config } = fallback {
+                *model = dir.join(&*model);
+                *config = dir.join(&*config);
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn build(self) -> Result<G2P, ConfigError> {
+        let lang = match &self.language {
+            Some(code) => Language::from_code(code).ok_or_else(|| ConfigError::UnknownLanguage(code.clone()))?,
+            None => Language::EnglishUS,
+        };
+        let dialect = match &self.dialect {
+            Some(name) => dialect(name).ok_or_else(|| ConfigError::UnknownDialect(name.clone()))?,
+            None => Dialect::from(&lang),
+        };
+        let mut builder = G2PBuilder::new(lang).options(self.options);
+        if let Some(configs) = &self.fallback {
+            let mut fallbacks = configs.iter().map(|f| fallback(f, &dialect)).collect::<Result<Vec<_>, _>>()?;
+            builder = builder.fallback(match fallbacks.len() {
+                0 => None,
+                1 => fallbacks.pop(),
+                _ => Some(Box::new(FallbackChain::new(fallbacks))),
+            });
+        }
+        let mut g2p = builder.dialect(dialect).build();
+        for path in self.lexicons {
+            if let Err(error) = g2p.lexicon.load_user_dictionary(&path) {
+                return Err(ConfigError::Lexicon { path, error });
+            }
+        }
+        Ok(g2p)
+    }
+}
+
+/// The dialect `name` names, or `None` if it is neither a dialect nor
+/// shaped like an espeak-ng voice ("fr", "en-gb-x-rp", "en-029").
+fn dialect(name: &str) -> Option<Dialect> {
+    Some(match name.to_lowercase().as_str() {
+        "us" => Dialect::US,
+        "gb" => Dialect::GB,
+        "scottish" => Dialect::Scottish,
+        "caribbean" => Dialect::Caribbean,
+        "australian" => Dialect::Australian,
+        "indian" => Dialect::Indian,
+        "irish" => Dialect::Irish,
+        voice => {
+            let lang = voice.split('-').next().unwrap_or_default();
+            let is_voice = (2..=3).contains(&lang.len())
+                && lang.chars().all(|c| c.is_ascii_alphabetic())
+                && voice.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            is_voice.then(|| Dialect::from_voice(voice))?
+        }
+    })
+}
+
+fn fallback(config: &FallbackConfig, dialect: &Dialect) -> Result<Box<dyn Fallback>, ConfigError> {
+    match config {
+        FallbackConfig::Lts => Ok(Box::new(LtsFallback::new(dialect.is_british()))),
+        #[cfg(feature = "espeak")]
+        FallbackConfig::Espeak => Ok(Box::new(crate::fallback::EspeakFallback::for_dialect(dialect))),
+        #[cfg(not(feature = "espeak"))]
+        FallbackConfig::Espeak => Err(ConfigError::MissingFeature("espeak")),
+        #[cfg(feature = "neural-fallback")]
+        FallbackConfig::Neural { model, config } => match crate::neural::NeuralFallback::load(model, config) {
+            Ok(neural) => Ok(Box::new(neural)),
+            Err(e) => Err(ConfigError::Fallback { name: "neural", error: e.to_string() }),
+        },
+        #[cfg(not(feature = "neural-fallback"))]
+        FallbackConfig::Neural { .. } => Err(ConfigError::MissingFeature("neural-fallback")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{OovPolicy, UnicodeForm};
+
+    #[test]
+    fn test_config() {
+        let config = G2PConfig::from_toml(
+            r#"
+            language = "en-gb"
+            dialect = "Australian"
+            fallback = []
+
+            [options]
+            unicode_form = "nfkc"
+            read_addresses = false
+            oov_policy = { mark = "<unk>" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.options.unicode_form, Some(UnicodeForm::Nfkc));
+        assert!(!config.options.read_addresses);
+        assert_eq!(config.options.oov_policy, OovPolicy::Mark("<unk>".to_string()));
+        // Options not given keep their defaults
+        assert!(config.options.year_style);
+
+        let g2p = config.build().unwrap();
+        assert_eq!(g2p.lexicon.lang, Language::EnglishGB);
+        assert_eq!(g2p.g2p("zorbified").unwrap().0, "<unk>");
+
+        assert!(matches!(G2PConfig::from_toml("langauge = \"en-gb\""), Err(ConfigError::Toml(_))));
+        // Misspelled options are rejected rather than left at their defaults
+        assert!(matches!(G2PConfig::from_toml("[options]\nunicode_fom = \"nfkc\""), Err(ConfigError::Toml(_))));
+        assert!(matches!(
+            G2PConfig::from_toml("[options.pause_markers]\nperiod = \".\"\nclose = \",\""),
+            Err(ConfigError::Toml(_))
+        ));
+        let unknown = G2PConfig { dialect: Some("austrlian".to_string()), ..Default::default() };
+        assert!(matches!(unknown.build(), Err(ConfigError::UnknownDialect(name)) if name == "austrlian"));
+        assert_eq!(dialect("en-gb-x-rp"), Some(Dialect::Voice("en-gb-x-rp".to_string())));
+        let unknown = G2PConfig { language: Some("xx".to_string()), ..Default::default() };
+        assert!(matches!(unknown.build(), Err(ConfigError::UnknownLanguage(code)) if code == "xx"));
+        #[cfg(not(feature = "espeak"))]
+        assert!(matches!(
+            G2PConfig::from_toml("fallback = [\"lts\", \"espeak\"]").unwrap().build(),
+            Err(ConfigError::MissingFeature("espeak"))
+        ));
+    }
+
+    #[test]
+    fn test_from_config() {
+        let dir = std::env::temp_dir().join(format!("misaki-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("names.json"), r#"{"zorblat": "zˈɔɹblæt"}"#).unwrap();
+        fs::write(dir.join("misaki.toml"), "fallback = [\"lts\"]\nlexicons = [\"names.json\"]\n").unwrap();
+
+        let g2p = G2P::from_config(dir.join("misaki.toml")).unwrap();
+        assert_eq!(g2p.g2p("zorblat").unwrap().0, "zˈɔɹblæt");
+        assert!(matches!(G2P::from_config(dir.join("missing.toml")), Err(ConfigError::Io(_))));
+
+        fs::write(dir.join("misaki.toml"), "lexicons = [\"missing.json\"]\n").unwrap();
+        assert!(matches!(G2P::from_config(dir.join("misaki.toml")), Err(ConfigError::Lexicon { .. })));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -22,6 +22,16 @@ pub fn load_gb_silver() -> HashMap<String, PhonemeEntry> {
     serde_json::from_str(data).expect("Failed to parse gb_silver.json")
 }
 
+pub fn load_us_espeak_map() -> HashMap<String, String> {
+    let data = include_str!("../data/us_espeak.json");
+    serde_json::from_str(data).expect("Failed to parse us_espeak.json")
+}
+
+pub fn load_gb_espeak_map() -> HashMap<String, String> {
+    let data = include_str!("../data/gb_espeak.json");
+    serde_json::from_str(data).expect("Failed to parse gb_espeak.json")
+}
+
 // pub fn load_it_gold() -> HashMap<String, PhonemeEntry> {
 //     let data = include_str!("../data/it_gold.json");
 //     serde_json::from_str(data).expect("Failed to parse it_gold.json")
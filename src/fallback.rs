@@ -1,7 +1,13 @@
+use crate::data;
+#[cfg(feature = "espeak")]
+use crate::language::Dialect;
+#[cfg(feature = "espeak")]
 use espeak_rs::text_to_phonemes;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Mutex;
 use thiserror::Error;
 
+#[cfg(feature = "espeak")]
 static ESPEAK_MUTEX: Mutex<()> = Mutex::new(());
 
 #[derive(Error, Debug)]
@@ -11,6 +17,11 @@ pub enum FallbackError {
         word: String,
         error: String,
     },
+    #[error("model error for '{word}': {error}")]
+    Model {
+        word: String,
+        error: String,
+    },
     #[error("no phonemes matched for '{word}'")]
     NoPhonemes {
         word: String,
@@ -23,54 +34,310 @@ pub enum FallbackError {
 pub trait Fallback: Send + Sync {
     /// Convert unknown word to phonemes
     /// Returns phonemes
-    /// Note: espeak-ng is rule-based and always produces output
     fn phonemize(&self, word: &str) -> Result<String, FallbackError>;
+
+    /// Converts several words at once, returning each one's phonemes and
+    /// rating: 1 for a fallback guess, or 0 with empty phonemes if it failed.
+    /// Backends override this to pay per-call costs (a lock, a model run) once.
+    fn phonemize_batch(&self, words: &[&str]) -> Vec<(String, u8)> {
+        words.iter().map(|word| rate(word, self.phonemize(word))).collect()
+    }
+}
+
+/// A `phonemize` result as a `phonemize_batch` entry.
+pub(crate) fn rate(word: &str, result: Result<String, FallbackError>) -> (String, u8) {
+    match result {
+        Ok(phonemes) => (phonemes, 1),
+        Err(e) => {
+            tracing::warn!("fallback error for '{}': {}", word, e);
+            (String::new(), 0)
+        }
+    }
+}
+
+/// Remembers the phonemes of up to `capacity` words, evicting the oldest
+/// first, so words that recur (a novel's character names) reach the wrapped
+/// fallback only once.
+pub struct CachedFallback<F> {
+    inner: F,
+    capacity: usize,
+    cache: Mutex<Cache>,
+}
+
+#[derive(Default)]
+struct Cache {
+    phonemes: HashMap<String, String>,
+    order: VecDeque<String>,
+}
+
+impl<F: Fallback> CachedFallback<F> {
+    pub fn new(inner: F, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            cache: Mutex::new(Cache::default()),
+        }
+    }
+
+    pub fn inner(&self) -> &F {
+        &self.inner
+    }
+
+    /// Number of cached words.
+    pub fn len(&self) -> usize {
+        self.cache.lock().map_or(0, |c| c.phonemes.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&self, word: &str, phonemes: &str) {
+        let Ok(mut cache) = self.cache.lock() else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        if cache.phonemes.len() >= self.capacity
+            && let Some(oldest) = cache.order.pop_front()
+        {
+            cache.phonemes.remove(&oldest);
+        }
+        if cache.phonemes.insert(word.to_string(), phonemes.to_string()).is_none() {
+            cache.order.push_back(word.to_string());
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.phonemes.clear();
+            cache.order.clear();
+        }
+    }
 }
 
-/// espeak-ng based fallback
+impl<F: Fallback> Fallback for CachedFallback<F> {
+    fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+        let lock_err = |e: std::sync::PoisonError<_>| FallbackError::MutexPoisoned(format!("{:?}", e));
+        if let Some(phonemes) = self.cache.lock().map_err(lock_err)?.phonemes.get(word) {
+            return Ok(phonemes.clone());
+        }
+        // Errors aren't cached, so a failing word is retried next time
+        let phonemes = self.inner.phonemize(word)?;
+        self.insert(word, &phonemes);
+        Ok(phonemes)
+    }
+
+    fn phonemize_batch(&self, words: &[&str]) -> Vec<(String, u8)> {
+        let mut results: Vec<Option<(String, u8)>> = match self.cache.lock() {
+            Ok(cache) => words.iter().map(|w| cache.phonemes.get(*w).map(|p| (p.clone(), 1))).collect(),
+            Err(_) => vec![None; words.len()],
+        };
+        let misses: Vec<&str> = words.iter().zip(&results).filter(|(_, r)| r.is_none()).map(|(w, _)| *w).collect();
+        let fresh = if misses.is_empty() { Vec::new() } else { self.inner.phonemize_batch(&misses) };
+        let mut fresh = fresh.into_iter();
+        for (word, result) in words.iter().zip(&mut results) {
+            if result.is_none() {
+                let (phonemes, rating) = fresh.next().unwrap_or_default();
+                if rating > 0 {
+                    self.insert(word, &phonemes);
+                }
+                *result = Some((phonemes, rating));
+            }
+        }
+        results.into_iter().map(Option::unwrap_or_default).collect()
+    }
+}
+
+/// Tries each fallback in turn, reading a word with the first that can: a
+/// model for most words, say, with letter-to-sound rules behind it.
+pub struct FallbackChain {
+    fallbacks: Vec<Box<dyn Fallback>>,
+}
+
+impl FallbackChain {
+    pub fn new(fallbacks: Vec<Box<dyn Fallback>>) -> Self {
+        Self { fallbacks }
+    }
+
+    pub fn len(&self) -> usize {
+        self.fallbacks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fallbacks.is_empty()
+    }
+}
+
+impl Fallback for FallbackChain {
+    fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+        let mut error = FallbackError::NoPhonemes { word: word.to_string() };
+        for fallback in &self.fallbacks {
+            match fallback.phonemize(word) {
+                Ok(phonemes) if !phonemes.is_empty() => return Ok(phonemes),
+                Ok(_) => {}
+                Err(e) => error = e,
+            }
+        }
+        Err(error)
+    }
+
+    fn phonemize_batch(&self, words: &[&str]) -> Vec<(String, u8)> {
+        let mut results = vec![(String::new(), 0); words.len()];
+        // Each fallback gets the words the ones before it failed on
+        for fallback in &self.fallbacks {
+            let missing: Vec<usize> = (0..words.len()).filter(|&i| results[i].1 == 0).collect();
+            if missing.is_empty() {
+                break;
+            }
+            let batch: Vec<&str> = missing.iter().map(|&i| words[i]).collect();
+            for (i, result) in missing.into_iter().zip(fallback.phonemize_batch(&batch)) {
+                results[i] = result;
+            }
+        }
+        results
+    }
+}
+
+/// Rewrites espeak-ng IPA into the lexicon's phoneme notation (tied
+/// diphthongs and affricates, `ɹ`, `ɡ`). Each position takes the longest
+/// matching entry, so "aɪɚ" wins over "aɪ". The bundled tables live in
+/// `data/{us,gb}_espeak.json`; `insert` and `remove` correct them.
+#[derive(Debug, Clone)]
+pub struct PhonemeMap {
+    entries: HashMap<String, String>,
+    longest: usize,
+}
+
+impl PhonemeMap {
+    pub fn new(entries: HashMap<String, String>) -> Self {
+        let longest = entries.keys().map(|k| k.chars().count()).max().unwrap_or(0);
+        Self { entries, longest }
+    }
+
+    /// The bundled table for US (`false`) or British (`true`) English.
+    pub fn for_dialect(british: bool) -> Self {
+        Self::new(if british { data::load_gb_espeak_map() } else { data::load_us_espeak_map() })
+    }
+
+    /// The table for an espeak-ng voice: the bundled ones for English voices
+    /// ("en-us" American, the others British), and none for other languages,
+    /// whose IPA is kept as espeak-ng writes it.
+    pub fn for_voice(voice: &str) -> Self {
+        if voice.starts_with("en") {
+            Self::for_dialect(!voice.starts_with("en-us"))
+        } else {
+            Self::new(HashMap::new())
+        }
+    }
+
+    /// A table from a JSON object of espeak → lexicon phonemes.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        Ok(Self::new(serde_json::from_str(json)?))
+    }
+
+    pub fn insert(&mut self, espeak: impl Into<String>, phonemes: impl Into<String>) {
+        let espeak = espeak.into();
+        self.longest = self.longest.max(espeak.chars().count());
+        self.entries.insert(espeak, phonemes.into());
+    }
+
+    pub fn remove(&mut self, espeak: &str) {
+        self.entries.remove(espeak);
+    }
+
+    pub fn get(&self, espeak: &str) -> Option<&str> {
+        self.entries.get(espeak).map(String::as_str)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn apply(&self, espeak: &str) -> String {
+        let mut result = String::with_capacity(espeak.len());
+        let mut rest = espeak;
+        'scan: while let Some(c) = rest.chars().next() {
+            for len in (1..=self.longest).rev() {
+                let Some(end) = rest.char_indices().nth(len - 1).map(|(i, c)| i + c.len_utf8()) else {
+                    continue;
+                };
+                if let Some(phonemes) = self.entries.get(&rest[..end]) {
+                    result.push_str(phonemes);
+                    rest = &rest[end..];
+                    continue 'scan;
+                }
+            }
+            result.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+        result
+    }
+}
+
+/// espeak-ng based fallback (`espeak` feature)
+#[cfg(feature = "espeak")]
 pub struct EspeakFallback {
-    british: bool,
+    voice: String,
+    /// Converts espeak's output to the lexicon's notation.
+    pub map: PhonemeMap,
 }
 
+#[cfg(feature = "espeak")]
 impl EspeakFallback {
     pub fn new(british: bool) -> Result<Self, String> {
-        Ok(Self { british })
+        Ok(Self::for_dialect(&if british { Dialect::GB } else { Dialect::US }))
     }
 
-    /// Convert espeak IPA output to misaki phoneme format
-    fn convert_espeak_to_misaki(&self, espeak_ipa: &str) -> String {
-        let mut result = espeak_ipa.to_string();
-
-        // Conversions to match misaki phoneme set
-        result = result.replace("iː", "ˈi").replace("i:", "ˈi");
-        result = result.replace("uː", "ˈu").replace("u:", "ˈu");
-        result = result.replace("ɜː", "ɜ").replace("ɜ:", "ɜ");
-        result = result.replace("ɔː", "ɔ").replace("ɔ:", "ɔ");
-        result = result.replace("ɑː", "ɑ").replace("ɑ:", "ɑ");
-        result = result.replace("ː", ""); // remove remaining length markers
-        result = result.replace("_", ""); // stop syllables
+    pub fn for_dialect(dialect: &Dialect) -> Self {
+        Self {
+            voice: dialect.espeak_voice().to_string(),
+            map: PhonemeMap::for_voice(dialect.espeak_voice()),
+        }
+    }
 
-        result.replace("ˈˈ", "ˈ").replace("ˌˌ", "ˌ")
+    /// Uses any installed espeak-ng voice ("en-au", "ru"), e.g. to read words
+    /// routed by `ScriptPolicy::Route`.
+    pub fn with_voice(voice: impl Into<String>) -> Self {
+        Self::for_dialect(&Dialect::from_voice(&voice.into()))
     }
 }
 
+#[cfg(feature = "espeak")]
 impl Fallback for EspeakFallback {
     fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
         let _lock = ESPEAK_MUTEX
             .lock()
             .map_err(|e| FallbackError::MutexPoisoned(format!("{:?}", e)))?;
-        let voice = if self.british { "en" } else { "en-us" };
+        self.phonemize_locked(word)
+    }
+
+    fn phonemize_batch(&self, words: &[&str]) -> Vec<(String, u8)> {
+        let _lock = match ESPEAK_MUTEX.lock() {
+            Ok(lock) => lock,
+            Err(e) => {
+                tracing::warn!("espeak mutex poisoned: {:?}", e);
+                return vec![(String::new(), 0); words.len()];
+            }
+        };
+        words.iter().map(|word| rate(word, self.phonemize_locked(word))).collect()
+    }
+}
 
+#[cfg(feature = "espeak")]
+impl EspeakFallback {
+    /// `phonemize` for a caller already holding `ESPEAK_MUTEX`.
+    fn phonemize_locked(&self, word: &str) -> Result<String, FallbackError> {
         // Use the portable espeak-rs call (used in kokoros)
-        match text_to_phonemes(word, voice, None, true, false) {
+        match text_to_phonemes(word, &self.voice, None, true, false) {
             Ok(phonemes) => {
                 if phonemes.is_empty() {
                     return Err(FallbackError::NoPhonemes {
                         word: word.to_string(),
                     });
                 }
-                let cleaned = self.convert_espeak_to_misaki(&phonemes.join(""));
-                Ok(cleaned)
+                Ok(self.map.apply(&phonemes.join("")))
             }
             Err(e) => Err(FallbackError::Espeak {
                 word: word.to_string(),
@@ -83,7 +350,137 @@ impl Fallback for EspeakFallback {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::language::Language;
+    use crate::lexicon::{Lexicon, PhonemeEntry};
+    use crate::phoneme::PhonemeString;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Reads every word as "x", counting calls.
+    #[derive(Default)]
+    struct Counting(AtomicUsize);
+
+    impl Fallback for Counting {
+        fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            if word.is_empty() {
+                return Err(FallbackError::NoPhonemes { word: word.to_string() });
+            }
+            Ok("x".to_string())
+        }
+    }
+
+    #[test]
+    fn test_cached_fallback() {
+        let cached = CachedFallback::new(Counting::default(), 2);
+        let calls = || cached.inner().0.load(Ordering::SeqCst);
+        for word in ["Frodo", "Sam", "Frodo", "Sam"] {
+            assert_eq!(cached.phonemize(word).unwrap(), "x");
+        }
+        assert_eq!(calls(), 2);
+
+        // At capacity the oldest word is evicted
+        cached.phonemize("Gollum").unwrap();
+        assert_eq!(cached.len(), 2);
+        cached.phonemize("Frodo").unwrap();
+        assert_eq!(calls(), 4);
+
+        // Errors aren't cached
+        assert!(cached.phonemize("").is_err());
+        assert!(cached.phonemize("").is_err());
+        assert_eq!(calls(), 6);
+
+        cached.clear();
+        assert!(cached.is_empty());
+    }
+
+    #[test]
+    fn test_phoneme_map() {
+        let us = PhonemeMap::for_dialect(false);
+        assert_eq!(us.apply("fˈaɪɚ"), "fˈa‍ɪ‍ɚ");
+        assert_eq!(us.apply("tʃˈɜːtʃ"), "t‍ʃˈɜːt‍ʃ");
+        assert_eq!(us.apply("kˈɑːɹ_"), "kˈɑː‍ɹ");
+        assert_eq!(us.apply("həlˈoʊ"), "həlˈo‍ʊ");
+        let gb = PhonemeMap::for_dialect(true);
+        assert_eq!(gb.apply("həlˈəʊ"), "həlˈə‍ʊ");
+        assert_eq!(gb.apply("ðeə"), "ðe‍ə");
+
+        let mut custom = PhonemeMap::from_json(r#"{"ɐ": "ə"}"#).unwrap();
+        assert_eq!(custom.apply("ɐbˈaʊt"), "əbˈaʊt");
+        custom.insert("aʊ", "a‍ʊ");
+        custom.remove("ɐ");
+        assert_eq!(custom.apply("ɐbˈaʊt"), "ɐbˈa‍ʊt");
+
+        assert_eq!(PhonemeMap::for_voice("en-us").apply("həlˈoʊ"), "həlˈo‍ʊ");
+        assert_eq!(PhonemeMap::for_voice("en-gb-scotland").apply("həlˈəʊ"), "həlˈə‍ʊ");
+        // Other languages' IPA isn't English's to rewrite
+        assert_eq!(PhonemeMap::for_voice("ru").apply("prʲɪvʲˈetʃɪ"), "prʲɪvʲˈetʃɪ");
+        assert_eq!(PhonemeMap::for_voice("es").entries().count(), 0);
+    }
+
+    #[test]
+    fn test_phoneme_map_inventory() {
+        // Every mapping must produce symbols the dialect's lexicon uses
+        for (british, lang) in [(false, Language::EnglishUS), (true, Language::EnglishGB)] {
+            let lexicon = Lexicon::new(lang);
+            let mut inventory = HashSet::new();
+            for entry in lexicon.golds.values().chain(lexicon.silvers.values()) {
+                match entry {
+                    PhonemeEntry::Simple(ps) => inventory.extend(ps.chars()),
+                    PhonemeEntry::Tagged(tagged) => inventory.extend(tagged.values().flatten().flat_map(|ps| ps.chars())),
+                }
+            }
+            for (espeak, phonemes) in PhonemeMap::for_dialect(british).entries() {
+                let missing: String = phonemes.chars().filter(|c| !inventory.contains(c)).collect();
+                assert!(missing.is_empty(), "{:?} -> {:?} uses {:?}", espeak, phonemes, missing);
+                assert!(PhonemeString::parse(phonemes).is_ok(), "{:?} -> {:?}", espeak, phonemes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_phonemize_batch() {
+        let counting = Counting::default();
+        assert_eq!(
+            counting.phonemize_batch(&["Frodo", "", "Sam"]),
+            [("x".to_string(), 1), (String::new(), 0), ("x".to_string(), 1)]
+        );
+
+        // Cached words skip the inner batch
+        let cached = CachedFallback::new(Counting::default(), 8);
+        cached.phonemize("Frodo").unwrap();
+        let batch = cached.phonemize_batch(&["Frodo", "Sam", "", "Sam"]);
+        assert_eq!(batch.iter().map(|(_, r)| *r).collect::<Vec<_>>(), [1, 1, 0, 1]);
+        assert_eq!(cached.inner().0.load(Ordering::SeqCst), 4);
+        assert_eq!(cached.len(), 2);
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        /// Reads words of up to three letters as "s".
+        struct Short;
+
+        impl Fallback for Short {
+            fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+                match word.len() {
+                    1..=3 => Ok("s".to_string()),
+                    _ => Err(FallbackError::NoPhonemes { word: word.to_string() }),
+                }
+            }
+        }
+
+        let chain = FallbackChain::new(vec![Box::new(Short), Box::new(Counting::default())]);
+        assert_eq!(chain.phonemize("Sam").unwrap(), "s");
+        assert_eq!(chain.phonemize("Frodo").unwrap(), "x");
+        assert!(matches!(chain.phonemize(""), Err(FallbackError::NoPhonemes { .. })));
+        assert_eq!(
+            chain.phonemize_batch(&["Frodo", "", "Sam"]),
+            [("x".to_string(), 1), (String::new(), 0), ("s".to_string(), 1)]
+        );
+        assert!(FallbackChain::new(Vec::new()).phonemize("Sam").is_err());
+    }
 
+    #[cfg(feature = "espeak")]
     #[test]
     fn test_espeak_fallback() {
         let fallback = EspeakFallback::new(false).expect("espeak should initialize");
@@ -96,6 +493,7 @@ mod tests {
         assert!(!phonemes.contains("ˈɛl"));  // Should not have spelled-out 'L'
     }
 
+    #[cfg(feature = "espeak")]
     #[test]
     fn test_espeak_nonsense_word() {
         let fallback = EspeakFallback::new(false).unwrap();
@@ -105,6 +503,7 @@ mod tests {
         assert!(!phonemes.is_empty(), "espeak should phonemize nonsense words");
     }
 
+    #[cfg(feature = "espeak")]
     #[test]
     fn test_espeak_phonemes_beat() {
         let fallback = EspeakFallback::new(false).unwrap();
@@ -117,6 +516,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "espeak")]
     #[test]
     fn test_espeak_american_vs_british() {
         let us = EspeakFallback::new(false).unwrap();
@@ -1,80 +1,470 @@
-use crate::language::Language;
-use crate::fallback::{Fallback, EspeakFallback, FallbackError};
-use crate::languages::{LanguageRules, english::English};
-use crate::lexicon::Lexicon;
+use crate::align::{self, PhonemeAlignment};
+#[cfg(feature = "config")]
+use crate::config::{ConfigError, G2PConfig};
+use crate::language::{Dialect, Language};
+#[cfg(feature = "espeak")]
+use crate::fallback::EspeakFallback;
+use crate::fallback::{Fallback, FallbackError};
+use crate::lts::LtsFallback;
+#[cfg(feature = "homograph-ml")]
+use crate::homograph::{HomographError, HomographModel};
+use crate::integrations::kokoro;
+use crate::languages::{self, LanguageRules, Neighbors, NumberVerbalizer, WordSplitter};
+use crate::lexicon::{Lexicon, Rating, TokenContext};
+use crate::markup;
+#[cfg(feature = "auto-detect")]
+use crate::multilingual::MultilingualG2P;
+use crate::normalize;
+use crate::oov::{OovRecorder, OovSource};
+use crate::options::{
+    DateOrder, EmojiPolicy, G2POptions, InputFormat, NumberLocale, NumberStyle, OovPolicy,
+    OutputFormat, PauseMarkers, ScriptPolicy, TokenizerKind,
+};
+use crate::phonology;
+use crate::pipeline::{BuiltinStage, PipelineCtx, Stage};
+use crate::post_process::PostProcess;
+use crate::result::{self, G2PResult};
+use crate::script::{self, Script};
+use crate::spacy;
+use crate::stream::SentenceStream;
+use crate::stress::Stress;
+use crate::timing;
 use thiserror::Error;
-use crate::tagger::PerceptronTagger;
-use crate::token::MToken;
-use num2words::Num2Words;
-use regex::Regex;
+use crate::tagger::Tagger;
+use crate::token::{Intonation, MToken};
+use crate::transliterate::Transliterator;
+use regex::{Match, Regex};
 use std::collections::HashMap;
+use std::io::BufRead;
+use std::ops::Deref;
+use std::sync::{Arc, LazyLock};
+
+/// Symbols that may sit inside a word ("twenty-one", "rock'n'roll", "and/or")
+/// rather than standing alone as punctuation.
+const WORD_JOINERS: &str = "'-_‘’/";
+
+/// Symbols ignored when deciding whether a word mixes letters, digits and
+/// other symbols (Python misaki's `SUBTOKEN_JUNKS`).
+const SUBTOKEN_JUNKS: &str = "',-._‘’/";
+
+/// Python misaki's link syntax: `[text](feature)`
+static LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^\)]*)\)").unwrap());
+
+/// Words of the text: runs of anything but whitespace
+static WORD_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\S+").unwrap());
+
+/// Words, with numbers whose thousands European sources group with
+/// (non-breaking) spaces kept whole: "1 000 000"
+static EUROPEAN_WORD_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[-−]?\d{1,3}(?:[ \u{00A0}\u{202F}]\d{3})+(?:,\d+)?\b|\S+").unwrap()
+});
 
 #[derive(Error, Debug)]
 pub enum G2PError {
     #[error("fallback error: {0}")]
     Fallback(#[from] FallbackError),
+    /// Reading the input of `g2p_stream` failed, or it wasn't UTF-8.
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    /// A word nothing could read, under `OovPolicy::Error`.
+    #[error("unknown word '{word}'")]
+    UnknownWord { word: String },
+    /// `G2P::try_new` for a `Language::Other` no pack was registered under
+    /// (see `languages::register`).
+    #[error("no language pack registered for {lang:?}")]
+    NoLanguagePack { lang: Language },
+    #[cfg(feature = "homograph-ml")]
+    #[error("homograph model error: {0}")]
+    Homograph(#[from] HomographError),
 }
 
 pub struct G2P {
     pub lexicon: Lexicon,
     pub unk: String,
+    pub options: G2POptions,
+    /// Accent folding for words the lexicon doesn't know ("naïve" → "naive").
+    /// Extend it with `insert`, or disable it with `Transliterator::empty()`.
+    pub transliterator: Transliterator,
     subtoken_regex: Regex,
-    tagger: PerceptronTagger,
+    tagger: Option<Box<dyn Tagger>>,
     rules: Box<dyn LanguageRules>,
+    numbers: Box<dyn NumberVerbalizer>,
+    word_splitter: Option<WordSplitter>,
+    /// The language's number locale, where the options don't set one.
+    number_locale: NumberLocale,
     fallback: Option<Box<dyn Fallback>>,
+    routes: HashMap<Script, Box<dyn Fallback>>,
+    /// Accent applied over the lexicon's phonemes.
+    dialect: Dialect,
+    oov_recorder: Option<Arc<OovRecorder>>,
+    /// Applied in order to every token's phonemes.
+    post_processes: Vec<Box<dyn PostProcess>>,
+    /// Custom stages, each with the built-in stage it runs after.
+    stages: Vec<(BuiltinStage, Box<dyn Stage>)>,
+    #[cfg(feature = "homograph-ml")]
+    homograph_model: Option<HomographModel>,
+}
+
+/// Configures a `G2P` beyond its language: options, the accent used for
+/// out-of-vocabulary words, and the fallback that reads them.
+pub struct G2PBuilder {
+    lang: Language,
+    options: G2POptions,
+    dialect: Dialect,
+    fallback: Option<Option<Box<dyn Fallback>>>,
+    tagger: Option<Box<dyn Tagger>>,
+    post_processes: Vec<Box<dyn PostProcess>>,
+    stages: Vec<(BuiltinStage, Box<dyn Stage>)>,
+    #[cfg(feature = "espeak")]
+    espeak: bool,
+}
+
+impl G2PBuilder {
+    pub fn new(lang: Language) -> Self {
+        Self {
+            dialect: Dialect::from(&lang),
+            lang,
+            options: G2POptions::default(),
+            fallback: None,
+            tagger: None,
+            post_processes: Vec::new(),
+            stages: Vec::new(),
+            #[cfg(feature = "espeak")]
+            espeak: false,
+        }
+    }
+
+    pub fn options(mut self, options: G2POptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Accent (the language's by default): the one English is read in over
+    /// its lexicon, e.g. `Dialect::Australian`, and the one out-of-vocabulary
+    /// words are read in, e.g. `Dialect::Voice("en-au".into())` with the
+    /// espeak fallback.
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Reads out-of-vocabulary words with espeak-ng in the builder's dialect.
+    #[cfg(feature = "espeak")]
+    pub fn espeak(mut self) -> Self {
+        self.espeak = true;
+        self
+    }
+
+    /// Replaces the dialect's fallback; `None` spells unknown words out.
+    pub fn fallback(mut self, fallback: Option<Box<dyn Fallback>>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Replaces the language's tagger (the bundled `PerceptronTagger` for
+    /// English), or tags a language that has none.
+    pub fn tagger(mut self, tagger: Box<dyn Tagger>) -> Self {
+        self.tagger = Some(tagger);
+        self
+    }
+
+    /// Rewrites each token's phonemes once they are resolved, after the
+    /// dialect's accent and before pause markers and `output_format`. Hooks
+    /// run in the order they are added, each seeing the last one's output.
+    pub fn post_process(mut self, hook: impl PostProcess + 'static) -> Self {
+        self.post_processes.push(Box::new(hook));
+        self
+    }
+
+    /// Runs `stage` on the tokens after the built-in stage `after` (see
+    /// `pipeline`). Stages added after the same one run in the order added.
+    /// The built-in stages themselves are fixed: all of them run, in order,
+    /// whatever is added between them.
+    pub fn stage(mut self, after: BuiltinStage, stage: impl Stage + 'static) -> Self {
+        self.stages.push((after, Box::new(stage)));
+        self
+    }
+
+    pub fn build(self) -> G2P {
+        let mut g2p = G2P::with_options(self.lang, self.options);
+        g2p.dialect = self.dialect.clone();
+        g2p.post_processes = self.post_processes;
+        g2p.stages = self.stages;
+        if let Some(tagger) = self.tagger {
+            g2p.tagger = Some(tagger);
+        }
+        g2p.fallback = match self.fallback {
+            Some(fallback) => fallback,
+            #[cfg(feature = "espeak")]
+            None if self.espeak => Some(Box::new(EspeakFallback::for_dialect(&self.dialect))),
+            // The letter-to-sound rules are English: other languages keep none
+            None if g2p.fallback.is_some() => Some(Box::new(LtsFallback::new(self.dialect.is_british()))),
+            None => None,
+        };
+        g2p
+    }
 }
 
 impl G2P {
+    pub fn builder(lang: Language) -> G2PBuilder {
+        G2PBuilder::new(lang)
+    }
+
+    /// Panics if `lang` is a `Language::Other` that was never registered;
+    /// `try_new` returns an error instead.
     pub fn new(lang: Language) -> Self {
+        Self::with_options(lang, G2POptions::default())
+    }
+
+    /// Like `new`, but fails with `G2PError::NoLanguagePack` for a
+    /// `Language::Other` that was never registered.
+    pub fn try_new(lang: Language) -> Result<Self, G2PError> {
+        Self::try_with_options(lang, G2POptions::default())
+    }
+
+    /// A G2P as a TOML file describes it: language, dialect, fallbacks,
+    /// user dictionaries and options (see `config`).
+    #[cfg(feature = "config")]
+    pub fn from_config(path: impl AsRef<std::path::Path>) -> Result<Self, ConfigError> {
+        G2PConfig::load(path)?.build()
+    }
+
+    /// Reads each sentence in the language it is detected as, with English
+    /// where detection isn't sure (see `multilingual`).
+    #[cfg(feature = "auto-detect")]
+    pub fn multilingual() -> MultilingualG2P {
+        MultilingualG2P::new(Language::EnglishUS)
+    }
+
+    /// Panics if `lang` is a `Language::Other` that was never registered;
+    /// `try_with_options` returns an error instead.
+    pub fn with_options(lang: Language, options: G2POptions) -> Self {
+        Self::try_with_options(lang, options).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like `with_options`, but fails with `G2PError::NoLanguagePack` for a
+    /// `Language::Other` that was never registered.
+    pub fn try_with_options(lang: Language, options: G2POptions) -> Result<Self, G2PError> {
         // Regex for subtokenization with better UTF-8 support using Unicode properties
-        let subtoken_regex = Regex::new(
+        // Combining marks stay with the letter before them (Devanagari vowel signs)
+        // Emoji sequences are matched first so they stay whole
+        let subtoken_regex = Regex::new(&format!(
+            "{}|{}",
+            normalize::EMOJI_PATTERN,
             r"(?x)
             ^['‘’]+ |
-            (?:^-)?(?:\d?[,.]?\d)+ |
+            (?:\B[-−])?(?:\d?[,.]?\d)+ |
             [\-_]+ |
             ['‘’]{2,} |
-            \p{L}+(?:[''']\p{L}+)* |
-            [^\s\-_0-9\p{L}''] |
+            \p{L}[\p{L}\p{M}]*(?:['‘’]\p{L}[\p{L}\p{M}]*)* |
+            [^\s\-_0-9\p{L}'‘’] |
             ['‘’]+$
-        ",
-        )
+        "
+        ))
         .unwrap();
 
-        let weights_json = include_str!("resources/tagger/weights.json");
-        let classes_txt = include_str!("resources/tagger/classes.txt");
-        let tags_json = include_str!("resources/tagger/tags.json");
-
-        let rules: Box<dyn LanguageRules> = match lang {
-            Language::EnglishUS | Language::EnglishGB => Box::new(English),
-            // Language::Italian => Box::new(Italian),
-        };
-
-        let fallback: Option<Box<dyn Fallback>> = match EspeakFallback::new(lang == Language::EnglishGB) {
-            Ok(fb) => Some(Box::new(fb)),
-            Err(e) => {
-                tracing::warn!("espeak-ng fallback unavailable: {}", e);
-                None
-            }
+        // English dialects are read from the lexicon of their base, then accented
+        let dialect = Dialect::from(&lang);
+        let lang = lang.base();
+        let pack = languages::pack(&lang).ok_or_else(|| G2PError::NoLanguagePack { lang: lang.clone() })?;
+        // The letter-to-sound rules are English
+        let fallback: Option<Box<dyn Fallback>> = match lang {
+            Language::EnglishUS | Language::EnglishGB => Some(Box::new(LtsFallback::new(lang == Language::EnglishGB))),
+            _ => None,
         };
 
-        Self {
-            lexicon: Lexicon::new(lang),
+        Ok(Self {
+            lexicon: Lexicon::from_entries(lang, pack.golds, pack.silvers),
             unk: "❓".to_string(),
+            options,
+            transliterator: Transliterator::default(),
             subtoken_regex,
-            tagger: PerceptronTagger::new(weights_json, classes_txt, tags_json),
-            rules,
+            tagger: pack.rules.tagger(),
+            rules: pack.rules,
+            numbers: pack.numbers,
+            word_splitter: pack.tokenizer,
+            number_locale: pack.number_locale,
             fallback,
+            routes: HashMap::new(),
+            dialect,
+            oov_recorder: None,
+            post_processes: Vec::new(),
+            stages: Vec::new(),
+            #[cfg(feature = "homograph-ml")]
+            homograph_model: None,
+        })
+    }
+
+    /// Lets an ONNX classifier choose between the tagged readings of the
+    /// homographs it knows, ahead of the rule-based heteronym handling.
+    #[cfg(feature = "homograph-ml")]
+    pub fn set_homograph_model(&mut self, model: HomographModel) {
+        self.homograph_model = Some(model);
+    }
+
+    /// Replaces the phonemizer for out-of-vocabulary words (the built-in
+    /// `LtsFallback` by default), e.g. with `EspeakFallback`; `None` spells
+    /// them out letter by letter.
+    pub fn set_fallback(&mut self, fallback: Option<Box<dyn Fallback>>) {
+        self.fallback = fallback;
+    }
+
+    /// Replaces the part-of-speech tagger (the bundled `PerceptronTagger` for
+    /// English, none for languages without one).
+    pub fn set_tagger(&mut self, tagger: Box<dyn Tagger>) {
+        self.tagger = Some(tagger);
+    }
+
+    /// Records every word read by the fallback, spelled out or marked unknown.
+    pub fn set_oov_recorder(&mut self, recorder: Arc<OovRecorder>) {
+        self.oov_recorder = Some(recorder);
+    }
+
+    fn record_oov(&self, word: &str, phonemes: &str, source: OovSource) {
+        if let Some(recorder) = &self.oov_recorder {
+            recorder.record(word, phonemes, source);
         }
     }
 
+    /// Registers the phonemizer for words in `script` under `ScriptPolicy::Route`,
+    /// e.g. `EspeakFallback::with_voice("ru")` for Cyrillic.
+    pub fn route_script(&mut self, script: Script, fallback: Box<dyn Fallback>) {
+        self.routes.insert(script, fallback);
+    }
+
+    /// Strips markup per `options.input_format`, cleans the text (see
+    /// `normalize::clean_text`) and resolves Python misaki's
+    /// link syntax, `[text](feature)`, where feature is `/phonemes/`,
+    /// `#num_flags#` or a stress value such as `+1` or `-0.5`.
+    ///
+    /// Returns the text with links replaced by their inner text, that text split
+    /// into segments (plain runs and link texts, in order), and the normalized
+    /// feature of each link segment keyed by segment index.
     pub fn preprocess(&self, text: &str) -> (String, Vec<String>, HashMap<usize, String>) {
-        // Simplified preprocess: just return the text and tokens for now
-        // Python handles links like [text](phonemes), we'll skip that for simplicity unless needed
-        let tokens: Vec<String> = text.split_whitespace().map(|s| s.to_string()).collect();
-        (text.to_string(), tokens, HashMap::new())
+        self.call(&self.options).preprocess(text)
     }
 
     pub fn tokenize(&self, text: &str) -> Vec<MToken> {
+        self.call(&self.options).tokenize(text)
+    }
+
+    /// Maps each phoneme of `g2p` output back to the characters of the
+    /// preprocessed text it was read from (see `align::align`).
+    pub fn align(&self, tokens: &[MToken]) -> Vec<PhonemeAlignment> {
+        self.align_with(tokens, &self.options)
+    }
+
+    /// `align` for output of `g2p_with`, whose `options.unk` may stand in
+    /// for the G2P's own.
+    pub fn align_with(&self, tokens: &[MToken], options: &G2POptions) -> Vec<PhonemeAlignment> {
+        align::align(tokens, self.call(options).unk)
+    }
+
+    pub fn g2p(&self, text: &str) -> Result<(String, Vec<MToken>), G2PError> {
+        self.g2p_with(text, &self.options)
+    }
+
+    /// Like `g2p`, but read with `options` in place of the G2P's own, so
+    /// that one G2P can serve callers that want different output.
+    pub fn g2p_with(&self, text: &str, options: &G2POptions) -> Result<(String, Vec<MToken>), G2PError> {
+        self.call(options).g2p(text)
+    }
+
+    /// Reads `reader` a sentence at a time, phonemizing each as `g2p` would
+    /// on its own, so a large document never has to be held whole. Stops
+    /// after the first read error.
+    pub fn g2p_stream<R: BufRead>(&self, reader: R) -> SentenceStream<'_, R> {
+        SentenceStream::new(self, reader)
+    }
+
+    /// Like `g2p`, but also returns the text split into Kokoro-sized phoneme
+    /// chunks and warnings for the words it could only guess at.
+    pub fn analyze(&self, text: &str) -> Result<G2PResult, G2PError> {
+        self.analyze_with(text, &self.options)
+    }
+
+    /// `analyze` with `options` in place of the G2P's own (see `g2p_with`).
+    pub fn analyze_with(&self, text: &str, options: &G2POptions) -> Result<G2PResult, G2PError> {
+        self.call(options).analyze(text)
+    }
+
+    fn call<'a>(&'a self, options: &'a G2POptions) -> Call<'a> {
+        Call { g2p: self, unk: options.unk.as_deref().unwrap_or(&self.unk), options }
+    }
+
+    /// The language's rules, for splitting text into sentences.
+    pub(crate) fn rules(&self) -> &dyn LanguageRules {
+        self.rules.as_ref()
+    }
+}
+
+/// One call of the pipeline: the G2P, read with the options it was called
+/// with. The pipeline's steps are its methods, seeing the G2P through
+/// `Deref` and the options as `self.options`.
+struct Call<'a> {
+    g2p: &'a G2P,
+    options: &'a G2POptions,
+    /// The options' `unk`, or the G2P's.
+    unk: &'a str,
+}
+
+impl Deref for Call<'_> {
+    type Target = G2P;
+
+    fn deref(&self) -> &G2P {
+        self.g2p
+    }
+}
+
+impl Call<'_> {
+    fn preprocess(&self, text: &str) -> (String, Vec<String>, HashMap<usize, String>) {
+        let text = match self.options.input_format {
+            InputFormat::Plain => normalize::clean_text(text, self.options.unicode_form),
+            InputFormat::Markdown => normalize::clean_text(&markup::strip_markdown(text), self.options.unicode_form),
+            InputFormat::Html => normalize::clean_text(&markup::strip_html(text), self.options.unicode_form),
+        };
+        let text = text.as_str();
+        let mut result = String::new();
+        let mut segments = Vec::new();
+        let mut features = HashMap::new();
+        let mut last_end = 0;
+
+        for caps in LINK_REGEX.captures_iter(text) {
+            let m = caps.get(0).unwrap();
+            let before = &text[last_end..m.start()];
+            if !before.is_empty() {
+                result.push_str(before);
+                segments.push(before.to_string());
+            }
+
+            let f = &caps[2];
+            let unsigned = f.strip_prefix(['-', '+']).unwrap_or(f);
+            let is_stress = !unsigned.is_empty() && unsigned.chars().all(|c| c.is_ascii_digit());
+            let feature = if is_stress || matches!(unsigned, "0.5") {
+                Some(f.to_string())
+            } else if f.len() > 1 && f.starts_with('/') && f.ends_with('/') {
+                Some(format!("/{}", f[1..].trim_end_matches('/')))
+            } else if f.len() > 1 && f.starts_with('#') && f.ends_with('#') {
+                Some(format!("#{}", f[1..].trim_end_matches('#')))
+            } else {
+                None
+            };
+            if let Some(feature) = feature {
+                features.insert(segments.len(), feature);
+            }
+            result.push_str(&caps[1]);
+            segments.push(caps[1].to_string());
+            last_end = m.end();
+        }
+        if last_end < text.len() {
+            result.push_str(&text[last_end..]);
+            segments.push(text[last_end..].to_string());
+        }
+        (result, segments, features)
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<MToken> {
         // Use language-tokenizer for word boundary detection (like spaCy in Python)
         // However, language-tokenizer with snowball does stemming, so we need to extract original text
         // Strategy: Use a simple word splitter that handles contractions, then apply subtokenization
@@ -82,239 +472,1359 @@ impl G2P {
 
         // Simple word splitting that handles contractions: split on whitespace and punctuation
         // but keep contractions together
-        let word_boundary_regex = Regex::new(r"\S+").unwrap();
+        let word_boundary_regex = match self.number_locale() {
+            NumberLocale::English => &*WORD_REGEX,
+            NumberLocale::European => &*EUROPEAN_WORD_REGEX,
+        };
         let mut tokens = Vec::new();
+        // Tokens record where they came from as char offsets into `text`
+        let char_starts: Vec<usize> = text.char_indices().map(|(b, _)| b).collect();
+        let token = |word: &str, start: usize| {
+            let char_at = |b: usize| char_starts.partition_point(|&x| x < b);
+            let mut tk = MToken::new(word.to_string(), "NN".to_string(), " ".to_string());
+            tk.char_span = (char_at(start), char_at(start + word.len()));
+            tk
+        };
 
-        for mat in word_boundary_regex.find_iter(text) {
-            let word = mat.as_str();
-            // Apply subtokenization regex to each word (like Python's subtokenize in retokenize)
-            // This handles abbreviations, numbers, etc. but preserves contractions
-            let subtokens: Vec<&str> = self
-                .subtoken_regex
-                .find_iter(word)
-                .map(|m| m.as_str())
-                .collect();
-
-            if subtokens.is_empty() {
-                // If regex doesn't match, use the word as-is
-                let tk = MToken::new(word.to_string(), "NN".to_string(), " ".to_string());
+        let words: Vec<(usize, usize)> = match &self.word_splitter {
+            Some(split) => split(text),
+            None => word_boundary_regex.find_iter(text).map(|m| (m.start(), m.end())).collect(),
+        };
+        for (base, end) in words {
+            let word = &text[base..end];
+
+            // Whole-word normalization ("10km", "-5°C") keeps the source text on a
+            // single token and stores the spoken replacement as its alias
+            let core = word
+                .trim_start_matches(['(', '[', '"', '\'', '“', '‘', '«'])
+                .trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '"', '\'', '”', '’', '»']);
+            let spoken = if core.is_empty() { None } else { self.normalize_word(core) };
+            // Dotted initialisms ("Ph.D.", "U.S.") stay whole, keeping their final period
+            let initialism = spoken.is_none() && normalize::is_initialism(core);
+            if spoken.is_some() || initialism {
+                let start = word.find(core).unwrap_or(0);
+                let mut end = start + core.len();
+                if initialism && word[end..].starts_with('.') {
+                    end += 1;
+                }
+                let (lead, core, trail) = (&word[..start], &word[start..end], &word[end..]);
+                for (b, c) in lead.char_indices() {
+                    tokens.push(token(c.encode_utf8(&mut [0; 4]), base + b));
+                }
+                let mut tk = token(core, base + start);
+                tk.underscore_mut().alias = spoken;
                 tokens.push(tk);
-            } else {
-                for sub in subtokens {
-                    let tk = MToken::new(sub.to_string(), "NN".to_string(), " ".to_string());
-                    tokens.push(tk);
+                for (b, c) in trail.char_indices() {
+                    tokens.push(token(c.encode_utf8(&mut [0; 4]), base + end + b));
+                }
+                continue;
+            }
+
+            // A language's own splitter gives whole words
+            if word.contains(char::is_whitespace) || self.word_splitter.is_some() {
+                tokens.push(token(word, base));
+                continue;
+            }
+
+            let pieces = match self.options.tokenizer {
+                TokenizerKind::Default => vec![(0, word.len())],
+                TokenizerKind::SpacyCompat => spacy::tokenize(word),
+            };
+            for (start, end) in pieces {
+                let (word, base) = (&word[start..end], base + start);
+                // Apply subtokenization regex to each word (like Python's subtokenize in retokenize)
+                // This handles abbreviations, numbers, etc. but preserves contractions
+                let subtokens: Vec<Match> = self.subtoken_regex.find_iter(word).collect();
+
+                if subtokens.is_empty() {
+                    // If regex doesn't match, use the word as-is
+                    tokens.push(token(word, base));
+                } else {
+                    // Characters no subtoken matched get tokens of their own, so no text is lost
+                    let mut pos = 0;
+                    for sub in subtokens {
+                        if sub.start() > pos {
+                            tokens.push(token(&word[pos..sub.start()], base + pos));
+                        }
+                        tokens.push(token(sub.as_str(), base + sub.start()));
+                        pos = sub.end();
+                    }
+                    if pos < word.len() {
+                        tokens.push(token(&word[pos..], base + pos));
+                    }
                 }
             }
         }
 
+        Self::set_whitespace(&mut tokens, text);
         tokens
     }
 
-    pub fn g2p(&self, text: &str) -> Result<(String, Vec<MToken>), G2PError> {
-        let (processed_text, _, _) = self.preprocess(text);
-        let mut tokens = self.tokenize(&processed_text);
+    /// Sets each token's `whitespace` to the text between it and the next
+    /// token (or the end of `text`), going by `char_span`.
+    fn set_whitespace(tokens: &mut [MToken], text: &str) {
+        let char_starts: Vec<usize> = text.char_indices().map(|(b, _)| b).collect();
+        let byte_at = |c: usize| char_starts.get(c).copied().unwrap_or(text.len());
+        for i in 0..tokens.len() {
+            let end = tokens[i].char_span.1;
+            let next = tokens.get(i + 1).map_or(char_starts.len(), |t| t.char_span.0);
+            tokens[i].whitespace = text[byte_at(end)..byte_at(next.max(end))].to_string();
+        }
+    }
+
+    fn g2p(&self, text: &str) -> Result<(String, Vec<MToken>), G2PError> {
+        let mut tokens = self.run_pipeline(text, BuiltinStage::Prosody, &self.stages)?;
+        if self.options.output_format != OutputFormat::Misaki {
+            for tk in &mut tokens {
+                if let Some(ps) = &tk.phonemes {
+                    tk.phonemes = Some(phonology::convert(ps, self.options.output_format));
+                }
+            }
+        }
 
-        // Collect words for tagging
-        let words_owned: Vec<String> = tokens.iter().map(|tk| tk.text.clone()).collect();
+        Ok((self.join_phonemes(&tokens), tokens))
+    }
+
+    fn analyze(&self, text: &str) -> Result<G2PResult, G2PError> {
+        let (phonemes, tokens) = self.g2p(text)?;
+        Ok(G2PResult {
+            text: text.to_string(),
+            chunks: result::chunk(&tokens, self.unk, kokoro::MAX_PHONEMES),
+            warnings: result::warnings(&tokens),
+            phonemes,
+            tokens,
+        })
+    }
+
+    /// Tokenizes and phonemizes `text` in the lexicon's notation, with the
+    /// built-in stages up to `Fallback` only: the reading of a word or a
+    /// number expansion, as part of a bigger text.
+    fn phonemize_tokens(&self, text: &str) -> Result<Vec<MToken>, G2PError> {
+        self.run_pipeline(text, BuiltinStage::Fallback, &[])
+    }
+
+    /// Runs `text` through the built-in stages up to `last`, each followed
+    /// by the custom `stages` added after it.
+    fn run_pipeline(
+        &self,
+        text: &str,
+        last: BuiltinStage,
+        stages: &[(BuiltinStage, Box<dyn Stage>)],
+    ) -> Result<Vec<MToken>, G2PError> {
+        let (processed_text, segments, features) = self.preprocess(text);
+        let ctx = PipelineCtx { text: &processed_text, lexicon: &self.lexicon, options: self.options, unk: self.unk };
+        let mut tokens = Vec::new();
+        for stage in BuiltinStage::ALL {
+            match stage {
+                BuiltinStage::Tokenize => tokens = self.tokenize_segments(&processed_text, &segments, &features),
+                BuiltinStage::Tag => self.tag(&mut tokens, &processed_text),
+                BuiltinStage::Retokenize => self.retokenize(&mut tokens),
+                BuiltinStage::Normalize => {
+                    self.apply_abbreviations(&mut tokens);
+                    self.apply_num_heuristics(&mut tokens);
+                    self.apply_emoji_policy(&mut tokens);
+                    if self.options.tokenizer == TokenizerKind::SpacyCompat {
+                        self.lookup_words(&mut tokens);
+                    }
+                }
+                BuiltinStage::Lexicon => self.read_known(&mut tokens)?,
+                BuiltinStage::Fallback => {
+                    self.read_unknown(&mut tokens)?;
+                    tokens = self.merge_groups(tokens);
+                }
+                BuiltinStage::Accent => self.apply_accent(&mut tokens),
+                BuiltinStage::Prosody => {
+                    Self::apply_intonation(&mut tokens);
+                    if let Some(markers) = &self.options.pause_markers {
+                        Self::apply_pause_markers(&mut tokens, markers);
+                    }
+                    if let Some(rate) = self.options.speaking_rate {
+                        timing::estimate_timestamps(&mut tokens, rate);
+                    }
+                }
+            }
+            for (after, custom) in stages {
+                if *after == stage {
+                    custom.process(&mut tokens, &ctx);
+                }
+            }
+            if stage == last {
+                break;
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Splits preprocessed text into tokens. Link texts become single
+    /// tokens carrying their feature.
+    fn tokenize_segments(&self, text: &str, segments: &[String], features: &HashMap<usize, String>) -> Vec<MToken> {
+        if features.is_empty() {
+            return self.tokenize(text);
+        }
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+        for (j, segment) in segments.iter().enumerate() {
+            let len = segment.chars().count();
+            match features.get(&j) {
+                Some(feature) => {
+                    let mut tk = MToken::new(segment.clone(), "NN".to_string(), " ".to_string());
+                    tk.char_span = (offset, offset + len);
+                    if let Some(ps) = feature.strip_prefix('/') {
+                        tk.phonemes = Some(ps.to_string());
+                    } else if let Some(flags) = feature.strip_prefix('#') {
+                        tk.underscore_mut().num_flags = flags.to_string();
+                    } else if let Ok(stress) = feature.parse::<f64>() {
+                        tk.underscore_mut().stress = Some(Stress::from(stress));
+                    }
+                    tokens.push(tk);
+                }
+                None => tokens.extend(self.tokenize(segment).into_iter().map(|mut tk| {
+                    tk.char_span = (tk.char_span.0 + offset, tk.char_span.1 + offset);
+                    tk
+                })),
+            }
+            offset += len;
+        }
+        Self::set_whitespace(&mut tokens, text);
+        tokens
+    }
+
+    /// Tags tokens with the tagger, subtokens of a word ("$5", "don't")
+    /// getting the word's tag. Without a tagger they stay nouns.
+    fn tag(&self, tokens: &mut [MToken], text: &str) {
+        let Some(tagger) = &self.tagger else {
+            return;
+        };
+        let word_of = self.tagging_words(tokens, text);
+        let mut words_owned: Vec<String> = Vec::new();
+        for (tk, &w) in tokens.iter().zip(&word_of) {
+            match words_owned.get_mut(w) {
+                Some(word) => word.push_str(&tk.text),
+                None => words_owned.push(tk.text.clone()),
+            }
+        }
         let words: Vec<&str> = words_owned.iter().map(|s| s.as_str()).collect();
-        let tags = self.tagger.tag(&words);
+        let tags = tagger.tag(&words);
 
-        tracing::debug!(
-            "g2p '{}' -> {} tokens, {} tags",
-            text,
-            tokens.len(),
-            tags.len()
-        );
+        tracing::debug!("g2p '{}' -> {} tokens, {} tags", text, tokens.len(), tags.len());
         for (i, tk) in tokens.iter().enumerate() {
             tracing::debug!("token[{}]: '{}'", i, tk.text);
         }
 
-        // Process tokens in reverse order (like Python) to build context
-        let mut contexts: Vec<crate::lexicon::TokenContext> =
-            vec![crate::lexicon::TokenContext::default(); tokens.len()];
+        for (tk, &w) in tokens.iter_mut().zip(&word_of) {
+            if let Some(tag) = tags.get(w) {
+                tk.tag = tag.tag.clone();
+                tk.underscore_mut().uncertain_tag = tag.uncertain;
+            }
+        }
+    }
 
-        // First, set tags
-        for (tk, tag) in tokens.iter_mut().zip(tags.iter()) {
-            tk.tag = tag.tag.clone();
+    /// What the lexicon needs to know of what follows `tokens[i]`. Tokens
+    /// are read in reverse (like Python), so the next one's phonemes are
+    /// known if it could be read, though its first letter has the last word.
+    fn context(tokens: &[MToken], i: usize) -> TokenContext {
+        let mut context = TokenContext { uncertain_tag: tokens[i].underscore().uncertain_tag, ..Default::default() };
+        let Some(next) = tokens.get(i + 1) else {
+            return context;
+        };
+        if let Some(phonemes) = &next.phonemes {
+            let vowels = "AIOQWYaiuæɑɒɔəɛɜɪʊʌᵻ";
+            let consonants = "bdfhjklmnpstvwzðŋɡɹɾʃʒʤʧθ";
+            for c in phonemes.chars() {
+                if vowels.contains(c) {
+                    context.future_vowel = Some(true);
+                    break;
+                } else if consonants.contains(c) {
+                    context.future_vowel = Some(false);
+                    break;
+                }
+            }
         }
+        let next_word = &next.text;
+        // Check if next word starts with vowel (simple heuristic)
+        if let Some(first_char) = next_word.chars().next() {
+            let first_lower = first_char.to_lowercase().next().unwrap();
+            if "aeiou".contains(first_lower) {
+                context.future_vowel = Some(true);
+            } else if first_char.is_alphabetic() {
+                context.future_vowel = Some(false);
+            }
+        }
+        if next_word.to_lowercase() == "to" {
+            context.future_to = true;
+        }
+        context.next_word = Some(next_word.to_lowercase());
+        context
+    }
 
-        // Process in reverse to build context from future tokens
+    /// The stress `token` is read with: its own, the options', or that of
+    /// its capitals.
+    fn stress(&self, token: &MToken) -> Option<Stress> {
+        token.underscore().stress.or_else(|| self.stress_override(&token.text)).or_else(|| self.cap_stress(&token.text))
+    }
+
+    /// Reads what can be read without guessing: aliases, numbers, other
+    /// scripts, heteronyms, the lexicon and the language's rules.
+    fn read_known(&self, tokens: &mut [MToken]) -> Result<(), G2PError> {
         for i in (0..tokens.len()).rev() {
+            if tokens[i].phonemes.is_some() {
+                continue;
+            }
             let word = tokens[i].text.clone();
             let tag = tokens[i].tag.clone();
-            let stress = if word == word.to_lowercase() {
-                None
-            } else {
-                Some(if word == word.to_uppercase() {
-                    self.lexicon.cap_stresses.1
-                } else {
-                    self.lexicon.cap_stresses.0
-                })
-            };
+            let stress = self.stress(&tokens[i]);
+            let context = Self::context(tokens, i);
 
-            // Determine context from next token
-            if i < tokens.len() - 1 {
-                let next_word = &tokens[i + 1].text;
-                // Check if next word starts with vowel (simple heuristic)
-                if let Some(first_char) = next_word.chars().next() {
-                    let first_lower = first_char.to_lowercase().next().unwrap();
-                    if "aeiou".contains(first_lower) {
-                        contexts[i].future_vowel = Some(true);
-                    } else if first_char.is_alphabetic() {
-                        contexts[i].future_vowel = Some(false);
-                    }
-                }
+            // Normalized tokens are read from their alias
+            if tokens[i].phonemes.is_none()
+                && let Some(alias) = tokens[i].underscore().alias.clone()
+            {
+                tokens[i].phonemes = Some(self.phonemize_spoken(&alias)?);
+            }
 
-                if next_word.to_lowercase() == "to" {
-                    contexts[i].future_to = true;
-                }
+            // Words in other scripts follow the script policy
+            if tokens[i].phonemes.is_none()
+                && let Some(script) = Script::detect(&word)
+                && !self.lexicon.lang.writes(script)
+            {
+                tokens[i].phonemes = Some(self.phonemize_script(&word, script)?);
             }
 
-            // Process current token
-            if tokens[i].phonemes.is_none() {
-                let ctx = Some(&contexts[i]);
+            // Unit symbols written as a separate word after a number ("10 km")
+            if tokens[i].phonemes.is_none()
+                && i > 0
+                && self.is_number(&tokens[i - 1].text)
+                && let Some(unit) = normalize::detached_unit_words(
+                    &word,
+                    &tokens[i - 1].text,
+                    self.lexicon.lang == Language::EnglishGB,
+                )
+            {
+                tokens[i].phonemes = Some(self.phonemize_spoken(&unit)?);
+            }
 
-                // Use get_word which handles special cases, lookup, and stemming
-                if let Some((ps, _)) = self.lexicon.get_word(&word, &tag, stress, ctx) {
-                    tokens[i].phonemes = Some(ps);
+            // Detached am/pm after a time or hour ("3:30 pm", "7 AM")
+            if tokens[i].phonemes.is_none()
+                && i > 0
+                && (self.is_number(&tokens[i - 1].text)
+                    || normalize::expand_time(&tokens[i - 1].text, |n| n.to_string()).is_some())
+                && let Some(ampm) = normalize::meridiem(&word)
+            {
+                tokens[i].phonemes = Some(self.phonemize_spoken(ampm)?);
+            }
+
+            // Dotted numbers announced as versions ("Version 2.0")
+            if tokens[i].phonemes.is_none()
+                && i > 0
+                && normalize::is_version_word(&tokens[i - 1].text)
+                && word.contains('.')
+                && let Some(spoken) = normalize::version_words(&word, |n| self.convert_cardinal(n))
+            {
+                tokens[i].phonemes = Some(self.phonemize_spoken(&spoken)?);
+            }
+
+            // Month and day as separate tokens ("Jan 5", "5 January")
+            if tokens[i].phonemes.is_none()
+                && let Some(spoken) = self.convert_split_date(tokens, i)
+            {
+                tokens[i].phonemes = Some(self.phonemize_spoken(&spoken)?);
+            }
+
+            #[cfg(feature = "homograph-ml")]
+            if tokens[i].phonemes.is_none()
+                && let Some(model) = &self.homograph_model
+                && model.is_homograph(&word)
+            {
+                let words: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+                if let Some(label) = model.classify(&words, i)?
+                    && let Some(p) = self.lexicon.get_word(
+                        &word,
+                        &label,
+                        stress,
+                        // The model's label stands in for the tag
+                        Some(&crate::lexicon::TokenContext { uncertain_tag: false, ..context.clone() }),
+                    )
+                {
+                    tokens[i].phonemes = Some(p.phonemes.into_owned());
+                    tokens[i].underscore_mut().rating = Some(p.rating);
                 }
+            }
 
-                if tokens[i].phonemes.is_none() {
-                    if word.contains('-') && word.len() > 1 {
-                        // Handle hyphenated words like "twenty-one"
-                        let parts: Vec<&str> = word.split('-').filter(|s| !s.is_empty()).collect();
-                        let mut sub_ps = Vec::new();
-                        for part in parts {
-                            let (p, _) = self.g2p(part)?;
-                            sub_ps.push(p);
-                        }
-                        tokens[i].phonemes = Some(sub_ps.join(" "));
-                    } else if self.is_number(&word) {
-                        let spoken = self.convert_number(&word);
-                        if spoken != word {
-                            let (p, _) = self.g2p(&spoken)?;
-                            tokens[i].phonemes = Some(p);
-                        }
-                    }
+            // Heteronyms whose context calls for their less common reading
+            if tokens[i].phonemes.is_none() {
+                let pair = |j: Option<usize>| j.and_then(|j| tokens.get(j)).map(|t| (t.text.as_str(), t.tag.as_str()));
+                let neighbors = Neighbors {
+                    prev2: pair(i.checked_sub(2)),
+                    prev: pair(i.checked_sub(1)),
+                    next: pair(Some(i + 1)),
+                };
+                let british = self.lexicon.lang == Language::EnglishGB;
+                // Without a tag to trust, only the neighbouring words can call for the other reading
+                let tag = if context.uncertain_tag { "" } else { &tag };
+                if let Some(ps) = self.rules.heteronym(&word, tag, &neighbors, british) {
+                    tokens[i].phonemes = Some(ps.to_string());
                 }
+            }
+
+            // Roman numerals have to be caught before lookup, which would spell them as NNPs
+            if tokens[i].phonemes.is_none()
+                && let Some(spoken) = self.convert_roman(&word, i.checked_sub(1).map(|j| &tokens[j]))
+            {
+                let p = self.phonemize_spoken(&spoken)?;
+                tokens[i].phonemes = Some(p);
+            }
 
-                if tokens[i].phonemes.is_none() {
-                    if let Some(ps) = self.rules.apply_rules(&word, &tag, &self.lexicon) {
-                        tokens[i].phonemes = Some(ps);
+            // Use get_word which handles special cases, lookup, and stemming
+            if tokens[i].phonemes.is_none()
+                && let Some(p) = self.lexicon.get_word(&word, &tag, stress, Some(&context))
+            {
+                tokens[i].phonemes = Some(p.phonemes.into_owned());
+                tokens[i].underscore_mut().rating = Some(p.rating);
+            }
+
+            if tokens[i].phonemes.is_none() {
+                // Numbers come before the hyphen split, which would drop the sign of "-5"
+                if self.is_number(&word) {
+                    // "+" right before a phone-length number starts an international one
+                    let international = i > 0
+                        && tokens[i - 1].text == "+"
+                        && tokens[i - 1].whitespace.is_empty()
+                        && word.chars().filter(char::is_ascii_digit).count() >= 7;
+                    let style = match self.options.number_style {
+                        NumberStyle::Auto if international || Self::in_digit_run(tokens, i) => NumberStyle::Digits,
+                        style => style,
+                    };
+                    // Without tags to go by, any year-like number may be one
+                    let year = self.tagger.is_none() || Self::in_year_context(tokens, i);
+                    let spoken = self.convert_flagged_number(&word, &tokens[i], style, year);
+                    if spoken != word {
+                        let p = self.phonemize_spoken(&spoken)?;
+                        tokens[i].phonemes = Some(p);
+                    }
+                } else if word.contains('-') && word.len() > 1 {
+                    // Handle hyphenated words like "twenty-one"
+                    let parts: Vec<&str> = word.split('-').filter(|s| !s.is_empty()).collect();
+                    let mut sub_ps = Vec::new();
+                    for part in parts {
+                        let p = self.phonemize_spoken(part)?;
+                        sub_ps.push(p);
                     }
+                    tokens[i].phonemes = Some(sub_ps.join(" "));
                 }
+            }
 
-                if tokens[i].phonemes.is_none() {
-                    if word.chars().count() > 1 {
-                        // Unknown multi-character word - use fallback
-                        let mut handled = false;
-                        if let Some(ref fallback) = self.fallback {
-                            match fallback.phonemize(&word) {
-                                Ok(ps) => {
-                                    tokens[i].phonemes = Some(ps);
-                                    handled = true;
-                                }
-                                Err(e) => {
-                                    tracing::error!("fallback error for '{}': {}", word, e);
-                                    return Err(G2PError::Fallback(e));
-                                }
-                            }
-                        }
+            if tokens[i].phonemes.is_none()
+                && let Some(ps) = self.rules.apply_rules(&word, &tag, &self.lexicon)
+            {
+                tokens[i].phonemes = Some(ps);
+            }
+        }
+        Ok(())
+    }
 
-                        if !handled {
-                            // No fallback available or failed, try character-by-character
-                            let mut char_ps = Vec::new();
-                            for c in word.chars() {
-                                let (p, _) = self.g2p(&c.to_string())?;
-                                char_ps.push(p);
-                            }
-                            tokens[i].phonemes = Some(char_ps.join(" "));
-                        }
-                    } else {
-                        // Try to normalize the character or return unknown
-                        let normalized: String = word
-                            .chars()
-                            .map(|c| match c {
-                                'é' | 'è' | 'ê' | 'ë' => 'e',
-                                'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' => 'a',
-                                'í' | 'ì' | 'î' | 'ï' => 'i',
-                                'ó' | 'ò' | 'ô' | 'ö' | 'õ' => 'o',
-                                'ú' | 'ù' | 'û' | 'ü' => 'u',
-                                'ñ' => 'n',
-                                'ç' => 'c',
-                                '—' | '–' => ' ', // map dashes to spaces
-                                _ => c,
-                            })
-                            .collect();
-
-                        if normalized != word {
-                            let (p, _) = self.g2p(&normalized)?;
-                            tokens[i].phonemes = Some(p);
+    /// Reads the words `read_known` couldn't: without accents, as compounds
+    /// or typos, as guessed names, with the fallback, or spelled out, as the
+    /// OOV policy says. Words left for the fallback go to it in one
+    /// `phonemize_batch` call; those it fails on are spelled out.
+    fn read_unknown(&self, tokens: &mut [MToken]) -> Result<(), G2PError> {
+        let fallback = match self.options.oov_policy {
+            OovPolicy::Fallback => self.fallback.as_ref(),
+            _ => None,
+        };
+        // Tokens waiting on the fallback
+        let mut pending = Vec::new();
+        for i in (0..tokens.len()).rev() {
+            if tokens[i].phonemes.is_some() {
+                continue;
+            }
+            let word = tokens[i].text.clone();
+            let tag = tokens[i].tag.clone();
+            let stress = self.stress(&tokens[i]);
+            let context = Self::context(tokens, i);
+
+            if word.chars().count() > 1 {
+                // Unknown multi-character word - try it without accents, as a compound, as a typo, then the fallback
+                let mut handled = false;
+                // Capitalized words may be names the tagger took for nouns
+                let is_name = tag.starts_with("NNP") || word.starts_with(char::is_uppercase);
+                let folded = self.transliterator.transliterate(&word);
+                if folded != word
+                    && let Some(p) = self.lexicon.get_word(&folded, &tag, stress, Some(&context))
+                {
+                    tokens[i].phonemes = Some(p.phonemes.into_owned());
+                    tokens[i].underscore_mut().rating = Some(p.rating);
+                    handled = true;
+                }
+                // Names aren't compounds: "Zorbington" is not "zorbing" + "ton"
+                if !handled
+                    && !is_name
+                    && let Some(p) = self.lexicon.split_compound(&word, &tag, Some(&context))
+                {
+                    tokens[i].phonemes = Some(p.phonemes.into_owned());
+                    tokens[i].underscore_mut().rating = Some(p.rating);
+                    handled = true;
+                }
+                if !handled
+                    && self.options.correct_typos
+                    && !is_name
+                    && let Some(correction) = self.lexicon.nearest_word(&word)
+                    && let Some(p) = self.lexicon.get_word(&correction, &tag, stress, Some(&context))
+                {
+                    tokens[i].phonemes = Some(p.phonemes.into_owned());
+                    tokens[i].underscore_mut().rating = Some(Rating::Corrected);
+                    tokens[i].underscore_mut().correction = Some(correction);
+                    handled = true;
+                }
+                // Names are guessed from their endings before the fallback
+                if !handled
+                    && fallback.is_some()
+                    && is_name
+                    && let Some(ps) = self.rules.guess_name(&word, &self.lexicon, self.dialect.is_british())
+                {
+                    self.record_oov(&word, &ps, OovSource::Fallback);
+                    tokens[i].phonemes = Some(ps);
+                    tokens[i].underscore_mut().rating = Some(Rating::Fallback);
+                    handled = true;
+                }
+                if !handled && fallback.is_some() {
+                    pending.push(i);
+                } else if !handled {
+                    self.spell_or_mark(&mut tokens[i], &word)?;
+                }
+            } else {
+                // Try to transliterate the character or return unknown
+                let normalized = self.transliterator.transliterate(&word);
+
+                if normalized != word {
+                    let p = self.phonemize_spoken(&normalized)?;
+                    tokens[i].phonemes = Some(p);
+                } else {
+                    // Handle standard punctuation and symbols gracefully
+                    if word.chars().count() == 1 {
+                        let c = word.chars().next().unwrap();
+                        if c.is_ascii_punctuation() || "—–…".contains(c) {
+                            tokens[i].phonemes = Some(" ".to_string());
                         } else {
-                            // Handle standard punctuation and symbols gracefully
-                            if word.chars().count() == 1 {
-                                let c = word.chars().next().unwrap();
-                                if c.is_ascii_punctuation() || "—–…".contains(c) {
-                                    tokens[i].phonemes = Some(" ".to_string());
-                                } else {
-                                    tokens[i].phonemes = Some(self.unk.clone());
-                                }
-                            } else {
-                                tokens[i].phonemes = Some(self.unk.clone());
-                            }
+                            self.mark_unknown(&mut tokens[i], &word)?;
                         }
+                    } else {
+                        self.mark_unknown(&mut tokens[i], &word)?;
                     }
                 }
             }
+        }
 
-            // Update context for previous tokens based on current phonemes
-            if i > 0 && tokens[i].phonemes.is_some() {
-                let vowels = "AIOQWYaiuæɑɒɔəɛɜɪʊʌᵻ";
-                let consonants = "bdfhjklmnpstvwzðŋɡɹɾʃʒʤʧθ";
-                let phonemes = tokens[i].phonemes.as_ref().unwrap();
-                for c in phonemes.chars() {
-                    if vowels.contains(c) {
-                        contexts[i - 1].future_vowel = Some(true);
-                        break;
-                    } else if consonants.contains(c) {
-                        contexts[i - 1].future_vowel = Some(false);
-                        break;
-                    }
+        if let Some(fallback) = fallback
+            && !pending.is_empty()
+        {
+            let words: Vec<&str> = pending.iter().map(|&i| tokens[i].text.as_str()).collect();
+            let results = fallback.phonemize_batch(&words);
+            for (i, (ps, rating)) in pending.into_iter().zip(results) {
+                let word = tokens[i].text.clone();
+                if rating == 0 {
+                    // Nothing readable (e.g. no letters), or the fallback failed
+                    self.spell_or_mark(&mut tokens[i], &word)?;
+                    continue;
                 }
+                self.record_oov(&word, &ps, OovSource::Fallback);
+                tokens[i].phonemes = Some(ps);
+                tokens[i].underscore_mut().rating = Some(Rating::Fallback);
             }
         }
+        Ok(())
+    }
 
-        let result = tokens
-            .iter()
-            .map(|tk| tk.phonemes.as_ref().unwrap_or(&self.unk).clone() + &tk.whitespace)
-            .collect::<String>();
+    /// Spells out a word nothing else could read, or marks it unknown where
+    /// the OOV policy says not to guess.
+    fn spell_or_mark(&self, token: &mut MToken, word: &str) -> Result<(), G2PError> {
+        if !matches!(self.options.oov_policy, OovPolicy::Fallback | OovPolicy::SpellOut) {
+            return self.mark_unknown(token, word);
+        }
+        let mut char_ps = Vec::new();
+        for c in word.chars() {
+            char_ps.push(self.phonemize_spoken(&c.to_string())?);
+        }
+        let spelled = char_ps.join(" ");
+        self.record_oov(word, &spelled, OovSource::Spelled);
+        token.phonemes = Some(spelled);
+        token.underscore_mut().rating = Some(Rating::Fallback);
+        Ok(())
+    }
 
-        Ok((result, tokens))
+    /// Applies the dialect's accent, then the `post_process` hooks.
+    fn apply_accent(&self, tokens: &mut [MToken]) {
+        for tk in tokens.iter_mut() {
+            if let Some(ps) = &tk.phonemes
+                && let Some(accented) = languages::english::accents::apply(ps, &self.dialect)
+            {
+                tk.phonemes = Some(accented);
+            }
+        }
+        for hook in &self.post_processes {
+            for tk in tokens.iter_mut() {
+                if let Some(ps) = &tk.phonemes {
+                    tk.phonemes = Some(hook.process(tk, ps));
+                }
+            }
+        }
     }
 
-    fn is_number(&self, word: &str) -> bool {
-        let clean = word.replace(",", "");
-        clean.parse::<i64>().is_ok()
+    /// Sets `intonation` on the last token of each sentence: the end of a run
+    /// of ".", "!", "?" and "…" (skipping the silenced periods of
+    /// abbreviations), or the last token of a text without final punctuation.
+    fn apply_intonation(tokens: &mut [MToken]) {
+        let is_terminal = |tk: &MToken| {
+            !tk.text.is_empty()
+                && tk.text.chars().all(|c| ".!?…".contains(c))
+                && !(tk.text == "." && tk.phonemes.as_deref() == Some(""))
+        };
+        let mut run = String::new();
+        for i in 0..tokens.len() {
+            if !is_terminal(&tokens[i]) {
+                continue;
+            }
+            run.push_str(&tokens[i].text);
+            if !tokens.get(i + 1).is_some_and(is_terminal) {
+                tokens[i].underscore_mut().intonation = Some(Intonation::from_punctuation(&run));
+                run.clear();
+            }
+        }
+        // Text trailing off without punctuation still ends as a statement
+        let last_end = tokens.iter().rposition(|tk| tk.underscore().intonation.is_some());
+        if let Some(last_word) = tokens.iter().rposition(|tk| tk.text.chars().any(char::is_alphanumeric))
+            && last_end.is_none_or(|end| end < last_word)
+        {
+            tokens[last_word].underscore_mut().intonation = Some(Intonation::Statement);
+        }
     }
 
-    fn convert_number(&self, word: &str) -> String {
-        let clean = word.replace(",", "");
-        if let Ok(val) = clean.parse::<i64>() {
-            let n2w = match self.lexicon.lang {
-                Language::EnglishUS | Language::EnglishGB => Num2Words::new(val),
-                // Language::Italian => Num2Words::new(val).lang(num2words::Lang::English),
+    /// Replaces the spaces punctuation is read as with `markers` and records
+    /// each pause's length in `MToken::pause`. Runs like "..." and "--" make
+    /// one pause, and a blank line after a token adds the paragraph marker.
+    fn apply_pause_markers(tokens: &mut [MToken], markers: &PauseMarkers) {
+        for i in 0..tokens.len() {
+            // Periods silenced as part of an abbreviation ("Mr.") stay silent
+            let is_pause = match tokens[i].phonemes.as_deref() {
+                Some(" ") => true,
+                Some("") => tokens[i].text != ".",
+                _ => false,
             };
-            if let Ok(spoken) = n2w.to_words() {
-                return spoken;
+            if is_pause {
+                let continues_run = i > 0
+                    && tokens[i - 1].whitespace.is_empty()
+                    && tokens[i - 1].text == tokens[i].text
+                    && tokens[i - 1].pause.is_some();
+                let run = 1 + tokens[i + 1..]
+                    .iter()
+                    .zip(&tokens[i..])
+                    .take_while(|(tk, prev)| prev.whitespace.is_empty() && tk.text == tokens[i].text)
+                    .count();
+                let pause = match tokens[i].text.as_str() {
+                    "." if run >= 3 || continues_run => Some((&markers.ellipsis, timing::pause_after("…"))),
+                    "." => Some((&markers.period, timing::pause_after("."))),
+                    "?" => Some((&markers.question, timing::pause_after("?"))),
+                    "!" => Some((&markers.exclamation, timing::pause_after("!"))),
+                    "…" => Some((&markers.ellipsis, timing::pause_after("…"))),
+                    "," | ";" | ":" => Some((&markers.clause, timing::pause_after(","))),
+                    "—" | "–" | "-" => Some((&markers.dash, timing::pause_after("—"))),
+                    _ => None,
+                };
+                if let Some((marker, duration)) = pause {
+                    // Only the first of a run like "..." carries the pause
+                    let first = !continues_run;
+                    tokens[i].phonemes = Some(if first { marker.clone() } else { String::new() });
+                    tokens[i].pause = Some(if first { duration } else { 0.0 });
+                }
+            }
+
+            if tokens[i].whitespace.matches('\n').count() >= 2 {
+                let phonemes = tokens[i].phonemes.get_or_insert_with(String::new);
+                if phonemes.trim().is_empty() {
+                    phonemes.clear();
+                }
+                phonemes.push_str(&markers.paragraph);
+                let pause = tokens[i].pause.unwrap_or(0.0);
+                tokens[i].pause = Some(pause.max(timing::PARAGRAPH_PAUSE));
             }
         }
-        word.to_string()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Stress implied by capitalization: none for lowercase words, the
+    /// lexicon's `cap_stresses` for capitalized and all-caps ones. Only
+    /// English capitalizes for emphasis; German capitalizes every noun.
+    fn cap_stress(&self, word: &str) -> Option<Stress> {
+        if word == word.to_lowercase() || !self.lexicon.lang.is_english() {
+            None
+        } else if word == word.to_uppercase() {
+            Some(self.lexicon.cap_stresses.1)
+        } else {
+            Some(self.lexicon.cap_stresses.0)
+        }
+    }
+
+    /// Index of the word each token is tagged as part of, so subtokens share
+    /// their word's tag as in Python misaki: the words of `word_groups`, or in
+    /// `SpacyCompat` mode spaCy's tokens.
+    fn tagging_words(&self, tokens: &[MToken], text: &str) -> Vec<usize> {
+        if self.options.tokenizer == TokenizerKind::Default {
+            let mut word_of = vec![0; tokens.len()];
+            for (w, group) in self.word_groups(tokens).into_iter().enumerate() {
+                word_of[group].fill(w);
+            }
+            return word_of;
+        }
+        let char_at: Vec<usize> = text.char_indices().map(|(b, _)| b).collect();
+        let span_ends: Vec<usize> =
+            spacy::tokenize(text).into_iter().map(|(_, end)| char_at.partition_point(|&b| b < end)).collect();
+        let mut word_of = Vec::with_capacity(tokens.len());
+        let mut last_span = None;
+        for tk in tokens {
+            let span = span_ends.partition_point(|&end| end <= tk.char_span.0);
+            if last_span != Some(span) || word_of.is_empty() {
+                word_of.push(word_of.last().map_or(0, |w| w + 1));
+            } else {
+                word_of.push(*word_of.last().unwrap());
+            }
+            last_span = Some(span);
+        }
+        word_of
+    }
+
+    /// Looks up each word marked by `retokenize` whole, then shorter runs of
+    /// its tokens from the left, like Python misaki: "do" "n't" is read as
+    /// "don't". The first token of a run found in the lexicon takes its
+    /// phonemes and the rest are silenced; tokens in no such run are read on
+    /// their own.
+    fn lookup_words(&self, tokens: &mut [MToken]) {
+        let mut start = 0;
+        while start < tokens.len() {
+            let end = start + 1 + tokens[start + 1..].iter().take_while(|tk| !tk.underscore().is_head).count();
+            let mut left = start;
+            while left + 1 < end {
+                let found = (left + 2..=end).rev().find_map(|right| {
+                    let run = &tokens[left..right];
+                    if run.iter().any(|tk| tk.phonemes.is_some() || tk.underscore().alias.is_some()) {
+                        return None;
+                    }
+                    let text: String = run.iter().map(|tk| tk.text.as_str()).collect();
+                    let tag = &MToken::merge(run, self.unk).tag;
+                    self.lexicon.get_word(&text, tag, self.cap_stress(&text), None).map(|p| (right, p))
+                });
+                match found {
+                    Some((right, p)) => {
+                        tokens[left].phonemes = Some(p.phonemes.into_owned());
+                        tokens[left].underscore_mut().rating = Some(p.rating);
+                        for tk in &mut tokens[left + 1..right] {
+                            tk.phonemes = Some(String::new());
+                        }
+                        left = right;
+                    }
+                    None => left += 1,
+                }
+            }
+            start = end;
+        }
+    }
+
+    /// Runs of subtokens written without whitespace between them ("$5.50",
+    /// "twenty-one", "abc123"), which make up one word. Punctuation, emoji,
+    /// link tokens and normalized words stand alone, as does every token
+    /// when the language splits words itself.
+    fn word_groups(&self, tokens: &[MToken]) -> Vec<std::ops::Range<usize>> {
+        let stands_alone = |tk: &MToken| {
+            tk.phonemes.is_some()
+                || tk.underscore().alias.is_some()
+                || (!tk.text.chars().any(char::is_alphanumeric)
+                    && !tk.text.chars().all(|c| WORD_JOINERS.contains(c))
+                    && !normalize::is_currency_symbol(&tk.text))
+        };
+        let mut groups = Vec::new();
+        let mut start = 0;
+        for i in 1..=tokens.len() {
+            let ends_word = i == tokens.len()
+                || self.word_splitter.is_some()
+                || !tokens[i - 1].whitespace.is_empty()
+                || stands_alone(&tokens[i])
+                || stands_alone(&tokens[i - 1]);
+            if ends_word {
+                groups.push(start..i);
+                start = i;
+            }
+        }
+        groups
+    }
+
+    /// Marks the words of `word_groups` as one word, like Python misaki's
+    /// `retokenize`: tokens after the first of a word lose `is_head`, and get
+    /// `prespace` when the word mixes letters, digits and symbols, so their
+    /// parts are read with a space between them.
+    fn retokenize(&self, tokens: &mut [MToken]) {
+        for std::ops::Range { start, end: i } in self.word_groups(tokens) {
+            if i > start + 1 {
+                let text: String = tokens[start..i].iter().map(|tk| tk.text.as_str()).collect();
+                let classes: std::collections::HashSet<u8> = text
+                    .chars()
+                    .filter(|c| !SUBTOKEN_JUNKS.contains(*c))
+                    .map(|c| if c.is_alphabetic() { 0 } else if c.is_ascii_digit() { 1 } else { 2 })
+                    .collect();
+                let prespace = text.contains('/') || classes.len() > 1;
+                for tk in &mut tokens[start + 1..i] {
+                    tk.underscore_mut().is_head = false;
+                    tk.underscore_mut().prespace = prespace;
+                }
+            }
+        }
+    }
+
+    /// Merges each word marked by `retokenize` into its head token (see
+    /// `MToken::merge`).
+    fn merge_groups(&self, tokens: Vec<MToken>) -> Vec<MToken> {
+        let mut merged = Vec::with_capacity(tokens.len());
+        let mut group: Vec<MToken> = Vec::new();
+        for tk in tokens {
+            if tk.underscore().is_head && !group.is_empty() {
+                merged.push(MToken::merge(&group, self.unk));
+                group.clear();
+            }
+            group.push(tk);
+        }
+        if !group.is_empty() {
+            merged.push(MToken::merge(&group, self.unk));
+        }
+        merged
+    }
+
+    fn join_phonemes(&self, tokens: &[MToken]) -> String {
+        tokens
+            .iter()
+            .map(|tk| tk.phonemes.as_deref().unwrap_or(self.unk).to_string() + tk.phoneme_space())
+            .collect()
+    }
+
+    /// Phonemizes replacement text (number words, expanded numerals, word parts)
+    /// for a single token, without the trailing whitespace `g2p` appends.
+    fn phonemize_spoken(&self, text: &str) -> Result<String, G2PError> {
+        let tokens = self.phonemize_tokens(text)?;
+        Ok(self.join_phonemes(&tokens).trim_end().to_string())
+    }
+
+    /// Expands a whole whitespace-delimited word (minus surrounding punctuation)
+    /// that the lexicon can't read, e.g. measurements like "10km".
+    fn normalize_word(&self, word: &str) -> Option<String> {
+        let british = self.lexicon.lang == Language::EnglishGB;
+        if self.options.read_addresses
+            && let Some(spoken) = normalize::expand_email(word).or_else(|| normalize::expand_url(word))
+        {
+            return Some(spoken);
+        }
+        let dictionary_word = |w: &str| {
+            let upper = w.to_uppercase();
+            if self.lexicon.golds.contains_key(w) {
+                Some(w.to_string())
+            } else {
+                self.lexicon.golds.contains_key(&upper).then_some(upper)
+            }
+        };
+        if let Some(spoken) = normalize::expand_handle(word, self.options.speak_sigils, dictionary_word) {
+            return Some(spoken);
+        }
+        normalize::expand_date(
+            word,
+            self.date_order(),
+            |n| self.convert_number(n),
+            |d| self.convert_ordinal(d as i64),
+        )
+        .or_else(|| {
+            normalize::expand_fraction(
+                word,
+                self.options.fraction_style,
+                |n| self.convert_cardinal(n),
+                |d| self.convert_ordinal(d as i64),
+            )
+        })
+        .or_else(|| normalize::expand_version(word, |n| self.convert_cardinal(n)))
+        .or_else(|| normalize::expand_range(word, |n| self.convert_number(n)))
+        .or_else(|| normalize::expand_scientific(word, |n| self.convert_cardinal(n)))
+        .or_else(|| normalize::expand_time(word, |n| self.convert_cardinal(n)))
+        .or_else(|| normalize::expand_measurement(word, british, |n| self.convert_cardinal(n)))
+        .or_else(|| self.convert_negative_currency(word))
+    }
+
+    fn date_order(&self) -> DateOrder {
+        self.options.date_order.unwrap_or(match self.lexicon.lang {
+            Language::EnglishUS => DateOrder::MonthFirst,
+            _ => DateOrder::DayFirst,
+        })
+    }
+
+    /// Reads "Jan 5" / "5 Jan" style dates where month and day are separate
+    /// tokens. Returns the replacement words for token `i`, if it is part of one.
+    fn convert_split_date(&self, tokens: &[MToken], i: usize) -> Option<String> {
+        // Look past an abbreviation dot: "Jan. 5"
+        let neighbor = |j: Option<usize>, step: isize| -> Option<&MToken> {
+            let j = j?;
+            let tk = tokens.get(j)?;
+            if tk.text == "." {
+                tokens.get(j.checked_add_signed(step)?)
+            } else {
+                Some(tk)
+            }
+        };
+        let prev = neighbor(i.checked_sub(1), -1);
+        let next = neighbor(i.checked_add(1), 1);
+        let day = |tk: &MToken| tk.text.parse::<i64>().ok().filter(|d| (1..=31).contains(d));
+        let word = &tokens[i].text;
+
+        if let Some(month) = normalize::month_name(word) {
+            let adjacent_day = prev.and_then(day).or_else(|| next.and_then(day));
+            return adjacent_day.map(|_| month.to_string());
+        }
+        let d = day(&tokens[i])?;
+        if prev.is_some_and(|tk| normalize::month_name(&tk.text).is_some()) {
+            Some(self.convert_ordinal(d))
+        } else if next.is_some_and(|tk| normalize::month_name(&tk.text).is_some()) {
+            Some(format!("the {} of", self.convert_ordinal(d)))
+        } else {
+            None
+        }
+    }
+
+    fn convert_ordinal(&self, val: i64) -> String {
+        self.numbers.ordinal(val).unwrap_or_else(|| val.to_string())
+    }
+
+    fn number_locale(&self) -> NumberLocale {
+        self.options.number_locale.unwrap_or(self.g2p.number_locale)
+    }
+
+    /// Rewrites a number in the configured locale to plain "1234.5" form.
+    fn canonical_number(&self, word: &str) -> String {
+        match self.number_locale() {
+            NumberLocale::English => word.replace(",", ""),
+            NumberLocale::European => word
+                .replace(['.', ' ', '\u{00A0}', '\u{202F}'], "")
+                .replace(',', "."),
+        }
+    }
+
+    fn is_number(&self, word: &str) -> bool {
+        let clean = self.canonical_number(word);
+        let unsigned = Self::strip_sign(&clean).unwrap_or(&clean);
+        (!unsigned.is_empty() && unsigned.chars().all(|c| c.is_ascii_digit()))
+            || Self::split_decimal(unsigned).is_some()
+    }
+
+    /// Strips a leading ASCII hyphen-minus or Unicode minus sign (U+2212).
+    fn strip_sign(word: &str) -> Option<&str> {
+        word.strip_prefix('-').or_else(|| word.strip_prefix('−'))
+    }
+
+    /// Splits "3.14" into ("3", "14"). The integer part may be empty (".5");
+    /// the fractional part must be digits only.
+    fn split_decimal(word: &str) -> Option<(&str, &str)> {
+        let (int_part, frac_part) = word.split_once('.')?;
+        if frac_part.is_empty()
+            || !frac_part.chars().all(|c| c.is_ascii_digit())
+            || !int_part.chars().all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+        Some((int_part, frac_part))
+    }
+
+    /// Aliases abbreviations followed by a period ("Dr.", "St.") to their
+    /// expansion, letting the language pick by the neighbouring words' tags.
+    fn apply_abbreviations(&self, tokens: &mut [MToken]) {
+        for i in 0..tokens.len().saturating_sub(1) {
+            if tokens[i + 1].text != "." || tokens[i].underscore().alias.is_some() {
+                continue;
+            }
+            let prev_tag = i.checked_sub(1).map(|j| tokens[j].tag.as_str());
+            let next_tag = tokens.get(i + 2).map(|t| t.tag.as_str());
+            let Some(abbreviation) = self.rules.expand_abbreviation(&tokens[i].text, prev_tag, next_tag) else {
+                continue;
+            };
+            // Mid-sentence the period is part of the abbreviation, not a pause;
+            // a capital after it starts the next sentence, unless it is a name
+            // the abbreviation is a title of
+            let next = tokens.get(i + 2).and_then(|t| t.text.chars().next());
+            let mid_sentence = abbreviation.before_name || next.is_some_and(|c| c.is_lowercase() || c.is_numeric());
+            tokens[i].underscore_mut().alias = Some(abbreviation.expansion.to_string());
+            if mid_sentence {
+                tokens[i + 1].phonemes = Some(String::new());
+            }
+        }
+    }
+
+    /// The stress `options.stress_overrides` sets for `word`, if any.
+    fn stress_override(&self, word: &str) -> Option<Stress> {
+        if self.options.stress_overrides.is_empty() {
+            return None;
+        }
+        self.options.stress_overrides.get(&word.to_lowercase()).copied()
+    }
+
+    /// Resolves a token nothing could read according to `options.oov_policy`:
+    /// its marker, silence, or an error.
+    fn mark_unknown(&self, token: &mut MToken, word: &str) -> Result<(), G2PError> {
+        let marker = match &self.options.oov_policy {
+            OovPolicy::Mark(marker) => marker.clone(),
+            OovPolicy::Skip => String::new(),
+            OovPolicy::Error => return Err(G2PError::UnknownWord { word: word.to_string() }),
+            OovPolicy::Fallback | OovPolicy::SpellOut => self.unk.to_string(),
+        };
+        self.record_oov(word, &marker, OovSource::Unknown);
+        token.phonemes = Some(marker);
+        token.underscore_mut().rating = Some(Rating::Unknown);
+        Ok(())
+    }
+
+    /// Reads a word in a non-Latin script according to `options.script_policy`.
+    fn phonemize_script(&self, word: &str, script: Script) -> Result<String, G2PError> {
+        match self.options.script_policy {
+            ScriptPolicy::Skip => Ok(String::new()),
+            ScriptPolicy::Transliterate => match script::to_latin(word) {
+                Some(latin) => self.phonemize_spoken(&latin),
+                None => Ok(self.unk.to_string()),
+            },
+            ScriptPolicy::Route => match self.routes.get(&script) {
+                Some(route) => Ok(route.phonemize(word)?),
+                None => Ok(self.unk.to_string()),
+            },
+            ScriptPolicy::Unknown => Ok(self.unk.to_string()),
+        }
+    }
+
+    /// Silences or describes emoji tokens according to `options.emoji_policy`.
+    fn apply_emoji_policy(&self, tokens: &mut [MToken]) {
+        for tk in tokens.iter_mut().filter(|tk| normalize::is_emoji(&tk.text)) {
+            match self.options.emoji_policy {
+                EmojiPolicy::Strip => tk.phonemes = Some(String::new()),
+                EmojiPolicy::Unknown => {}
+                EmojiPolicy::Describe => {
+                    tk.underscore_mut().alias = Some(normalize::describe_emoji(&tk.text));
+                    // Descriptions of adjacent emoji ("🎉👍") are separate words
+                    if tk.whitespace.is_empty() {
+                        tk.whitespace = " ".to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sets `num_flags` from surrounding tokens: a currency symbol before a
+    /// number ("$5") and an ordinal suffix after one ("5th"). The absorbed
+    /// symbol/suffix tokens are silenced.
+    fn apply_num_heuristics(&self, tokens: &mut [MToken]) {
+        for i in 0..tokens.len() {
+            if !self.is_number(&tokens[i].text) {
+                continue;
+            }
+            if i > 0 && normalize::is_currency_symbol(&tokens[i - 1].text) {
+                let symbol = tokens[i - 1].text.clone();
+                let underscore = tokens[i].underscore_mut();
+                underscore.currency = Some(symbol);
+                underscore.num_flags.push('$');
+                tokens[i - 1].phonemes = Some(String::new());
+            }
+            if let Some(next) = tokens.get(i + 1)
+                && matches!(next.text.to_lowercase().as_str(), "st" | "nd" | "rd" | "th")
+                && tokens[i].text.chars().all(|c| c.is_ascii_digit())
+            {
+                tokens[i].underscore_mut().num_flags.push('o');
+                tokens[i + 1].phonemes = Some(String::new());
+            }
+        }
+    }
+
+    /// Reads a number token honoring its `num_flags`, falling back to `style`.
+    /// `year` allows the year reading where the flags don't settle it.
+    fn convert_flagged_number(&self, word: &str, token: &MToken, style: NumberStyle, year: bool) -> String {
+        let flags = token.underscore().num_flags.as_str();
+        let clean = self.canonical_number(word);
+        if flags.contains('$') {
+            let symbol = token.underscore().currency.as_deref().unwrap_or("$");
+            let unsigned = Self::strip_sign(&clean);
+            let spoken = self.numbers.currency(unsigned.unwrap_or(&clean), symbol);
+            let spoken = match unsigned {
+                Some(_) => spoken.and_then(|s| self.numbers.negative(&s, self.options.negative_style)),
+                None => spoken,
+            };
+            if let Some(spoken) = spoken {
+                return spoken;
+            }
+        }
+        if flags.contains('o')
+            && let Ok(val) = clean.parse::<i64>()
+        {
+            return self.convert_ordinal(val);
+        }
+        if flags.contains('y')
+            && let Ok(val) = clean.parse::<i64>()
+            && let Some(spoken) = self.numbers.year(val)
+        {
+            return spoken;
+        }
+        if flags.contains('d') {
+            return self.convert_number_with_style(word, NumberStyle::Digits, false);
+        }
+        if flags.contains('c') {
+            return self.convert_cardinal(word);
+        }
+        self.convert_number_with_style(word, style, year)
+    }
+
+    /// Reads a signed amount of money written as one word ("-$5") as a
+    /// negative amount.
+    fn convert_negative_currency(&self, word: &str) -> Option<String> {
+        let unsigned = Self::strip_sign(word)?;
+        let symbol = unsigned.chars().next()?;
+        let amount = &unsigned[symbol.len_utf8()..];
+        let symbol = symbol.to_string();
+        if !normalize::is_currency_symbol(&symbol) || !self.is_number(amount) || Self::strip_sign(amount).is_some() {
+            return None;
+        }
+        let spoken = self.numbers.currency(&self.canonical_number(amount), &symbol)?;
+        self.numbers.negative(&spoken, self.options.negative_style)
+    }
+
+    /// True if the number at token `i` stands where a year would: after "in",
+    /// "since" or "by", next to a month ("5 January, 2024") or era ("1066
+    /// AD"), or alone with no noun after it. "1500 people" counts people.
+    fn in_year_context(tokens: &[MToken], i: usize) -> bool {
+        const YEAR_WORDS: &[&str] = &["by", "in", "since"];
+        const ERAS: &[&str] = &["AD", "BC", "BCE", "CE"];
+
+        let is_month = |tk: &MToken| normalize::month_name(tk.text.trim_end_matches('.')).is_some();
+        // Skip back over the ", 5" of "January 5, 2024"
+        let mut before = tokens[..i].iter().rev().skip_while(|tk| tk.text == ",");
+        let prev = before.next();
+        if prev.is_some_and(|tk| YEAR_WORDS.contains(&tk.text.to_lowercase().as_str()) || is_month(tk)) {
+            return true;
+        }
+        if prev.is_some_and(|tk| tk.text.len() <= 2 && tk.text.chars().all(|c| c.is_ascii_digit()))
+            && before.next().is_some_and(is_month)
+        {
+            return true;
+        }
+
+        let mut after = tokens[i + 1..].iter();
+        match after.next() {
+            Some(next) if ERAS.contains(&next.text.trim_end_matches('.')) || is_month(next) => true,
+            // An adjective may come between the number and its noun ("1500 red balloons")
+            Some(next) => !std::iter::once(next)
+                .chain(after)
+                .find(|tk| !tk.tag.starts_with("JJ"))
+                .is_some_and(|tk| tk.tag.starts_with("NN")),
+            None => true,
+        }
+    }
+
+    /// True if token `i` belongs to a run of three or more adjacent plain digit
+    /// groups read digit by digit: single digits, or groups that look like a
+    /// phone number or ID, e.g. the "1 415 555 0190" of a phone number or the
+    /// "020 7946 0958" with its leading zero. Other runs ("10 20 30") are a
+    /// list of numbers.
+    fn in_digit_run(tokens: &[MToken], i: usize) -> bool {
+        let is_group = |tk: &MToken| !tk.text.is_empty() && tk.text.chars().all(|c| c.is_ascii_digit());
+        if !is_group(&tokens[i]) {
+            return false;
+        }
+        let before = tokens[..i].iter().rev().take_while(|tk| is_group(tk)).count();
+        let after = tokens[i + 1..].iter().take_while(|tk| is_group(tk)).count();
+        let run = &tokens[i - before..=i + after];
+        if run.len() < 3 {
+            return false;
+        }
+        let single_digits = run.iter().all(|tk| tk.text.len() == 1);
+        let leading_zero = run.iter().any(|tk| tk.text.len() > 1 && tk.text.starts_with('0'));
+        // Ten digits or more, ending in four like a line number
+        let phone = run[run.len() - 1].text.len() == 4 && run.iter().map(|tk| tk.text.len()).sum::<usize>() >= 10;
+        single_digits || leading_zero || phone
+    }
+
+    fn convert_number(&self, word: &str) -> String {
+        self.convert_number_with_style(word, self.options.number_style, true)
+    }
+
+    /// Reads a number in `style`, as a year if `year` allows it and it looks like one.
+    fn convert_number_with_style(&self, word: &str, style: NumberStyle, year: bool) -> String {
+        let plain_digits = !word.is_empty() && word.chars().all(|c| c.is_ascii_digit());
+        let digits = match style {
+            NumberStyle::Digits => plain_digits,
+            NumberStyle::Auto => plain_digits && word.len() > 1 && word.starts_with('0'),
+            NumberStyle::Cardinal => false,
+        };
+        if digits {
+            return self.convert_digits(word);
+        }
+
+        if self.options.year_style
+            && year
+            && Self::is_year(word)
+            && let Ok(val) = word.parse::<i64>()
+            && let Some(spoken) = self.numbers.year(val)
+        {
+            return spoken;
+        }
+        self.convert_cardinal(word)
+    }
+
+    /// Reads a digit string one digit at a time: "0190" → "zero one nine zero".
+    fn convert_digits(&self, word: &str) -> String {
+        let digits: String = word.chars().filter(char::is_ascii_digit).collect();
+        self.numbers.digits(&digits).unwrap_or_else(|| word.to_string())
+    }
+
+    /// Like `convert_number`, but never applies the year reading. Used where the
+    /// number quantifies something ("2024km").
+    fn convert_cardinal(&self, word: &str) -> String {
+        let clean = self.canonical_number(word);
+        if let Some(unsigned) = Self::strip_sign(&clean)
+            && !unsigned.is_empty()
+        {
+            let spoken = self.convert_cardinal(unsigned);
+            if spoken == unsigned {
+                return word.to_string();
+            }
+            return self.numbers.negative(&spoken, self.options.negative_style).unwrap_or_else(|| word.to_string());
+        }
+
+        if !clean.is_empty() && clean.chars().all(|c| c.is_ascii_digit()) {
+            if let Some(spoken) = self.convert_integer(&clean) {
+                return spoken;
+            }
+        } else if let Some((int_part, frac_part)) = Self::split_decimal(&clean)
+            && let Some(spoken) = self.numbers.decimal(int_part, frac_part)
+        {
+            return spoken;
+        }
+        word.to_string()
+    }
+
+    /// Reads an unsigned digit string as a cardinal, handing values beyond
+    /// `i64` to the pack.
+    fn convert_integer(&self, digits: &str) -> Option<String> {
+        match digits.parse::<i64>() {
+            Ok(val) => self.numbers.cardinal(val),
+            Err(_) => self.numbers.large_cardinal(digits),
+        }
+    }
+
+    /// Same shape the tagger normalizes to `!YEAR` (four plain digits), narrowed
+    /// to a range where reading as a year is the likely intent.
+    fn is_year(word: &str) -> bool {
+        word.len() == 4
+            && word.chars().all(|c| c.is_ascii_digit())
+            && word.parse::<i64>().is_ok_and(|y| (1100..2100).contains(&y))
+    }
+
+    /// Expands an uppercase roman numeral to words when the context makes a
+    /// numeral reading likely: cardinal after section words ("Chapter XIV"),
+    /// ordinal for short I/V/X numerals after a proper name ("Henry VIII"),
+    /// and cardinal for standalone I/V/X numerals that aren't ordinary
+    /// dictionary words, where a numeral can start.
+    fn convert_roman(&self, word: &str, prev: Option<&MToken>) -> Option<String> {
+        const SECTION_WORDS: &[&str] = &[
+            "act", "appendix", "article", "book", "chapter", "episode", "level", "part", "phase",
+            "round", "scene", "season", "section", "stage", "title", "vol", "volume", "war",
+        ];
+
+        // The only names and titles a lone "I" is read after: elsewhere it is
+        // the pronoun ("In London I met Tom")
+        const REGNAL_WORDS: &[&str] = &[
+            "alexander", "alfonso", "anne", "benedict", "boniface", "catherine", "charles", "clement",
+            "constantine", "czar", "darius", "david", "duke", "edward", "elizabeth", "emperor", "empress",
+            "ferdinand", "francis", "frederick", "george", "gregory", "gustav", "harold", "henry", "innocent",
+            "isabella", "ivan", "james", "john", "joseph", "julius", "kaiser", "king", "leo", "leopold",
+            "louis", "malcolm", "mary", "maximilian", "napoleon", "nicholas", "otto", "paul", "pedro",
+            "peter", "pharaoh", "philip", "pius", "pope", "prince", "princess", "ptolemy", "queen",
+            "ramesses", "richard", "robert", "rudolf", "selim", "sultan", "tsar", "urban", "victor",
+            "wilhelm", "william",
+        ];
+
+        let val = Self::parse_roman(word)?;
+        let prev_word = prev.map(|tk| tk.text.as_str()).unwrap_or("");
+        let prev_lower = prev_word.to_lowercase();
+        if SECTION_WORDS.contains(&prev_lower.as_str()) {
+            return self.numbers.cardinal(val);
+        }
+        if word == "I" && !REGNAL_WORDS.contains(&prev_lower.as_str()) {
+            return None;
+        }
+
+        // Regnal numerals are short and of I/V/X only ("Henry V", "Louis XIV"), so
+        // "Washington DC" and "Michael CD" keep their letters, and the name needs
+        // more than one letter so "P M" isn't read as "P the 1000th"
+        let prev_is_name = prev.is_some_and(|tk| tk.tag == "NNP")
+            && prev_word.chars().count() > 1
+            && prev_word.chars().next().is_some_and(|c| c.is_uppercase())
+            && prev_word.chars().skip(1).all(|c| c.is_lowercase());
+        let regnal = word.len() <= 5 && word.chars().all(|c| "IVX".contains(c));
+        if prev_is_name && regnal {
+            let ordinal = self.numbers.ordinal(val)?;
+            return Some(format!("the {}", ordinal));
+        }
+
+        // Standalone: "II", "XIV" where a numeral is plausible, at the start of a
+        // sentence or list item or after "the", but not acronyms like "CD", "CLI"
+        // or "VI" the editor in "Edit with VI"
+        let numeral_context = prev.is_none_or(|tk| {
+            prev_lower == "the" || !tk.text.chars().any(|c| c.is_alphanumeric())
+        });
+        let standalone = word.len() >= 2
+            && word.chars().all(|c| "IVX".contains(c))
+            && numeral_context
+            && !self.lexicon.golds.contains_key(&word.to_lowercase());
+        if standalone {
+            return self.numbers.cardinal(val);
+        }
+        None
+    }
+
+    /// Parses a canonical uppercase roman numeral (1..=3999). Non-canonical
+    /// spellings such as "IIII" or "IL" are rejected so ordinary words don't match.
+    fn parse_roman(word: &str) -> Option<i64> {
+        const NUMERALS: &[(i64, &str)] = &[
+            (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"),
+            (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+        ];
+
+        if word.is_empty() || !word.chars().all(|c| "IVXLCDM".contains(c)) {
+            return None;
+        }
+        let mut rest = word;
+        let mut val = 0;
+        for &(n, numeral) in NUMERALS {
+            while let Some(tail) = rest.strip_prefix(numeral) {
+                val += n;
+                rest = tail;
+            }
+        }
+        if !rest.is_empty() || val == 0 || val > 3999 {
+            return None;
+        }
+
+        // Re-encode and compare to reject non-canonical forms
+        let mut canonical = String::new();
+        let mut remaining = val;
+        for &(n, numeral) in NUMERALS {
+            while remaining >= n {
+                canonical.push_str(numeral);
+                remaining -= n;
+            }
+        }
+        (canonical == word).then_some(val)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{FractionStyle, NegativeStyle};
+    use crate::result::G2PWarning;
+    use crate::tagger::{PerceptronTagger, TaggerOptions};
 
     #[test]
     fn test_g2p_basic() {
@@ -324,6 +1834,1106 @@ mod tests {
         assert!(!phonemes.contains("❓"));
     }
 
+    #[test]
+    fn test_convert_number_decimal() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let g2p = g2p.call(&g2p.options);
+        assert!(g2p.is_number("3.14159"));
+        assert!(!g2p.is_number("1.2.3"));
+        assert_eq!(g2p.convert_number("3.14"), "three point one four");
+        assert_eq!(g2p.convert_number("0.05"), "zero point zero five");
+        assert_eq!(g2p.convert_number("-2.5"), "minus two point five");
+        assert_eq!(g2p.convert_number("1,000.5"), "one thousand point five");
+
+        let (phonemes, _) = g2p.g2p("3.14159").unwrap();
+        let (dot, _) = g2p.g2p("dot").unwrap();
+        assert!(!phonemes.contains("❓"));
+        assert!(!phonemes.contains(dot.trim()), "should not read the dot, got: {}", phonemes);
+    }
+
+    #[test]
+    fn test_convert_number_year() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let g2p = g2p.call(&g2p.options);
+        assert_eq!(g2p.convert_number("2024"), "twenty twenty-four");
+        assert_eq!(g2p.convert_number("1984"), "nineteen eighty-four");
+        assert_eq!(g2p.convert_number("1900"), "nineteen hundred");
+        assert_eq!(g2p.convert_number("2005"), "two thousand and five");
+        // Out of range or comma-grouped values stay cardinal
+        assert_eq!(g2p.convert_number("5000"), "five thousand");
+        assert_eq!(g2p.convert_number("1,984"), "one thousand nine hundred and eighty-four");
+
+        let options = G2POptions {
+            year_style: false,
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let g2p = g2p.call(&g2p.options);
+        assert_eq!(g2p.convert_number("2024"), "two thousand and twenty-four");
+    }
+
+    #[test]
+    fn test_year_context() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let read = |text: &str| g2p.g2p(text).unwrap().0;
+        let cases = [
+            ("1500 people came.", "one thousand five hundred people came."),
+            ("In 1999 we met.", "In nineteen ninety-nine we met."),
+            ("We met since 1984.", "We met since nineteen eighty-four."),
+            ("Done by 2030.", "Done by twenty thirty."),
+            ("It ended 1453 AD.", "It ended fourteen fifty-three AD."),
+            ("On 5 January, 2024.", "On 5 January, twenty twenty-four."),
+            ("1984 was cold.", "nineteen eighty-four was cold."),
+        ];
+        for (text, spoken) in cases {
+            assert_eq!(read(text), read(spoken), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_roman_numerals() {
+        assert_eq!(Call::parse_roman("XIV"), Some(14));
+        assert_eq!(Call::parse_roman("MCMXC"), Some(1990));
+        assert_eq!(Call::parse_roman("IIII"), None);
+        assert_eq!(Call::parse_roman("IL"), None);
+        assert_eq!(Call::parse_roman("Xiv"), None);
+
+        let g2p = G2P::new(Language::EnglishUS);
+        let g2p = g2p.call(&g2p.options);
+        let chapter = MToken::new("CHAPTER".to_string(), "NN".to_string(), " ".to_string());
+        let henry = MToken::new("Henry".to_string(), "NNP".to_string(), " ".to_string());
+        assert_eq!(g2p.convert_roman("XIV", Some(&chapter)).as_deref(), Some("fourteen"));
+        assert_eq!(g2p.convert_roman("I", Some(&chapter)).as_deref(), Some("one"));
+        assert_eq!(g2p.convert_roman("VIII", Some(&henry)).as_deref(), Some("the eighth"));
+        assert_eq!(g2p.convert_roman("XIV", None).as_deref(), Some("fourteen"));
+        assert_eq!(g2p.convert_roman("I", None), None);
+        assert_eq!(g2p.convert_roman("MIX", None), None);
+        assert_eq!(g2p.convert_roman("CD", None), None);
+        let the = MToken::new("the".to_string(), "DT".to_string(), " ".to_string());
+        assert_eq!(g2p.convert_roman("XII", Some(&the)).as_deref(), Some("twelve"));
+        let with = MToken::new("with".to_string(), "IN".to_string(), " ".to_string());
+        assert_eq!(g2p.convert_roman("VI", Some(&with)), None);
+        // Acronyms that happen to be canonical numerals
+        for acronym in ["CLI", "MDC", "DC"] {
+            assert_eq!(g2p.convert_roman(acronym, None), None, "{}", acronym);
+            assert_eq!(g2p.convert_roman(acronym, Some(&the)), None, "{}", acronym);
+        }
+        let p = MToken::new("P".to_string(), "NNP".to_string(), " ".to_string());
+        assert_eq!(g2p.convert_roman("M", Some(&p)), None);
+        // Acronyms after a name aren't regnal numbers
+        let washington = MToken::new("Washington".to_string(), "NNP".to_string(), " ".to_string());
+        assert_eq!(g2p.convert_roman("DC", Some(&washington)), None);
+        let michael = MToken::new("Michael".to_string(), "NNP".to_string(), " ".to_string());
+        assert_eq!(g2p.convert_roman("CD", Some(&michael)), None);
+        let (_, tokens) = g2p.g2p("He lives in Washington DC now.").unwrap();
+        let dc = tokens.iter().find(|tk| tk.text == "DC").unwrap();
+        let (numeral, _) = g2p.g2p("the six hundredth").unwrap();
+        assert_ne!(dc.phonemes.as_deref(), Some(numeral.trim_end()));
+        let london = MToken::new("London".to_string(), "NNP".to_string(), " ".to_string());
+        assert_eq!(g2p.convert_roman("I", Some(&london)), None);
+        assert_eq!(g2p.convert_roman("I", Some(&henry)).as_deref(), Some("the first"));
+
+        // The pronoun after a capitalized word
+        for text in ["In London I met Tom", "On Monday I went"] {
+            let (_, tokens) = g2p.g2p(text).unwrap();
+            let i = tokens.iter().find(|tk| tk.text == "I").unwrap();
+            assert_eq!(i.phonemes, g2p.g2p("I").unwrap().1[0].phonemes, "{}", text);
+        }
+        let (regnal, _) = g2p.g2p("King Henry I").unwrap();
+        let (spoken, _) = g2p.g2p("King Henry the first").unwrap();
+        assert_eq!(regnal, spoken);
+
+        // Acronyms in running text keep their letters
+        let cases = [
+            ("Use the CLI tool.", "Use the one hundred fifty-one tool."),
+            ("The MDC report.", "The one thousand six hundred report."),
+            ("Edit with VI now.", "Edit with six now."),
+        ];
+        for (text, numeral) in cases {
+            assert_ne!(g2p.g2p(text).unwrap().0, g2p.g2p(numeral).unwrap().0, "{}", text);
+        }
+
+        let (roman, _) = g2p.g2p("CHAPTER XIV").unwrap();
+        let (spoken, _) = g2p.g2p("CHAPTER fourteen").unwrap();
+        assert_eq!(roman, spoken);
+    }
+
+    #[test]
+    fn test_units_and_percent() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("100%", "one hundred percent"),
+            ("-5°C", "minus five degrees Celsius"),
+            ("10km", "ten kilometers"),
+            ("3kg", "three kilograms"),
+            ("2024km", "two thousand and twenty-four kilometers"),
+            ("10 km", "ten kilometers"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim_end(), expected.trim_end(), "Failed for '{}'", text);
+        }
+
+        let (_, tokens) = g2p.g2p("(10km).").unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|tk| tk.text.as_str()).collect();
+        assert_eq!(texts, ["(", "10km", ")", "."]);
+        assert_eq!(tokens[1].underscore().alias.as_deref(), Some("ten kilometers"));
+    }
+
+    #[test]
+    fn test_clock_times() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("3:30pm", "three thirty PM"),
+            ("14:05", "fourteen oh five"),
+            ("at 3:30 pm", "at three thirty PM"),
+            ("7 am", "seven AM"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim_end(), expected.trim_end(), "Failed for '{}'", text);
+        }
+    }
+
+    #[test]
+    fn test_dates() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("2024-01-05", "January fifth, twenty twenty-four"),
+            ("5/1/2024", "May first, twenty twenty-four"),
+            ("on Jan 5", "on January fifth"),
+            ("Jan. 5, 2024", "January. fifth, twenty twenty-four"),
+            ("5 March", "the fifth of March"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim_end(), expected.trim_end(), "Failed for '{}'", text);
+        }
+
+        let g2p_gb = G2P::new(Language::EnglishGB);
+        let (p, _) = g2p_gb.g2p("5/1/2024").unwrap();
+        let (expected, _) = g2p_gb.g2p("the fifth of January, twenty twenty-four").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+    }
+
+    #[test]
+    fn test_fractions() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let (p, _) = g2p.g2p("1/2").unwrap();
+        let (expected, _) = g2p.g2p("one half").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+
+        let options = G2POptions {
+            fraction_style: FractionStyle::Over,
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let (p, _) = g2p.g2p("3/4").unwrap();
+        let (expected, _) = g2p.g2p("three over four").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+    }
+
+    #[test]
+    fn test_number_style() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let g2p = g2p.call(&g2p.options);
+        assert_eq!(g2p.convert_number("0190"), "zero one nine zero");
+        assert_eq!(g2p.convert_number("42"), "forty-two");
+
+        // Long numbers are still counts; only phone-number shapes are read digit by digit
+        let (p, _) = g2p.g2p("The world has 7800000000 people.").unwrap();
+        let (expected, _) = g2p.g2p("The world has seven billion eight hundred million people.").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+        let (_, tokens) = g2p.g2p("Call +14155550190").unwrap();
+        let (expected, _) = g2p.g2p("one four one five five five five zero one nine zero").unwrap();
+        assert_eq!(tokens[2].phonemes.as_deref(), Some(expected.trim_end()));
+        // A short signed number isn't a phone number
+        let (_, tokens) = g2p.g2p("+10 points").unwrap();
+        let (expected, _) = g2p.g2p("ten").unwrap();
+        assert_eq!(tokens[1].phonemes.as_deref(), Some(expected.trim_end()));
+
+        let (p, _) = g2p.g2p("415 555 0190").unwrap();
+        let (expected, _) = g2p.g2p("four one five five five five zero one nine zero").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+        let (p, _) = g2p.g2p("020 7946 0958").unwrap();
+        let (expected, _) = g2p.g2p("zero two zero seven nine four six zero nine five eight").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+        // A list of numbers, not a phone number
+        let (p, _) = g2p.g2p("10 20 30").unwrap();
+        let (expected, _) = g2p.g2p("ten twenty thirty").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+        let (p, _) = g2p.g2p("scores of 100 250 375").unwrap();
+        let (expected, _) = g2p.g2p("scores of one hundred two hundred fifty three hundred seventy-five").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+
+        let options = G2POptions {
+            number_style: NumberStyle::Digits,
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let g2p = g2p.call(&g2p.options);
+        assert_eq!(g2p.convert_number("42"), "four two");
+        assert_eq!(g2p.convert_number("3.5"), "three point five");
+
+        let options = G2POptions {
+            number_style: NumberStyle::Cardinal,
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let g2p = g2p.call(&g2p.options);
+        assert_eq!(g2p.convert_number("0190"), "one hundred ninety");
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let g2p = g2p.call(&g2p.options);
+        assert!(g2p.is_number("−3.2"));
+        assert_eq!(g2p.convert_number("-5"), "minus five");
+        assert_eq!(g2p.convert_number("−3.2"), "minus three point two");
+        assert_eq!(g2p.convert_number("-0.5"), "minus zero point five");
+
+        let cases = [
+            ("−3.2", "minus three point two"),
+            ("It was -5 degrees.", "It was minus five degrees."),
+            ("Set it to -5.", "Set it to minus five."),
+            ("(-5)", "(minus five)"),
+            ("-$5", "minus five dollars"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim(), expected.trim(), "{}", text);
+        }
+
+        let options = G2POptions {
+            negative_style: NegativeStyle::Negative,
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let g2p = g2p.call(&g2p.options);
+        assert_eq!(g2p.convert_number("-5"), "negative five");
+    }
+
+    #[test]
+    fn test_number_ranges() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("5-10", "five to ten"),
+            ("1939–1945", "nineteen thirty-nine to nineteen forty-five"),
+            ("pages 5-10.", "pages five to ten."),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim_end(), expected.trim_end(), "Failed for '{}'", text);
+        }
+    }
+
+    #[test]
+    fn test_large_and_scientific_numbers() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let g2p = g2p.call(&g2p.options);
+        assert_eq!(
+            g2p.convert_number("12,000,000,000,000,000,000,000"),
+            "twelve sextillion"
+        );
+        assert_eq!(
+            g2p.convert_cardinal("98765432109876543210"),
+            "ninety-eight quintillion seven hundred sixty-five quadrillion four hundred thirty-two trillion one hundred nine billion eight hundred seventy-six million five hundred forty-three thousand two hundred and ten"
+        );
+        assert_eq!(g2p.convert_cardinal("1.5"), "one point five");
+        assert!(g2p.is_number("98765432109876543210"));
+
+        let cases = [
+            (
+                "The number 123456789012345678901234 is big.",
+                "The number one hundred twenty-three sextillion four hundred fifty-six quintillion seven hundred eighty-nine quadrillion twelve trillion three hundred forty-five billion six hundred seventy-eight million nine hundred one thousand two hundred and thirty-four is big.",
+            ),
+            ("1e9", "one times ten to the nine"),
+            ("3.5e-4", "three point five times ten to the minus four"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim_end(), expected.trim_end(), "Failed for '{}'", text);
+        }
+    }
+
+    #[test]
+    fn test_version_numbers() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("Version 2.0", "Version two point oh"),
+            ("1.21.3", "one point twenty-one point three"),
+            ("v2.0", "version two point oh"),
+            ("version 3", "version three"),
+            ("3.14", "three point one four"),
+            // Product names don't announce versions
+            ("Python 3.12", "Python three point one two"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim_end(), expected.trim_end(), "Failed for '{}'", text);
+        }
+        let (version, _) = g2p.g2p("version").unwrap();
+        let (p, _) = g2p.g2p("the V8 engine").unwrap();
+        assert!(!p.contains(version.trim()), "V8 read as a version: {p}");
+    }
+
+    #[test]
+    fn test_number_locale() {
+        let options = G2POptions {
+            number_locale: Some(NumberLocale::European),
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let g2p = g2p.call(&g2p.options);
+        assert!(g2p.is_number("1.234.567,89"));
+        assert_eq!(
+            g2p.convert_number("1.234.567,89"),
+            "one million two hundred thirty-four thousand five hundred and sixty-seven point eight nine"
+        );
+        assert_eq!(g2p.convert_number("3,5"), "three point five");
+
+        let (p, tokens) = g2p.g2p("1 000 000 people").unwrap();
+        assert_eq!(tokens[0].text, "1 000 000");
+        let (expected, _) = g2p.g2p("one million people").unwrap();
+        assert_eq!(p.trim_end(), expected.trim_end());
+    }
+
+    #[test]
+    fn test_addresses() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("See https://example.com/docs.", "See example dot com slash docs."),
+            ("Mail john@example.org today", "Mail john at example dot org today"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim(), expected.trim(), "Failed for '{}'", text);
+        }
+
+        let options = G2POptions { read_addresses: false, ..Default::default() };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let (_, tokens) = g2p.g2p("example.com").unwrap();
+        assert!(tokens.iter().all(|t| t.underscore().alias.is_none()));
+    }
+
+    #[test]
+    fn test_handles() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("#MachineLearning", "hashtag machine learning"),
+            ("thanks @openai!", "thanks at open AI!"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim(), expected.trim(), "Failed for '{}'", text);
+        }
+
+        let options = G2POptions { speak_sigils: false, ..Default::default() };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let (_, tokens) = g2p.g2p("#MachineLearning").unwrap();
+        assert_eq!(tokens[0].underscore().alias.as_deref(), Some("machine learning"));
+    }
+
+    #[test]
+    fn test_transliteration() {
+        let mut g2p = G2P::new(Language::EnglishUS);
+        let (p, _) = g2p.g2p("naïve").unwrap();
+        let (expected, _) = g2p.g2p("naive").unwrap();
+        assert_eq!(p, expected);
+
+        g2p.transliterator.insert('ø', "o");
+        let (p, _) = g2p.g2p("høuse").unwrap();
+        let (expected, _) = g2p.g2p("house").unwrap();
+        assert_eq!(p, expected);
+
+        g2p.transliterator = Transliterator::empty();
+        let (_, tokens) = g2p.g2p("é").unwrap();
+        assert_eq!(tokens[0].phonemes.as_deref(), Some(g2p.unk.as_str()));
+    }
+
+    #[test]
+    fn test_script_policy() {
+        struct Echo;
+        impl Fallback for Echo {
+            fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+                Ok(format!("<{}>", word))
+            }
+        }
+
+        let with_policy = |script_policy| {
+            let options = G2POptions { script_policy, ..Default::default() };
+            G2P::with_options(Language::EnglishUS, options)
+        };
+        let hello = G2P::new(Language::EnglishUS).g2p("Hello").unwrap().0;
+        let hello = hello.trim();
+
+        let (p, tokens) = with_policy(ScriptPolicy::Unknown).g2p("Hello 世界").unwrap();
+        assert_eq!(p.trim(), format!("{} ❓", hello));
+        assert_eq!(tokens.len(), 2);
+
+        let (p, _) = with_policy(ScriptPolicy::Skip).g2p("Hello 世界").unwrap();
+        assert_eq!(p.trim(), hello);
+
+        let g2p = with_policy(ScriptPolicy::Transliterate);
+        let (p, _) = g2p.g2p("Здравствуй").unwrap();
+        let (expected, _) = g2p.g2p("Zdravstvuy").unwrap();
+        assert_eq!(p, expected);
+
+        let mut g2p = with_policy(ScriptPolicy::Route);
+        g2p.route_script(Script::Han, Box::new(Echo));
+        let (p, _) = g2p.g2p("世界 Здравствуй").unwrap();
+        assert_eq!(p.trim(), "<世界> ❓");
+    }
+
+    #[test]
+    fn test_heteronyms() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("I have read it", "I have red it"),
+            ("the lead pipe", "the led pipe"),
+            ("Wear and tear", "Wear and tare"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p, expected, "Failed for '{}'", text);
+        }
+
+        let (p, _) = g2p.g2p("I will read it").unwrap();
+        assert!(p.contains("ɹˈiːd"), "{}", p);
+    }
+
+    #[test]
+    fn test_custom_tagger() {
+        // Tags every word as a past tense verb
+        let past = |words: &[&str]| vec!["VBD".to_string(); words.len()];
+        let g2p = G2P::builder(Language::EnglishUS).tagger(Box::new(past)).build();
+        let (_, tokens) = g2p.g2p("read").unwrap();
+        assert_eq!(tokens[0].tag, "VBD");
+        assert!(tokens[0].phonemes.as_deref().unwrap().contains('ɛ'));
+
+        let mut g2p = G2P::new(Language::EnglishUS);
+        g2p.set_tagger(Box::new(|words: &[&str]| vec!["VB".to_string(); words.len()]));
+        let (_, tokens) = g2p.g2p("read").unwrap();
+        assert!(tokens[0].phonemes.as_deref().unwrap().contains('i'));
+    }
+
+    #[cfg(feature = "spanish")]
+    #[test]
+    fn test_pack_tagger() {
+        // Spanish has no tagger, so the English one doesn't tag its words
+        let g2p = G2P::new(Language::Spanish);
+        let (_, tokens) = g2p.g2p("Ella canta bien").unwrap();
+        assert!(tokens.iter().all(|tk| tk.tag == "NN"));
+        let (_, tokens) = G2P::new(Language::EnglishUS).g2p("She sings well").unwrap();
+        assert_eq!(tokens[0].tag, "PRP");
+    }
+
+    #[test]
+    fn test_caps_heading() {
+        // Read like the sentence-case text, not as a string of names spelled out
+        let g2p = G2P::new(Language::EnglishUS);
+        let (_, tokens) = g2p.g2p("BREAKING NEWS: NASA LAUNCHES NEW ROCKET FROM FLORIDA").unwrap();
+        let from = tokens.iter().find(|tk| tk.text == "FROM").unwrap();
+        assert_eq!(from.tag, "IN");
+        let (spelled, _) = g2p.g2p("F R O M").unwrap();
+        assert_ne!(from.phonemes.as_deref(), Some(spelled.trim_end()));
+        assert!(from.phonemes.as_deref().unwrap().starts_with("fɹ"));
+    }
+
+    #[test]
+    fn test_uncertain_tags() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let (_, tokens) = g2p.g2p("Yesterday I read the book").unwrap();
+        assert_eq!(tokens[2].phonemes.as_deref(), Some("ɹˈɛd"));
+
+        // Below the threshold the tag is ignored and "read" keeps its usual reading
+        let options = TaggerOptions { min_confidence: 0.95, ..Default::default() };
+        let tagger = PerceptronTagger::english().with_options(options);
+        let g2p = G2P::builder(Language::EnglishUS).tagger(Box::new(tagger)).build();
+        let (_, tokens) = g2p.g2p("Yesterday I read the book").unwrap();
+        assert_ne!(tokens[2].phonemes.as_deref(), Some("ɹˈɛd"));
+    }
+
+    #[test]
+    fn test_builder() {
+        let options = G2POptions { year_style: false, ..Default::default() };
+        let g2p = G2P::builder(Language::EnglishUS).options(options).dialect(Dialect::GB).build();
+        assert!(!g2p.options.year_style);
+        // British phonemes for OOV words, without the r
+        let (_, tokens) = g2p.g2p("Zorbington").unwrap();
+        assert!(!tokens[0].phonemes.as_deref().unwrap().contains('ɹ'));
+        // Capitalized, so a name even if not tagged one: not "zorbing" + "ton"
+        assert_eq!(tokens[0].underscore().rating, Some(Rating::Fallback));
+
+        let g2p = G2P::builder(Language::EnglishUS).fallback(None).build();
+        let (_, tokens) = g2p.g2p("zorbified").unwrap();
+        assert!(tokens[0].phonemes.as_deref().unwrap().contains(' '));
+    }
+
+    #[cfg(feature = "spanish")]
+    #[test]
+    fn test_builder_non_english() {
+        // No English letter-to-sound rules for a language that isn't English
+        let g2p = G2P::builder(Language::Spanish).build();
+        assert!(g2p.fallback.is_none());
+        let plain = G2P::new(Language::Spanish);
+        assert_eq!(g2p.g2p("Hola, señor Zorbington.").unwrap().0, plain.g2p("Hola, señor Zorbington.").unwrap().0);
+    }
+
+    #[test]
+    fn test_post_process() {
+        let g2p = G2P::builder(Language::EnglishUS)
+            .post_process(|tk: &MToken, ps: &str| if tk.text.eq_ignore_ascii_case("darn") { "bˈip".to_string() } else { ps.to_string() })
+            .post_process(|_: &MToken, ps: &str| ps.replace('ɹ', "r"))
+            .options(G2POptions { output_format: OutputFormat::Ipa, ..Default::default() })
+            .build();
+        let plain = G2P::new(Language::EnglishUS);
+        let (_, tokens) = g2p.g2p("Darn, the red car.").unwrap();
+        assert_eq!(tokens[0].phonemes.as_deref(), Some("bˈip"));
+        // Later hooks see earlier ones' output, in the lexicon's notation
+        let red = plain.g2p("red").unwrap().0.replace('ɹ', "r");
+        assert_eq!(tokens[3].phonemes, Some(phonology::convert(&red, OutputFormat::Ipa)));
+    }
+
+    #[test]
+    fn test_stages() {
+        // A domain normalizer, before the lexicon reads its aliases
+        let expand = |tokens: &mut Vec<MToken>, _: &PipelineCtx| {
+            for tk in tokens.iter_mut().filter(|tk| tk.text == "kube") {
+                tk.underscore_mut().alias = Some("kubernetes".to_string());
+            }
+        };
+        // Bleeping, once words are read
+        let bleep = |tokens: &mut Vec<MToken>, ctx: &PipelineCtx| {
+            for tk in tokens.iter_mut().filter(|tk| tk.text.eq_ignore_ascii_case("darn")) {
+                tk.phonemes = Some(ctx.unk.to_string());
+            }
+        };
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = |name: &'static str| {
+            let order = order.clone();
+            move |tokens: &mut Vec<MToken>, ctx: &PipelineCtx| order.lock().unwrap().push((name, ctx.text.to_string(), tokens.len()))
+        };
+        let g2p = G2P::builder(Language::EnglishUS)
+            .stage(BuiltinStage::Prosody, log("prosody"))
+            .stage(BuiltinStage::Normalize, expand)
+            .stage(BuiltinStage::Fallback, bleep)
+            .stage(BuiltinStage::Tokenize, log("tokenize"))
+            .build();
+
+        let (_, tokens) = g2p.g2p("Darn kube.").unwrap();
+        // Stages run after their built-in, and not for the alias read within
+        let text = "Darn kube.".to_string();
+        assert_eq!(*order.lock().unwrap(), [("tokenize", text.clone(), 3), ("prosody", text, 3)]);
+        assert_eq!(tokens[0].phonemes.as_deref(), Some(g2p.unk.as_str()));
+        assert_eq!(tokens[1].phonemes, g2p.g2p("kubernetes").unwrap().1[0].phonemes);
+    }
+
+    #[test]
+    fn test_english_dialects() {
+        let (ps, _) = G2P::new(Language::EnglishAU).g2p("better").unwrap();
+        assert_eq!(ps, "bˈɛɾə");
+        let (ps, _) = G2P::new(Language::EnglishSC).g2p("car").unwrap();
+        assert_eq!(ps, "kˈaɹ");
+        // The builder's dialect accents the language's lexicon
+        let g2p = G2P::builder(Language::EnglishGB).dialect(Dialect::Indian).build();
+        let (ps, _) = g2p.g2p("the thing").unwrap();
+        assert_eq!(ps, "də tˈɪŋ");
+    }
+
+    #[test]
+    fn test_oov_recorder() {
+        let mut g2p = G2P::new(Language::EnglishUS);
+        let recorder = Arc::new(OovRecorder::new());
+        g2p.set_oov_recorder(recorder.clone());
+        g2p.g2p("Zorbington met the cat and Zorbington left ☃").unwrap();
+
+        let entries = recorder.entries();
+        assert_eq!(entries[0].word, "Zorbington");
+        assert_eq!(entries[0].count, 2);
+        assert_eq!(entries[0].source, OovSource::Fallback);
+        assert!(entries.iter().any(|e| e.word == "☃" && e.source == OovSource::Unknown));
+        assert!(!entries.iter().any(|e| e.word == "cat"));
+    }
+
+    #[test]
+    fn test_oov_fallback() {
+        let mut g2p = G2P::new(Language::EnglishUS);
+        let (_, tokens) = g2p.g2p("the zorbified plinth").unwrap();
+        assert!(tokens.iter().all(|t| t.phonemes.as_deref().is_some_and(|p| !p.contains('❓'))));
+        assert!(tokens[1].phonemes.as_deref().unwrap().starts_with('z'));
+
+        // Without a fallback, unknown words are spelled out
+        g2p.set_fallback(None);
+        let (_, tokens) = g2p.g2p("zorbified").unwrap();
+        assert!(tokens[0].phonemes.as_deref().unwrap().contains(' '));
+
+        // The fallback gets the unknown words in one batch; failures are spelled out
+        struct Batches(std::sync::Mutex<Vec<Vec<String>>>);
+        impl Fallback for Batches {
+            fn phonemize(&self, _word: &str) -> Result<String, FallbackError> {
+                unreachable!("words are batched")
+            }
+            fn phonemize_batch(&self, words: &[&str]) -> Vec<(String, u8)> {
+                self.0.lock().unwrap().push(words.iter().map(|w| w.to_string()).collect());
+                words.iter().map(|&w| if w == "qwxz" { (String::new(), 0) } else { (format!("<{}>", w), 1) }).collect()
+            }
+        }
+        let batches = Arc::new(Batches(std::sync::Mutex::new(Vec::new())));
+        struct Shared(Arc<Batches>);
+        impl Fallback for Shared {
+            fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+                self.0.phonemize(word)
+            }
+            fn phonemize_batch(&self, words: &[&str]) -> Vec<(String, u8)> {
+                self.0.phonemize_batch(words)
+            }
+        }
+        g2p.set_fallback(Some(Box::new(Shared(batches.clone()))));
+        let (_, tokens) = g2p.g2p("the zorbified plinth qwxz").unwrap();
+        let batch = batches.0.lock().unwrap().clone();
+        assert_eq!(batch.len(), 1);
+        let mut words = batch[0].clone();
+        words.sort();
+        assert_eq!(words, ["qwxz", "zorbified"]);
+        assert_eq!(tokens[1].phonemes.as_deref(), Some("<zorbified>"));
+        assert!(tokens[3].phonemes.as_deref().unwrap().contains(' '));
+    }
+
+    #[test]
+    fn test_compound_oov() {
+        // Split before the fallback gets a say, not spelled out without one
+        let g2p = G2P::builder(Language::EnglishUS).fallback(None).build();
+        let (_, tokens) = g2p.g2p("my catbowl").unwrap();
+        let ps = tokens[1].phonemes.as_deref().unwrap();
+        assert!(ps.starts_with("kˈæt") && ps.contains('ˌ') && !ps.contains(' '));
+        assert_eq!(tokens[1].underscore().rating, Some(Rating::Stemmed));
+    }
+
+    #[test]
+    fn test_oov_policy() {
+        let with_policy = |oov_policy| G2P::with_options(Language::EnglishUS, G2POptions { oov_policy, ..Default::default() });
+        let read = |g2p: &G2P| g2p.g2p("the zorbified cat").map(|(_, tokens)| tokens[1].phonemes.clone().unwrap());
+
+        let guessed = read(&with_policy(OovPolicy::Fallback)).unwrap();
+        assert!(guessed.starts_with('z') && !guessed.contains(' '));
+        assert!(read(&with_policy(OovPolicy::SpellOut)).unwrap().contains(' '));
+        assert_eq!(read(&with_policy(OovPolicy::Mark("<unk>".to_string()))).unwrap(), "<unk>");
+        assert_eq!(read(&with_policy(OovPolicy::Skip)).unwrap(), "");
+        assert!(matches!(
+            read(&with_policy(OovPolicy::Error)),
+            Err(G2PError::UnknownWord { word }) if word == "zorbified"
+        ));
+        // Known words never reach the policy
+        assert!(with_policy(OovPolicy::Error).g2p("the cat").is_ok());
+        // Nor are names guessed from their endings under another
+        let name = |g2p: &G2P| g2p.g2p("Zorbington met the cat").unwrap().1.remove(0);
+        let guessed = name(&with_policy(OovPolicy::Fallback));
+        assert!(guessed.phonemes.as_deref().unwrap().ends_with("tən"));
+        assert_eq!(guessed.underscore().rating, Some(Rating::Fallback));
+        assert_eq!(name(&with_policy(OovPolicy::Mark("<unk>".to_string()))).phonemes.unwrap(), "<unk>");
+    }
+
+    #[test]
+    fn test_g2p_with() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let options = G2POptions {
+            unk: Some("?".to_string()),
+            oov_policy: OovPolicy::Mark("<unk>".to_string()),
+            number_style: NumberStyle::Digits,
+            stress_overrides: HashMap::from([("cat".to_string(), Stress::Remove)]),
+            ..Default::default()
+        };
+        let (_, tokens) = g2p.g2p_with("the zorbified Cat 42", &options).unwrap();
+        assert_eq!(tokens[1].phonemes.as_deref(), Some("<unk>"));
+        assert_eq!(tokens[2].phonemes.as_deref(), Some("kæt"));
+        assert_eq!(tokens[3].phonemes, g2p.g2p("four two").map(|(p, _)| Some(p)).unwrap());
+        let options = G2POptions { emoji_policy: EmojiPolicy::Unknown, oov_policy: OovPolicy::Fallback, ..options };
+        assert_eq!(g2p.g2p_with("🎉", &options).unwrap().0, "?");
+        let options = G2POptions { unk: Some("<?>".to_string()), ..options };
+        let (phonemes, mut tokens) = g2p.g2p_with("🎉 cat", &options).unwrap();
+        // A token left without phonemes is aligned as the marker it was read as
+        tokens[0].phonemes = None;
+        let last = *g2p.align_with(&tokens, &options).last().unwrap();
+        assert_eq!(phonemes.chars().nth(last.index), Some(last.phoneme));
+        assert_ne!(g2p.align(&tokens).last(), Some(&last));
+
+        // The G2P's own options are untouched
+        let (_, tokens) = g2p.g2p("the zorbified Cat 42").unwrap();
+        assert_ne!(tokens[1].phonemes.as_deref(), Some("<unk>"));
+        assert_eq!(tokens[2].phonemes.as_deref(), Some("kˈæt"));
+        assert_eq!(g2p.g2p_with("the cat", &g2p.options).unwrap(), g2p.g2p("the cat").unwrap());
+    }
+
+    #[test]
+    fn test_smart_quotes() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let (p, _) = g2p.g2p("I don’t know, “she” said").unwrap();
+        let (expected, _) = g2p.g2p("I don't know, \"she\" said").unwrap();
+        assert_eq!(p, expected);
+
+        let (p, _) = g2p.g2p("zero\u{200B}width").unwrap();
+        let (expected, _) = g2p.g2p("zerowidth").unwrap();
+        assert_eq!(p, expected);
+    }
+
+    #[test]
+    fn test_pause_markers() {
+        let text = "Well, wait... Now — go!\n\nNext";
+        let g2p = G2P::new(Language::EnglishUS);
+        let (p, tokens) = g2p.g2p(text).unwrap();
+        assert!(!p.contains([',', '.', '—', '!', '…']));
+        assert!(tokens.iter().all(|t| t.pause.is_none()));
+
+        let options = G2POptions {
+            pause_markers: Some(PauseMarkers::default()),
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let (p, tokens) = g2p.g2p(text).unwrap();
+        let markers: String = p.chars().filter(|c| ",.—!?…".contains(*c)).collect();
+        assert_eq!(markers, ",…—!…");
+        let pause = |text: &str| tokens.iter().find(|t| t.text == text).and_then(|t| t.pause);
+        assert_eq!(pause(","), Some(timing::pause_after(",")));
+        assert_eq!(pause("!"), Some(timing::PARAGRAPH_PAUSE));
+        assert_eq!(pause("Next"), None);
+
+        let options = G2POptions {
+            pause_markers: Some(PauseMarkers { clause: "<sp>".to_string(), ..Default::default() }),
+            ..Default::default()
+        };
+        let (p, _) = G2P::with_options(Language::EnglishUS, options).g2p("Yes, no").unwrap();
+        assert!(p.contains("<sp>"));
+    }
+
+    #[test]
+    fn test_intonation() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let (_, tokens) = g2p.g2p("Mr. Smith is here. Is he?! Wow! \"Really?\" Yes...").unwrap();
+        let ends: Vec<(&str, Intonation)> = tokens
+            .iter()
+            .filter_map(|t| Some((t.text.as_str(), t.underscore().intonation?)))
+            .collect();
+        assert_eq!(
+            ends,
+            [
+                (".", Intonation::Statement),
+                ("!", Intonation::Question),
+                ("!", Intonation::Exclamation),
+                ("?", Intonation::Question),
+                (".", Intonation::Statement),
+            ]
+        );
+
+        let (_, tokens) = g2p.g2p("No punctuation here").unwrap();
+        assert_eq!(tokens[2].underscore().intonation, Some(Intonation::Statement));
+    }
+
+    #[test]
+    fn test_input_format() {
+        let with_format = |input_format| {
+            let options = G2POptions { input_format, ..Default::default() };
+            G2P::with_options(Language::EnglishUS, options)
+        };
+        let plain = G2P::new(Language::EnglishUS);
+        let (expected, _) = plain.g2p("Intro. Read the docs.").unwrap();
+
+        let (p, _) = with_format(InputFormat::Markdown).g2p("## Intro\nRead *the* [docs](https://docs.rs).").unwrap();
+        assert_eq!(p.split_whitespace().collect::<Vec<_>>(), expected.split_whitespace().collect::<Vec<_>>());
+        let (p, _) = with_format(InputFormat::Html).g2p("<h2>Intro</h2><p>Read <em>the</em> docs.</p>").unwrap();
+        assert_eq!(p.split_whitespace().collect::<Vec<_>>(), expected.split_whitespace().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_emoji_policy() {
+        let text = "Done 🎉👍🏽";
+        let with_policy = |emoji_policy| {
+            let options = G2POptions { emoji_policy, ..Default::default() };
+            G2P::with_options(Language::EnglishUS, options)
+        };
+
+        let g2p = with_policy(EmojiPolicy::Strip);
+        let (p, tokens) = g2p.g2p(text).unwrap();
+        let (expected, _) = g2p.g2p("Done").unwrap();
+        assert_eq!(p.trim(), expected.trim());
+        assert_eq!(tokens.len(), 3);
+
+        let g2p = with_policy(EmojiPolicy::Unknown);
+        let (_, tokens) = g2p.g2p(text).unwrap();
+        assert!(tokens[1..].iter().all(|t| t.phonemes.as_deref() != Some("") && t.underscore().alias.is_none()));
+
+        let g2p = with_policy(EmojiPolicy::Describe);
+        let (p, _) = g2p.g2p(text).unwrap();
+        let (expected, _) = g2p.g2p("Done party popper emoji thumbs up emoji").unwrap();
+        assert_eq!(p.trim(), expected.trim());
+    }
+
+    #[test]
+    fn test_initialisms() {
+        let g2p = G2P::new(Language::EnglishUS);
+        // Read letter by letter, with the final letter taking primary stress
+        let cases = [
+            ("a Ph.D. from", "ɐ pˌiˌAʧdˈi fɹʌm"),
+            ("the U.S. economy", "ði jˌuˈɛs ɪkˈɑːnəmi"),
+            ("Is it A.I.?", "ˌɪz ɪɾ ˌAˈI"),
+        ];
+        for (text, expected) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            assert_eq!(p.trim(), expected, "Failed for '{}'", text);
+        }
+
+        let (_, tokens) = g2p.g2p("(U.S.A.),").unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, ["(", "U.S.A.", ")", ","]);
+    }
+
+    #[test]
+    fn test_abbreviations() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("Dr. Smith", "Doctor Smith"),
+            ("on Main St. today", "on Main Street today"),
+            ("near St. Paul", "near Saint Paul"),
+            ("apples, pears, etc.", "apples, pears, et cetera."),
+            ("apples, pears, etc. The end", "apples, pears, et cetera. The end"),
+            ("apples etc. and pears", "apples et cetera and pears"),
+            ("Smith vs. Jones", "Smith versus Jones"),
+            ("on the 3rd. Then", "on the third. Then"),
+            ("I live on Mulholland Dr. now", "I live on Mulholland Drive now"),
+            ("I crossed Abbey Rd. today", "I crossed Abbey Road today"),
+            ("on Main Rd.", "on Main Road."),
+            ("the Dr. said so", "the Doctor said so"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim(), expected.trim(), "Failed for '{}'", text);
+        }
+
+        // Without the period the word is left alone
+        let (_, tokens) = g2p.g2p("the st").unwrap();
+        assert!(tokens[1].underscore().alias.is_none());
+    }
+
+    #[test]
+    fn test_preprocess_links() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let (text, segments, features) = g2p.preprocess("Call [Kokoro](/kˈOkəɹO/) at [1984](#d#) now");
+        assert_eq!(text, "Call Kokoro at 1984 now");
+        assert_eq!(segments, ["Call ", "Kokoro", " at ", "1984", " now"]);
+        assert_eq!(features.get(&1).map(String::as_str), Some("/kˈOkəɹO"));
+        assert_eq!(features.get(&3).map(String::as_str), Some("#d"));
+    }
+
+    #[test]
+    fn test_char_spans() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let (_, tokens) = g2p.g2p("“Don't go,” said [Kokoro](/kˈOkəɹO/).").unwrap();
+        let spans: Vec<(&str, (usize, usize))> = tokens.iter().map(|t| (t.text.as_str(), t.char_span)).collect();
+        assert!(spans.contains(&("Don't", (1, 6))));
+        assert!(spans.contains(&("go", (7, 9))));
+        assert!(spans.contains(&("Kokoro", (17, 23))));
+
+        let (phonemes, tokens) = g2p.g2p("Hi Kokoro").unwrap();
+        let alignment = g2p.align(&tokens);
+        let last = alignment.last().unwrap();
+        assert_eq!(phonemes.chars().nth(last.index), Some(last.phoneme));
+        assert_eq!(last.char_span, (8, 9));
+    }
+
+    #[test]
+    fn test_detokenize() {
+        let g2p = G2P::new(Language::EnglishUS);
+        for text in [
+            "Hello, world.",
+            "\"Don't go,\" she said (quietly)!",
+            "It costs $5.50 today.",
+            "twenty-one  spaces\tand\nlines ",
+            "Mr. Smith's 🙂 café",
+        ] {
+            assert_eq!(MToken::detokenize(&g2p.tokenize(text)), text);
+        }
+        let (_, tokens) = g2p.g2p("Mr. Smith paid $5.50, didn't he?").unwrap();
+        assert_eq!(MToken::detokenize(&tokens), "Mr. Smith paid $5.50, didn't he?");
+    }
+
+    #[test]
+    fn test_multiline_text() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let text = "The cat sat.\n\tIt ran.\n\nThen  it  stopped.";
+        let (p, tokens) = g2p.g2p(text).unwrap();
+        // Line breaks, tabs and runs of spaces become single spaces
+        assert!(!p.contains(['\n', '\t']));
+        assert_eq!(p, g2p.g2p("The cat sat. It ran. Then it stopped.").unwrap().0);
+        assert!(crate::kokoro::encode(&p).is_ok());
+        assert_eq!(MToken::detokenize(&tokens), text);
+        let chars: Vec<char> = p.chars().collect();
+        assert!(crate::align::align(&tokens, &g2p.unk).iter().all(|a| chars[a.index] == a.phoneme));
+    }
+
+    #[test]
+    fn test_spacy_tokenizer() {
+        let options = G2POptions {
+            tokenizer: TokenizerKind::SpacyCompat,
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        assert_eq!(
+            g2p.tokenize("I don't know.").iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+            ["I", "do", "n't", "know", "."]
+        );
+
+        // Split words are read whole, and come back as one token
+        let (_, tokens) = g2p.g2p("I don't know twenty-one").unwrap();
+        let lexicon = |word: &str| g2p.lexicon.get_word(word, "", None, None).unwrap().phonemes.into_owned();
+        assert_eq!(tokens[1].text, "don't");
+        assert_eq!(tokens[1].phonemes, Some(lexicon("don't")));
+        assert_eq!(tokens[3].phonemes, Some(lexicon("twenty-one")));
+    }
+
+    #[test]
+    fn test_retokenize() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let g2p = g2p.call(&g2p.options);
+        let mut tokens = g2p.tokenize("$5.50 abc123, twenty-one");
+        g2p.retokenize(&mut tokens);
+        let flags: Vec<(&str, bool, bool)> = tokens
+            .iter()
+            .map(|t| (t.text.as_str(), t.underscore().is_head, t.underscore().prespace))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                ("$", true, false),
+                ("5.50", false, true),
+                ("abc", true, false),
+                ("123", false, true),
+                (",", true, false),
+                ("twenty", true, false),
+                ("-", false, false),
+                ("one", false, false),
+            ]
+        );
+
+        // Subtokens are tagged as the word they make up
+        let text = "a well-known dog";
+        let tokens = g2p.tokenize(text);
+        let word_of = g2p.tagging_words(&tokens, text);
+        assert_eq!(word_of, [0, 1, 1, 1, 2]);
+
+        let (_, tokens) = g2p.g2p("It costs $5.50, twenty-one each.").unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, ["It", "costs", "$5.50", ",", "twenty-one", "each", "."]);
+        assert_eq!(tokens[2].underscore().currency.as_deref(), Some("$"));
+        assert_eq!(tokens[2].char_span, (9, 14));
+        assert!(tokens.iter().all(|t| t.underscore().is_head));
+    }
+
+    #[test]
+    fn test_speaking_rate() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let (_, tokens) = g2p.g2p("Hello there.").unwrap();
+        assert!(tokens.iter().all(|t| t.start_ts.is_none()));
+
+        let options = G2POptions {
+            speaking_rate: Some(1.0),
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let (_, tokens) = g2p.g2p("Hello there.").unwrap();
+        let ends: Vec<f64> = tokens.iter().map(|t| t.end_ts.unwrap()).collect();
+        assert!(ends[0] > 0.0 && ends.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(tokens[1].start_ts, Some(ends[0]));
+    }
+
+    #[test]
+    fn test_output_format() {
+        let options = G2POptions {
+            output_format: OutputFormat::XSampa,
+            ..Default::default()
+        };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let (phonemes, tokens) = g2p.g2p("the cat").unwrap();
+        assert_eq!(phonemes.trim_end(), "D@ k\"{t");
+        assert_eq!(tokens[1].phonemes.as_deref(), Some("k\"{t"));
+
+        // Numbers are phonemized recursively and must be converted only once
+        let (phonemes, _) = g2p.g2p("2 cats").unwrap();
+        assert!(phonemes.starts_with("t\"u"));
+    }
+
+    #[test]
+    fn test_analyze() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let result = g2p.analyze("Hello Zqwxbr.").unwrap();
+        assert_eq!(result.chunks, [result.phonemes.trim()]);
+        assert!(matches!(
+            &result.warnings[..],
+            [G2PWarning::Guessed { word, char_span: (6, 12) }] if word == "Zqwxbr"
+        ));
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"kind\":\"guessed\""));
+        let back: G2PResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, result);
+    }
+
+    #[test]
+    fn test_correct_typos() {
+        let options = G2POptions { correct_typos: true, ..Default::default() };
+        let g2p = G2P::with_options(Language::EnglishUS, options);
+        let result = g2p.analyze("A new restirction.").unwrap();
+        let (ps, _) = g2p.g2p("restriction").unwrap();
+        assert_eq!(result.tokens[2].phonemes.as_deref(), Some(ps.trim()));
+        assert_eq!(result.tokens[2].underscore().rating, Some(Rating::Corrected));
+        assert!(matches!(
+            &result.warnings[..],
+            [G2PWarning::Corrected { word, correction, .. }] if word == "restirction" && correction == "restriction"
+        ));
+
+        // Off by default: left to the fallback
+        let (_, tokens) = G2P::new(Language::EnglishUS).g2p("restirction").unwrap();
+        assert_eq!(tokens[0].underscore().rating, Some(Rating::Fallback));
+    }
+
+    #[test]
+    fn test_num_flags() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let cases = [
+            ("[1984](#d#)", "one nine eight four"),
+            ("[1984](#c#)", "one thousand nine hundred and eighty-four"),
+            ("[5](#o#)", "fifth"),
+            ("[12](#$#)", "twelve dollars"),
+            ("the 5th", "the fifth"),
+            ("$123.45", "one hundred twenty-three dollars and forty-five cents"),
+            ("£1", "one pound"),
+        ];
+        for (text, spoken) in cases {
+            let (p, _) = g2p.g2p(text).unwrap();
+            let (expected, _) = g2p.g2p(spoken).unwrap();
+            assert_eq!(p.trim(), expected.trim(), "Failed for '{}'", text);
+        }
+
+        let (_, tokens) = g2p.g2p("$5").unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].underscore().num_flags, "$");
+        assert_eq!(tokens[0].underscore().currency.as_deref(), Some("$"));
+
+        let (p, _) = g2p.g2p("[Kokoro](/kˈOkəɹO/)").unwrap();
+        assert_eq!(p.trim(), "kˈOkəɹO");
+    }
+
     // #[test]
     // fn test_g2p_italian() {
     //     let g2p = G2P::new(Language::Italian);
@@ -0,0 +1,135 @@
+//! Optional neural homograph disambiguation (`homograph-ml` feature).
+//!
+//! A small ONNX classifier looks at a window of words around a homograph and
+//! picks which of its lexicon readings, keyed by tag, applies. The model reads
+//! an `int64` tensor of word ids shaped `[1, 2 * window + 1]` and returns label
+//! logits shaped `[1, labels.len()]`. ONNX Runtime is loaded dynamically, so
+//! `libonnxruntime` must be installed (or pointed to by `ORT_DYLIB_PATH`).
+
+use ort::session::Session;
+use ort::value::Tensor;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HomographError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("onnx runtime error: {0}")]
+    Onnx(#[from] ort::Error),
+    #[error("model mutex poisoned")]
+    MutexPoisoned,
+}
+
+/// The JSON file shipped next to the model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HomographConfig {
+    /// Words of context on each side of the homograph.
+    pub window: usize,
+    /// Lowercased word → id. Must contain "<pad>" and "<unk>".
+    pub vocab: HashMap<String, i64>,
+    /// Output labels (lexicon tags) in logit order.
+    pub labels: Vec<String>,
+    /// Homograph → the labels it can take.
+    pub homographs: HashMap<String, Vec<String>>,
+}
+
+impl HomographConfig {
+    /// Word ids for the window centred on `words[index]`, padded at the edges.
+    pub fn context_ids(&self, words: &[&str], index: usize) -> Vec<i64> {
+        let pad = self.vocab.get("<pad>").copied().unwrap_or(0);
+        let unk = self.vocab.get("<unk>").copied().unwrap_or(1);
+        (0..=2 * self.window)
+            .map(|k| {
+                (index + k)
+                    .checked_sub(self.window)
+                    .and_then(|j| words.get(j))
+                    .map_or(pad, |w| self.vocab.get(&w.to_lowercase()).copied().unwrap_or(unk))
+            })
+            .collect()
+    }
+
+    /// The best label for `word` among those it can take.
+    pub fn pick_label(&self, word: &str, logits: &[f32]) -> Option<&str> {
+        let allowed = self.homographs.get(&word.to_lowercase())?;
+        self.labels
+            .iter()
+            .zip(logits)
+            .filter(|(label, _)| allowed.contains(label))
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(label, _)| label.as_str())
+    }
+}
+
+pub struct HomographModel {
+    config: HomographConfig,
+    session: Mutex<Session>,
+}
+
+impl HomographModel {
+    pub fn load(model_path: impl AsRef<Path>, config_path: impl AsRef<Path>) -> Result<Self, HomographError> {
+        let config: HomographConfig = serde_json::from_str(&fs::read_to_string(config_path)?)?;
+        let session = Session::builder()?.commit_from_file(model_path)?;
+        Ok(Self {
+            config,
+            session: Mutex::new(session),
+        })
+    }
+
+    pub fn is_homograph(&self, word: &str) -> bool {
+        self.config.homographs.contains_key(&word.to_lowercase())
+    }
+
+    /// Picks the label (lexicon tag) for `words[index]`, or `None` if it isn't
+    /// a homograph the model knows.
+    pub fn classify(&self, words: &[&str], index: usize) -> Result<Option<String>, HomographError> {
+        if !words.get(index).is_some_and(|w| self.is_homograph(w)) {
+            return Ok(None);
+        }
+        let ids = self.config.context_ids(words, index);
+        let input = Tensor::from_array(([1, ids.len()], ids))?;
+        let mut session = self.session.lock().map_err(|_| HomographError::MutexPoisoned)?;
+        let outputs = session.run(ort::inputs![input])?;
+        let (_, logits) = outputs[0].try_extract_tensor::<f32>()?;
+        Ok(self.config.pick_label(words[index], logits).map(str::to_string))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> HomographConfig {
+        serde_json::from_str(
+            r#"{
+                "window": 2,
+                "vocab": {"<pad>": 0, "<unk>": 1, "i": 2, "have": 3, "read": 4, "it": 5},
+                "labels": ["VB", "VBD", "NN"],
+                "homographs": {"read": ["VB", "VBD"]}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_context_ids() {
+        let config = config();
+        let words = ["I", "have", "read", "it", "today"];
+        assert_eq!(config.context_ids(&words, 2), [2, 3, 4, 5, 1]);
+        assert_eq!(config.context_ids(&words, 0), [0, 0, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_pick_label() {
+        let config = config();
+        // "NN" scores highest but isn't a reading of "read"
+        assert_eq!(config.pick_label("Read", &[0.1, 0.7, 0.9]), Some("VBD"));
+        assert_eq!(config.pick_label("lead", &[0.1, 0.7, 0.9]), None);
+    }
+}
@@ -0,0 +1,205 @@
+//! Token ids for Kokoro TTS models.
+//!
+//! Kokoro reads one id per phoneme symbol from the `vocab` in its
+//! `config.json`, with `PAD` (0) added before and after the sequence.
+
+use thiserror::Error;
+
+/// Id Kokoro expects at both ends of a sequence.
+pub const PAD: i64 = 0;
+
+/// Most phoneme ids (padding excluded) Kokoro accepts in one call.
+pub const MAX_PHONEMES: usize = 510;
+
+/// The `vocab` of Kokoro-82M's `config.json`.
+pub const VOCAB: &[(char, i64)] = &[
+    (';', 1),
+    (':', 2),
+    (',', 3),
+    ('.', 4),
+    ('!', 5),
+    ('?', 6),
+    ('—', 9),
+    ('…', 10),
+    ('"', 11),
+    ('(', 12),
+    (')', 13),
+    ('“', 14),
+    ('”', 15),
+    (' ', 16),
+    ('\u{0303}', 17),
+    ('ʣ', 18),
+    ('ʥ', 19),
+    ('ʦ', 20),
+    ('ʨ', 21),
+    ('ᵝ', 22),
+    ('\u{AB67}', 23),
+    ('A', 24),
+    ('I', 25),
+    ('O', 31),
+    ('Q', 33),
+    ('S', 35),
+    ('T', 36),
+    ('W', 39),
+    ('Y', 41),
+    ('ᵊ', 42),
+    ('a', 43),
+    ('b', 44),
+    ('c', 45),
+    ('d', 46),
+    ('e', 47),
+    ('f', 48),
+    ('h', 50),
+    ('i', 51),
+    ('j', 52),
+    ('k', 53),
+    ('l', 54),
+    ('m', 55),
+    ('n', 56),
+    ('o', 57),
+    ('p', 58),
+    ('q', 59),
+    ('r', 60),
+    ('s', 61),
+    ('t', 62),
+    ('u', 63),
+    ('v', 64),
+    ('w', 65),
+    ('x', 66),
+    ('y', 67),
+    ('z', 68),
+    ('ɑ', 69),
+    ('ɐ', 70),
+    ('ɒ', 71),
+    ('æ', 72),
+    ('β', 75),
+    ('ɔ', 76),
+    ('ɕ', 77),
+    ('ç', 78),
+    ('ɖ', 80),
+    ('ð', 81),
+    ('ʤ', 82),
+    ('ə', 83),
+    ('ɚ', 85),
+    ('ɛ', 86),
+    ('ɜ', 87),
+    ('ɟ', 90),
+    ('ɡ', 92),
+    ('ɥ', 99),
+    ('ɨ', 101),
+    ('ɪ', 102),
+    ('ʝ', 103),
+    ('ɯ', 110),
+    ('ɰ', 111),
+    ('ŋ', 112),
+    ('ɳ', 113),
+    ('ɲ', 114),
+    ('ɴ', 115),
+    ('ø', 116),
+    ('ɸ', 118),
+    ('θ', 119),
+    ('œ', 120),
+    ('ɹ', 123),
+    ('ɾ', 125),
+    ('ɻ', 126),
+    ('ʁ', 128),
+    ('ɽ', 129),
+    ('ʂ', 130),
+    ('ʃ', 131),
+    ('ʈ', 132),
+    ('ʧ', 133),
+    ('ʊ', 135),
+    ('ʋ', 136),
+    ('ʌ', 138),
+    ('ɣ', 139),
+    ('ɤ', 140),
+    ('χ', 142),
+    ('ʎ', 143),
+    ('ʒ', 147),
+    ('ʔ', 148),
+    ('ˈ', 156),
+    ('ˌ', 157),
+    ('ː', 158),
+    ('ʰ', 162),
+    ('ʲ', 164),
+    ('↓', 169),
+    ('→', 171),
+    ('↗', 172),
+    ('↘', 173),
+    ('ᵻ', 177),
+];
+
+/// Tied sequences of the lexicon and the single symbols Kokoro was trained on.
+const TIES: &[(&str, &str)] = &[
+    ("e\u{200D}ɪ", "A"),
+    ("a\u{200D}ɪ", "I"),
+    ("o\u{200D}ʊ", "O"),
+    ("ə\u{200D}ʊ", "Q"),
+    ("a\u{200D}ʊ", "W"),
+    ("ɔ\u{200D}ɪ", "Y"),
+    ("d\u{200D}ʒ", "ʤ"),
+    ("t\u{200D}ʃ", "ʧ"),
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum KokoroError {
+    #[error("symbols missing from the Kokoro vocab: {0:?}")]
+    UnknownSymbols(Vec<char>),
+}
+
+/// Id of `symbol` in `VOCAB`.
+pub fn id(symbol: char) -> Option<i64> {
+    VOCAB.iter().find(|(c, _)| *c == symbol).map(|(_, id)| *id)
+}
+
+/// Rewrites tied diphthongs and affricates ("e‍ɪ", "d‍ʒ") to the single
+/// symbols in Kokoro's vocab ("A", "ʤ") and drops any other ties.
+pub fn normalize(phonemes: &str) -> String {
+    let mut phonemes = phonemes.to_string();
+    for (tied, symbol) in TIES {
+        phonemes = phonemes.replace(tied, symbol);
+    }
+    phonemes.replace('\u{200D}', "")
+}
+
+/// Distinct symbols of `phonemes` (after `normalize`) that Kokoro has no id
+/// for, in order of first appearance.
+pub fn unknown_symbols(phonemes: &str) -> Vec<char> {
+    let mut unknown = Vec::new();
+    for c in normalize(phonemes).chars() {
+        if id(c).is_none() && !unknown.contains(&c) {
+            unknown.push(c);
+        }
+    }
+    unknown
+}
+
+/// Token ids for `phonemes`, without the surrounding `PAD`s. Fails, listing
+/// the offending symbols, rather than dropping anything Kokoro can't read.
+pub fn encode(phonemes: &str) -> Result<Vec<i64>, KokoroError> {
+    let phonemes = normalize(phonemes);
+    let ids: Vec<Option<i64>> = phonemes.chars().map(id).collect();
+    if ids.iter().any(Option::is_none) {
+        return Err(KokoroError::UnknownSymbols(unknown_symbols(&phonemes)));
+    }
+    Ok(ids.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("kˈæt").unwrap(), [53, 156, 72, 62]);
+        // Tied diphthongs use Kokoro's single-letter symbols
+        assert_eq!(encode("ɐbˈe‍ɪt"), encode("ɐbˈAt"));
+        assert_eq!(encode("d‍ʒˈʌmp.").unwrap()[..2], [82, 156]);
+    }
+
+    #[test]
+    fn test_unknown_symbols() {
+        assert_eq!(encode("kˈæt❓ɬɬ"), Err(KokoroError::UnknownSymbols(vec!['❓', 'ɬ'])));
+        assert!(unknown_symbols("hˈO wˈɜɹld!").is_empty());
+    }
+}
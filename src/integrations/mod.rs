@@ -0,0 +1,5 @@
+//! Encoders that turn phonemes into the input ids of specific TTS engines.
+
+pub mod kokoro;
+#[cfg(feature = "piper")]
+pub mod piper;
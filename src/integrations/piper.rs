@@ -0,0 +1,166 @@
+//! Phoneme ids for Piper voices (`piper` feature).
+//!
+//! Piper voices are trained on espeak-ng IPA and ship their symbol table as
+//! the `phoneme_id_map` of the voice's `.onnx.json`. Sequences are framed as
+//! `^ _ p1 _ p2 _ ... $`: BOS, then every phoneme followed by PAD, then EOS.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+const BOS: &str = "^";
+const EOS: &str = "$";
+const PAD: &str = "_";
+
+/// Lexicon symbols with a different spelling in espeak's IPA. Ties are
+/// dropped.
+const ESPEAK: &[(char, &str)] = &[
+    ('A', "eɪ"),
+    ('I', "aɪ"),
+    ('O', "oʊ"),
+    ('Q', "əʊ"),
+    ('W', "aʊ"),
+    ('Y', "ɔɪ"),
+    ('ʤ', "dʒ"),
+    ('ʧ', "tʃ"),
+    ('ᵊ', "ə"),
+    ('\u{200D}', ""),
+];
+
+#[derive(Error, Debug)]
+pub enum PiperError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("voice uses phoneme_type '{0}', only espeak voices are supported")]
+    UnsupportedPhonemeType(String),
+    #[error("phoneme_id_map is missing '{0}'")]
+    MissingToken(&'static str),
+    #[error("symbols missing from the voice's phoneme_id_map: {0:?}")]
+    UnknownSymbols(Vec<char>),
+}
+
+#[derive(Deserialize)]
+struct VoiceConfig {
+    #[serde(default)]
+    phoneme_type: Option<String>,
+    phoneme_id_map: HashMap<String, Vec<i64>>,
+}
+
+/// Rewrites lexicon phonemes in espeak-ng's IPA, which Piper voices expect:
+/// "hˈO" → "hˈoʊ".
+pub fn to_espeak(phonemes: &str) -> String {
+    let mut out = String::with_capacity(phonemes.len());
+    for c in phonemes.chars() {
+        match ESPEAK.iter().find(|(from, _)| *from == c) {
+            Some((_, to)) => out.push_str(to),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Encodes phonemes with a Piper voice's symbol table.
+#[derive(Debug, Clone)]
+pub struct PiperEncoder {
+    id_map: HashMap<char, Vec<i64>>,
+}
+
+impl PiperEncoder {
+    /// Reads the `phoneme_id_map` from a voice config (`.onnx.json`).
+    pub fn from_config(json: &str) -> Result<Self, PiperError> {
+        let config: VoiceConfig = serde_json::from_str(json)?;
+        match config.phoneme_type.as_deref() {
+            None | Some("espeak") => {}
+            Some(other) => return Err(PiperError::UnsupportedPhonemeType(other.to_string())),
+        }
+        let id_map: HashMap<char, Vec<i64>> = config
+            .phoneme_id_map
+            .into_iter()
+            .filter_map(|(symbol, ids)| {
+                let mut chars = symbol.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some((c, ids)),
+                    _ => None,
+                }
+            })
+            .collect();
+        for token in [BOS, EOS, PAD] {
+            if !id_map.contains_key(&token.chars().next().unwrap()) {
+                return Err(PiperError::MissingToken(token));
+            }
+        }
+        Ok(Self { id_map })
+    }
+
+    pub fn load(config_path: impl AsRef<Path>) -> Result<Self, PiperError> {
+        Self::from_config(&fs::read_to_string(config_path)?)
+    }
+
+    fn ids(&self, token: &str) -> &[i64] {
+        &self.id_map[&token.chars().next().unwrap()]
+    }
+
+    /// Ids for lexicon phonemes, framed with BOS, PAD and EOS. Fails, listing
+    /// the offending symbols, rather than dropping anything the voice can't
+    /// read.
+    pub fn encode(&self, phonemes: &str) -> Result<Vec<i64>, PiperError> {
+        let phonemes = to_espeak(phonemes);
+        let mut unknown = Vec::new();
+        for c in phonemes.chars() {
+            if !self.id_map.contains_key(&c) && !unknown.contains(&c) {
+                unknown.push(c);
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(PiperError::UnknownSymbols(unknown));
+        }
+
+        let mut ids = self.ids(BOS).to_vec();
+        ids.extend(self.ids(PAD));
+        for c in phonemes.chars() {
+            ids.extend(&self.id_map[&c]);
+            ids.extend(self.ids(PAD));
+        }
+        ids.extend(self.ids(EOS));
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"{
+        "phoneme_type": "espeak",
+        "phoneme_id_map": {
+            "_": [0], "^": [1], "$": [2], " ": [3],
+            "a": [14], "e": [18], "k": [23], "t": [32], "æ": [39], "ɪ": [74], "ˈ": [120]
+        }
+    }"#;
+
+    #[test]
+    fn test_to_espeak() {
+        assert_eq!(to_espeak("hˈO"), "hˈoʊ");
+        assert_eq!(to_espeak("d‍ʒˈʌmp"), "dʒˈʌmp");
+    }
+
+    #[test]
+    fn test_encode() {
+        let encoder = PiperEncoder::from_config(CONFIG).unwrap();
+        assert_eq!(encoder.encode("kˈæt").unwrap(), [1, 0, 23, 0, 120, 0, 39, 0, 32, 0, 2]);
+        assert_eq!(encoder.encode("ˈA").unwrap(), [1, 0, 120, 0, 18, 0, 74, 0, 2]);
+        assert!(matches!(encoder.encode("kˈɑː"), Err(PiperError::UnknownSymbols(s)) if s == ['ɑ', 'ː']));
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let text = CONFIG.replace("\"espeak\"", "\"text\"");
+        assert!(matches!(PiperEncoder::from_config(&text), Err(PiperError::UnsupportedPhonemeType(_))));
+        let no_pad = CONFIG.replace("\"_\": [0], ", "");
+        assert!(matches!(PiperEncoder::from_config(&no_pad), Err(PiperError::MissingToken("_"))));
+    }
+}
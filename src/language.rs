@@ -1,14 +1,250 @@
+use crate::script::Script;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+/// A language to read. Languages are added over time and behind features,
+/// so a match on one needs a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[non_exhaustive]
 pub enum Language {
     EnglishUS,
     EnglishGB,
+    /// Australian, the GB lexicon with its accent applied over it (see
+    /// `languages::english::accents`).
+    EnglishAU,
+    /// Indian, the GB lexicon with its accent applied over it.
+    EnglishIN,
+    /// Irish, the rhotic US lexicon with its accent applied over it.
+    EnglishIE,
+    /// Scottish, the rhotic US lexicon with its accent applied over it.
+    EnglishSC,
+    /// Castilian, read by rule (see `languages::spanish`).
+    #[cfg(feature = "spanish")]
+    Spanish,
+    /// Read by rule from a seed lexicon (see `languages::french`).
+    #[cfg(feature = "french")]
+    French,
+    /// Read by rule, compounds split against a seed lexicon (see
+    /// `languages::german`).
+    #[cfg(feature = "german")]
+    German,
+    /// Brazilian Portuguese, read by rule (see `languages::portuguese`).
+    #[cfg(feature = "portuguese")]
+    PortugueseBR,
+    /// European Portuguese, read by rule (see `languages::portuguese`).
+    #[cfg(feature = "portuguese")]
+    PortuguesePT,
+    /// Read from kana or romaji, kanji through a seed lexicon and a table of
+    /// jōyō kanji readings (see `languages::japanese`).
+    #[cfg(feature = "ja")]
+    Japanese,
+    /// Read through pinyin from a seed table of words and a full table of
+    /// characters (see `languages::mandarin`).
+    #[cfg(feature = "zh")]
+    Mandarin,
+    /// Read by rule from hangul (see `languages::korean`).
+    #[cfg(feature = "ko")]
+    Korean,
+    /// Read by rule from Devanagari (see `languages::hindi`).
+    #[cfg(feature = "hi")]
+    Hindi,
+    /// Northern Vietnamese, read by rule (see `languages::vietnamese`).
+    #[cfg(feature = "vi")]
+    Vietnamese,
     // Italian,
+    /// A language added with `languages::register`, by its registered name.
+    Other(String),
 }
 
 impl Language {
     pub fn is_english(&self) -> bool {
-        matches!(self, Language::EnglishUS | Language::EnglishGB)
+        matches!(
+            self,
+            Language::EnglishUS
+                | Language::EnglishGB
+                | Language::EnglishAU
+                | Language::EnglishIN
+                | Language::EnglishIE
+                | Language::EnglishSC
+        )
+    }
+
+    /// The language whose lexicon a dialect is read from: US English for the
+    /// rhotic accents, GB English for the others. Other languages are their
+    /// own.
+    pub fn base(&self) -> Language {
+        match self {
+            Language::EnglishIE | Language::EnglishSC => Language::EnglishUS,
+            Language::EnglishAU | Language::EnglishIN => Language::EnglishGB,
+            lang => lang.clone(),
+        }
+    }
+
+    /// The language of a code as espeak-ng voices name them ("en-us",
+    /// "en-gb", "es", "pt-br"), or a registered language's name. `None` for
+    /// a language whose feature is off or that was never registered.
+    pub fn from_code(code: &str) -> Option<Language> {
+        Some(match code.to_ascii_lowercase().as_str() {
+            "en-us" | "en" => Language::EnglishUS,
+            "en-gb" => Language::EnglishGB,
+            "en-au" => Language::EnglishAU,
+            "en-in" => Language::EnglishIN,
+            "en-ie" => Language::EnglishIE,
+            "en-gb-scotland" => Language::EnglishSC,
+            #[cfg(feature = "spanish")]
+            "es" => Language::Spanish,
+            #[cfg(feature = "french")]
+            "fr" => Language::French,
+            #[cfg(feature = "german")]
+            "de" => Language::German,
+            #[cfg(feature = "portuguese")]
+            "pt-br" => Language::PortugueseBR,
+            #[cfg(feature = "portuguese")]
+            "pt" | "pt-pt" => Language::PortuguesePT,
+            #[cfg(feature = "ja")]
+            "ja" => Language::Japanese,
+            #[cfg(feature = "zh")]
+            "cmn" | "zh" => Language::Mandarin,
+            #[cfg(feature = "ko")]
+            "ko" => Language::Korean,
+            #[cfg(feature = "hi")]
+            "hi" => Language::Hindi,
+            #[cfg(feature = "vi")]
+            "vi" => Language::Vietnamese,
+            _ if crate::languages::is_registered(code) => Language::Other(code.to_string()),
+            _ => return None,
+        })
+    }
+
+    /// Whether words in `script` are read as this language rather than by
+    /// `options.script_policy`. Registered languages read every script.
+    pub fn writes(&self, script: Script) -> bool {
+        match self {
+            #[cfg(feature = "ja")]
+            Language::Japanese => matches!(script, Script::Latin | Script::Han | Script::Kana),
+            #[cfg(feature = "zh")]
+            Language::Mandarin => matches!(script, Script::Latin | Script::Han),
+            #[cfg(feature = "ko")]
+            Language::Korean => matches!(script, Script::Latin | Script::Hangul),
+            #[cfg(feature = "hi")]
+            Language::Hindi => matches!(script, Script::Latin | Script::Devanagari),
+            Language::Other(_) => true,
+            _ => script == Script::Latin,
+        }
+    }
+}
+
+/// Regional accent of English, used to pick the espeak-ng voice and phoneme
+/// mapping for out-of-vocabulary words, and the accent applied over the
+/// lexicon (see `languages::english::accents`).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Dialect {
+    US,
+    GB,
+    Scottish,
+    Caribbean,
+    Australian,
+    Indian,
+    Irish,
+    /// Any other installed espeak-ng voice ("en-au", "en-in").
+    Voice(String),
+}
+
+impl Dialect {
+    /// The dialect of an espeak-ng voice name.
+    pub fn from_voice(voice: &str) -> Self {
+        match voice {
+            "en-us" => Dialect::US,
+            "en" | "en-gb" => Dialect::GB,
+            "en-gb-scotland" => Dialect::Scottish,
+            "en-029" => Dialect::Caribbean,
+            _ => Dialect::Voice(voice.to_string()),
+        }
+    }
+
+    pub fn espeak_voice(&self) -> &str {
+        match self {
+            Dialect::US => "en-us",
+            // espeak-ng has no voice of its own for these
+            Dialect::GB | Dialect::Australian | Dialect::Indian | Dialect::Irish => "en",
+            Dialect::Scottish => "en-gb-scotland",
+            Dialect::Caribbean => "en-029",
+            Dialect::Voice(voice) => voice,
+        }
+    }
+
+    /// Whether out-of-vocabulary words should use British rather than
+    /// American phonemes.
+    pub fn is_british(&self) -> bool {
+        match self {
+            Dialect::US => false,
+            Dialect::Voice(voice) => !voice.starts_with("en-us"),
+            _ => true,
+        }
+    }
+}
+
+impl From<&Language> for Dialect {
+    /// Other languages use the espeak-ng voice of their name.
+    fn from(lang: &Language) -> Self {
+        match lang {
+            Language::EnglishUS => Dialect::US,
+            Language::EnglishGB => Dialect::GB,
+            Language::EnglishAU => Dialect::Australian,
+            Language::EnglishIN => Dialect::Indian,
+            Language::EnglishIE => Dialect::Irish,
+            Language::EnglishSC => Dialect::Scottish,
+            #[cfg(feature = "spanish")]
+            Language::Spanish => Dialect::Voice("es".to_string()),
+            #[cfg(feature = "french")]
+            Language::French => Dialect::Voice("fr".to_string()),
+            #[cfg(feature = "german")]
+            Language::German => Dialect::Voice("de".to_string()),
+            #[cfg(feature = "portuguese")]
+            Language::PortugueseBR => Dialect::Voice("pt-br".to_string()),
+            #[cfg(feature = "portuguese")]
+            Language::PortuguesePT => Dialect::Voice("pt".to_string()),
+            #[cfg(feature = "ja")]
+            Language::Japanese => Dialect::Voice("ja".to_string()),
+            #[cfg(feature = "zh")]
+            Language::Mandarin => Dialect::Voice("cmn".to_string()),
+            #[cfg(feature = "ko")]
+            Language::Korean => Dialect::Voice("ko".to_string()),
+            #[cfg(feature = "hi")]
+            Language::Hindi => Dialect::Voice("hi".to_string()),
+            #[cfg(feature = "vi")]
+            Language::Vietnamese => Dialect::Voice("vi".to_string()),
+            Language::Other(name) => Dialect::from_voice(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dialect_voices() {
+        assert_eq!(Dialect::from(&Language::EnglishGB).espeak_voice(), "en");
+        assert_eq!(Dialect::from_voice("en-gb-scotland"), Dialect::Scottish);
+        assert_eq!(Dialect::from_voice("en-au"), Dialect::Voice("en-au".to_string()));
+        assert!(Dialect::from_voice("en-au").is_british());
+        assert!(!Dialect::US.is_british());
+        assert_eq!(Dialect::from(&Language::EnglishAU), Dialect::Australian);
+        assert_eq!(Dialect::Irish.espeak_voice(), "en");
+    }
+
+    #[test]
+    fn test_from_code() {
+        assert_eq!(Language::from_code("en-GB"), Some(Language::EnglishGB));
+        assert_eq!(Language::from_code("en"), Some(Language::EnglishUS));
+        assert_eq!(Language::from_code("xx"), None);
+    }
+
+    #[test]
+    fn test_base() {
+        assert_eq!(Language::EnglishAU.base(), Language::EnglishGB);
+        assert_eq!(Language::EnglishSC.base(), Language::EnglishUS);
+        assert_eq!(Language::EnglishUS.base(), Language::EnglishUS);
+        assert!(Language::EnglishIN.is_english());
     }
 }
@@ -1,19 +1,693 @@
+use crate::language::Language;
 use crate::lexicon::Lexicon;
-use super::LanguageRules;
+use crate::tagger::{PerceptronTagger, Tagger};
+use super::{Abbreviation, LanguageRules, Neighbors};
 
 pub struct English;
 
+/// Abbreviation (lowercase, without its period) → (as a title before a
+/// name, elsewhere). `None` for those never read before a name, whose period
+/// before a capital ends the sentence ("etc. The").
+const ABBREVIATIONS: &[(&str, Option<&str>, &str)] = &[
+    ("mr", Some("Mister"), "Mister"),
+    ("mrs", Some("Missus"), "Missus"),
+    ("ms", Some("Miz"), "Miz"),
+    ("dr", Some("Doctor"), "Doctor"),
+    ("st", Some("Saint"), "Street"),
+    ("mt", Some("Mount"), "Mount"),
+    ("prof", Some("Professor"), "Professor"),
+    ("jr", None, "Junior"),
+    ("sr", None, "Senior"),
+    ("gen", Some("General"), "General"),
+    ("capt", Some("Captain"), "Captain"),
+    ("lt", Some("Lieutenant"), "Lieutenant"),
+    ("sgt", Some("Sergeant"), "Sergeant"),
+    ("gov", Some("Governor"), "Governor"),
+    ("sen", Some("Senator"), "Senator"),
+    ("rep", Some("Representative"), "Representative"),
+    ("ave", None, "Avenue"),
+    ("blvd", None, "Boulevard"),
+    ("dept", None, "department"),
+    ("approx", None, "approximately"),
+    ("etc", None, "et cetera"),
+    ("vs", Some("versus"), "versus"),
+];
+
+/// Abbreviations read as the street of an address after a name, and after a
+/// number where that flag is set: "Main St.", "Mulholland Dr.", "Abbey Rd.",
+/// but "Dr. Smith", "the Dr." and the "3rd." of an ordinal elsewhere.
+const STREETS: &[(&str, &str, bool)] = &[("st", "Street", true), ("dr", "Drive", true), ("rd", "Road", false)];
+
+/// A derivational affix: its spelling, its sound, and the endings a stem
+/// changes to before it, as (written before the affix, in the stem):
+/// "happi-ness" is read from "happy", "lov-able" from "love".
+struct Affix {
+    spelling: &'static str,
+    phonemes: &'static str,
+    stems: &'static [(&'static str, &'static str)],
+}
+
+/// Suffixes, unstressed and added after the stem's phonemes. Tried in order,
+/// so longer spellings that end in a shorter one come first.
+const SUFFIXES: &[Affix] = &[
+    Affix { spelling: "ness", phonemes: "nəs", stems: &[("i", "y")] },
+    Affix { spelling: "ment", phonemes: "mənt", stems: &[] },
+    Affix { spelling: "less", phonemes: "ləs", stems: &[("i", "y")] },
+    Affix { spelling: "ful", phonemes: "fəl", stems: &[("i", "y")] },
+    Affix { spelling: "able", phonemes: "əbəl", stems: &[("", "e"), ("i", "y")] },
+    Affix { spelling: "ly", phonemes: "li", stems: &[("i", "y")] },
+];
+
+/// Prefixes, with secondary stress and added before the stem's phonemes.
+const PREFIXES: &[Affix] = &[
+    Affix { spelling: "un", phonemes: "ˌʌn", stems: &[] },
+    Affix { spelling: "pre", phonemes: "pɹˌi", stems: &[] },
+    Affix { spelling: "re", phonemes: "ɹˌi", stems: &[] },
+];
+
+/// Fewest letters a stem or compound part may have, so "re-d" or "un-it"
+/// aren't read as derived.
+const MIN_STEM: usize = 3;
+
+/// How many affixes deep a word is taken apart ("un-help-ful-ness").
+const MAX_DEPTH: usize = 3;
+
+impl Affix {
+    /// The spellings the stem of `rest` (the word without the affix) may have.
+    fn stems<'a>(&'a self, rest: &'a str) -> impl Iterator<Item = String> + 'a {
+        let respelled = self.stems.iter().filter_map(move |&(written, stem)| {
+            rest.strip_suffix(written).map(|base| format!("{}{}", base, stem))
+        });
+        std::iter::once(rest.to_string()).chain(respelled).filter(|stem| stem.chars().count() >= MIN_STEM)
+    }
+}
+
+/// Reads an out-of-vocabulary word as a known stem with the affixes of
+/// `SUFFIXES` and `PREFIXES`. Stems are themselves taken apart, as affixed
+/// words or compounds (see `Lexicon::split_compound`), up to `depth` affixes
+/// deep.
+fn derive(word: &str, tag: &str, lexicon: &Lexicon, depth: usize) -> Option<String> {
+    let read = |stem: &str| {
+        lexicon
+            .get_word(stem, tag, None, None)
+            .or_else(|| lexicon.split_compound(stem, tag, None))
+            .map(|p| p.phonemes.into_owned())
+            .or_else(|| depth.checked_sub(1).and_then(|depth| derive(stem, tag, lexicon, depth)))
+    };
+
+    let lower = word.to_lowercase();
+    for affix in SUFFIXES {
+        if let Some(rest) = lower.strip_suffix(affix.spelling)
+            && let Some(ps) = affix.stems(rest).find_map(|stem| read(&stem))
+        {
+            return Some(ps + affix.phonemes);
+        }
+    }
+    for affix in PREFIXES {
+        if let Some(rest) = lower.strip_prefix(affix.spelling)
+            && let Some(ps) = affix.stems(rest).find_map(|stem| read(&stem))
+        {
+            return Some(format!("{}{}", affix.phonemes, ps));
+        }
+    }
+    None
+}
+
 impl LanguageRules for English {
     fn apply_rules(&self, word: &str, tag: &str, lexicon: &Lexicon) -> Option<String> {
-        let ctx = None; // Context not available in apply_rules, use get_word instead
-        if let Some((ps, _)) = lexicon.stem_s(word, tag, None, ctx) {
-            Some(ps)
-        } else if let Some((ps, _)) = lexicon.stem_ed(word, tag, None, ctx) {
-            Some(ps)
-        } else if let Some((ps, _)) = lexicon.stem_ing(word, tag, None, ctx) {
-            Some(ps)
-        } else {
-            None
+        if !word.chars().all(char::is_alphabetic) {
+            return None;
+        }
+        derive(word, tag, lexicon, MAX_DEPTH)
+    }
+
+    fn guess_name(&self, word: &str, lexicon: &Lexicon, british: bool) -> Option<String> {
+        if !word.chars().all(char::is_alphabetic) {
+            return None;
+        }
+        names::read(word, lexicon, british)
+    }
+
+    fn heteronym(&self, word: &str, tag: &str, neighbors: &Neighbors, british: bool) -> Option<&'static str> {
+        heteronyms::resolve(word, tag, neighbors, british)
+    }
+
+    fn expand_abbreviation(&self, word: &str, prev_tag: Option<&str>, next_tag: Option<&str>) -> Option<Abbreviation> {
+        let lower = word.to_lowercase();
+        let is_name = |tag: Option<&str>| tag.is_some_and(|t| t.starts_with("NNP"));
+        // A name before wins: "Main St. Paul" is the street, not the saint
+        if let Some(&(_, street, after_number)) = STREETS.iter().find(|(abbr, ..)| *abbr == lower)
+            && (is_name(prev_tag) || (after_number && prev_tag == Some("CD")))
+        {
+            return Some(Abbreviation { expansion: street, before_name: false });
+        }
+        let &(_, title, elsewhere) = ABBREVIATIONS.iter().find(|(abbr, ..)| *abbr == lower)?;
+        Some(match title {
+            Some(title) if is_name(next_tag) => Abbreviation { expansion: title, before_name: true },
+            _ => Abbreviation { expansion: elsewhere, before_name: false },
+        })
+    }
+
+    fn tagger(&self) -> Option<Box<dyn Tagger>> {
+        Some(Box::new(PerceptronTagger::english()))
+    }
+}
+
+/// Contextual rules for words spelled alike but pronounced differently. Each
+/// rule returns the reading the lexicon lacks (its entry is the other one),
+/// or `None` to keep the lexicon's.
+mod heteronyms {
+    use super::Neighbors;
+
+    const AUXILIARIES: &[&str] = &[
+        "to", "will", "would", "can", "could", "shall", "should", "must", "might", "may", "'ll", "please",
+        "don't", "doesn't", "didn't", "won't", "can't", "not",
+    ];
+    const PERFECT_OR_PASSIVE: &[&str] = &[
+        "have", "has", "had", "'ve", "'d", "having", "was", "were", "been", "being", "is", "are", "be",
+    ];
+    const PARTICLES: &[&str] = &[
+        "up", "down", "off", "out", "apart", "open", "into", "through", "away", "around", "round", "back",
+    ];
+
+    fn lower(word: Option<(&str, &str)>) -> Option<String> {
+        word.map(|(w, _)| w.to_lowercase())
+    }
+
+    fn is_one_of(word: &Option<String>, set: &[&str]) -> bool {
+        word.as_deref().is_some_and(|w| set.contains(&w))
+    }
+
+    pub fn resolve(word: &str, tag: &str, neighbors: &Neighbors, british: bool) -> Option<&'static str> {
+        let prev = lower(neighbors.prev);
+        let prev2 = lower(neighbors.prev2);
+        let next = lower(neighbors.next);
+        let verb = tag.starts_with("VB");
+
+        match word.to_lowercase().as_str() {
+            // "I have read" / "she read it yesterday" → red; "to read" → reed
+            "read" => {
+                let past = matches!(tag, "VBD" | "VBN") || is_one_of(&prev, PERFECT_OR_PASSIVE);
+                (past && !is_one_of(&prev, AUXILIARIES)).then_some("ɹˈɛd")
+            }
+            // The metal: "lead pipe", "made of lead"; otherwise "lead the way" → leed
+            "lead" => {
+                let metal_next = is_one_of(
+                    &next,
+                    &[
+                        "poisoning", "pipe", "pipes", "paint", "pencil", "pencils", "weight", "weights",
+                        "shot", "bullet", "bullets", "balloon", "oxide", "acetate", "ore", "mine", "mines",
+                        "levels", "content", "exposure", "crystal", "roof",
+                    ],
+                );
+                let metal_prev = is_one_of(&prev, &["of", "red", "white", "molten", "unleaded"]);
+                (!verb && (metal_next || metal_prev)).then_some("lˈɛd")
+            }
+            // "a live show", "we go live" → lyve; "they live here" → liv
+            "live" => {
+                let adjective = matches!(tag, "JJ" | "RB")
+                    || is_one_of(&prev, &["go", "goes", "went", "going", "gone", "broadcast", "aired", "streamed"])
+                    || is_one_of(
+                        &next,
+                        &[
+                            "music", "show", "shows", "broadcast", "performance", "stream", "streaming",
+                            "audience", "wire", "ammunition", "bait", "concert", "recording", "album",
+                            "event", "coverage", "action", "feed", "chat", "demo",
+                        ],
+                    );
+                let verb_context = is_one_of(&prev, AUXILIARIES)
+                    || is_one_of(&prev, &["i", "you", "we", "they", "who", "people"]);
+                (adjective && !verb_context).then_some("lˈa\u{200d}ɪv")
+            }
+            // "wind the clock", "wind down" → wynd; "the wind" → wind
+            "wind" => {
+                let verb_context = is_one_of(&prev, AUXILIARIES)
+                    || is_one_of(&next, &["up", "down", "back", "the", "it", "your", "my", "his", "her", "its"]);
+                let noun_context = is_one_of(&prev, &["the", "a", "of", "cold", "strong", "north", "south", "east", "west"]);
+                (verb_context && !noun_context).then_some("wˈa\u{200d}ɪnd")
+            }
+            // "tear it up", "wear and tear" → tare; "a tear fell" → teer
+            "tear" => {
+                let rip = is_one_of(&next, PARTICLES)
+                    || (prev.as_deref() == Some("and") && prev2.as_deref() == Some("wear"))
+                    || is_one_of(&prev, AUXILIARIES)
+                    || (is_one_of(&next, &["it", "them", "this", "that", "the", "my", "your"]) && !is_one_of(&prev, &["a", "the"]));
+                rip.then_some(if british { "tˈe\u{200d}ə" } else { "tˈɛ\u{200d}ɹ" })
+            }
+            // The fish: "sea bass", "bass fishing"; otherwise the music
+            "bass" => {
+                let fish = is_one_of(&next, &["fishing", "fish", "boat", "lure", "lures", "fillet", "fillets"])
+                    || is_one_of(
+                        &prev,
+                        &["sea", "striped", "largemouth", "smallmouth", "caught", "catch", "fried", "grilled", "baked"],
+                    );
+                fish.then_some(if british { "bˈas" } else { "bˈæs" })
+            }
+            // "bow down", "take a bow", "the bow of the ship" → bough; "bow and arrow" → beau
+            "bow" => {
+                let bend = is_one_of(&next, &["down", "before", "out"])
+                    || (prev.as_deref() == Some("a")
+                        && is_one_of(&prev2, &["take", "takes", "took", "taking", "taken", "deep"]))
+                    || (prev.as_deref() == Some("the") && next.as_deref() == Some("of"))
+                    || is_one_of(&prev, AUXILIARIES);
+                bend.then_some("bˈa\u{200d}ʊ")
+            }
+            // Past of "wind": "wound up", "she wound the clock" → wownd; "the wound" → woond
+            "wound" => {
+                let wind = is_one_of(&next, &["up", "down", "around", "round", "back", "tightly", "tight", "the", "it"])
+                    && !is_one_of(&prev, &["the", "a", "his", "her", "my", "your", "their"]);
+                wind.then_some("wˈa\u{200d}ʊnd")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Readings for names out of the lexicon, from their endings: "Kowalski" is
+/// "Kowal" read by letter-to-sound rules and a "-ski" stressed before it.
+mod names {
+    use super::Language;
+    use crate::fallback::Fallback;
+    use crate::lexicon::Lexicon;
+    use crate::lts::LtsFallback;
+
+    /// Where a name with the ending takes its stress.
+    #[derive(Clone, Copy)]
+    enum Stress {
+        /// On the first syllable, as most English names ("Johnson", "Goldberg").
+        First,
+        /// On the syllable before the ending ("Kowalski", "Rodriguez").
+        BeforeEnding,
+    }
+
+    /// Name endings: (spelling, US, GB, stress). Longer spellings that end in
+    /// a shorter one come first.
+    const ENDINGS: &[(&str, &str, &str, Stress)] = &[
+        ("stein", "sta\u{200d}ɪn", "sta\u{200d}ɪn", Stress::First),
+        ("berg", "bɜːɡ", "bɜːɡ", Stress::First),
+        ("ford", "fəɹd", "fəd", Stress::First),
+        ("vich", "vɪt\u{200d}ʃ", "vɪt\u{200d}ʃ", Stress::BeforeEnding),
+        ("son", "sən", "sən", Stress::First),
+        ("sen", "sən", "sən", Stress::First),
+        ("man", "mən", "mən", Stress::First),
+        ("ton", "tən", "tən", Stress::First),
+        ("ley", "li", "li", Stress::First),
+        ("ski", "ski", "ski", Stress::BeforeEnding),
+        ("sky", "ski", "ski", Stress::BeforeEnding),
+        ("ez", "ɛz", "ɛz", Stress::BeforeEnding),
+    ];
+
+    const VOWELS: &str = "AIOQWYaeiouæɐɑɒɔəɚɛɜɪʊʌᵻ";
+
+    /// Reduced vowels, which don't take stress unless they start a diphthong.
+    const REDUCED: &str = "əɚᵻ";
+
+    /// Moves the primary stress to the start of the last full vowel, or of
+    /// the last vowel if all are reduced.
+    fn stress_last_vowel(ps: &str) -> String {
+        let chars: Vec<char> = ps.chars().filter(|&c| c != 'ˈ' && c != 'ˌ').collect();
+        let full = |i: usize| !REDUCED.contains(chars[i]) || chars.get(i + 1) == Some(&'\u{200d}');
+        let vowel = |i: &usize| VOWELS.contains(chars[*i]);
+        let last = |full_only: bool| (0..chars.len()).rev().find(|i| vowel(i) && (!full_only || full(*i)));
+        let Some(mut at) = last(true).or_else(|| last(false)) else {
+            return chars.into_iter().collect();
+        };
+        // The start of a diphthong, not its second half
+        while at >= 2 && chars[at - 1] == '\u{200d}' {
+            at -= 2;
+        }
+        let mut out: String = chars[..at].iter().collect();
+        out.push('ˈ');
+        out.extend(&chars[at..]);
+        out
+    }
+
+    /// Phonemes for `word`, a name out of the lexicon, or `None` if it has
+    /// none of the endings.
+    pub fn read(word: &str, lexicon: &Lexicon, british: bool) -> Option<String> {
+        let lower = word.to_lowercase();
+        let &(spelling, us, gb, stress) = ENDINGS.iter().find(|(spelling, ..)| lower.ends_with(spelling))?;
+        let stem = &lower[..lower.len() - spelling.len()];
+        if stem.chars().count() < 2 {
+            return None;
+        }
+        // The lexicon's stems only if it is as British as the name is read
+        let in_lexicon = (lexicon.lang == Language::EnglishGB) == british;
+        let stem_ps = match lexicon.get_word(stem, "NNP", None, None).filter(|_| in_lexicon) {
+            Some(p) => p.phonemes.into_owned(),
+            None => LtsFallback::new(british).phonemize(stem).ok()?,
+        };
+        let stem_ps = match stress {
+            Stress::First => stem_ps,
+            Stress::BeforeEnding => stress_last_vowel(&stem_ps),
+        };
+        Some(stem_ps + if british { gb } else { us })
+    }
+}
+
+/// Regional accents as rewrites of the phonemes of the US or GB lexicon
+/// they are read over (see `Language::base`). They keep to the misaki
+/// inventory, so sounds Kokoro has no symbol for are approximated.
+pub mod accents {
+    use crate::language::Dialect;
+
+    const ZWJ: char = '\u{200d}';
+
+    /// The single letters the lexicon writes some diphthongs as ("dˈOnt"),
+    /// spelled out tied before an accent's rules, which match tied forms.
+    const SHORTHANDS: &[(char, &str)] = &[
+        ('A', "e\u{200d}ɪ"),
+        ('I', "a\u{200d}ɪ"),
+        ('O', "o\u{200d}ʊ"),
+        ('Q', "ə\u{200d}ʊ"),
+        ('W', "a\u{200d}ʊ"),
+        ('Y', "ɔ\u{200d}ɪ"),
+    ];
+
+    /// Over GB, whose trap-bath split Australian shares: wider FACE, PRICE,
+    /// MOUTH and GOAT, NEAR and SQUARE as long vowels, TRAP raised, STRUT
+    /// and START fronted, and flapped "t" (see `flap`).
+    const AUSTRALIAN: &[(&str, &str)] = &[
+        ("e\u{200d}ɪ", "æ\u{200d}ɪ"),
+        ("a\u{200d}ɪ", "ɑ\u{200d}ɪ"),
+        ("a\u{200d}ʊ", "æ\u{200d}ʊ"),
+        ("ə\u{200d}ʊ", "ɐ\u{200d}ʊ"),
+        ("i\u{200d}ə", "ɪː"),
+        ("e\u{200d}ə", "eː"),
+        ("a", "æ"),
+        ("ʌ", "ɐ"),
+        ("ɑː", "ɐː"),
+    ];
+
+    /// Over GB: dental stops for "th", FACE and GOAT as long vowels, and LOT
+    /// and STRUT closer to "ɔ" and "ə".
+    const INDIAN: &[(&str, &str)] = &[
+        ("θ", "t"),
+        ("ð", "d"),
+        ("e\u{200d}ɪ", "eː"),
+        ("ə\u{200d}ʊ", "oː"),
+        ("ɒ", "ɔ"),
+        ("ʌ", "ə"),
+    ];
+
+    /// Over US, which keeps the "r": no flapping, stops for "th", an open
+    /// TRAP, and FACE and GOAT as long vowels.
+    const IRISH: &[(&str, &str)] = &[
+        ("ɾ", "t"),
+        ("θ", "t"),
+        ("ð", "d"),
+        ("æ", "a"),
+        ("e\u{200d}ɪ", "eː"),
+        ("o\u{200d}ʊ", "oː"),
+        ("ə\u{200d}ʊ", "oː"),
+    ];
+
+    /// Over US: no flapping, TRAP, BATH and START merged in "a", LOT and
+    /// THOUGHT in "ɔ", FOOT and GOOSE in "u", FACE and GOAT as pure vowels,
+    /// no vowel length, and every "r" said.
+    const SCOTTISH: &[(&str, &str)] = &[
+        ("ɾ", "t"),
+        ("ɑː\u{200d}ɹ", "aɹ"),
+        ("ɑː", "a"),
+        ("ɑ", "ɔ"),
+        ("æ", "a"),
+        ("ɔː", "ɔ"),
+        ("e\u{200d}ɪ", "e"),
+        ("o\u{200d}ʊ", "o"),
+        ("ə\u{200d}ʊ", "o"),
+        ("ʊ\u{200d}ɹ", "uɹ"),
+        ("ɪ\u{200d}ɹ", "iɹ"),
+        ("ɛ\u{200d}ɹ", "eɹ"),
+        ("ʊ", "u"),
+        ("uː", "u"),
+        ("iː", "i"),
+        ("ɚ", "əɹ"),
+    ];
+
+    /// `phonemes` in `dialect`'s accent, `None` if it reads its lexicon as
+    /// it is.
+    pub fn apply(phonemes: &str, dialect: &Dialect) -> Option<String> {
+        let rules = match dialect {
+            Dialect::Australian => AUSTRALIAN,
+            Dialect::Indian => INDIAN,
+            Dialect::Irish => IRISH,
+            Dialect::Scottish => SCOTTISH,
+            _ => return None,
+        };
+        let ps = rewrite(&untie_shorthands(phonemes), rules);
+        Some(if *dialect == Dialect::Australian { flap(&ps) } else { ps })
+    }
+
+    fn untie_shorthands(phonemes: &str) -> String {
+        let mut out = String::with_capacity(phonemes.len());
+        for c in phonemes.chars() {
+            match SHORTHANDS.iter().find(|&&(letter, _)| letter == c) {
+                Some((_, tied)) => out += tied,
+                None => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Applies each rule in turn, wherever its sounds stand on their own:
+    /// not tied into a diphthong and not lengthened.
+    fn rewrite(phonemes: &str, rules: &[(&str, &str)]) -> String {
+        let mut ps = phonemes.to_string();
+        for &(from, to) in rules {
+            let mut out = String::with_capacity(ps.len());
+            let mut last = 0;
+            for (at, _) in ps.match_indices(from) {
+                let before = ps[..at].chars().next_back();
+                let after = ps[at + from.len()..].chars().next();
+                if before == Some(ZWJ) || matches!(after, Some(ZWJ | 'ː')) {
+                    continue;
+                }
+                out += &ps[last..at];
+                out += to;
+                last = at + from.len();
+            }
+            out += &ps[last..];
+            ps = out;
+        }
+        ps
+    }
+
+    fn is_vowel(c: char) -> bool {
+        "aeiouæɐɑɒɔəɛɜɪʊʌᵊᵻː".contains(c)
+    }
+
+    /// "t" after a vowel and before an unstressed one is a flap, as in
+    /// "better".
+    fn flap(phonemes: &str) -> String {
+        let chars: Vec<char> = phonemes.chars().collect();
+        chars
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| {
+                let between = i > 0 && is_vowel(chars[i - 1]) && chars.get(i + 1).is_some_and(|&n| is_vowel(n));
+                if c == 't' && between { 'ɾ' } else { c }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::{Dialect, Language};
+    use crate::lexicon::PhonemeEntry;
+    use std::collections::HashMap;
+
+    fn context<'a>(prev2: Option<&'a str>, prev: Option<&'a str>, next: Option<&'a str>) -> Neighbors<'a> {
+        Neighbors {
+            prev2: prev2.map(|w| (w, "NN")),
+            prev: prev.map(|w| (w, "NN")),
+            next: next.map(|w| (w, "NN")),
+        }
+    }
+
+    fn resolve(word: &str, tag: &str, prev2: Option<&str>, prev: Option<&str>, next: Option<&str>) -> Option<&'static str> {
+        English.heteronym(word, tag, &context(prev2, prev, next), false)
+    }
+
+    #[test]
+    fn test_heteronym_read() {
+        assert_eq!(resolve("read", "VBD", None, Some("I"), Some("it")), Some("ɹˈɛd"));
+        assert_eq!(resolve("read", "VB", Some("I"), Some("have"), Some("it")), Some("ɹˈɛd"));
+        assert_eq!(resolve("read", "VB", Some("I"), Some("will"), Some("it")), None);
+        assert_eq!(resolve("read", "VB", None, Some("to"), Some("it")), None);
+    }
+
+    #[test]
+    fn test_heteronym_lead() {
+        assert_eq!(resolve("lead", "NN", None, Some("the"), Some("pipe")), Some("lˈɛd"));
+        assert_eq!(resolve("lead", "NN", Some("made"), Some("of"), None), Some("lˈɛd"));
+        assert_eq!(resolve("lead", "NN", Some("follow"), Some("my"), None), None);
+        assert_eq!(resolve("lead", "VB", None, Some("to"), Some("the")), None);
+    }
+
+    #[test]
+    fn test_heteronym_live() {
+        assert_eq!(resolve("live", "NN", None, Some("a"), Some("show")), Some("lˈa\u{200d}ɪv"));
+        assert_eq!(resolve("live", "JJ", Some("we"), Some("go"), None), Some("lˈa\u{200d}ɪv"));
+        assert_eq!(resolve("live", "VBP", None, Some("they"), Some("here")), None);
+        assert_eq!(resolve("live", "JJ", None, Some("to"), None), None);
+    }
+
+    #[test]
+    fn test_heteronym_wind() {
+        assert_eq!(resolve("wind", "IN", None, None, Some("the")), Some("wˈa\u{200d}ɪnd"));
+        assert_eq!(resolve("wind", "VB", None, Some("to"), Some("down")), Some("wˈa\u{200d}ɪnd"));
+        assert_eq!(resolve("wind", "NN", None, Some("the"), Some("blew")), None);
+    }
+
+    #[test]
+    fn test_heteronym_tear() {
+        assert_eq!(resolve("tear", "VBD", None, Some("don't"), Some("it")), Some("tˈɛ\u{200d}ɹ"));
+        assert_eq!(resolve("tear", "NN", Some("wear"), Some("and"), None), Some("tˈɛ\u{200d}ɹ"));
+        assert_eq!(
+            English.heteronym("tear", "VB", &context(None, None, Some("up")), true),
+            Some("tˈe\u{200d}ə")
+        );
+        assert_eq!(resolve("tear", "NN", None, Some("a"), Some("fell")), None);
+    }
+
+    #[test]
+    fn test_heteronym_bass() {
+        assert_eq!(resolve("bass", "NN", None, Some("sea"), None), Some("bˈæs"));
+        assert_eq!(resolve("bass", "NN", None, None, Some("fishing")), Some("bˈæs"));
+        assert_eq!(resolve("bass", "NN", None, Some("the"), Some("guitar")), None);
+    }
+
+    #[test]
+    fn test_heteronym_bow() {
+        assert_eq!(resolve("bow", "VB", None, None, Some("down")), Some("bˈa\u{200d}ʊ"));
+        assert_eq!(resolve("bow", "NN", Some("take"), Some("a"), None), Some("bˈa\u{200d}ʊ"));
+        assert_eq!(resolve("bow", "NN", None, Some("the"), Some("of")), Some("bˈa\u{200d}ʊ"));
+        assert_eq!(resolve("bow", "NN", None, Some("a"), Some("and")), None);
+    }
+
+    #[test]
+    fn test_heteronym_wound() {
+        assert_eq!(resolve("wound", "VBD", None, Some("he"), Some("up")), Some("wˈa\u{200d}ʊnd"));
+        assert_eq!(resolve("wound", "NN", None, Some("the"), Some("healed")), None);
+        assert_eq!(resolve("wound", "NN", None, Some("his"), Some("up")), None);
+    }
+
+    #[test]
+    fn test_expand_abbreviation() {
+        let expand = |word: &str, prev: Option<&str>, next: Option<&str>| {
+            English.expand_abbreviation(word, prev, next).map(|a| (a.expansion, a.before_name))
+        };
+        assert_eq!(expand("St", Some("IN"), Some("NNP")), Some(("Saint", true)));
+        assert_eq!(expand("St", Some("NNP"), Some("IN")), Some(("Street", false)));
+        assert_eq!(expand("St", Some("NNP"), Some("NNP")), Some(("Street", false)));
+        assert_eq!(expand("Dr", None, Some("NNP")), Some(("Doctor", true)));
+        // "Drive" only in an address
+        assert_eq!(expand("Dr", Some("NNP"), Some(".")), Some(("Drive", false)));
+        assert_eq!(expand("Dr", Some("CD"), None), Some(("Drive", false)));
+        assert_eq!(expand("Dr", Some("DT"), Some("VBD")), Some(("Doctor", false)));
+        assert_eq!(expand("MR", None, None), Some(("Mister", false)));
+        assert_eq!(expand("etc", Some("NN"), Some("NNP")), Some(("et cetera", false)));
+        // Read as an ordinal's ending ("3rd."), not "Road"
+        assert_eq!(expand("rd", Some("CD"), None), None);
+        assert_eq!(expand("Rd", Some("NNP"), Some(".")), Some(("Road", false)));
+        assert_eq!(expand("Smith", None, None), None);
+    }
+
+    #[test]
+    fn test_accents() {
+        let accent = |ps: &str, dialect: Dialect| accents::apply(ps, &dialect).unwrap();
+        assert_eq!(accent("bˈɛtə", Dialect::Australian), "bˈɛɾə");
+        assert_eq!(accent("fˈe\u{200d}ɪs", Dialect::Australian), "fˈæ\u{200d}ɪs");
+        assert_eq!(accent("kˈat", Dialect::Australian), "kˈæt");
+        assert_eq!(accent("ətˈak", Dialect::Australian), "ətˈæk");
+        assert_eq!(accent("ðə θˈɪŋ", Dialect::Indian), "də tˈɪŋ");
+        assert_eq!(accent("ɡˈə\u{200d}ʊt", Dialect::Indian), "ɡˈoːt");
+        assert_eq!(accent("bˈɛɾɚ", Dialect::Irish), "bˈɛtɚ");
+        assert_eq!(accent("kˈɑː\u{200d}ɹ", Dialect::Scottish), "kˈaɹ");
+        assert_eq!(accent("fˈʊt", Dialect::Scottish), "fˈut");
+        assert_eq!(accent("hˈa\u{200d}ʊs", Dialect::Scottish), "hˈa\u{200d}ʊs");
+        assert_eq!(accents::apply("bˈɛɾɚ", &Dialect::US), None);
+    }
+
+    #[test]
+    fn test_accent_shorthands() {
+        // The lexicon's single-letter diphthongs are accented as their tied forms
+        let read = |lang: Language, text: &str| crate::G2P::new(lang).g2p(text).unwrap().0;
+        let accented = |lang: Language| ["don't", "say", "my"].map(|word| read(lang.clone(), word));
+        assert_eq!(accented(Language::EnglishAU), ["dˈɐ\u{200d}ʊnt", "sˈæ\u{200d}ɪ", "mɑ\u{200d}ɪ"]);
+        assert_eq!(accented(Language::EnglishIN), ["dˈoːnt", "sˈeː", "ma\u{200d}ɪ"]);
+        assert_eq!(accented(Language::EnglishIE), ["dˈoːnt", "sˈeː", "ma\u{200d}ɪ"]);
+        assert_eq!(accented(Language::EnglishSC), ["dˈont", "sˈe", "ma\u{200d}ɪ"]);
+        assert_eq!(read(Language::EnglishIE, "don't know"), "dˈoːnt nˈoː");
+        assert_eq!(accents::apply("mˈI", &Dialect::Australian).unwrap(), "mˈɑ\u{200d}ɪ");
+    }
+
+    fn lexicon() -> Lexicon {
+        let golds: HashMap<String, PhonemeEntry> = [
+            ("happy", "hˈæpi"),
+            ("help", "hˈɛlp"),
+            ("pay", "pˈA"),
+            ("love", "lˈʌv"),
+            ("quick", "kwˈɪk"),
+            ("heat", "hˈit"),
+            ("door", "dˈɔɹ"),
+            ("knob", "nˈɑb"),
+        ]
+        .into_iter()
+        .map(|(w, ps)| (w.to_string(), PhonemeEntry::Simple(ps.to_string())))
+        .collect();
+        Lexicon::from_entries(Language::EnglishUS, golds, HashMap::new())
+    }
+
+    #[test]
+    fn test_derivational_suffixes() {
+        let lexicon = lexicon();
+        let rules = |word: &str| English.apply_rules(word, "NN", &lexicon);
+        assert_eq!(rules("happiness").as_deref(), Some("hˈæpinəs"));
+        assert_eq!(rules("payment").as_deref(), Some("pˈAmənt"));
+        assert_eq!(rules("helpless").as_deref(), Some("hˈɛlpləs"));
+        assert_eq!(rules("lovable").as_deref(), Some("lˈʌvəbəl"));
+        assert_eq!(rules("quickly").as_deref(), Some("kwˈɪkli"));
+        assert_eq!(rules("helpfulness").as_deref(), Some("hˈɛlpfəlnəs"));
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let lexicon = lexicon();
+        let rules = |word: &str| English.apply_rules(word, "VB", &lexicon);
+        assert_eq!(rules("unhappy").as_deref(), Some("ˌʌnhˈæpi"));
+        assert_eq!(rules("preheat").as_deref(), Some("pɹˌihˈit"));
+        assert_eq!(rules("reheat").as_deref(), Some("ɹˌihˈit"));
+        assert_eq!(rules("unhelpful").as_deref(), Some("ˌʌnhˈɛlpfəl"));
+    }
+
+    #[test]
+    fn test_compound_stems() {
+        let lexicon = lexicon();
+        let rules = |word: &str| English.apply_rules(word, "NN", &lexicon);
+        assert_eq!(rules("doorknobless").as_deref(), Some("dˈɔɹnˌɑbləs"));
+        // Whole compounds are G2P's to split, after the rules
+        assert_eq!(rules("doorknob"), None);
+        assert_eq!(rules("ly"), None);
+    }
+
+    #[test]
+    fn test_names() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        let name = |word: &str| English.guess_name(word, &lexicon, false).unwrap();
+        assert_eq!(name("Goldberg"), "ɡˈo\u{200d}ʊldbɜːɡ");
+        assert!(name("Zorbson").starts_with("zˈ") && name("Zorbson").ends_with("sən"));
+        // Stressed before "-ski", on the nearest full vowel rather than a
+        // schwa from the letter-to-sound rules
+        for word in ["Kowalski", "Yablonski"] {
+            let ps = name(word);
+            let (_, stressed) = ps.split_once('ˈ').unwrap();
+            assert!(ps.ends_with("ski") && !stressed.starts_with(['ə', 'ᵻ']), "{}: {}", word, ps);
         }
+        assert_eq!(English.guess_name("Zorb", &lexicon, false), None);
+        // Guesses, left to `G2P::read_unknown`, not rules
+        assert_eq!(English.apply_rules("Goldberg", "NNP", &lexicon), None);
     }
 }
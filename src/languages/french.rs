@@ -0,0 +1,562 @@
+//! Rule-based French, in the notation espeak-ng's "fr" voice gives Kokoro:
+//! nasal vowels with a combining tilde, "ʁ", and the stress mark before the
+//! last vowel of the word. Words the rules get wrong go in `LEXICON`.
+
+use crate::lexicon::{Lexicon, PhonemeEntry};
+use crate::options::NegativeStyle;
+use super::{LanguageRules, Neighbors, NumberVerbalizer, read_decimal};
+use std::collections::HashMap;
+
+pub struct French;
+
+/// Seed lexicon of common words whose spelling misleads the rules.
+const LEXICON: &[(&str, &str)] = &[
+    ("et", "e"),
+    ("est", "ɛ"),
+    ("les", "le"),
+    ("des", "de"),
+    ("mes", "me"),
+    ("tes", "te"),
+    ("ses", "se"),
+    ("ces", "se"),
+    ("eu", "ˈy"),
+    ("août", "ˈut"),
+    ("monsieur", "məsjˈø"),
+    ("messieurs", "mesjˈø"),
+    ("femme", "fˈam"),
+    ("fils", "fˈis"),
+    ("ville", "vˈil"),
+    ("mille", "mˈil"),
+    ("million", "miljˈɔ̃"),
+    ("milliard", "miljˈaʁ"),
+    ("tranquille", "tʁɑ̃kˈil"),
+    ("oignon", "ɔɲˈɔ̃"),
+    ("second", "səɡˈɔ̃"),
+    ("seconde", "səɡˈɔ̃d"),
+    ("six", "sˈis"),
+    ("dix", "dˈis"),
+    ("sept", "sˈɛt"),
+    ("huit", "ɥˈit"),
+    ("soixante", "swasˈɑ̃t"),
+    ("deuxième", "døzjˈɛm"),
+    ("sixième", "sizjˈɛm"),
+    ("dixième", "dizjˈɛm"),
+    ("pays", "peˈi"),
+    ("yeux", "jˈø"),
+    ("œil", "ˈœj"),
+    ("ouest", "wˈɛst"),
+    ("fait", "fˈɛ"),
+    ("faisons", "fəzˈɔ̃"),
+    ("plus", "plˈy"),
+    ("sont", "sˈɔ̃"),
+    ("ont", "ˈɔ̃"),
+    ("aujourd'hui", "oʒuʁdɥˈi"),
+];
+
+/// Words read with their final consonant before a vowel ("les amis"), and
+/// how.
+const LIAISONS: &[(&str, &str)] = &[
+    ("les", "lez"),
+    ("des", "dez"),
+    ("mes", "mez"),
+    ("tes", "tez"),
+    ("ses", "sez"),
+    ("ces", "sez"),
+    ("aux", "oz"),
+    ("nous", "nuz"),
+    ("vous", "vuz"),
+    ("ils", "ilz"),
+    ("elles", "ɛlz"),
+    ("on", "ɔ̃n"),
+    ("un", "œ̃n"),
+    ("en", "ɑ̃n"),
+    ("mon", "mɔ̃n"),
+    ("ton", "tɔ̃n"),
+    ("son", "sɔ̃n"),
+    ("dans", "dɑ̃z"),
+    ("sans", "sɑ̃z"),
+    ("chez", "ʃez"),
+    ("très", "tʁɛz"),
+    ("deux", "døz"),
+    ("trois", "tʁwaz"),
+    ("vingt", "vɛ̃t"),
+    ("est", "ɛt"),
+    ("sont", "sɔ̃t"),
+    ("ont", "ɔ̃t"),
+    ("petit", "pətit"),
+    ("grand", "ɡʁɑ̃t"),
+];
+
+/// Function words read without stress.
+const UNSTRESSED: &[&str] = &[
+    "à", "au", "aux", "ce", "de", "du", "elle", "elles", "en", "il", "ils", "je", "la", "le", "leur", "leurs", "ma",
+    "me", "mon", "ne", "nous", "on", "ou", "que", "qui", "sa", "se", "son", "ta", "te", "ton", "tu", "un", "une",
+    "vous", "y",
+];
+
+/// Words of more than one syllable whose "-ent" is read, not the mute
+/// ending of a verb ("ils parlent"). Adverbs in "-ment" are read too.
+const SOUNDED_ENT: &[&str] = &[
+    "absent", "accident", "adolescent", "agent", "argent", "client", "coefficient", "conséquent", "content",
+    "continent", "couvent", "différent", "éloquent", "équivalent", "évident", "excellent", "fréquent", "ingrédient",
+    "innocent", "intelligent", "orient", "parent", "patient", "permanent", "président", "présent", "prudent",
+    "quotient", "récent", "serpent", "souvent", "talent", "urgent",
+];
+
+/// Verbs in "-ment" whose ending is mute, unlike the adverbs.
+const MUTE_MENT: &[&str] = &[
+    "affirment", "aiment", "animent", "calment", "confirment", "dorment", "enferment", "estiment", "expriment",
+    "ferment", "forment", "impriment", "informent", "sèment", "suppriment", "transforment",
+];
+
+/// Elided words before an apostrophe ("l'homme", "qu'il").
+const ELISIONS: &[(&str, &str)] = &[
+    ("c", "s"),
+    ("d", "d"),
+    ("j", "ʒ"),
+    ("l", "l"),
+    ("m", "m"),
+    ("n", "n"),
+    ("s", "s"),
+    ("t", "t"),
+    ("qu", "k"),
+    ("jusqu", "ʒysk"),
+    ("lorsqu", "lɔʁsk"),
+    ("puisqu", "pɥisk"),
+];
+
+pub fn lexicon() -> HashMap<String, PhonemeEntry> {
+    LEXICON.iter().map(|&(w, ps)| (w.to_string(), PhonemeEntry::Simple(ps.to_string()))).collect()
+}
+
+impl LanguageRules for French {
+    fn apply_rules(&self, word: &str, tag: &str, lexicon: &Lexicon) -> Option<String> {
+        let lower = word.to_lowercase().replace('’', "'");
+        if let Some((head, rest)) = lower.split_once('\'')
+            && let Some(&(_, head)) = ELISIONS.iter().find(|(e, _)| *e == head)
+        {
+            let rest = match lexicon.lookup(rest, tag, None, None) {
+                Some(p) => p.phonemes,
+                None => phonemize(rest)?.into(),
+            };
+            return Some(head.to_string() + &rest);
+        }
+        phonemize(&lower)
+    }
+
+    fn heteronym(&self, word: &str, _tag: &str, neighbors: &Neighbors, _british: bool) -> Option<&'static str> {
+        let (next, _) = neighbors.next?;
+        let lower = word.to_lowercase();
+        let &(_, linked) = LIAISONS.iter().find(|(w, _)| *w == lower)?;
+        // "h" is taken as mute, as in most words
+        next.starts_with(|c: char| is_vowel(c.to_lowercase().next().unwrap_or(c)) || matches!(c, 'h' | 'H')).then_some(linked)
+    }
+}
+
+/// Stands for a final "e" that is written but not read, keeping the letter
+/// before it from counting as final.
+const MUTE_E: char = '·';
+
+fn is_vowel(c: char) -> bool {
+    "aeiouyàâäéèêëîïôöùûüœ".contains(c) || c == MUTE_E
+}
+
+/// Softens a "c" or "g" before it, a mute "e" too ("place", "mange").
+fn is_front(c: Option<char>) -> bool {
+    c.is_some_and(|c| "eiyéèêë".contains(c) || c == MUTE_E)
+}
+
+/// Vowel groups, not counting the "u" of "qu" and "gu".
+fn syllables(word: &[char]) -> usize {
+    let mut count = 0;
+    for (i, &c) in word.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|j| word[j]);
+        let silent_u = c == 'u' && matches!(prev, Some('q' | 'g'));
+        let after_vowel = prev.is_some_and(|p| is_vowel(p) && !(p == 'u' && i >= 2 && matches!(word[i - 2], 'q' | 'g')));
+        if is_vowel(c) && !silent_u && !after_vowel {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Rewrites the end of a word to what is read of it: "-er" and "-ez" as
+/// "é", silent final consonants dropped, a final "e" made mute, as is the
+/// "-ent" of verbs.
+fn read_ending(word: &str) -> Vec<char> {
+    let mut w: Vec<char> = word.chars().collect();
+    let polysyllabic = syllables(&w) > 1;
+    let ends = |w: &[char], s: &str| w.iter().rev().take(s.chars().count()).rev().copied().eq(s.chars());
+    // Monosyllables keep theirs ("vent", "dent"), but not after a vowel ("rient")
+    let mute_ent = ends(&w, "ent")
+        && !SOUNDED_ENT.contains(&word)
+        && ((w.len() > 3 && is_vowel(w[w.len() - 4]))
+            || (polysyllabic && (!ends(&w, "ment") || MUTE_MENT.contains(&word))));
+    if ends(&w, "aient") {
+        w.truncate(w.len() - 3);
+    } else if mute_ent {
+        w.truncate(w.len() - 2);
+    } else if (polysyllabic && ends(&w, "er")) || ends(&w, "ez") || (!polysyllabic && ends(&w, "es")) {
+        w.truncate(w.len() - 2);
+        w.push('é');
+    } else if w.len() > 2 && ends(&w, "et") {
+        w.truncate(w.len() - 2);
+        w.push('è');
+    }
+    while w.len() > 1 {
+        let last = w[w.len() - 1];
+        let prev = w[w.len() - 2];
+        if "sxztdpg".contains(last) || (last == 'c' && prev == 'n') {
+            w.pop();
+        } else {
+            break;
+        }
+    }
+    if w.len() > 1 && w[w.len() - 1] == 'e' {
+        let prev = w[w.len() - 2];
+        let after_vowel = is_vowel(prev) && !(prev == 'u' && w.len() > 2 && matches!(w[w.len() - 3], 'q' | 'g'));
+        if syllables(&w) > 1 || after_vowel {
+            *w.last_mut().unwrap() = MUTE_E;
+        }
+    }
+    w
+}
+
+/// Phonemes for a French word, or `None` if it has letters French doesn't
+/// use.
+pub fn phonemize(word: &str) -> Option<String> {
+    let lower = word.to_lowercase();
+    if lower.is_empty() || !lower.chars().all(|c| c.is_ascii_lowercase() || is_vowel(c) || c == 'ç') {
+        return None;
+    }
+    let w = read_ending(&lower);
+    let sounded_ent = SOUNDED_ENT.contains(&lower.as_str());
+    let at = |i: usize| w.get(i).copied();
+    let starts = |i: usize, s: &str| s.chars().enumerate().all(|(k, c)| at(i + k) == Some(c));
+    // A vowel before "n" or "m" is nasal unless another vowel or nasal follows
+    let nasal = |i: usize| matches!(at(i), Some('n' | 'm')) && !at(i + 1).is_some_and(|c| is_vowel(c) || c == 'n' || c == 'm');
+
+    // (phonemes, is a vowel)
+    let mut out: Vec<(&str, bool)> = Vec::new();
+    let mut i = 0;
+    while i < w.len() {
+        let c = w[i];
+        let prev = i.checked_sub(1).map(|j| w[j]);
+        let next = at(i + 1);
+        let (ps, vowel, len): (&str, bool, usize) = match c {
+            MUTE_E => ("", false, 1),
+            'e' if starts(i, "eau") => ("o", true, 3),
+            'a' if starts(i, "au") => ("o", true, 2),
+            'o' if starts(i, "oin") && nasal(i + 2) => ("wɛ̃", true, 3),
+            'o' if starts(i, "oi") || starts(i, "oî") => ("wa", true, 2),
+            'o' if starts(i, "oy") && at(i + 2).is_some_and(is_vowel) => ("waj", true, 2),
+            'o' if starts(i, "ou") || starts(i, "où") || starts(i, "oû") => {
+                if at(i + 2).is_some_and(|c| is_vowel(c) && c != MUTE_E) { ("w", false, 2) } else { ("u", true, 2) }
+            }
+            'a' if (starts(i, "ail") && i + 3 == w.len()) || starts(i, "aill") => ("aj", true, if starts(i, "aill") { 4 } else { 3 }),
+            'e' if (starts(i, "eil") && i + 3 == w.len()) || starts(i, "eill") => ("ɛj", true, if starts(i, "eill") { 4 } else { 3 }),
+            'e' | 'œ' if (starts(i + 1, "uil") && i + 4 == w.len()) || starts(i + 1, "uill") => {
+                ("œj", true, if starts(i + 1, "uill") { 5 } else { 4 })
+            }
+            'o' if starts(i, "ouill") => ("uj", true, 5),
+            'e' | 'œ' if next == Some('u') => {
+                let open = at(i + 2).is_some_and(|c| !"sz".contains(c) && !is_vowel(c));
+                if open { ("œ", true, 2) } else { ("ø", true, 2) }
+            }
+            'a' | 'e' if next == Some('i') && nasal(i + 2) => ("ɛ̃", true, 3),
+            'a' if starts(i, "ai") || starts(i, "aî") => ("ɛ", true, 2),
+            'e' if starts(i, "ei") => ("ɛ", true, 2),
+            'a' if next == Some('y') && at(i + 2).is_some_and(is_vowel) => ("ɛj", true, 2),
+            // The "-ient" of nouns and adjectives is "jɑ̃" ("client", "patient")
+            'i' if starts(i, "ien") && nasal(i + 2) && i > 0 && !(sounded_ent && i + 3 == w.len()) => ("jɛ̃", true, 3),
+            'i' | 'î' | 'y' if nasal(i + 1) => ("ɛ̃", true, 2),
+            'a' | 'e' if nasal(i + 1) => ("ɑ̃", true, 2),
+            'o' if nasal(i + 1) => ("ɔ̃", true, 2),
+            'u' if nasal(i + 1) => ("œ̃", true, 2),
+            'u' if next == Some('i') => ("ɥi", true, 2),
+            'i' if starts(i, "ill") && prev.is_some_and(|p| !is_vowel(p)) => ("ij", true, 3),
+            'a' | 'à' | 'â' | 'ä' => ("a", true, 1),
+            'é' => ("e", true, 1),
+            'è' | 'ê' | 'ë' => ("ɛ", true, 1),
+            'e' => {
+                let (n1, n2) = (at(i + 1), at(i + 2));
+                let consonant = |c: Option<char>| c.is_some_and(|c| !is_vowel(c));
+                let closed = n1 == Some('x')
+                    || (consonant(n1) && n2.is_none())
+                    || (consonant(n1) && consonant(n2) && (n1 == n2 || !matches!(n2, Some('r' | 'l' | 'h'))));
+                if closed { ("ɛ", true, 1) } else { ("ə", true, 1) }
+            }
+            'i' | 'î' | 'ï' | 'y' if next.is_some_and(|c| is_vowel(c) && c != MUTE_E) => ("j", false, 1),
+            'i' | 'î' | 'ï' | 'y' => ("i", true, 1),
+            'o' if next.is_none() || (next == Some('s') && at(i + 2).is_some_and(is_vowel)) || next == Some('z') => {
+                ("o", true, 1)
+            }
+            'ô' => ("o", true, 1),
+            'o' | 'ö' => ("ɔ", true, 1),
+            'u' | 'û' | 'ü' | 'ù' if next.is_some_and(|c| is_vowel(c) && c != MUTE_E) => ("ɥ", false, 1),
+            'u' | 'û' | 'ü' | 'ù' => ("y", true, 1),
+            'œ' => ("œ", true, 1),
+            'c' if next == Some('h') => ("ʃ", false, 2),
+            'p' if next == Some('h') => ("f", false, 2),
+            't' if next == Some('h') => ("t", false, 2),
+            'g' if next == Some('n') => ("ɲ", false, 2),
+            'q' if next == Some('u') => ("k", false, 2),
+            'q' => ("k", false, 1),
+            'g' if next == Some('u') && is_front(at(i + 2)) => ("ɡ", false, 2),
+            'g' if next == Some('e') && at(i + 2).is_some_and(|c| "aou".contains(c)) => ("ʒ", false, 2),
+            'g' if is_front(next) => ("ʒ", false, 1),
+            'g' => ("ɡ", false, 1 + usize::from(next == Some('g'))),
+            'c' if next == Some('c') && is_front(at(i + 2)) => ("ks", false, 2),
+            'c' if is_front(next) => ("s", false, 1),
+            'ç' => ("s", false, 1),
+            'c' => ("k", false, 1 + usize::from(next == Some('c'))),
+            's' if next == Some('c') && is_front(at(i + 2)) => ("s", false, 2),
+            's' if next == Some('s') => ("s", false, 2),
+            's' if prev.is_some_and(is_vowel) && next.is_some_and(is_vowel) => ("z", false, 1),
+            't' if starts(i + 1, "ion") && prev != Some('s') => ("s", false, 1),
+            // "-tient" nouns read like "-tion" ("patient", "quotient")
+            't' if sounded_ent && i + 4 == w.len() && starts(i + 1, "ien") => ("s", false, 1),
+            'x' if i == 1 && prev == Some('e') && next.is_some_and(is_vowel) => ("ɡz", false, 1),
+            'x' => ("ks", false, 1),
+            'h' => ("", false, 1),
+            'j' => ("ʒ", false, 1),
+            'r' => ("ʁ", false, 1 + usize::from(next == Some('r'))),
+            'b' | 'd' | 'f' | 'k' | 'l' | 'm' | 'n' | 'p' | 's' | 't' | 'v' | 'w' | 'z' => {
+                let ps = match c {
+                    'b' => "b",
+                    'd' => "d",
+                    'f' => "f",
+                    'k' => "k",
+                    'l' => "l",
+                    'm' => "m",
+                    'n' => "n",
+                    'p' => "p",
+                    's' => "s",
+                    't' => "t",
+                    'v' => "v",
+                    'w' => "w",
+                    _ => "z",
+                };
+                (ps, false, 1 + usize::from(next == Some(c)))
+            }
+            _ => return None,
+        };
+        out.push((ps, vowel));
+        i += len;
+    }
+
+    let stressed = if UNSTRESSED.contains(&lower.as_str()) { None } else { out.iter().rposition(|&(_, v)| v) };
+    let mut phonemes = String::new();
+    for (k, (ps, _)) in out.iter().enumerate() {
+        if stressed == Some(k) {
+            // After any glide at the start of the vowel ("wa", "ɥi")
+            let glide = ps.chars().take_while(|c| "jwɥ".contains(*c)).map(char::len_utf8).sum::<usize>();
+            phonemes.push_str(&ps[..glide]);
+            phonemes.push('ˈ');
+            phonemes.push_str(&ps[glide..]);
+        } else {
+            phonemes.push_str(ps);
+        }
+    }
+    Some(phonemes)
+}
+
+const UNITS: [&str; 17] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "onze", "douze", "treize",
+    "quatorze", "quinze", "seize",
+];
+const TENS: [&str; 7] = ["", "dix", "vingt", "trente", "quarante", "cinquante", "soixante"];
+
+/// 1 to 99.
+fn below_hundred(n: u64) -> String {
+    match n {
+        0..17 => UNITS[n as usize].to_string(),
+        17..20 => format!("dix-{}", UNITS[n as usize - 10]),
+        80 => "quatre-vingts".to_string(),
+        // Seventies and nineties count on from sixty and eighty
+        70..80 => format!("soixante{}{}", if n == 71 { " et " } else { "-" }, below_hundred(n - 60)),
+        81..100 => format!("quatre-vingt-{}", below_hundred(n - 80)),
+        _ if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+        _ if n % 10 == 1 => format!("{} et un", TENS[n as usize / 10]),
+        _ => format!("{}-{}", TENS[n as usize / 10], UNITS[n as usize % 10]),
+    }
+}
+
+/// 1 to 999. "cents" and "quatre-vingts" lose their "s" unless they end
+/// the number (`last`).
+fn below_thousand(n: u64, last: bool) -> String {
+    let (hundreds, rest) = (n / 100, n % 100);
+    let mut words = match hundreds {
+        0 => String::new(),
+        1 => "cent".to_string(),
+        _ if rest == 0 && last => format!("{} cents", UNITS[hundreds as usize]),
+        _ => format!("{} cent", UNITS[hundreds as usize]),
+    };
+    if rest > 0 {
+        if !words.is_empty() {
+            words.push(' ');
+        }
+        let tens = below_hundred(rest);
+        words += if last { tens.as_str() } else { tens.strip_suffix('s').unwrap_or(&tens) };
+    }
+    words
+}
+
+/// French numbers, with the long scale ("milliard" is 10⁹, "billion" 10¹²).
+pub struct FrenchNumbers;
+
+impl NumberVerbalizer for FrenchNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("moins {}", words));
+        }
+        if n == 0 {
+            return Some(UNITS[0].to_string());
+        }
+        let mut n = n as u64;
+        let mut words = Vec::new();
+        for (scale, name) in [
+            (1_000_000_000_000_000_000, "trillion"),
+            (1_000_000_000_000_000, "billiard"),
+            (1_000_000_000_000, "billion"),
+            (1_000_000_000, "milliard"),
+            (1_000_000, "million"),
+        ] {
+            let count = n / scale;
+            n %= scale;
+            if count > 0 {
+                // Scale words are nouns, so "cents" before them keeps its "s"
+                let plural = if count > 1 { "s" } else { "" };
+                words.push(format!("{} {}{}", below_thousand(count, true), name, plural));
+            }
+        }
+        match n / 1000 {
+            0 => {}
+            1 => words.push("mille".to_string()),
+            thousands => words.push(format!("{} mille", below_thousand(thousands, false))),
+        }
+        if !n.is_multiple_of(1000) {
+            words.push(below_thousand(n % 1000, true));
+        }
+        Some(words.join(" "))
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        if n == 1 {
+            return Some("premier".to_string());
+        }
+        let cardinal = self.cardinal(n)?;
+        let stem = cardinal.strip_suffix('s').filter(|s| s.ends_with("vingt") || s.ends_with("cent")).unwrap_or(&cardinal);
+        let stem = if let Some(s) = stem.strip_suffix("cinq") {
+            format!("{}cinqu", s)
+        } else if let Some(s) = stem.strip_suffix("neuf") {
+            format!("{}neuv", s)
+        } else {
+            stem.strip_suffix('e').unwrap_or(stem).to_string()
+        };
+        Some(stem + "ième")
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "virgule", " ")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("moins {}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize() {
+        let cases = [
+            ("bonjour", "bɔ̃ʒˈuʁ"),
+            ("merci", "mɛʁsˈi"),
+            ("chat", "ʃˈa"),
+            ("maison", "mɛzˈɔ̃"),
+            ("parler", "paʁlˈe"),
+            ("enfant", "ɑ̃fˈɑ̃"),
+            ("fille", "fˈij"),
+            ("travail", "tʁavˈaj"),
+            ("nation", "nasjˈɔ̃"),
+            ("rose", "ʁˈoz"),
+            ("heureuse", "œʁˈøz"),
+            ("vingt", "vˈɛ̃"),
+            ("beaucoup", "bokˈu"),
+            ("bien", "bjˈɛ̃"),
+            ("pain", "pˈɛ̃"),
+            ("trois", "tʁwˈa"),
+            ("quatre", "kˈatʁ"),
+            ("le", "lə"),
+            ("place", "plˈas"),
+            ("mange", "mˈɑ̃ʒ"),
+            // The mute "-ent" of verbs, but not of nouns, adverbs or monosyllables
+            ("parlent", "pˈaʁl"),
+            ("mangent", "mˈɑ̃ʒ"),
+            ("chantent", "ʃˈɑ̃t"),
+            ("jouent", "ʒˈu"),
+            ("aiment", "ˈɛm"),
+            ("moment", "mɔmˈɑ̃"),
+            ("souvent", "suvˈɑ̃"),
+            ("rapidement", "ʁapidəmˈɑ̃"),
+            ("vent", "vˈɑ̃"),
+            ("couvent", "kuvˈɑ̃"),
+            ("client", "kljˈɑ̃"),
+            ("patient", "pasjˈɑ̃"),
+            ("quotient", "kɔsjˈɑ̃"),
+            ("coefficient", "kɔɛfisjˈɑ̃"),
+            ("ingrédient", "ɛ̃ɡʁedjˈɑ̃"),
+            ("bien", "bjˈɛ̃"),
+        ];
+        for (word, phonemes) in cases {
+            assert_eq!(phonemize(word).as_deref(), Some(phonemes), "{}", word);
+        }
+        assert_eq!(phonemize("straße"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let n = FrenchNumbers;
+        let cases = [
+            (0, "zéro"),
+            (17, "dix-sept"),
+            (21, "vingt et un"),
+            (71, "soixante et onze"),
+            (80, "quatre-vingts"),
+            (81, "quatre-vingt-un"),
+            (99, "quatre-vingt-dix-neuf"),
+            (200, "deux cents"),
+            (201, "deux cent un"),
+            (1999, "mille neuf cent quatre-vingt-dix-neuf"),
+            (80_000, "quatre-vingt mille"),
+            (200_000_000, "deux cents millions"),
+            (-5, "moins cinq"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(n.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(n.ordinal(1).as_deref(), Some("premier"));
+        assert_eq!(n.ordinal(4).as_deref(), Some("quatrième"));
+        assert_eq!(n.ordinal(5).as_deref(), Some("cinquième"));
+        assert_eq!(n.ordinal(80).as_deref(), Some("quatre-vingtième"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::French);
+        let (phonemes, _) = g2p.g2p("Les amis de l'homme ont trois chats.").unwrap();
+        assert_eq!(phonemes.split_whitespace().collect::<Vec<_>>(), ["lez", "amˈi", "də", "lˈɔm", "ˈɔ̃", "tʁwˈa", "ʃˈa"]);
+        let (phonemes, _) = g2p.g2p("Ils parlent. Elles chantent.").unwrap();
+        assert_eq!(phonemes.split_whitespace().collect::<Vec<_>>(), ["il", "pˈaʁl", "ɛl", "ʃˈɑ̃t"]);
+
+        // Decimal commas and thousands dots
+        for (text, spoken) in [("3,14", "trois virgule un quatre"), ("1.000 personnes", "mille personnes")] {
+            assert_eq!(g2p.g2p(text).unwrap().0, g2p.g2p(spoken).unwrap().0, "{}", text);
+        }
+    }
+}
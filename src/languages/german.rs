@@ -0,0 +1,503 @@
+//! Rule-based German, in the notation espeak-ng's "de" voice gives Kokoro,
+//! with misaki's single symbols for the diphthongs ("I" for "ei", "W" for
+//! "au", "Y" for "eu"). Words the rules can't read are split into compounds
+//! of lexicon words ("Haustür" → "Haus" + "Tür").
+
+use crate::lexicon::{Lexicon, PhonemeEntry};
+use crate::options::NegativeStyle;
+use super::{LanguageRules, NumberVerbalizer, read_decimal};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+pub struct German;
+
+/// Seed lexicon: words with short vowels the rules would lengthen, and common
+/// compound parts, number words included.
+const LEXICON: &[(&str, &str)] = &[
+    ("ab", "ˈap"),
+    ("am", "am"),
+    ("an", "ˈan"),
+    ("bis", "bˈɪs"),
+    ("das", "das"),
+    ("dem", "deːm"),
+    ("den", "deːn"),
+    ("der", "dɛɐ"),
+    ("des", "dɛs"),
+    ("dich", "dˈɪç"),
+    ("die", "diː"),
+    ("ein", "ˈIn"),
+    ("eine", "ˈInə"),
+    ("er", "eːɐ"),
+    ("es", "ˈɛs"),
+    ("hat", "hˈat"),
+    ("ich", "ˈɪç"),
+    ("im", "ɪm"),
+    ("in", "ˈɪn"),
+    ("ist", "ˈɪst"),
+    ("man", "man"),
+    ("mich", "mˈɪç"),
+    ("mit", "mˈɪt"),
+    ("nicht", "nˈɪçt"),
+    ("ob", "ˈɔp"),
+    ("sich", "zˈɪç"),
+    ("sie", "ziː"),
+    ("um", "ˈʊm"),
+    ("und", "ʊnt"),
+    ("vom", "fɔm"),
+    ("von", "fɔn"),
+    ("was", "vˈas"),
+    ("wir", "viːɐ"),
+    ("zu", "ʦuː"),
+    ("arbeit", "ˈaɐbIt"),
+    ("bahn", "bˈaːn"),
+    ("haus", "hˈWs"),
+    ("hof", "hˈoːf"),
+    ("platz", "plˈaʦ"),
+    ("stadt", "ʃtˈat"),
+    ("straße", "ʃtʁˈaːsə"),
+    ("tür", "tˈyːɐ"),
+    ("zeit", "ʦˈIt"),
+    ("null", "nˈʊl"),
+    ("eins", "ˈIns"),
+    ("zwei", "ʦvˈI"),
+    ("drei", "dʁˈI"),
+    ("vier", "fˈiːɐ"),
+    ("fünf", "fˈynf"),
+    ("sechs", "zˈɛks"),
+    ("sieben", "zˈiːbən"),
+    ("acht", "ˈaxt"),
+    ("neun", "nˈYn"),
+    ("zehn", "ʦˈeːn"),
+    ("elf", "ˈɛlf"),
+    ("zwölf", "ʦvˈœlf"),
+    ("sechzehn", "zˈɛçʦeːn"),
+    ("siebzehn", "zˈiːpʦeːn"),
+    ("zwanzig", "ʦvˈanʦɪç"),
+    ("dreißig", "dʁˈIsɪç"),
+    ("vierzig", "fˈɪɐʦɪç"),
+    ("fünfzig", "fˈynfʦɪç"),
+    ("sechzig", "zˈɛçʦɪç"),
+    ("siebzig", "zˈiːpʦɪç"),
+    ("achtzig", "ˈaxʦɪç"),
+    ("neunzig", "nˈYnʦɪç"),
+    ("hundert", "hˈʊndɐt"),
+    ("tausend", "tˈWzənt"),
+    ("million", "mɪljˈoːn"),
+    ("millionen", "mɪljˈoːnən"),
+    ("milliarde", "mɪljˈaɐdə"),
+    ("milliarden", "mɪljˈaɐdən"),
+];
+
+/// Unstressed prefixes, stress falling on the syllable after.
+const PREFIXES: &[&str] = &["ver", "zer", "ent", "emp", "be", "ge", "er"];
+
+/// Linking elements between the parts of a compound ("Arbeitszeit").
+const LINKS: &[&str] = &["", "s", "es", "n", "en"];
+
+pub fn lexicon() -> HashMap<String, PhonemeEntry> {
+    LEXICON.iter().map(|&(w, ps)| (w.to_string(), PhonemeEntry::Simple(ps.to_string()))).collect()
+}
+
+impl LanguageRules for German {
+    fn apply_rules(&self, word: &str, tag: &str, lexicon: &Lexicon) -> Option<String> {
+        let lower = word.to_lowercase();
+        split_compound(&lower, tag, lexicon).or_else(|| phonemize(&lower))
+    }
+}
+
+/// Reads `word` as a compound of lexicon words, the first carrying the
+/// stress and the others secondary stress. Longer first parts are tried
+/// first.
+fn split_compound(word: &str, tag: &str, lexicon: &Lexicon) -> Option<String> {
+    let known = |w: &str| lexicon.lookup(w, tag, None, None).map(|p| p.phonemes);
+    let bounds: Vec<usize> = word.char_indices().map(|(b, _)| b).collect();
+    // Parts are at least three letters long
+    for &split in bounds.iter().rev().filter(|&&b| word[..b].chars().count() >= 3) {
+        let Some(head) = known(&word[..split]) else { continue };
+        for link in LINKS {
+            let Some(rest) = word[split..].strip_prefix(link).filter(|r| r.chars().count() >= 3) else { continue };
+            if let Some(tail) = known(rest).or_else(|| split_compound(rest, tag, lexicon).map(Cow::Owned)) {
+                return Some(format!("{}{}{}", head, link, tail.replace('ˈ', "ˌ")));
+            }
+        }
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Unit {
+    /// A vowel with its long and short readings; `long` is set when the
+    /// spelling says ("ah", "ee", "ie"), and `reducible` for a plain "e".
+    Vowel { long: &'static str, short: &'static str, fixed: Option<bool>, reducible: bool },
+    /// A consonant, `double` if it closes the syllable before it on its own
+    /// ("ck", "ss", "tt").
+    Consonant { sym: &'static str, double: bool },
+}
+
+use Unit::*;
+
+const fn vowel(long: &'static str, short: &'static str) -> Unit {
+    Vowel { long, short, fixed: None, reducible: false }
+}
+
+const fn long(sym: &'static str) -> Unit {
+    Vowel { long: sym, short: sym, fixed: Some(true), reducible: false }
+}
+
+const fn cons(sym: &'static str) -> Unit {
+    Consonant { sym, double: false }
+}
+
+const fn double(sym: &'static str) -> Unit {
+    Consonant { sym, double: true }
+}
+
+fn is_vowel(c: Option<&char>) -> bool {
+    matches!(c, Some('a' | 'e' | 'i' | 'o' | 'u' | 'ä' | 'ö' | 'ü' | 'y'))
+}
+
+/// Spells `word` as sounds, with vowel length and stress still open.
+fn units(word: &[char], prefix_len: usize) -> Option<Vec<Unit>> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    let starts = |i: usize, s: &str| s.chars().enumerate().all(|(k, c)| word.get(i + k) == Some(&c));
+    while i < word.len() {
+        let c = word[i];
+        let next = word.get(i + 1);
+        let (units, len): (&[Unit], usize) = match c {
+            'a' if next == Some(&'a') || next == Some(&'h') => (&[long("aː")], 2),
+            'a' | 'e' if matches!(next, Some('i' | 'y')) => (&[long("I")], 2),
+            'a' if next == Some(&'u') => (&[long("W")], 2),
+            'a' => (&[vowel("aː", "a")], 1),
+            'ä' if next == Some(&'u') => (&[long("Y")], 2),
+            'ä' if next == Some(&'h') => (&[long("ɛː")], 2),
+            'ä' => (&[vowel("ɛː", "ɛ")], 1),
+            'e' if next == Some(&'e') || next == Some(&'h') => (&[long("eː")], 2),
+            'e' if next == Some(&'u') => (&[long("Y")], 2),
+            'e' => (&[Vowel { long: "eː", short: "ɛ", fixed: None, reducible: true }], 1),
+            'i' if starts(i, "ieh") => (&[long("iː")], 3),
+            'i' if next == Some(&'e') || next == Some(&'h') => (&[long("iː")], 2),
+            'i' if next == Some(&'g') && i + 2 == word.len() => (&[Vowel { long: "ɪ", short: "ɪ", fixed: Some(false), reducible: false }, cons("ç")], 2),
+            'i' => (&[vowel("iː", "ɪ")], 1),
+            'o' if next == Some(&'o') || next == Some(&'h') => (&[long("oː")], 2),
+            'o' => (&[vowel("oː", "ɔ")], 1),
+            'ö' if next == Some(&'h') => (&[long("øː")], 2),
+            'ö' => (&[vowel("øː", "œ")], 1),
+            'u' if next == Some(&'h') => (&[long("uː")], 2),
+            'u' => (&[vowel("uː", "ʊ")], 1),
+            'ü' if next == Some(&'h') => (&[long("yː")], 2),
+            'ü' | 'y' => (&[vowel("yː", "y")], 1),
+            's' if starts(i, "sch") => (&[double("ʃ")], 3),
+            // "sp" and "st" begin a stem with "ʃ"
+            's' if (i == 0 || i == prefix_len) && matches!(next, Some('p' | 't')) => (&[cons("ʃ")], 1),
+            's' if next == Some(&'s') => (&[double("s")], 2),
+            's' if is_vowel(next) => (&[cons("z")], 1),
+            's' | 'ß' => (&[cons("s")], 1),
+            'c' if starts(i, "chs") => (&[cons("k"), cons("s")], 3),
+            'c' if next == Some(&'h') => {
+                // After a back vowel, but not "eu" or "äu"
+                let back = i >= 1 && matches!(word[i - 1], 'a' | 'o' | 'u') && !(i >= 2 && matches!(word[i - 2], 'e' | 'ä'));
+                (if i == 0 { &[cons("k")] } else if back { &[cons("x")] } else { &[cons("ç")] }, 2)
+            }
+            'c' if next == Some(&'k') => (&[double("k")], 2),
+            'c' if matches!(next, Some('e' | 'i' | 'ä')) => (&[cons("ʦ")], 1),
+            'c' | 'k' => (&[cons("k")], 1),
+            'n' if next == Some(&'g') => (&[double("ŋ")], 2),
+            'n' if next == Some(&'k') => (&[cons("ŋ")], 1),
+            'q' if next == Some(&'u') => (&[cons("k"), cons("v")], 2),
+            'p' if next == Some(&'h') => (&[cons("f")], 2),
+            't' if next == Some(&'h') => (&[cons("t")], 2),
+            't' if starts(i, "tion") => (&[cons("ʦ"), cons("j")], 2),
+            't' if next == Some(&'z') => (&[double("ʦ")], 2),
+            'd' if next == Some(&'t') => (&[cons("t")], 2),
+            'z' => (&[cons("ʦ")], 1),
+            'v' => (&[cons("f")], 1),
+            'w' => (&[cons("v")], 1),
+            'j' => (&[cons("j")], 1),
+            'x' => (&[cons("k"), cons("s")], 1),
+            'h' => (&[cons("h")], 1),
+            'b' | 'd' | 'f' | 'g' | 'l' | 'm' | 'n' | 'p' | 'r' | 't' => {
+                let sym = match c {
+                    'b' => "b",
+                    'd' => "d",
+                    'f' => "f",
+                    'g' => "ɡ",
+                    'l' => "l",
+                    'm' => "m",
+                    'n' => "n",
+                    'p' => "p",
+                    'r' => "r",
+                    _ => "t",
+                };
+                if next == Some(&c) { (&[double(sym)], 2) } else { (&[cons(sym)], 1) }
+            }
+            _ => return None,
+        };
+        out.extend_from_slice(units);
+        i += len;
+    }
+    Some(out)
+}
+
+/// Phonemes for a German word, or `None` if it has letters German doesn't
+/// use.
+pub fn phonemize(word: &str) -> Option<String> {
+    let lower: Vec<char> = word.to_lowercase().chars().collect();
+    let text: String = lower.iter().collect();
+    let prefix = PREFIXES.iter().find(|p| text.starts_with(*p)).map_or(0, |p| p.len());
+    let units = units(&lower, prefix)?;
+    let nuclei: Vec<usize> = units.iter().enumerate().filter(|(_, u)| matches!(u, Vowel { .. })).map(|(i, _)| i).collect();
+    if nuclei.is_empty() {
+        return None;
+    }
+
+    // Stress falls on the first syllable of the stem, or on a stressed suffix
+    let stressed = if ["ion", "tät", "ur"].iter().any(|s| text.ends_with(s)) || (text.ends_with("ei") && nuclei.len() > 2) {
+        nuclei.len() - 1
+    } else if let Some(ie) = ["ieren", "iert"].iter().find(|s| text.ends_with(*s)) {
+        nuclei.len() - if ie.len() == 5 { 2 } else { 1 }
+    } else if prefix > 0 && (nuclei.len() > 2 || (nuclei.len() == 2 && prefix == 3)) {
+        1
+    } else {
+        0
+    };
+    let stressed = nuclei[stressed];
+
+    let is_long = |i: usize| match units[i] {
+        Vowel { fixed: Some(long), .. } => long,
+        // Long in open syllables and before one consonant
+        _ => {
+            let weight: usize = units[i + 1..]
+                .iter()
+                .take_while(|u| matches!(u, Consonant { .. }))
+                .map(|u| if matches!(u, Consonant { double: true, .. }) { 2 } else { 1 })
+                .sum();
+            weight <= 1
+        }
+    };
+
+    let mut out = String::new();
+    let mut i = 0;
+    while i < units.len() {
+        let next = units.get(i + 1);
+        match units[i] {
+            Vowel { long, short, reducible, .. } => {
+                if i == stressed {
+                    out.push('ˈ');
+                }
+                if reducible && i != stressed {
+                    // An unstressed "er" before a consonant or the end is "ɐ"
+                    let after = units.get(i + 2);
+                    if matches!(next, Some(Consonant { sym: "r", .. })) && !matches!(after, Some(Vowel { .. })) {
+                        out.push('ɐ');
+                        i += 2;
+                        continue;
+                    }
+                    out.push('ə');
+                } else {
+                    out.push_str(if is_long(i) { long } else { short });
+                }
+            }
+            Consonant { sym, .. } => {
+                let coda = !matches!(next, Some(Vowel { .. }));
+                let sym = match sym {
+                    // "r" after a long vowel is vocalized
+                    "r" if coda && i > 0 && matches!(units[i - 1], Vowel { .. }) && is_long(i - 1) => "ɐ",
+                    "r" => "ʁ",
+                    // Final devoicing, except before "l" and "r"
+                    "b" | "d" | "ɡ" if coda && !matches!(next, Some(Consonant { sym: "l" | "r", .. })) => match sym {
+                        "b" => "p",
+                        "d" => "t",
+                        _ => "k",
+                    },
+                    sym => sym,
+                };
+                out.push_str(sym);
+            }
+        }
+        i += 1;
+    }
+    Some(out)
+}
+
+const UNITS: [&str; 20] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "zehn", "elf", "zwölf",
+    "dreizehn", "vierzehn", "fünfzehn", "sechzehn", "siebzehn", "achtzehn", "neunzehn",
+];
+const TENS: [&str; 10] =
+    ["", "", "zwanzig", "dreißig", "vierzig", "fünfzig", "sechzig", "siebzig", "achtzig", "neunzig"];
+
+/// "eins" as the first part of a compound.
+fn ein(words: String) -> String {
+    match words.strip_suffix("eins") {
+        Some(stem) => stem.to_string() + "ein",
+        None => words,
+    }
+}
+
+/// 1 to 99.
+fn below_hundred(n: u64) -> String {
+    match n {
+        0..20 => UNITS[n as usize].to_string(),
+        _ if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+        _ => format!("{}und{}", ein(UNITS[n as usize % 10].to_string()), TENS[n as usize / 10]),
+    }
+}
+
+/// 1 to 999 999, as one word.
+fn below_million(n: u64) -> String {
+    let mut words = String::new();
+    if n >= 1000 {
+        words += &ein(below_million(n / 1000));
+        words += "tausend";
+    }
+    let hundreds = n % 1000 / 100;
+    if hundreds > 0 {
+        words += &ein(UNITS[hundreds as usize].to_string());
+        words += "hundert";
+    }
+    if !n.is_multiple_of(100) {
+        words += &below_hundred(n % 100);
+    }
+    words
+}
+
+/// German numbers, with the long scale ("Milliarde" is 10⁹, "Billion" 10¹²).
+pub struct GermanNumbers;
+
+impl NumberVerbalizer for GermanNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("minus {}", words));
+        }
+        if n == 0 {
+            return Some(UNITS[0].to_string());
+        }
+        let mut n = n as u64;
+        let mut words = Vec::new();
+        for (scale, one, many) in [
+            (1_000_000_000_000_000_000, "Trillion", "Trillionen"),
+            (1_000_000_000_000_000, "Billiarde", "Billiarden"),
+            (1_000_000_000_000, "Billion", "Billionen"),
+            (1_000_000_000, "Milliarde", "Milliarden"),
+            (1_000_000, "Million", "Millionen"),
+        ] {
+            match n / scale {
+                0 => {}
+                1 => words.push(format!("eine {}", one)),
+                count => words.push(format!("{} {}", ein(below_million(count)), many)),
+            }
+            n %= scale;
+        }
+        if n > 0 {
+            words.push(below_million(n));
+        }
+        Some(words.join(" "))
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        let words = self.cardinal(n)?;
+        let last = n.rem_euclid(100);
+        Some(match last {
+            1 => words.strip_suffix("eins")?.to_string() + "erste",
+            3 => words.strip_suffix("drei")?.to_string() + "dritte",
+            7 => words.strip_suffix("sieben")?.to_string() + "siebte",
+            8 => words + "e",
+            _ if last > 0 && last < 20 => words + "te",
+            _ => words + "ste",
+        })
+    }
+
+    /// Years before 2000 are read in hundreds ("neunzehnhundertneunundneunzig").
+    fn year(&self, n: i64) -> Option<String> {
+        match n {
+            1100..2000 => {
+                let rest = if n % 100 > 0 { below_hundred(n as u64 % 100) } else { String::new() };
+                Some(format!("{}hundert{}", UNITS[n as usize / 100], rest))
+            }
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "Komma", " ")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("minus {}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize() {
+        let cases = [
+            ("haus", "hˈWs"),
+            ("buch", "bˈuːx"),
+            ("straße", "ʃtʁˈaːsə"),
+            ("kinder", "kˈɪndɐ"),
+            ("tag", "tˈaːk"),
+            ("schule", "ʃˈuːlə"),
+            ("verstehen", "fɐʃtˈeːən"),
+            ("bäckerei", "bɛkəʁˈI"),
+            ("nation", "naʦjˈoːn"),
+            ("hund", "hˈʊnt"),
+            ("könig", "kˈøːnɪç"),
+            ("wasser", "vˈasɐ"),
+            ("tür", "tˈyːɐ"),
+            ("fisch", "fˈɪʃ"),
+            ("vogel", "fˈoːɡəl"),
+            ("junge", "jˈʊŋə"),
+        ];
+        for (word, phonemes) in cases {
+            assert_eq!(phonemize(word).as_deref(), Some(phonemes), "{}", word);
+        }
+        assert_eq!(phonemize("ça"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let n = GermanNumbers;
+        let cases = [
+            (0, "null"),
+            (1, "eins"),
+            (17, "siebzehn"),
+            (21, "einundzwanzig"),
+            (101, "einhunderteins"),
+            (1_000, "eintausend"),
+            (2_345, "zweitausenddreihundertfünfundvierzig"),
+            (1_000_000, "eine Million"),
+            (21_000_001, "einundzwanzig Millionen eins"),
+            (-4, "minus vier"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(n.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(n.year(1999).as_deref(), Some("neunzehnhundertneunundneunzig"));
+        assert_eq!(n.year(2024).as_deref(), Some("zweitausendvierundzwanzig"));
+        assert_eq!(n.ordinal(1).as_deref(), Some("erste"));
+        assert_eq!(n.ordinal(3).as_deref(), Some("dritte"));
+        assert_eq!(n.ordinal(4).as_deref(), Some("vierte"));
+        assert_eq!(n.ordinal(20).as_deref(), Some("zwanzigste"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::German);
+        let (phonemes, _) = g2p.g2p("Die Haustür und 21 Arbeitszeit.").unwrap();
+        assert_eq!(
+            phonemes.split_whitespace().collect::<Vec<_>>(),
+            ["diː", "hˈWstˌyːɐ", "ʊnt", "ˈInʊntʦvˌanʦɪç", "ˈaɐbItsʦˌIt"]
+        );
+
+        // Decimal commas and thousands dots
+        for (text, spoken) in [("3,14", "drei Komma eins vier"), ("1.000 Leute", "eintausend Leute")] {
+            assert_eq!(g2p.g2p(text).unwrap().0, g2p.g2p(spoken).unwrap().0, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! Hindi read by rule from Devanagari. Each consonant carries a schwa
+//! unless a vowel sign or virama replaces it; the schwa is then dropped at
+//! the end of a word and between a vowel-consonant and a consonant-vowel
+//! ("कमला" is "kəmlaː"), scanning from the right as in Hindi speech.
+
+use crate::lexicon::Lexicon;
+use crate::options::NegativeStyle;
+use unicode_normalization::UnicodeNormalization;
+use super::{LanguageRules, NumberVerbalizer, read_decimal};
+
+pub struct Hindi;
+
+impl LanguageRules for Hindi {
+    fn apply_rules(&self, word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+        match word {
+            "।" | "॥" => Some(".".to_string()),
+            _ => phonemize(word),
+        }
+    }
+}
+
+fn consonant(c: char) -> Option<&'static str> {
+    Some(match c {
+        'क' => "k",
+        'ख' => "kʰ",
+        'ग' => "ɡ",
+        'घ' => "ɡʱ",
+        'ङ' => "ŋ",
+        'च' => "ʧ",
+        'छ' => "ʧʰ",
+        'ज' => "ʤ",
+        'झ' => "ʤʱ",
+        'ञ' => "ɲ",
+        'ट' => "ʈ",
+        'ठ' => "ʈʰ",
+        'ड' => "ɖ",
+        'ढ' => "ɖʱ",
+        'ण' => "ɳ",
+        'त' => "t",
+        'थ' => "tʰ",
+        'द' => "d",
+        'ध' => "dʱ",
+        'न' => "n",
+        'प' => "p",
+        'फ' => "pʰ",
+        'ब' => "b",
+        'भ' => "bʱ",
+        'म' => "m",
+        'य' => "j",
+        'र' => "ɾ",
+        'ल' => "l",
+        'व' => "ʋ",
+        'श' => "ʃ",
+        'ष' => "ʂ",
+        'स' => "s",
+        'ह' => "ɦ",
+        _ => return None,
+    })
+}
+
+/// Consonants with a nukta, for sounds borrowed from Persian and English.
+fn nukta(c: char) -> Option<&'static str> {
+    Some(match c {
+        'क' => "q",
+        'ख' => "x",
+        'ग' => "ɣ",
+        'ज' => "z",
+        'फ' => "f",
+        'ड' => "ɽ",
+        'ढ' => "ɽʱ",
+        _ => return None,
+    })
+}
+
+/// Independent vowels and vowel signs.
+fn vowel(c: char) -> Option<&'static str> {
+    Some(match c {
+        'अ' => "ə",
+        'आ' | 'ा' => "aː",
+        'इ' | 'ि' => "ɪ",
+        'ई' | 'ी' => "iː",
+        'उ' | 'ु' => "ʊ",
+        'ऊ' | 'ू' => "uː",
+        'ऋ' | 'ृ' => "ɾɪ",
+        'ए' | 'े' => "eː",
+        'ऐ' | 'ै' => "ɛː",
+        'ओ' | 'ो' => "oː",
+        'औ' | 'ौ' => "ɔː",
+        'ऑ' | 'ॉ' => "ɔ",
+        _ => return None,
+    })
+}
+
+fn is_sign(c: char) -> bool {
+    matches!(c, '\u{093E}'..='\u{094C}')
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Vowel {
+    /// The schwa a consonant carries by default.
+    Inherent,
+    /// Taken away by a virama, or dropped.
+    Silent,
+    Written(&'static str),
+}
+
+/// A consonant (or none) with its vowel.
+#[derive(Debug, Clone, Copy)]
+struct Akshara {
+    consonant: Option<&'static str>,
+    vowel: Vowel,
+    /// Anusvara: a nasal consonant before a stop, else a nasal vowel.
+    anusvara: bool,
+    /// Chandrabindu: a nasal vowel.
+    chandrabindu: bool,
+    /// Visarga, an "ɦ" after the vowel.
+    visarga: bool,
+}
+
+fn aksharas(word: &str) -> Option<Vec<Akshara>> {
+    // Precomposed nukta consonants come apart into consonant and nukta
+    let chars: Vec<char> = word.nfd().collect();
+    let mut out: Vec<Akshara> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(mut cons) = consonant(c) {
+            if chars.get(i + 1) == Some(&'\u{093C}') {
+                cons = nukta(c).unwrap_or(cons);
+                i += 1;
+            }
+            out.push(Akshara { consonant: Some(cons), vowel: Vowel::Inherent, anusvara: false, chandrabindu: false, visarga: false });
+        } else if let Some(v) = vowel(c) {
+            match out.last_mut() {
+                Some(last) if is_sign(c) && last.consonant.is_some() && last.vowel == Vowel::Inherent => {
+                    last.vowel = Vowel::Written(v)
+                }
+                _ if is_sign(c) => return None,
+                _ => out.push(Akshara { consonant: None, vowel: Vowel::Written(v), anusvara: false, chandrabindu: false, visarga: false }),
+            }
+        } else {
+            let last = out.last_mut()?;
+            match c {
+                '\u{094D}' => last.vowel = Vowel::Silent,
+                '\u{0901}' => last.chandrabindu = true,
+                '\u{0902}' => last.anusvara = true,
+                '\u{0903}' => last.visarga = true,
+                _ => return None,
+            }
+        }
+        i += 1;
+    }
+    Some(out)
+}
+
+/// Drops the schwas Hindi doesn't pronounce, from the end of the word
+/// back: the last one unless a cluster comes before it, and any between a
+/// vowel-consonant and a consonant-vowel.
+fn delete_schwas(word: &mut [Akshara]) {
+    let has_vowel = |a: &Akshara| a.vowel != Vowel::Silent;
+    for i in (1..word.len()).rev() {
+        if word[i].vowel != Vowel::Inherent || word[i].anusvara || word[i].chandrabindu || word[i].consonant.is_none() {
+            continue;
+        }
+        let drop = match word.get(i + 1) {
+            None => has_vowel(&word[i - 1]),
+            Some(next) => has_vowel(&word[i - 1]) && next.consonant.is_some() && has_vowel(next),
+        };
+        if drop {
+            word[i].vowel = Vowel::Silent;
+        }
+    }
+}
+
+/// Nasalizes a vowel: "aː" is "ãː".
+fn nasalize(vowel: &str) -> String {
+    let mut chars = vowel.chars();
+    let first = chars.next().map(String::from).unwrap_or_default();
+    format!("{}\u{0303}{}", first, chars.as_str())
+}
+
+/// Phonemes for a word in Devanagari, `None` if it has anything else.
+pub fn phonemize(word: &str) -> Option<String> {
+    let mut word = aksharas(word)?;
+    if word.is_empty() {
+        return None;
+    }
+    delete_schwas(&mut word);
+
+    let mut out = String::new();
+    for (i, a) in word.iter().enumerate() {
+        out += a.consonant.unwrap_or_default();
+        let vowel = match a.vowel {
+            Vowel::Inherent => "ə",
+            Vowel::Silent => "",
+            Vowel::Written(v) => v,
+        };
+        // An anusvara before a stop is a nasal made in the same place
+        let next = word.get(i + 1).and_then(|n| n.consonant).and_then(|c| c.chars().next());
+        let place = match next {
+            Some('k' | 'ɡ' | 'q' | 'x' | 'ɣ') => Some("ŋ"),
+            Some('ʧ' | 'ʤ') => Some("ɲ"),
+            Some('ʈ' | 'ɖ' | 'ɽ') => Some("ɳ"),
+            Some('t' | 'd' | 'n') => Some("n"),
+            Some('p' | 'b' | 'm') => Some("m"),
+            _ => None,
+        };
+        match place {
+            Some(nasal) if a.anusvara && !a.chandrabindu && !vowel.is_empty() => {
+                out += vowel;
+                out += nasal;
+            }
+            _ if (a.anusvara || a.chandrabindu) && !vowel.is_empty() => out += &nasalize(vowel),
+            _ => out += vowel,
+        }
+        if a.visarga {
+            out += "ɦ";
+        }
+    }
+    Some(out)
+}
+
+/// 0 to 99, which Hindi names one by one.
+const BELOW_HUNDRED: [&str; 100] = [
+    "शून्य", "एक", "दो", "तीन", "चार", "पाँच", "छह", "सात", "आठ", "नौ",
+    "दस", "ग्यारह", "बारह", "तेरह", "चौदह", "पंद्रह", "सोलह", "सत्रह", "अठारह", "उन्नीस",
+    "बीस", "इक्कीस", "बाईस", "तेईस", "चौबीस", "पच्चीस", "छब्बीस", "सत्ताईस", "अट्ठाईस", "उनतीस",
+    "तीस", "इकतीस", "बत्तीस", "तैंतीस", "चौंतीस", "पैंतीस", "छत्तीस", "सैंतीस", "अड़तीस", "उनतालीस",
+    "चालीस", "इकतालीस", "बयालीस", "तैंतालीस", "चवालीस", "पैंतालीस", "छियालीस", "सैंतालीस", "अड़तालीस", "उनचास",
+    "पचास", "इक्यावन", "बावन", "तिरपन", "चौवन", "पचपन", "छप्पन", "सत्तावन", "अट्ठावन", "उनसठ",
+    "साठ", "इकसठ", "बासठ", "तिरसठ", "चौंसठ", "पैंसठ", "छियासठ", "सड़सठ", "अड़सठ", "उनहत्तर",
+    "सत्तर", "इकहत्तर", "बहत्तर", "तिहत्तर", "चौहत्तर", "पचहत्तर", "छिहत्तर", "सतहत्तर", "अठहत्तर", "उन्यासी",
+    "अस्सी", "इक्यासी", "बयासी", "तिरासी", "चौरासी", "पचासी", "छियासी", "सत्तासी", "अट्ठासी", "नवासी",
+    "नब्बे", "इक्यानवे", "बानवे", "तिरानवे", "चौरानवे", "पचानवे", "छियानवे", "सत्तानवे", "अट्ठानवे", "निन्यानवे",
+];
+
+/// Hindi numbers in the Indian grouping: "सौ" (100), "हज़ार" (1000), "लाख"
+/// (10⁵), "करोड़" (10⁷), "अरब" (10⁹), "खरब" (10¹¹).
+pub struct HindiNumbers;
+
+impl NumberVerbalizer for HindiNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("ऋण {}", words));
+        }
+        if n < 100 {
+            return Some(BELOW_HUNDRED[n as usize].to_string());
+        }
+        let mut n = n as u64;
+        let mut words = Vec::new();
+        for (scale, name) in [
+            (100_000_000_000, "खरब"),
+            (1_000_000_000, "अरब"),
+            (10_000_000, "करोड़"),
+            (100_000, "लाख"),
+            (1_000, "हज़ार"),
+            (100, "सौ"),
+        ] {
+            let count = n / scale;
+            n %= scale;
+            if count > 0 {
+                words.push(self.cardinal(count as i64)?);
+                words.push(name.to_string());
+            }
+        }
+        if n > 0 {
+            words.push(BELOW_HUNDRED[n as usize].to_string());
+        }
+        Some(words.join(" "))
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        match n {
+            1 => Some("पहला".to_string()),
+            2 => Some("दूसरा".to_string()),
+            3 => Some("तीसरा".to_string()),
+            4 => Some("चौथा".to_string()),
+            6 => Some("छठा".to_string()),
+            _ => self.cardinal(n).map(|words| format!("{}वाँ", words)),
+        }
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "दशमलव", " ")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("ऋण {}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize() {
+        let cases = [
+            ("नमस्ते", "nəməsteː"),
+            ("कमल", "kəməl"),
+            ("कमला", "kəmlaː"),
+            ("बदलना", "bədəlnaː"),
+            ("समझना", "səməʤʱnaː"),
+            ("भारत", "bʱaːɾət"),
+            ("हिंदी", "ɦɪndiː"),
+            ("मैं", "mɛ̃ː"),
+            ("पाँच", "pa\u{303}ːʧ"),
+            ("ज़रूर", "zəɾuːɾ"),
+            ("मित्र", "mɪtɾə"),
+            ("किताब", "kɪtaːb"),
+            ("दुःख", "dʊɦkʰ"),
+        ];
+        for (word, ps) in cases {
+            assert_eq!(phonemize(word).as_deref(), Some(ps), "{}", word);
+        }
+        assert_eq!(phonemize("ि"), None);
+        assert_eq!(phonemize("abc"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let cases = [
+            (0, "शून्य"),
+            (15, "पंद्रह"),
+            (99, "निन्यानवे"),
+            (100, "एक सौ"),
+            (1_947, "एक हज़ार नौ सौ सैंतालीस"),
+            (123_456, "एक लाख तेईस हज़ार चार सौ छप्पन"),
+            (10_000_000, "एक करोड़"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(HindiNumbers.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(HindiNumbers.ordinal(2).as_deref(), Some("दूसरा"));
+        assert_eq!(HindiNumbers.ordinal(5).as_deref(), Some("पाँचवाँ"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::Hindi);
+        let (ps, _) = g2p.g2p("मेरे पास 5 किताबें हैं").unwrap();
+        assert_eq!(ps, "meːɾeː paːs pa\u{303}ːʧ kɪtaːbe\u{303}ː ɦɛ\u{303}ː");
+    }
+}
@@ -0,0 +1,609 @@
+//! Japanese read from kana, or from Hepburn romaji by way of kana, one
+//! mora at a time. Kanji the seed lexicon lacks are read from a table of
+//! the jōyō kanji (see `read_kanji`). Text is split into runs of one script,
+//! with the particles は, へ and を split off runs of hiragana (see
+//! `segment`); that misses words inside longer kana runs. No morphological
+//! analyzer ships with the crate, but `pack_with` takes one (a wrapper
+//! around lindera or vibrato, say) to split words instead.
+//!
+//! Pitch accent is not predicted; `phonemize_kana` marks a known downstep
+//! with "↓" for callers with an accent dictionary.
+
+use crate::lexicon::{Lexicon, PhonemeEntry};
+use crate::options::{NegativeStyle, NumberLocale};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use super::{LanguagePack, LanguageRules, NumberVerbalizer, WordSplitter, read_decimal};
+
+pub struct Japanese;
+
+impl LanguageRules for Japanese {
+    fn apply_rules(&self, word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+        match word {
+            // Particles keep their old spelling
+            "は" => Some("ɰa".to_string()),
+            "へ" => Some("e".to_string()),
+            "を" => Some("o".to_string()),
+            "。" => Some(".".to_string()),
+            "、" => Some(",".to_string()),
+            "！" => Some("!".to_string()),
+            "？" => Some("?".to_string()),
+            "「" | "」" | "『" | "』" => Some(String::new()),
+            _ if word.chars().all(is_kana) => phonemize_kana(word, None),
+            _ if word.chars().all(|c| script(c) == Script::Kanji) => phonemize_kana(&read_kanji(word)?, None),
+            _ => phonemize_kana(&romaji_to_kana(word)?, None),
+        }
+    }
+}
+
+/// Words read from their kana, as (spelling, kana).
+const LEXICON: &[(&str, &str)] = &[
+    ("こんにちは", "こんにちわ"),
+    ("こんばんは", "こんばんわ"),
+    ("今日", "きょう"),
+    ("元気", "げんき"),
+    ("先生", "せんせい"),
+    ("円", "えん"),
+    ("名前", "なまえ"),
+    ("学生", "がくせい"),
+    ("大学", "だいがく"),
+    ("年", "ねん"),
+    ("日本", "にほん"),
+    ("日本語", "にほんご"),
+    ("明日", "あした"),
+    ("時間", "じかん"),
+    ("本", "ほん"),
+    ("東京", "とうきょう"),
+    ("水", "みず"),
+    ("犬", "いぬ"),
+    ("猫", "ねこ"),
+    ("私", "わたし"),
+    ("人", "ひと"),
+    ("何", "なに"),
+    // Compounds not read as their kanji's on'yomi
+    ("一人", "ひとり"),
+    ("二人", "ふたり"),
+    ("人々", "ひとびと"),
+    ("大人", "おとな"),
+    ("今年", "ことし"),
+    ("今朝", "けさ"),
+    ("昨日", "きのう"),
+    ("毎朝", "まいあさ"),
+    ("毎晩", "まいばん"),
+    ("曜日", "ようび"),
+    ("友達", "ともだち"),
+    ("時計", "とけい"),
+    ("上手", "じょうず"),
+    ("下手", "へた"),
+    ("学校", "がっこう"),
+    ("一緒", "いっしょ"),
+    ("結婚", "けっこん"),
+    ("出発", "しゅっぱつ"),
+    ("切符", "きっぷ"),
+    ("雑誌", "ざっし"),
+];
+
+/// A kanji's main on'yomi and its kun'yomi without okurigana, in hiragana.
+type Readings = (Option<&'static str>, Option<&'static str>);
+
+/// The jōyō kanji and their readings.
+static KANJI: LazyLock<HashMap<char, Readings>> = LazyLock::new(|| {
+    include_str!("../../data/ja_kanji.tsv")
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split('\t').map(|field| Some(field).filter(|&f| f != "-"));
+            let kanji = fields.next()??.chars().next()?;
+            Some((kanji, (fields.next()?, fields.next()?)))
+        })
+        .collect()
+});
+
+/// Kana for a run of kanji missing from the lexicon. A lone kanji takes its
+/// kun'yomi, as the stem its okurigana follow ("行" of "行きます"); a
+/// compound takes the on'yomi of each kanji, after the longest lexicon word
+/// at each point ("日本" of "日本人"). "々" repeats the kanji before it.
+fn read_kanji(word: &str) -> Option<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if let [kanji] = chars[..] {
+        let &(on, kun) = KANJI.get(&kanji)?;
+        return kun.or(on).map(str::to_string);
+    }
+    let mut kana = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let known = (2..=chars.len() - i).rev().find_map(|len| {
+            let s: String = chars[i..i + len].iter().collect();
+            LEXICON.iter().find(|&&(w, _)| w == s).map(|&(_, k)| (len, k))
+        });
+        if let Some((len, reading)) = known {
+            kana.push_str(reading);
+            i += len;
+            continue;
+        }
+        let kanji = if chars[i] == '々' && i > 0 { chars[i - 1] } else { chars[i] };
+        let &(on, kun) = KANJI.get(&kanji)?;
+        kana.push_str(on.or(kun)?);
+        i += 1;
+    }
+    Some(kana)
+}
+
+pub fn lexicon() -> HashMap<String, PhonemeEntry> {
+    LEXICON
+        .iter()
+        .filter_map(|&(word, kana)| Some((word.to_string(), PhonemeEntry::Simple(phonemize_kana(kana, None)?))))
+        .collect()
+}
+
+/// The Japanese pack with words split by `tokenizer`, such as a
+/// morphological analyzer; register it under a name of its own with
+/// `languages::register`.
+pub fn pack_with(tokenizer: WordSplitter) -> LanguagePack {
+    LanguagePack {
+        golds: lexicon(),
+        silvers: HashMap::new(),
+        rules: Box::new(Japanese),
+        numbers: Box::new(JapaneseNumbers),
+        tokenizer: Some(tokenizer),
+        number_locale: NumberLocale::English,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Script {
+    Kanji,
+    Hiragana,
+    Katakana,
+    /// Latin letters and digits, for romaji and numbers.
+    Latin,
+    Space,
+    /// Punctuation, one character to a word.
+    Other,
+}
+
+fn script(c: char) -> Script {
+    match c {
+        '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '々' => Script::Kanji,
+        '\u{3041}'..='\u{309F}' => Script::Hiragana,
+        '\u{30A1}'..='\u{30FF}' | '\u{FF66}'..='\u{FF9F}' => Script::Katakana,
+        _ if c.is_alphanumeric() => Script::Latin,
+        _ if c.is_whitespace() => Script::Space,
+        _ => Script::Other,
+    }
+}
+
+/// Splits a trailing "は" or "へ" off a run of hiragana, where it is the
+/// topic or direction particle ("これは", "どこへ") and reads "wa" or "e".
+fn push_kana_run(words: &mut Vec<(usize, usize)>, text: &str, start: usize, end: usize) {
+    let run = &text[start..end];
+    match run.char_indices().last() {
+        Some((at, 'は' | 'へ')) if at > 0 => words.extend([(start, start + at), (start + at, end)]),
+        _ => words.push((start, end)),
+    }
+}
+
+/// Splits text where the script changes, since Japanese doesn't space its
+/// words. A long vowel mark stays with the kana before it, "." or ","
+/// between digits with the number, and the particles "を", "は" and "へ"
+/// are split off runs of hiragana.
+pub fn segment(text: &str) -> Vec<(usize, usize)> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start: Option<(usize, Script)> = None;
+    for (i, &(b, c)) in chars.iter().enumerate() {
+        // "を" is only ever the object particle
+        let mut class = if c == 'を' { Script::Other } else { script(c) };
+        if let Some((_, run)) = start {
+            let between_digits = matches!(c, '.' | ',')
+                && i > 0
+                && chars[i - 1].1.is_ascii_digit()
+                && chars.get(i + 1).is_some_and(|(_, c)| c.is_ascii_digit());
+            if (c == 'ー' && run == Script::Hiragana) || (between_digits && run == Script::Latin) {
+                class = run;
+            }
+            if class == run && class != Script::Other {
+                continue;
+            }
+            match run {
+                Script::Hiragana => push_kana_run(&mut words, text, start.unwrap().0, b),
+                _ => words.push((start.unwrap().0, b)),
+            }
+        }
+        start = (class != Script::Space).then_some((b, class));
+    }
+    match start {
+        Some((b, Script::Hiragana)) => push_kana_run(&mut words, text, b, text.len()),
+        Some((b, _)) => words.push((b, text.len())),
+        None => {}
+    }
+    words
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(script(c), Script::Hiragana | Script::Katakana)
+}
+
+fn to_katakana(c: char) -> char {
+    match c {
+        '\u{3041}'..='\u{3096}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Katakana with the sounds of their morae.
+const KANA: &[(char, &str)] = &[
+    ('ア', "a"), ('イ', "i"), ('ウ', "ɯ"), ('エ', "e"), ('オ', "o"),
+    ('カ', "ka"), ('キ', "kʲi"), ('ク', "kɯ"), ('ケ', "ke"), ('コ', "ko"),
+    ('ガ', "ɡa"), ('ギ', "ɡʲi"), ('グ', "ɡɯ"), ('ゲ', "ɡe"), ('ゴ', "ɡo"),
+    ('サ', "sa"), ('シ', "ɕi"), ('ス', "sɯ"), ('セ', "se"), ('ソ', "so"),
+    ('ザ', "za"), ('ジ', "ʥi"), ('ズ', "zɯ"), ('ゼ', "ze"), ('ゾ', "zo"),
+    ('タ', "ta"), ('チ', "ʨi"), ('ツ', "ʦɯ"), ('テ', "te"), ('ト', "to"),
+    ('ダ', "da"), ('ヂ', "ʥi"), ('ヅ', "zɯ"), ('デ', "de"), ('ド', "do"),
+    ('ナ', "na"), ('ニ', "ɲi"), ('ヌ', "nɯ"), ('ネ', "ne"), ('ノ', "no"),
+    ('ハ', "ha"), ('ヒ', "çi"), ('フ', "ɸɯ"), ('ヘ', "he"), ('ホ', "ho"),
+    ('バ', "ba"), ('ビ', "bʲi"), ('ブ', "bɯ"), ('ベ', "be"), ('ボ', "bo"),
+    ('パ', "pa"), ('ピ', "pʲi"), ('プ', "pɯ"), ('ペ', "pe"), ('ポ', "po"),
+    ('マ', "ma"), ('ミ', "mʲi"), ('ム', "mɯ"), ('メ', "me"), ('モ', "mo"),
+    ('ヤ', "ja"), ('ユ', "jɯ"), ('ヨ', "jo"),
+    ('ラ', "ɾa"), ('リ', "ɾʲi"), ('ル', "ɾɯ"), ('レ', "ɾe"), ('ロ', "ɾo"),
+    ('ワ', "ɰa"), ('ヮ', "ɰa"), ('ヰ', "i"), ('ヱ', "e"), ('ヲ', "o"), ('ヴ', "vɯ"),
+];
+
+fn small_vowel(c: char) -> Option<char> {
+    match c {
+        'ァ' | 'ャ' => Some('a'),
+        'ィ' => Some('i'),
+        'ゥ' | 'ュ' => Some('ɯ'),
+        'ェ' => Some('e'),
+        'ォ' | 'ョ' => Some('o'),
+        _ => None,
+    }
+}
+
+/// The sounds of each mora of `kana`, with "Q" for a small "tsu" and "N"
+/// for "n"; `None` if it isn't all kana.
+pub fn morae(kana: &str) -> Option<Vec<String>> {
+    let mut morae: Vec<String> = Vec::new();
+    for c in kana.chars().map(to_katakana) {
+        match c {
+            'ッ' => morae.push("Q".to_string()),
+            'ン' => morae.push("N".to_string()),
+            'ー' => morae.push("ː".to_string()),
+            // Small kana change the vowel of the mora before them: "キャ" is
+            // "kʲa", "ティ" is "ti", "ウィ" is "ɰi"
+            _ if let Some(vowel) = small_vowel(c) => {
+                let glide = matches!(c, 'ャ' | 'ュ' | 'ョ');
+                match morae.last_mut() {
+                    Some(last) if last == "ɯ" => *last = format!("ɰ{}", vowel),
+                    Some(last) if last.chars().count() > 1 => {
+                        last.pop();
+                        if glide && !last.ends_with(['ʲ', 'ɕ', 'ʨ', 'ʥ', 'ɲ', 'ç', 'j']) {
+                            last.push('ʲ');
+                        }
+                        last.push(vowel);
+                    }
+                    _ => morae.push(vowel.to_string()),
+                }
+            }
+            _ => morae.push(KANA.iter().find(|&&(k, _)| k == c)?.1.to_string()),
+        }
+    }
+    Some(morae)
+}
+
+/// Phonemes for a word in kana, with "↓" after mora `accent` (counting
+/// from 1) where the pitch falls; `None` leaves pitch unmarked.
+pub fn phonemize_kana(kana: &str, accent: Option<usize>) -> Option<String> {
+    let morae = morae(kana)?;
+    let mut out = String::new();
+    let mut prev = "";
+    for (i, mora) in morae.iter().enumerate() {
+        let next = morae.get(i + 1).and_then(|m| m.chars().next());
+        let last_vowel = prev.chars().last().filter(|_| prev != "Q" && prev != "N");
+        match mora.as_str() {
+            "Q" => out.push('ʔ'),
+            // "n" takes the place of the consonant after it
+            "N" => out.push(match next {
+                Some('p' | 'b' | 'm') => 'm',
+                Some('k' | 'ɡ') => 'ŋ',
+                Some('t' | 'd' | 'n' | 'ʦ' | 'ɾ' | 'z') => 'n',
+                Some('ʨ' | 'ʥ' | 'ɲ') => 'ɲ',
+                _ => 'ɴ',
+            }),
+            // Doubled vowels, "ou" and "ei" are long
+            v @ ("a" | "i" | "ɯ" | "e" | "o")
+                if last_vowel.is_some_and(|l| v.starts_with(l) || (l == 'o' && v == "ɯ") || (l == 'e' && v == "i")) =>
+            {
+                out.push('ː')
+            }
+            m => out.push_str(m),
+        }
+        if accent == Some(i + 1) {
+            out.push('↓');
+        }
+        prev = mora;
+    }
+    Some(out)
+}
+
+/// Hepburn (and Kunrei) syllables in katakana.
+const ROMAJI: &[(&str, &str)] = &[
+    ("a", "ア"), ("i", "イ"), ("u", "ウ"), ("e", "エ"), ("o", "オ"),
+    ("ka", "カ"), ("ki", "キ"), ("ku", "ク"), ("ke", "ケ"), ("ko", "コ"),
+    ("kya", "キャ"), ("kyu", "キュ"), ("kyo", "キョ"),
+    ("ga", "ガ"), ("gi", "ギ"), ("gu", "グ"), ("ge", "ゲ"), ("go", "ゴ"),
+    ("gya", "ギャ"), ("gyu", "ギュ"), ("gyo", "ギョ"),
+    ("sa", "サ"), ("shi", "シ"), ("si", "シ"), ("su", "ス"), ("se", "セ"), ("so", "ソ"),
+    ("sha", "シャ"), ("shu", "シュ"), ("sho", "ショ"), ("she", "シェ"),
+    ("sya", "シャ"), ("syu", "シュ"), ("syo", "ショ"),
+    ("za", "ザ"), ("ji", "ジ"), ("zi", "ジ"), ("zu", "ズ"), ("ze", "ゼ"), ("zo", "ゾ"),
+    ("ja", "ジャ"), ("ju", "ジュ"), ("jo", "ジョ"), ("je", "ジェ"),
+    ("zya", "ジャ"), ("zyu", "ジュ"), ("zyo", "ジョ"),
+    ("ta", "タ"), ("chi", "チ"), ("ti", "チ"), ("tsu", "ツ"), ("tu", "ツ"), ("te", "テ"), ("to", "ト"),
+    ("cha", "チャ"), ("chu", "チュ"), ("cho", "チョ"), ("che", "チェ"),
+    ("tya", "チャ"), ("tyu", "チュ"), ("tyo", "チョ"),
+    ("da", "ダ"), ("di", "ヂ"), ("du", "ヅ"), ("de", "デ"), ("do", "ド"),
+    ("na", "ナ"), ("ni", "ニ"), ("nu", "ヌ"), ("ne", "ネ"), ("no", "ノ"),
+    ("nya", "ニャ"), ("nyu", "ニュ"), ("nyo", "ニョ"),
+    ("ha", "ハ"), ("hi", "ヒ"), ("fu", "フ"), ("hu", "フ"), ("he", "ヘ"), ("ho", "ホ"),
+    ("hya", "ヒャ"), ("hyu", "ヒュ"), ("hyo", "ヒョ"),
+    ("fa", "ファ"), ("fi", "フィ"), ("fe", "フェ"), ("fo", "フォ"),
+    ("ba", "バ"), ("bi", "ビ"), ("bu", "ブ"), ("be", "ベ"), ("bo", "ボ"),
+    ("bya", "ビャ"), ("byu", "ビュ"), ("byo", "ビョ"),
+    ("pa", "パ"), ("pi", "ピ"), ("pu", "プ"), ("pe", "ペ"), ("po", "ポ"),
+    ("pya", "ピャ"), ("pyu", "ピュ"), ("pyo", "ピョ"),
+    ("ma", "マ"), ("mi", "ミ"), ("mu", "ム"), ("me", "メ"), ("mo", "モ"),
+    ("mya", "ミャ"), ("myu", "ミュ"), ("myo", "ミョ"),
+    ("ya", "ヤ"), ("yu", "ユ"), ("yo", "ヨ"),
+    ("ra", "ラ"), ("ri", "リ"), ("ru", "ル"), ("re", "レ"), ("ro", "ロ"),
+    ("rya", "リャ"), ("ryu", "リュ"), ("ryo", "リョ"),
+    ("wa", "ワ"), ("wo", "ヲ"),
+    ("va", "ヴァ"), ("vi", "ヴィ"), ("vu", "ヴ"), ("ve", "ヴェ"), ("vo", "ヴォ"),
+    ("-", "ー"),
+];
+
+/// Katakana for a word in romaji, `None` if it doesn't read as Japanese.
+/// Macrons ("Tōkyō") mark long vowels.
+pub fn romaji_to_kana(romaji: &str) -> Option<String> {
+    let mut text = String::new();
+    for c in romaji.to_lowercase().chars() {
+        match c {
+            'ā' | 'â' => text.push_str("a-"),
+            'ī' | 'î' => text.push_str("i-"),
+            'ū' | 'û' => text.push_str("u-"),
+            'ē' | 'ê' => text.push_str("e-"),
+            'ō' | 'ô' => text.push_str("o-"),
+            c => text.push(c),
+        }
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let is_vowel = |c: Option<&char>| matches!(c, Some('a' | 'i' | 'u' | 'e' | 'o'));
+    let mut kana = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (c, next) = (chars[i], chars.get(i + 1));
+        // A doubled consonant (or the "t" of "tch") is a small "tsu"
+        if c.is_ascii_alphabetic() && !is_vowel(Some(&c)) && c != 'n' && (next == Some(&c) || (c == 't' && next == Some(&'c'))) {
+            kana.push('ッ');
+            i += 1;
+            continue;
+        }
+        if c == 'n' && !is_vowel(next) && next != Some(&'y') {
+            kana.push('ン');
+            i += if next == Some(&'\'') { 2 } else { 1 };
+            continue;
+        }
+        if c == 'm' && matches!(next, Some('b' | 'p' | 'm')) {
+            kana.push('ン');
+            i += 1;
+            continue;
+        }
+        let (len, syllable) = (1..=3).rev().find_map(|len| {
+            let s: String = chars.get(i..i + len)?.iter().collect();
+            ROMAJI.iter().find(|&&(r, _)| r == s).map(|&(_, k)| (len, k))
+        })?;
+        kana.push_str(syllable);
+        i += len;
+    }
+    Some(kana)
+}
+
+const DIGITS: [&str; 10] = ["ぜろ", "いち", "に", "さん", "よん", "ご", "ろく", "なな", "はち", "きゅう"];
+
+/// Japanese numbers, in hiragana, grouped by ten thousands ("まん", "おく",
+/// "ちょう", "けい").
+pub struct JapaneseNumbers;
+
+impl JapaneseNumbers {
+    /// 1 to 9999, with the sound changes of "さんびゃく" and "はっせん".
+    fn below_man(n: u64) -> String {
+        let mut words = String::new();
+        let (thousands, hundreds, tens, units) = (n / 1000, n / 100 % 10, n / 10 % 10, n % 10);
+        words += match thousands {
+            0 => "",
+            1 => "せん",
+            3 => "さんぜん",
+            8 => "はっせん",
+            d => DIGITS[d as usize],
+        };
+        if thousands > 1 && !matches!(thousands, 3 | 8) {
+            words += "せん";
+        }
+        words += match hundreds {
+            0 => "",
+            1 => "ひゃく",
+            3 => "さんびゃく",
+            6 => "ろっぴゃく",
+            8 => "はっぴゃく",
+            d => DIGITS[d as usize],
+        };
+        if hundreds > 1 && !matches!(hundreds, 3 | 6 | 8) {
+            words += "ひゃく";
+        }
+        if tens > 1 {
+            words += DIGITS[tens as usize];
+        }
+        if tens > 0 {
+            words += "じゅう";
+        }
+        if units > 0 {
+            words += DIGITS[units as usize];
+        }
+        words
+    }
+}
+
+impl NumberVerbalizer for JapaneseNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("まいなす{}", words));
+        }
+        if n == 0 {
+            return Some(DIGITS[0].to_string());
+        }
+        let mut n = n as u64;
+        let mut words = String::new();
+        for (scale, name) in [(10_000_000_000_000_000, "けい"), (1_000_000_000_000, "ちょう"), (100_000_000, "おく"), (10_000, "まん")] {
+            let count = n / scale;
+            n %= scale;
+            if count == 0 {
+                continue;
+            }
+            let mut group = Self::below_man(count);
+            // "いっちょう", "はっけい"
+            if matches!(name, "ちょう" | "けい") {
+                for (plain, short) in [("いち", "いっ"), ("はち", "はっ"), ("じゅう", "じゅっ")] {
+                    if let Some(stem) = group.strip_suffix(plain) {
+                        group = format!("{}{}", stem, short);
+                    }
+                }
+            }
+            words += &group;
+            words += name;
+        }
+        if n > 0 {
+            words += &Self::below_man(n);
+        }
+        Some(words)
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        self.cardinal(n).map(|words| format!("だい{}", words))
+    }
+
+    /// Digits run together, without spaces.
+    fn digits(&self, digits: &str) -> Option<String> {
+        digits.chars().map(|c| Some(DIGITS[c.to_digit(10)? as usize])).collect()
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "てん", "")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("まいなす{}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize_kana() {
+        let cases = [
+            ("さくら", "sakɯɾa"),
+            ("ありがとう", "aɾʲiɡatoː"),
+            ("きょう", "kʲoː"),
+            ("がっこう", "ɡaʔkoː"),
+            ("しんぶん", "ɕimbɯɴ"),
+            ("てんき", "teŋkʲi"),
+            ("せんせい", "seɴseː"),
+            ("コーヒー", "koːçiː"),
+            ("パーティー", "paːtiː"),
+            ("ウィキ", "ɰikʲi"),
+        ];
+        for (kana, ps) in cases {
+            assert_eq!(phonemize_kana(kana, None).as_deref(), Some(ps), "{}", kana);
+        }
+        assert_eq!(phonemize_kana("はし", Some(1)).as_deref(), Some("ha↓ɕi"));
+        assert_eq!(morae("きょうと").map(|m| m.len()), Some(3));
+        assert_eq!(phonemize_kana("東", None), None);
+    }
+
+    #[test]
+    fn test_romaji() {
+        assert_eq!(romaji_to_kana("konnichiwa").as_deref(), Some("コンニチワ"));
+        assert_eq!(romaji_to_kana("matcha").as_deref(), Some("マッチャ"));
+        assert_eq!(romaji_to_kana("shimbun").as_deref(), Some("シンブン"));
+        assert_eq!(romaji_to_kana("Tōkyō").as_deref(), Some("トーキョー"));
+        assert_eq!(romaji_to_kana("christmas"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let cases = [
+            (0, "ぜろ"),
+            (11, "じゅういち"),
+            (300, "さんびゃく"),
+            (1_868, "せんはっぴゃくろくじゅうはち"),
+            (3_000, "さんぜん"),
+            (10_000, "いちまん"),
+            (120_000_000, "いちおくにせんまん"),
+            (1_000_000_000_000, "いっちょう"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(JapaneseNumbers.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(JapaneseNumbers.ordinal(3).as_deref(), Some("だいさん"));
+    }
+
+    #[test]
+    fn test_segment() {
+        let text = "私はコーヒーを3.5杯、のみました。";
+        let words: Vec<&str> = segment(text).into_iter().map(|(s, e)| &text[s..e]).collect();
+        assert_eq!(words, ["私", "は", "コーヒー", "を", "3.5", "杯", "、", "のみました", "。"]);
+        let text = "これは本です。ごはんをたべた。";
+        let words: Vec<&str> = segment(text).into_iter().map(|(s, e)| &text[s..e]).collect();
+        assert_eq!(words, ["これ", "は", "本", "です", "。", "ごはん", "を", "たべた", "。"]);
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::Japanese);
+        let (_, tokens) = g2p.g2p("私は猫です。").unwrap();
+        let phonemes: Vec<&str> = tokens.iter().filter_map(|t| t.phonemes.as_deref()).collect();
+        assert_eq!(phonemes, ["ɰataɕi", "ɰa", "neko", "desɯ", "."]);
+        let (_, tokens) = g2p.g2p("これは本です。").unwrap();
+        let phonemes: Vec<&str> = tokens.iter().filter_map(|t| t.phonemes.as_deref()).collect();
+        assert_eq!(phonemes[1], "ɰa");
+
+        // Kanji outside the lexicon are read from the jōyō table
+        let (ps, _) = g2p.g2p("東京に行きます。").unwrap();
+        assert_eq!(ps, "toːkʲoːɲiikʲimasɯ.");
+        let (ps, _) = g2p.g2p("毎日、電車で会社に行きます。").unwrap();
+        assert_eq!(ps, "maiɲiʨi,deɴɕadekaiɕaɲiikʲimasɯ.");
+        let (ps, tokens) = g2p.g2p("日本人の友達と新しい本を買いました。").unwrap();
+        assert!(!ps.contains('❓'), "{}", ps);
+        let phonemes: Vec<&str> = tokens.iter().filter_map(|t| t.phonemes.as_deref()).collect();
+        assert_eq!(phonemes[..3], ["ɲihoɲʥiɴ", "no", "tomodaʨi"]);
+        assert_eq!(read_kanji("日曜日").as_deref(), Some("にちようび"));
+        assert_eq!(read_kanji("山々").as_deref(), Some("さんさん"));
+
+        // A morphological analyzer plugs in as the tokenizer
+        super::super::register("ja-spaced", || pack_with(Box::new(|text: &str| {
+            text.split(' ').scan(0, |at, word| {
+                let span = (*at, *at + word.len());
+                *at += word.len() + 1;
+                Some(span)
+            }).collect()
+        })));
+        let g2p = G2P::new(Language::Other("ja-spaced".to_string()));
+        let (_, tokens) = g2p.g2p("きょう は 2 にち").unwrap();
+        let phonemes: Vec<&str> = tokens.iter().filter_map(|t| t.phonemes.as_deref()).collect();
+        assert_eq!(phonemes, ["kʲoː", "ɰa", "ɲi", "ɲiʨi"]);
+    }
+}
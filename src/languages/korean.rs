@@ -0,0 +1,369 @@
+//! Korean read by rule: each hangul syllable is taken apart into jamo, the
+//! sound changes between syllables are applied (liaison, aspiration with
+//! "ㅎ", final consonant neutralization, tensification, nasalization, "ㄹ"
+//! assimilation), and the result is spelled in IPA.
+
+use crate::lexicon::Lexicon;
+use crate::options::NegativeStyle;
+use super::{LanguageRules, NumberVerbalizer, read_decimal};
+
+pub struct Korean;
+
+impl LanguageRules for Korean {
+    fn apply_rules(&self, word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+        phonemize(word)
+    }
+}
+
+const INITIALS: [char; 19] =
+    ['ㄱ', 'ㄲ', 'ㄴ', 'ㄷ', 'ㄸ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅃ', 'ㅅ', 'ㅆ', 'ㅇ', 'ㅈ', 'ㅉ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ'];
+const VOWELS: [char; 21] = [
+    'ㅏ', 'ㅐ', 'ㅑ', 'ㅒ', 'ㅓ', 'ㅔ', 'ㅕ', 'ㅖ', 'ㅗ', 'ㅘ', 'ㅙ', 'ㅚ', 'ㅛ', 'ㅜ', 'ㅝ', 'ㅞ', 'ㅟ', 'ㅠ', 'ㅡ', 'ㅢ',
+    'ㅣ',
+];
+/// Final consonants, after the empty one.
+const FINALS: [char; 27] = [
+    'ㄱ', 'ㄲ', 'ㄳ', 'ㄴ', 'ㄵ', 'ㄶ', 'ㄷ', 'ㄹ', 'ㄺ', 'ㄻ', 'ㄼ', 'ㄽ', 'ㄾ', 'ㄿ', 'ㅀ', 'ㅁ', 'ㅂ', 'ㅄ', 'ㅅ',
+    'ㅆ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+];
+
+/// A hangul syllable as jamo; an onset of "ㅇ" is silent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Syllable {
+    onset: char,
+    vowel: char,
+    coda: Option<char>,
+}
+
+/// Takes a precomposed hangul syllable apart.
+fn decompose(c: char) -> Option<Syllable> {
+    let index = (c as u32).checked_sub(0xAC00).filter(|&i| i < 11172)? as usize;
+    Some(Syllable {
+        onset: INITIALS[index / 588],
+        vowel: VOWELS[index % 588 / 28],
+        coda: (index % 28).checked_sub(1).map(|f| FINALS[f]),
+    })
+}
+
+/// A final cluster as its two consonants.
+fn split(coda: char) -> (char, Option<char>) {
+    match coda {
+        'ㄳ' => ('ㄱ', Some('ㅅ')),
+        'ㄵ' => ('ㄴ', Some('ㅈ')),
+        'ㄶ' => ('ㄴ', Some('ㅎ')),
+        'ㄺ' => ('ㄹ', Some('ㄱ')),
+        'ㄻ' => ('ㄹ', Some('ㅁ')),
+        'ㄼ' => ('ㄹ', Some('ㅂ')),
+        'ㄽ' => ('ㄹ', Some('ㅅ')),
+        'ㄾ' => ('ㄹ', Some('ㅌ')),
+        'ㄿ' => ('ㄹ', Some('ㅍ')),
+        'ㅀ' => ('ㄹ', Some('ㅎ')),
+        'ㅄ' => ('ㅂ', Some('ㅅ')),
+        c => (c, None),
+    }
+}
+
+/// The seven sounds a syllable can end in.
+fn neutralize(coda: char) -> char {
+    match coda {
+        'ㄱ' | 'ㄲ' | 'ㅋ' | 'ㄳ' | 'ㄺ' => 'ㄱ',
+        'ㄴ' | 'ㄵ' | 'ㄶ' => 'ㄴ',
+        'ㄷ' | 'ㅅ' | 'ㅆ' | 'ㅈ' | 'ㅊ' | 'ㅌ' | 'ㅎ' => 'ㄷ',
+        'ㄹ' | 'ㄼ' | 'ㄽ' | 'ㄾ' | 'ㅀ' => 'ㄹ',
+        'ㅁ' | 'ㄻ' => 'ㅁ',
+        'ㅂ' | 'ㅍ' | 'ㅄ' | 'ㄿ' => 'ㅂ',
+        c => c,
+    }
+}
+
+fn aspirate(c: char) -> Option<char> {
+    match c {
+        'ㄱ' => Some('ㅋ'),
+        'ㄷ' | 'ㅅ' => Some('ㅌ'),
+        'ㅂ' => Some('ㅍ'),
+        'ㅈ' => Some('ㅊ'),
+        _ => None,
+    }
+}
+
+fn tense(c: char) -> char {
+    match c {
+        'ㄱ' => 'ㄲ',
+        'ㄷ' => 'ㄸ',
+        'ㅂ' => 'ㅃ',
+        'ㅅ' => 'ㅆ',
+        'ㅈ' => 'ㅉ',
+        c => c,
+    }
+}
+
+/// Applies the sound changes at each boundary between syllables.
+fn apply_sandhi(syllables: &mut [Syllable]) {
+    for i in 0..syllables.len().saturating_sub(1) {
+        let (head, tail) = syllables.split_at_mut(i + 1);
+        let (cur, next) = (&mut head[i], &mut tail[0]);
+        let Some(coda) = cur.coda else { continue };
+        let (first, second) = split(coda);
+        let last = second.unwrap_or(first);
+        let rest = second.map(|_| first);
+        // "ㅎ" merges with a plain consonant next to it into an aspirate
+        if last == 'ㅎ' {
+            match next.onset {
+                'ㄱ' | 'ㄷ' | 'ㅈ' => next.onset = aspirate(next.onset).unwrap_or(next.onset),
+                'ㅅ' => next.onset = 'ㅆ',
+                'ㄴ' if rest.is_none() => {
+                    cur.coda = Some('ㄴ');
+                    continue;
+                }
+                'ㄴ' | 'ㅇ' => {}
+                _ => continue,
+            }
+            cur.coda = rest;
+        } else if next.onset == 'ㅎ'
+            && let Some(aspirated) = aspirate(neutralize(last))
+        {
+            next.onset = aspirated;
+            cur.coda = rest;
+        }
+        // A final consonant carries over to a syllable starting with a
+        // vowel; "ㄷ" and "ㅌ" become "ㅈ" and "ㅊ" before "ㅣ"
+        if next.onset == 'ㅇ'
+            && let Some(coda) = cur.coda
+            && coda != 'ㅇ'
+        {
+            let (first, second) = split(coda);
+            let moved = second.unwrap_or(first);
+            next.onset = match (moved, next.vowel) {
+                ('ㄷ', 'ㅣ') => 'ㅈ',
+                ('ㅌ', 'ㅣ') => 'ㅊ',
+                _ => moved,
+            };
+            cur.coda = second.map(|_| first);
+        }
+    }
+    for syllable in syllables.iter_mut() {
+        syllable.coda = syllable.coda.map(neutralize);
+    }
+    for i in 0..syllables.len().saturating_sub(1) {
+        let (head, tail) = syllables.split_at_mut(i + 1);
+        let (cur, next) = (&mut head[i], &mut tail[0]);
+        let Some(coda) = cur.coda else { continue };
+        match (coda, next.onset) {
+            // Plain consonants after a stop are tense
+            ('ㄱ' | 'ㄷ' | 'ㅂ', 'ㄱ' | 'ㄷ' | 'ㅂ' | 'ㅅ' | 'ㅈ') => next.onset = tense(next.onset),
+            // "ㄹ" is "ㄴ" after any final but "ㄴ" and "ㄹ"
+            ('ㅁ' | 'ㅇ', 'ㄹ') => next.onset = 'ㄴ',
+            ('ㄱ' | 'ㄷ' | 'ㅂ', 'ㄹ') => next.onset = 'ㄴ',
+            ('ㄴ', 'ㄹ') => cur.coda = Some('ㄹ'),
+            ('ㄹ', 'ㄴ') => next.onset = 'ㄹ',
+            _ => {}
+        }
+        // Stops before a nasal are nasals
+        if matches!(next.onset, 'ㄴ' | 'ㅁ') {
+            cur.coda = Some(match coda {
+                'ㄱ' => 'ㅇ',
+                'ㄷ' => 'ㄴ',
+                'ㅂ' => 'ㅁ',
+                c => c,
+            });
+        }
+    }
+}
+
+/// Phonemes for a word in hangul, `None` if it has anything else.
+pub fn phonemize(word: &str) -> Option<String> {
+    let mut syllables = word.chars().map(decompose).collect::<Option<Vec<_>>>()?;
+    if syllables.is_empty() {
+        return None;
+    }
+    apply_sandhi(&mut syllables);
+
+    let mut out = String::new();
+    for (i, syllable) in syllables.iter().enumerate() {
+        let prev_coda = i.checked_sub(1).map(|j| syllables[j].coda);
+        // Plain stops are voiced after a vowel or a sonorant
+        let voiced = matches!(prev_coda, Some(None | Some('ㄴ' | 'ㄹ' | 'ㅁ' | 'ㅇ')));
+        let palatal = matches!(syllable.vowel, 'ㅣ' | 'ㅑ' | 'ㅒ' | 'ㅕ' | 'ㅖ' | 'ㅛ' | 'ㅠ');
+        out += match syllable.onset {
+            'ㄱ' if voiced => "ɡ",
+            'ㄱ' => "k",
+            'ㄲ' => "k͈",
+            'ㅋ' => "kʰ",
+            'ㄴ' => "n",
+            'ㄷ' if voiced => "d",
+            'ㄷ' => "t",
+            'ㄸ' => "t͈",
+            'ㅌ' => "tʰ",
+            'ㄹ' if prev_coda == Some(Some('ㄹ')) => "l",
+            'ㄹ' => "ɾ",
+            'ㅁ' => "m",
+            'ㅂ' if voiced => "b",
+            'ㅂ' => "p",
+            'ㅃ' => "p͈",
+            'ㅍ' => "pʰ",
+            'ㅅ' if palatal => "ɕ",
+            'ㅅ' => "s",
+            'ㅆ' if palatal => "ɕ͈",
+            'ㅆ' => "s͈",
+            'ㅈ' if voiced => "ʥ",
+            'ㅈ' => "ʨ",
+            'ㅉ' => "ʨ͈",
+            'ㅊ' => "ʨʰ",
+            'ㅎ' => "h",
+            _ => "",
+        };
+        out += match syllable.vowel {
+            'ㅏ' => "a",
+            'ㅐ' => "ɛ",
+            'ㅑ' => "ja",
+            'ㅒ' => "jɛ",
+            'ㅓ' => "ʌ",
+            'ㅔ' => "e",
+            'ㅕ' => "jʌ",
+            'ㅖ' => "je",
+            'ㅗ' => "o",
+            'ㅘ' => "wa",
+            'ㅙ' => "wɛ",
+            'ㅚ' | 'ㅞ' => "we",
+            'ㅛ' => "jo",
+            'ㅜ' => "u",
+            'ㅝ' => "wʌ",
+            'ㅟ' => "wi",
+            'ㅠ' => "ju",
+            'ㅡ' => "ɯ",
+            // "ㅢ" is only itself at the start of a word
+            'ㅢ' if i == 0 && syllable.onset == 'ㅇ' => "ɯi",
+            _ => "i",
+        };
+        out += match syllable.coda {
+            Some('ㄱ') => "k",
+            Some('ㄴ') => "n",
+            Some('ㄷ') => "t",
+            Some('ㄹ') => "l",
+            Some('ㅁ') => "m",
+            Some('ㅂ') => "p",
+            Some('ㅇ') => "ŋ",
+            _ => "",
+        };
+    }
+    Some(out)
+}
+
+const DIGITS: [&str; 10] = ["영", "일", "이", "삼", "사", "오", "육", "칠", "팔", "구"];
+
+/// Sino-Korean numbers in hangul, grouped by ten thousands ("만", "억",
+/// "조", "경").
+pub struct KoreanNumbers;
+
+impl KoreanNumbers {
+    /// 1 to 9999; a leading one before "십", "백" and "천" goes unsaid.
+    fn below_man(n: u64) -> String {
+        let mut words = String::new();
+        for (unit, value) in [("천", 1000), ("백", 100), ("십", 10), ("", 1)] {
+            let digit = (n / value % 10) as usize;
+            if digit > 1 || (digit == 1 && unit.is_empty()) {
+                words += DIGITS[digit];
+            }
+            if digit > 0 {
+                words += unit;
+            }
+        }
+        words
+    }
+}
+
+impl NumberVerbalizer for KoreanNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("마이너스 {}", words));
+        }
+        if n == 0 {
+            return Some(DIGITS[0].to_string());
+        }
+        let mut n = n as u64;
+        let mut words = String::new();
+        for (scale, name) in [(10_000_000_000_000_000, "경"), (1_000_000_000_000, "조"), (100_000_000, "억"), (10_000, "만")] {
+            let count = n / scale;
+            n %= scale;
+            match count {
+                0 => continue,
+                // "만", but "일억"
+                1 if name == "만" => {}
+                _ => words += &Self::below_man(count),
+            }
+            words += name;
+        }
+        if n > 0 {
+            words += &Self::below_man(n);
+        }
+        Some(words)
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        self.cardinal(n).map(|words| format!("제{}", words))
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "점", " ")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("마이너스 {}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize() {
+        let cases = [
+            ("한국어", "hanɡuɡʌ"),
+            ("학교", "hakk͈jo"),
+            ("감사합니다", "kamsahamnida"),
+            ("좋아요", "ʨoajo"),
+            ("좋다", "ʨotʰa"),
+            ("축하", "ʨʰukʰa"),
+            ("같이", "kaʨʰi"),
+            ("신라", "ɕilla"),
+            ("국물", "kuŋmul"),
+            ("읽다", "ikt͈a"),
+            ("값이", "kapɕ͈i"),
+            ("의사", "ɯisa"),
+            ("안녕하세요", "annjʌŋhasejo"),
+        ];
+        for (word, ps) in cases {
+            assert_eq!(phonemize(word).as_deref(), Some(ps), "{}", word);
+        }
+        assert_eq!(phonemize("ㅋㅋ"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let cases = [
+            (0, "영"),
+            (10, "십"),
+            (11, "십일"),
+            (100, "백"),
+            (1_234, "천이백삼십사"),
+            (10_000, "만"),
+            (20_000, "이만"),
+            (100_000_000, "일억"),
+            (123_456_789, "일억이천삼백사십오만육천칠백팔십구"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(KoreanNumbers.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(KoreanNumbers.ordinal(1).as_deref(), Some("제일"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::Korean);
+        let (ps, _) = g2p.g2p("안녕하세요 학교에 갑니다").unwrap();
+        assert_eq!(ps, "annjʌŋhasejo hakk͈joe kamnida");
+    }
+}
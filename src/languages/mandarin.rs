@@ -0,0 +1,571 @@
+//! Mandarin read through pinyin: hanzi are looked up in a seed table of
+//! words, then character by character, and pinyin written in the text
+//! ("ni3hao3", "nǐ hǎo") is read as is. Each syllable ends in its tone: "→" (first),
+//! "↗" (second), "↓" (third), "↘" (fourth), nothing when neutral.
+//!
+//! Words come before characters, which is how polyphonic characters get
+//! their reading: "行" is "xíng" alone but "háng" in "银行". Text is split
+//! by longest match against the word table.
+
+use crate::lexicon::Lexicon;
+use crate::options::NegativeStyle;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use super::{LanguageRules, NumberVerbalizer, read_decimal};
+
+pub struct Mandarin;
+
+impl LanguageRules for Mandarin {
+    fn apply_rules(&self, word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+        match word {
+            "。" => Some(".".to_string()),
+            "，" | "、" => Some(",".to_string()),
+            "！" => Some("!".to_string()),
+            "？" => Some("?".to_string()),
+            "：" => Some(":".to_string()),
+            "；" => Some(";".to_string()),
+            "“" | "”" | "《" | "》" | "「" | "」" => Some(String::new()),
+            _ if word.chars().all(is_hanzi) => phonemize_hanzi(word),
+            _ => phonemize_pinyin(word),
+        }
+    }
+}
+
+/// Common characters in their usual reading, as "字zi4" with numbered
+/// tones (5 is neutral), over the reading the full table gives them.
+const CHARS: &str = "\
+的de5 一yi1 是shi4 不bu4 了le5 人ren2 我wo3 在zai4 有you3 他ta1 这zhe4 中zhong1 大da4 来lai2 上shang4 国guo2 \
+个ge4 到dao4 说shuo1 们men5 为wei4 子zi3 和he2 你ni3 地di4 出chu1 道dao4 也ye3 时shi2 年nian2 得de5 就jiu4 \
+那na4 要yao4 下xia4 以yi3 生sheng1 会hui4 自zi4 着zhe5 去qu4 之zhi1 过guo4 家jia1 学xue2 对dui4 可ke3 她ta1 \
+里li3 后hou4 小xiao3 么me5 心xin1 多duo1 天tian1 而er2 能neng2 好hao3 都dou1 然ran2 没mei2 日ri4 于yu2 起qi3 \
+还hai2 发fa1 成cheng2 事shi4 只zhi3 作zuo4 当dang1 想xiang3 看kan4 文wen2 无wu2 开kai1 手shou3 十shi2 用yong4 \
+主zhu3 行xing2 方fang1 又you4 如ru2 前qian2 所suo3 本ben3 见jian4 经jing1 头tou2 面mian4 公gong1 同tong2 三san1 \
+已yi3 老lao3 从cong2 动dong4 两liang3 长chang2 知zhi1 民min2 样yang4 现xian4 分fen1 将jiang1 外wai4 但dan4 身shen1 \
+些xie1 与yu3 高gao1 意yi4 进jin4 把ba3 法fa3 此ci3 实shi2 回hui2 二er4 理li3 美mei3 点dian3 月yue4 明ming2 其qi2 \
+种zhong3 声sheng1 全quan2 工gong1 己ji3 话hua4 儿er2 者zhe3 向xiang4 情qing2 部bu4 正zheng4 名ming2 定ding4 女nü3 \
+问wen4 力li4 机ji1 给gei3 等deng3 几ji3 很hen3 业ye4 最zui4 间jian1 新xin1 什shen2 打da3 便bian4 位wei4 因yin1 \
+重zhong4 被bei4 走zou3 电dian4 四si4 第di4 门men2 相xiang1 次ci4 东dong1 政zheng4 海hai3 口kou3 使shi3 教jiao4 \
+西xi1 再zai4 平ping2 真zhen1 听ting1 世shi4 气qi4 信xin4 北bei3 少shao3 关guan1 并bing4 内nei4 加jia1 化hua4 \
+由you2 却que4 代dai4 军jun1 产chan3 入ru4 先xian1 山shan1 五wu3 太tai4 水shui3 万wan4 市shi4 眼yan3 体ti3 别bie2 \
+处chu4 总zong3 才cai2 场chang3 师shi1 书shu1 比bi3 住zhu4 员yuan2 九jiu3 笑xiao4 性xing4 通tong1 目mu4 华hua2 \
+报bao4 立li4 马ma3 命ming4 张zhang1 活huo2 难nan2 神shen2 数shu4 件jian4 安an1 表biao3 原yuan2 车che1 白bai2 \
+应ying1 路lu4 期qi1 叫jiao4 死si3 常chang2 提ti2 感gan3 金jin1 何he2 更geng4 反fan3 合he2 放fang4 做zuo4 系xi4 \
+计ji4 或huo4 司si1 利li4 受shou4 光guang1 王wang2 果guo3 亲qin1 界jie4 及ji2 今jin1 京jing1 务wu4 制zhi4 解jie3 \
+各ge4 任ren4 至zhi4 清qing1 物wu4 台tai2 象xiang4 记ji4 边bian1 共gong4 风feng1 战zhan4 干gan4 接jie1 它ta1 \
+许xu3 八ba1 特te4 觉jue2 望wang4 直zhi2 服fu2 毛mao2 林lin2 题ti2 建jian4 南nan2 度du4 统tong3 色se4 字zi4 \
+请qing3 交jiao1 爱ai4 让rang4 认ren4 算suan4 论lun4 百bai3 吃chi1 义yi4 科ke1 怎zen3 元yuan2 社she4 术shu4 \
+结jie2 六liu4 功gong1 指zhi3 思si1 非fei1 流liu2 每mei3 青qing1 管guan3 夫fu1 连lian2 远yuan3 资zi1 队dui4 \
+跟gen1 带dai4 花hua1 快kuai4 条tiao2 院yuan4 变bian4 联lian2 言yan2 权quan2 往wang3 展zhan3 该gai1 领ling3 \
+传chuan2 近jin4 留liu2 红hong2 治zhi4 决jue2 周zhou1 保bao3 达da2 办ban4 运yun4 武wu3 半ban4 候hou4 七qi1 \
+必bi4 城cheng2 父fu4 强qiang2 步bu4 完wan2 深shen1 区qu1 即ji2 求qiu2 品pin3 士shi4 转zhuan3 量liang4 空kong1 \
+众zhong4 技ji4 轻qing1 程cheng2 告gao4 江jiang1 语yu3 英ying1 基ji1 满man3 式shi4 李li3 息xi1 写xie3 呢ne5 \
+识shi2 极ji2 令ling4 黄huang2 德de2 收shou1 脸lian3 钱qian2 未wei4 持chi2 取qu3 设she4 始shi3 双shuang1 历li4 \
+越yue4 史shi3 商shang1 千qian1 片pian4 容rong2 研yan2 像xiang4 找zhao3 友you3 孩hai2 站zhan4 广guang3 改gai3 \
+形xing2 早zao3 房fang2 音yin1 火huo3 则ze2 首shou3 单dan1 影ying3 失shi1 拿na2 网wang3 香xiang1 专zhuan1 \
+石shi2 弟di4 谁shei2 校xiao4 读du2 飞fei1 观guan1 包bao1 组zu3 造zao4 视shi4 喜xi3 离li2 坐zuo4 讲jiang3 \
+怕pa4 母mu3 局ju2 根gen1 准zhun3 终zhong1 乐le4 级ji2 精jing1 哪na3 冷leng3 图tu2 妈ma1 吗ma5 吧ba5 啊a5 \
+谢xie4 零ling2 您nin2 朋peng2 昨zuo2 午wu3 晚wan3 饭fan4 茶cha2 喝he1 买mai3 卖mai4 钟zhong1 岁sui4 块kuai4 \
+猫mao1 狗gou3 鱼yu2 鸟niao3 雨yu3 雪xue3 冬dong1 春chun1 夏xia4 秋qiu1 星xing1 汉han4 医yi1 病bing4 累lei4 \
+忙mang2 饿e4 热re4 贵gui4 哥ge1 姐jie3 妹mei4 爸ba4 睡shui4 玩wan2 跑pao3 唱chang4 歌ge1 跳tiao4 舞wu3 帮bang1 \
+号hao4 亿yi4 负fu4 脑nao3 普pu3 差cha4 漂piao4 亮liang4 衣yi1 欢huan1";
+
+/// Words in numbered pinyin: common words with a neutral syllable, and
+/// polyphonic characters in their other readings.
+const WORDS: &[(&str, &str)] = &[
+    ("一样", "yi1 yang4"),
+    ("东西", "dong1 xi5"),
+    ("为了", "wei4 le5"),
+    ("为什么", "wei4 shen2 me5"),
+    ("了解", "liao3 jie3"),
+    ("什么", "shen2 me5"),
+    ("你好", "ni3 hao3"),
+    ("先生", "xian1 sheng5"),
+    ("朋友", "peng2 you5"),
+    ("认为", "ren4 wei2"),
+    ("成为", "cheng2 wei2"),
+    ("差不多", "cha4 bu5 duo1"),
+    ("行业", "hang2 ye4"),
+    ("银行", "yin2 hang2"),
+    ("长大", "zhang3 da4"),
+    ("校长", "xiao4 zhang3"),
+    ("家长", "jia1 zhang3"),
+    ("得到", "de2 dao4"),
+    ("觉得", "jue2 de5"),
+    ("睡觉", "shui4 jiao4"),
+    ("还钱", "huan2 qian2"),
+    ("音乐", "yin1 yue4"),
+    ("重新", "chong2 xin1"),
+    ("好奇", "hao4 qi2"),
+    ("爱好", "ai4 hao4"),
+    ("首都", "shou3 du1"),
+    ("地方", "di4 fang5"),
+    ("睡着", "shui4 zhao2"),
+    ("便宜", "pian2 yi5"),
+    ("大夫", "dai4 fu5"),
+    ("谢谢", "xie4 xie5"),
+    ("怎么", "zen3 me5"),
+    ("孩子", "hai2 zi5"),
+    ("妈妈", "ma1 ma5"),
+    ("爸爸", "ba4 ba5"),
+    ("哥哥", "ge1 ge5"),
+    ("姐姐", "jie3 jie5"),
+    ("妹妹", "mei4 mei5"),
+    ("弟弟", "di4 di5"),
+    ("时候", "shi2 hou5"),
+    ("喜欢", "xi3 huan5"),
+    ("知道", "zhi1 dao5"),
+    ("漂亮", "piao4 liang5"),
+    ("事情", "shi4 qing5"),
+    ("衣服", "yi1 fu5"),
+    ("意思", "yi4 si5"),
+    ("我们", "wo3 men5"),
+    ("你们", "ni3 men5"),
+    ("他们", "ta1 men5"),
+];
+
+/// Readings of every character: `CHARS` first, then one reading each for
+/// the rest of the CJK Unified Ideographs block, from `data/zh_pinyin.tsv`.
+static CHAR_TABLE: LazyLock<HashMap<char, &'static str>> = LazyLock::new(|| {
+    let mut table = HashMap::new();
+    for entry in CHARS.split_whitespace() {
+        let mut chars = entry.chars();
+        if let Some(c) = chars.next() {
+            table.entry(c).or_insert(chars.as_str());
+        }
+    }
+    let lines = include_str!("../../data/zh_pinyin.tsv").lines().filter(|line| !line.starts_with('#'));
+    for (py, hanzi) in lines.filter_map(|line| line.split_once('\t')) {
+        for c in hanzi.chars() {
+            table.entry(c).or_insert(py);
+        }
+    }
+    table
+});
+
+fn is_hanzi(c: char) -> bool {
+    matches!(c, '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '〇')
+}
+
+/// Splits text into words: runs of hanzi by longest match against the
+/// word table, runs of letters and digits whole, punctuation alone.
+pub fn segment(text: &str) -> Vec<(usize, usize)> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let byte = |i: usize| chars.get(i).map_or(text.len(), |&(b, _)| b);
+    let max_len = WORDS.iter().map(|(w, _)| w.chars().count()).max().unwrap_or(1);
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i].1;
+        let len = if is_hanzi(c) {
+            (2..=max_len)
+                .rev()
+                .find(|&len| i + len <= chars.len() && WORDS.iter().any(|(w, _)| *w == &text[byte(i)..byte(i + len)]))
+                .unwrap_or(1)
+        } else if c.is_alphanumeric() {
+            let mut j = i + 1;
+            // "." and "," between digits stay with the number
+            while j < chars.len() {
+                let d = chars[j].1;
+                let in_number = matches!(d, '.' | ',')
+                    && chars[j - 1].1.is_ascii_digit()
+                    && chars.get(j + 1).is_some_and(|(_, d)| d.is_ascii_digit());
+                let letter = d.is_alphanumeric() && !is_hanzi(d);
+                if !letter && !in_number {
+                    break;
+                }
+                j += 1;
+            }
+            j - i
+        } else {
+            1
+        };
+        if !c.is_whitespace() {
+            words.push((byte(i), byte(i + len)));
+        }
+        i += len;
+    }
+    words
+}
+
+/// Numbered pinyin for a word of hanzi, `None` if a character isn't known.
+pub fn pinyin(word: &str) -> Option<Vec<String>> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut syllables = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let known = (2..=chars.len() - i).rev().find_map(|len| {
+            let part: String = chars[i..i + len].iter().collect();
+            WORDS.iter().find(|(w, _)| *w == part).map(|&(_, py)| (len, py))
+        });
+        match known {
+            Some((len, py)) => {
+                syllables.extend(py.split(' ').map(String::from));
+                i += len;
+            }
+            None => {
+                syllables.push(CHAR_TABLE.get(&chars[i])?.to_string());
+                i += 1;
+            }
+        }
+    }
+    Some(syllables)
+}
+
+/// Tone changes within a word: a third tone before another becomes second,
+/// "不" is second before a fourth, "一" second before a fourth and fourth
+/// before the others (but not counting, as in "十一").
+fn sandhi(chars: &[char], syllables: &mut [(String, u8)]) {
+    let numerals = "零一二三四五六七八九十百千万亿两第";
+    for i in 0..syllables.len() {
+        let Some(&(_, next)) = syllables.get(i + 1) else { break };
+        let prev_numeral = i > 0 && chars.get(i - 1).is_some_and(|&c| numerals.contains(c));
+        syllables[i].1 = match (chars.get(i), syllables[i].1, next) {
+            (_, 3, 3) => 2,
+            (Some('不'), 4, 4) => 2,
+            (Some('一'), 1, 4) if !prev_numeral => 2,
+            (Some('一'), 1, 1..=3) if !prev_numeral => 4,
+            (_, tone, _) => tone,
+        };
+    }
+}
+
+/// Phonemes for a word of hanzi.
+pub fn phonemize_hanzi(word: &str) -> Option<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut syllables = pinyin(word)?.iter().map(|s| split_tone(s)).collect::<Option<Vec<_>>>()?;
+    // Tables give one syllable per character
+    if syllables.len() == chars.len() {
+        sandhi(&chars, &mut syllables);
+    }
+    syllables.iter().map(|(s, tone)| syllable(s, *tone)).collect()
+}
+
+/// Phonemes for pinyin, numbered ("ni3hao3") or with tone marks ("nǐhǎo"),
+/// `None` unless every syllable is pinyin and at least one has a tone.
+pub fn phonemize_pinyin(word: &str) -> Option<String> {
+    let text = word.to_lowercase().replace('\'', "");
+    let syllables = if text.contains(|c: char| c.is_ascii_digit()) {
+        // Each syllable ends in its tone number
+        let mut syllables = Vec::new();
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            if c.is_ascii_digit() {
+                syllables.push(split_tone(&text[start..=i])?);
+                start = i + 1;
+            }
+        }
+        (start == text.len()).then_some(syllables)?
+    } else {
+        split_marked(&text)?
+    };
+    if syllables.iter().all(|&(_, tone)| tone == 5) {
+        return None;
+    }
+    syllables.iter().map(|(s, tone)| syllable(s, *tone)).collect()
+}
+
+/// Base letters and tone of a vowel with a tone mark.
+fn unmark(c: char) -> (char, u8) {
+    const MARKED: [(char, [char; 4]); 6] = [
+        ('a', ['ā', 'á', 'ǎ', 'à']),
+        ('e', ['ē', 'é', 'ě', 'è']),
+        ('i', ['ī', 'í', 'ǐ', 'ì']),
+        ('o', ['ō', 'ó', 'ǒ', 'ò']),
+        ('u', ['ū', 'ú', 'ǔ', 'ù']),
+        ('ü', ['ǖ', 'ǘ', 'ǚ', 'ǜ']),
+    ];
+    for (base, marks) in MARKED {
+        if let Some(tone) = marks.iter().position(|&m| m == c) {
+            return (base, tone as u8 + 1);
+        }
+    }
+    (c, 5)
+}
+
+/// A syllable with its tone number or mark taken off.
+fn split_tone(syllable: &str) -> Option<(String, u8)> {
+    if let Some(last) = syllable.chars().last()
+        && let Some(tone @ 1..=5) = last.to_digit(10)
+    {
+        let base = &syllable[..syllable.len() - 1];
+        return Some((base.replace('v', "ü"), tone as u8));
+    }
+    let mut tone = 5;
+    let base = syllable
+        .chars()
+        .map(|c| {
+            let (base, t) = unmark(c);
+            tone = tone.min(t);
+            base
+        })
+        .collect::<String>();
+    Some((base.replace('v', "ü"), tone))
+}
+
+/// Splits pinyin with tone marks into syllables, the longest first.
+fn split_marked(text: &str) -> Option<Vec<(String, u8)>> {
+    if text.is_empty() {
+        return Some(Vec::new());
+    }
+    let ends: Vec<usize> = text.char_indices().map(|(i, c)| i + c.len_utf8()).collect();
+    ends.iter().rev().find_map(|&end| {
+        let (base, tone) = split_tone(&text[..end])?;
+        parse(&base)?;
+        let mut rest = split_marked(&text[end..])?;
+        rest.insert(0, (base, tone));
+        Some(rest)
+    })
+}
+
+const INITIALS: &[(&str, &str)] = &[
+    ("zh", "ʈʂ"), ("ch", "ʈʂʰ"), ("sh", "ʂ"), ("b", "p"), ("p", "pʰ"), ("m", "m"), ("f", "f"), ("d", "t"),
+    ("t", "tʰ"), ("n", "n"), ("l", "l"), ("g", "k"), ("k", "kʰ"), ("h", "x"), ("j", "ʨ"), ("q", "ʨʰ"),
+    ("x", "ɕ"), ("r", "ɻ"), ("z", "ʦ"), ("c", "ʦʰ"), ("s", "s"),
+];
+
+const FINALS: &[(&str, &str)] = &[
+    ("a", "a"), ("o", "o"), ("e", "ɤ"), ("ê", "ɛ"), ("ai", "ai"), ("ei", "ei"), ("ao", "au"), ("ou", "ou"),
+    ("an", "an"), ("en", "ən"), ("ang", "aŋ"), ("eng", "əŋ"), ("ong", "ʊŋ"), ("er", "ɚ"),
+    ("i", "i"), ("ia", "ja"), ("ie", "je"), ("iao", "jau"), ("iou", "jou"), ("ian", "jɛn"), ("in", "in"),
+    ("iang", "jaŋ"), ("ing", "iŋ"), ("iong", "jʊŋ"), ("io", "jo"),
+    ("u", "u"), ("ua", "wa"), ("uo", "wo"), ("uai", "wai"), ("uei", "wei"), ("uan", "wan"), ("uen", "wən"),
+    ("uang", "waŋ"), ("ueng", "wəŋ"),
+    ("ü", "y"), ("üe", "ɥe"), ("üan", "ɥɛn"), ("ün", "yn"),
+];
+
+/// The initial and final of a toneless syllable, spelled out ("yu" is
+/// "ü", "dui" is "d" and "uei"), or `None` if it isn't pinyin.
+fn parse(syllable: &str) -> Option<(&'static str, String)> {
+    let (initial, rest) = INITIALS
+        .iter()
+        .find(|(i, _)| syllable.starts_with(i))
+        .map_or(("", syllable), |&(i, _)| (i, &syllable[i.len()..]));
+    let mut fin = match (initial, rest.chars().next()) {
+        ("", _) if let Some(rest) = syllable.strip_prefix('y') => match rest {
+            _ if rest.starts_with('u') => rest.replacen('u', "ü", 1),
+            _ if rest.starts_with('i') => rest.to_string(),
+            _ => format!("i{}", rest),
+        },
+        ("", _) if let Some(rest) = syllable.strip_prefix('w') => match rest {
+            _ if rest.starts_with('u') => rest.to_string(),
+            _ => format!("u{}", rest),
+        },
+        ("j" | "q" | "x", Some('u')) => rest.replacen('u', "ü", 1),
+        _ => rest.to_string(),
+    };
+    fin = match fin.as_str() {
+        "iu" => "iou".to_string(),
+        "ui" => "uei".to_string(),
+        "un" => "uen".to_string(),
+        _ => fin,
+    };
+    let initial = INITIALS.iter().find(|(i, _)| *i == initial).map_or("", |&(_, ps)| ps);
+    FINALS.iter().any(|(f, _)| *f == fin).then_some((initial, fin))
+}
+
+/// Phonemes for one toneless syllable and its tone.
+fn syllable(base: &str, tone: u8) -> Option<String> {
+    let sounds = match base {
+        // Interjections such as "呣" (m) and "嗯" (n, ng) are a nasal alone
+        "m" | "n" | "hm" => base.to_string(),
+        "ng" | "hng" => base.replace("ng", "ŋ"),
+        _ => {
+            let (initial, fin) = parse(base)?;
+            let fin = match (initial, fin.as_str()) {
+                // The "i" of "zi" and "zhi" is a buzzed continuation of the consonant
+                ("ʦ" | "ʦʰ" | "s", "i") => "ɹ",
+                ("ʈʂ" | "ʈʂʰ" | "ʂ" | "ɻ", "i") => "ɻ",
+                (_, fin) => FINALS.iter().find(|(f, _)| *f == fin)?.1,
+            };
+            format!("{}{}", initial, fin)
+        }
+    };
+    let tone = match tone {
+        1 => "→",
+        2 => "↗",
+        3 => "↓",
+        4 => "↘",
+        _ => "",
+    };
+    Some(format!("{}{}", sounds, tone))
+}
+
+const DIGITS: [char; 10] = ['零', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+
+/// Mandarin numbers in hanzi, grouped by ten thousands ("万", "亿").
+pub struct MandarinNumbers;
+
+impl MandarinNumbers {
+    /// 1 to 9999; `leading` when nothing is read before it, so ten is "十"
+    /// rather than "一十".
+    fn below_wan(n: u64, leading: bool) -> String {
+        let mut words = String::new();
+        let mut zero = false;
+        for (unit, value) in [("千", 1000), ("百", 100), ("十", 10), ("", 1)] {
+            let digit = (n / value % 10) as usize;
+            if digit == 0 {
+                // One "零" stands for any run of zeros inside the number
+                zero = !words.is_empty();
+                continue;
+            }
+            if zero {
+                words.push('零');
+                zero = false;
+            }
+            match (digit, unit) {
+                (1, "十") if leading && words.is_empty() => {}
+                // "两" counts hundreds and thousands
+                (2, "千" | "百") if words.is_empty() => words.push('两'),
+                _ => words.push(DIGITS[digit]),
+            }
+            words += unit;
+        }
+        words
+    }
+
+    fn read(n: u64, leading: bool) -> String {
+        for (scale, name) in [(100_000_000, '亿'), (10_000, '万')] {
+            if n < scale {
+                continue;
+            }
+            let (high, low) = (n / scale, n % scale);
+            let mut words = if high == 2 { "两".to_string() } else { Self::read(high, leading) };
+            words.push(name);
+            if low > 0 {
+                // A gap below the next group is read as "零"
+                if low < scale / 10 {
+                    words.push('零');
+                }
+                words += &Self::read(low, false);
+            }
+            return words;
+        }
+        Self::below_wan(n, leading)
+    }
+}
+
+impl NumberVerbalizer for MandarinNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("负{}", words));
+        }
+        if n == 0 {
+            return Some("零".to_string());
+        }
+        Some(Self::read(n as u64, true))
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        self.cardinal(n).map(|words| format!("第{}", words))
+    }
+
+    /// Years are read digit by digit.
+    fn year(&self, n: i64) -> Option<String> {
+        (n >= 0).then(|| n.to_string().chars().filter_map(|d| d.to_digit(10)).map(|d| DIGITS[d as usize]).collect())
+    }
+
+    /// Digits run together, without spaces.
+    fn digits(&self, digits: &str) -> Option<String> {
+        digits.chars().map(|c| Some(DIGITS[c.to_digit(10)? as usize])).collect()
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "点", "")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("负{}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize_hanzi() {
+        let cases = [
+            ("你好", "ni↗xau↓"),
+            ("中国", "ʈʂʊŋ→kwo↗"),
+            ("是", "ʂɻ↘"),
+            ("谢谢", "ɕje↘ɕje"),
+            ("银行", "in↗xaŋ↗"),
+            ("行", "ɕiŋ↗"),
+            ("不要", "pu↗jau↘"),
+            ("一样", "i↗jaŋ↘"),
+            ("一千", "i↘ʨʰjɛn→"),
+            ("十一", "ʂɻ↗i→"),
+            ("女", "ny↓"),
+            ("四", "sɹ↘"),
+        ];
+        for (word, ps) in cases {
+            assert_eq!(phonemize_hanzi(word).as_deref(), Some(ps), "{}", word);
+        }
+        assert_eq!(phonemize_hanzi("㐀"), None);
+
+        // Characters past the seed table come from the full one
+        let cases = [("苹果", "pʰiŋ↗kwo↓"), ("葡萄", "pʰu↗tʰau↗"), ("蝴蝶", "xu↗tje↗"), ("咖啡", "kʰa→fei→")];
+        for (word, ps) in cases {
+            assert_eq!(phonemize_hanzi(word).as_deref(), Some(ps), "{}", word);
+        }
+        assert!(CHAR_TABLE.keys().all(|c| phonemize_hanzi(&c.to_string()).is_some()));
+    }
+
+    #[test]
+    fn test_pinyin() {
+        assert_eq!(phonemize_pinyin("ni3hao3").as_deref(), Some("ni↓xau↓"));
+        assert_eq!(phonemize_pinyin("Běijīng").as_deref(), Some("pei↓ʨiŋ→"));
+        assert_eq!(phonemize_pinyin("xi1'an1").as_deref(), Some("ɕi→an→"));
+        assert_eq!(phonemize_pinyin("lüe4").as_deref(), Some("lɥe↘"));
+        assert_eq!(phonemize_pinyin("dui4").as_deref(), Some("twei↘"));
+        assert_eq!(phonemize_pinyin("hello"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let cases = [
+            (0, "零"),
+            (10, "十"),
+            (15, "十五"),
+            (110, "一百一十"),
+            (101, "一百零一"),
+            (2_000, "两千"),
+            (1_010, "一千零一十"),
+            (20_000, "两万"),
+            (100_005, "十万零五"),
+            (300_000_000, "三亿"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(MandarinNumbers.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(MandarinNumbers.ordinal(1).as_deref(), Some("第一"));
+        assert_eq!(MandarinNumbers.year(2024).as_deref(), Some("二零二四"));
+        assert_eq!(MandarinNumbers.decimal("", "05").as_deref(), Some("零点零五"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::Mandarin);
+        let (_, tokens) = g2p.g2p("我们去银行，好吗？").unwrap();
+        let words: Vec<(&str, &str)> =
+            tokens.iter().map(|t| (t.text.as_str(), t.phonemes.as_deref().unwrap_or_default())).collect();
+        assert_eq!(
+            words,
+            [
+                ("我们", "wo↓mən"),
+                ("去", "ʨʰy↘"),
+                ("银行", "in↗xaŋ↗"),
+                ("，", ","),
+                ("好", "xau↓"),
+                ("吗", "ma"),
+                ("？", "?"),
+            ]
+        );
+
+        let (ps, _) = g2p.g2p("我有123个苹果。").unwrap();
+        assert!(!ps.contains('❓'), "{}", ps);
+        assert!(ps.contains("pʰiŋ↗kwo↓"), "{}", ps);
+    }
+}
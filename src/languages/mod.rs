@@ -1,8 +1,471 @@
-use crate::lexicon::Lexicon;
+use crate::data;
+use crate::language::Language;
+use crate::lexicon::{Lexicon, PhonemeEntry};
+use crate::normalize;
+use crate::options::{NegativeStyle, NumberLocale};
+use crate::tagger::Tagger;
+use num2words::Num2Words;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// A token's neighbours as (text, tag) pairs, for context-dependent rules.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Neighbors<'a> {
+    pub prev2: Option<(&'a str, &'a str)>,
+    pub prev: Option<(&'a str, &'a str)>,
+    pub next: Option<(&'a str, &'a str)>,
+}
+
+/// How an abbreviation written with a trailing period is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Abbreviation {
+    pub expansion: &'static str,
+    /// Read as a title of the name after it ("Dr. Smith"), whose capital
+    /// doesn't start a new sentence.
+    pub before_name: bool,
+}
 
 pub trait LanguageRules: Send + Sync {
     fn apply_rules(&self, word: &str, tag: &str, lexicon: &Lexicon) -> Option<String>;
+
+    /// A guess at a name out of the lexicon ("Kowalski"), in British or
+    /// American phonemes as the fallback's would be. `G2P` reads it as it
+    /// does the fallback's: only under `OovPolicy::Fallback` with a fallback
+    /// set, and recorded as an OOV word.
+    fn guess_name(&self, _word: &str, _lexicon: &Lexicon, _british: bool) -> Option<String> {
+        None
+    }
+
+    /// Spoken expansion of an abbreviation written with a trailing period
+    /// ("Dr" in "Dr. Smith"), given the tags of the surrounding words.
+    fn expand_abbreviation(&self, _word: &str, _prev_tag: Option<&str>, _next_tag: Option<&str>) -> Option<Abbreviation> {
+        None
+    }
+
+    /// Phonemes for a heteronym ("read", "lead", "live") when its context calls
+    /// for the reading the lexicon doesn't give by default.
+    fn heteronym(&self, _word: &str, _tag: &str, _neighbors: &Neighbors, _british: bool) -> Option<&'static str> {
+        None
+    }
+
+    /// The part-of-speech tagger for the language's words, in Penn Treebank
+    /// tags; `None` leaves every word a noun, and readings that depend on the
+    /// tags don't apply.
+    fn tagger(&self) -> Option<Box<dyn Tagger>> {
+        None
+    }
+}
+
+/// Spoken forms of numbers, `None` for those a language can't read. `G2P`
+/// reads every number through its pack's verbalizer, so what a language
+/// can't read is left as written rather than read in English.
+pub trait NumberVerbalizer: Send + Sync {
+    fn cardinal(&self, n: i64) -> Option<String>;
+    fn ordinal(&self, n: i64) -> Option<String>;
+    /// A number read as a year ("nineteen ninety-nine"); a cardinal by default.
+    fn year(&self, n: i64) -> Option<String> {
+        self.cardinal(n)
+    }
+
+    /// A string of digits read one at a time, as in a phone number; each
+    /// digit's cardinal by default.
+    fn digits(&self, digits: &str) -> Option<String> {
+        let words: Option<Vec<String>> =
+            digits.chars().map(|c| self.cardinal(c.to_digit(10)? as i64)).collect();
+        words.map(|words| words.join(" "))
+    }
+
+    /// A cardinal with more digits than `i64` holds; digit by digit by
+    /// default.
+    fn large_cardinal(&self, digits: &str) -> Option<String> {
+        self.digits(digits)
+    }
+
+    /// A decimal from the digits either side of its point ("3", "14"); the
+    /// whole part may be empty (".5").
+    fn decimal(&self, _whole: &str, _fraction: &str) -> Option<String> {
+        None
+    }
+
+    /// A negative number from the reading of its absolute value, worded as
+    /// `style` asks where the language has a choice.
+    fn negative(&self, _words: &str, _style: NegativeStyle) -> Option<String> {
+        None
+    }
+
+    /// An amount of money in plain "1234.5" form, in the currency of
+    /// `symbol` ("$", "€"); `None` reads the amount as a plain number.
+    fn currency(&self, _amount: &str, _symbol: &str) -> Option<String> {
+        None
+    }
+}
+
+/// A decimal as the whole part's cardinal, `point` and the fraction digit
+/// by digit, the way most languages read it aloud; `sep` goes between them.
+#[cfg(any(
+    test,
+    feature = "spanish",
+    feature = "french",
+    feature = "german",
+    feature = "portuguese",
+    feature = "ja",
+    feature = "zh",
+    feature = "ko",
+    feature = "hi",
+    feature = "vi"
+))]
+fn read_decimal(numbers: &dyn NumberVerbalizer, whole: &str, fraction: &str, point: &str, sep: &str) -> Option<String> {
+    let whole = if whole.is_empty() { "0" } else { whole };
+    let whole = match whole.parse::<i64>() {
+        Ok(n) => numbers.cardinal(n)?,
+        Err(_) => numbers.large_cardinal(whole)?,
+    };
+    Some([whole, point.to_string(), numbers.digits(fraction)?].join(sep))
+}
+
+pub struct EnglishNumbers;
+
+impl EnglishNumbers {
+    /// Short-scale names of the powers of a thousand beyond the first.
+    const SCALES: &[&str] = &[
+        "thousand", "million", "billion", "trillion", "quadrillion", "quintillion",
+        "sextillion", "septillion", "octillion", "nonillion", "decillion", "undecillion",
+        "duodecillion", "tredecillion", "quattuordecillion", "quindecillion", "sexdecillion",
+        "septendecillion", "octodecillion", "novemdecillion", "vigintillion",
+    ];
+
+    /// An unsigned integer or decimal in plain "1234.5" form.
+    fn number(&self, number: &str) -> Option<String> {
+        match number.split_once('.') {
+            Some((whole, fraction)) => self.decimal(whole, fraction),
+            None => match number.parse::<i64>() {
+                Ok(n) => self.cardinal(n),
+                Err(_) => self.large_cardinal(number),
+            },
+        }
+    }
+}
+
+impl NumberVerbalizer for EnglishNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        Num2Words::new(n).to_words().ok()
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        Num2Words::new(n).ordinal().to_words().ok()
+    }
+
+    fn year(&self, n: i64) -> Option<String> {
+        Num2Words::new(n).year().to_words().ok()
+    }
+
+    /// Three digits at a time with short-scale names up to vigintillion, with
+    /// "and" before the last tens and units as num2words puts it; anything
+    /// longer is read digit by digit.
+    fn large_cardinal(&self, digits: &str) -> Option<String> {
+        let digits = digits.trim_start_matches('0');
+        let head = digits.len() % 3;
+        let mut groups = Vec::new();
+        if head > 0 {
+            groups.push(&digits[..head]);
+        }
+        groups.extend(digits.as_bytes()[head..].chunks(3).filter_map(|c| std::str::from_utf8(c).ok()));
+        if groups.len() > Self::SCALES.len() + 1 {
+            return self.digits(digits);
+        }
+
+        let mut words = Vec::new();
+        for (k, group) in groups.iter().enumerate() {
+            let scale = groups.len() - 1 - k;
+            let val: i64 = group.parse().ok()?;
+            if val == 0 {
+                continue;
+            }
+            if scale == 0 && val % 100 != 0 {
+                if val >= 100 {
+                    words.push(self.cardinal(val / 100 * 100)?);
+                }
+                words.push("and".to_string());
+                words.push(self.cardinal(val % 100)?);
+                continue;
+            }
+            words.push(self.cardinal(val)?);
+            if scale > 0 {
+                words.push(Self::SCALES[scale - 1].to_string());
+            }
+        }
+        Some(words.join(" "))
+    }
+
+    /// Python misaki reads the fraction digit by digit: "three point one four".
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        let mut words = Vec::new();
+        if !whole.is_empty() {
+            words.push(self.number(whole)?);
+        }
+        words.push("point".to_string());
+        words.push(self.digits(fraction)?);
+        Some(words.join(" "))
+    }
+
+    fn negative(&self, words: &str, style: NegativeStyle) -> Option<String> {
+        let sign = match style {
+            NegativeStyle::Minus => "minus",
+            NegativeStyle::Negative => "negative",
+        };
+        Some(format!("{} {}", sign, words))
+    }
+
+    fn currency(&self, amount: &str, symbol: &str) -> Option<String> {
+        normalize::expand_currency(amount, symbol, |n| self.number(n).unwrap_or_else(|| n.to_string()))
+    }
+}
+
+/// Splits text into words, as byte ranges.
+pub type WordSplitter = Box<dyn Fn(&str) -> Vec<(usize, usize)> + Send + Sync>;
+
+/// Everything `G2P` needs for a language.
+pub struct LanguagePack {
+    /// Entries in the schema of the bundled `data/*_gold.json`.
+    pub golds: HashMap<String, PhonemeEntry>,
+    pub silvers: HashMap<String, PhonemeEntry>,
+    pub rules: Box<dyn LanguageRules>,
+    pub numbers: Box<dyn NumberVerbalizer>,
+    /// Word boundaries, for scripts not written with spaces between words;
+    /// `None` splits at whitespace.
+    pub tokenizer: Option<WordSplitter>,
+    /// How the language writes its numbers ("3,14" in Spanish), where
+    /// `G2POptions::number_locale` doesn't say.
+    pub number_locale: NumberLocale,
+}
+
+type PackFactory = Arc<dyn Fn() -> LanguagePack + Send + Sync>;
+
+static REGISTRY: LazyLock<RwLock<HashMap<String, PackFactory>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Makes `Language::Other(name)` available to `G2P::new`, which calls
+/// `factory` for each `G2P` it builds. Replaces any pack registered under
+/// the same name.
+pub fn register(name: &str, factory: impl Fn() -> LanguagePack + Send + Sync + 'static) {
+    REGISTRY.write().unwrap().insert(name.to_string(), Arc::new(factory));
+}
+
+pub fn is_registered(name: &str) -> bool {
+    REGISTRY.read().unwrap().contains_key(name)
+}
+
+/// The pack for `lang`: the bundled one for English and the languages of
+/// enabled features, otherwise the registered one, or `None` if nothing is
+/// registered under its name.
+pub fn pack(lang: &Language) -> Option<LanguagePack> {
+    let english = |golds, silvers| LanguagePack {
+        golds,
+        silvers,
+        rules: Box::new(english::English),
+        numbers: Box::new(EnglishNumbers),
+        tokenizer: None,
+        number_locale: NumberLocale::English,
+    };
+    match lang {
+        Language::EnglishUS | Language::EnglishIE | Language::EnglishSC => {
+            Some(english(data::load_us_gold(), data::load_us_silver()))
+        }
+        Language::EnglishGB | Language::EnglishAU | Language::EnglishIN => {
+            Some(english(data::load_gb_gold(), data::load_gb_silver()))
+        }
+        #[cfg(feature = "spanish")]
+        Language::Spanish => Some(LanguagePack {
+            golds: HashMap::new(),
+            silvers: HashMap::new(),
+            rules: Box::new(spanish::Spanish),
+            numbers: Box::new(spanish::SpanishNumbers),
+            tokenizer: None,
+            number_locale: NumberLocale::European,
+        }),
+        #[cfg(feature = "french")]
+        Language::French => Some(LanguagePack {
+            golds: french::lexicon(),
+            silvers: HashMap::new(),
+            rules: Box::new(french::French),
+            numbers: Box::new(french::FrenchNumbers),
+            tokenizer: None,
+            number_locale: NumberLocale::European,
+        }),
+        #[cfg(feature = "german")]
+        Language::German => Some(LanguagePack {
+            golds: german::lexicon(),
+            silvers: HashMap::new(),
+            rules: Box::new(german::German),
+            numbers: Box::new(german::GermanNumbers),
+            tokenizer: None,
+            number_locale: NumberLocale::European,
+        }),
+        #[cfg(feature = "portuguese")]
+        Language::PortugueseBR | Language::PortuguesePT => {
+            let brazilian = *lang == Language::PortugueseBR;
+            Some(LanguagePack {
+                golds: HashMap::new(),
+                silvers: HashMap::new(),
+                rules: Box::new(if brazilian { portuguese::Portuguese::BRAZILIAN } else { portuguese::Portuguese::EUROPEAN }),
+                numbers: Box::new(portuguese::PortugueseNumbers { brazilian }),
+                tokenizer: None,
+                number_locale: NumberLocale::European,
+            })
+        }
+        #[cfg(feature = "ja")]
+        Language::Japanese => Some(japanese::pack_with(Box::new(japanese::segment))),
+        #[cfg(feature = "zh")]
+        Language::Mandarin => Some(LanguagePack {
+            golds: HashMap::new(),
+            silvers: HashMap::new(),
+            rules: Box::new(mandarin::Mandarin),
+            numbers: Box::new(mandarin::MandarinNumbers),
+            tokenizer: Some(Box::new(mandarin::segment)),
+            number_locale: NumberLocale::English,
+        }),
+        #[cfg(feature = "ko")]
+        Language::Korean => Some(LanguagePack {
+            golds: HashMap::new(),
+            silvers: HashMap::new(),
+            rules: Box::new(korean::Korean),
+            numbers: Box::new(korean::KoreanNumbers),
+            tokenizer: None,
+            number_locale: NumberLocale::English,
+        }),
+        #[cfg(feature = "hi")]
+        Language::Hindi => Some(LanguagePack {
+            golds: HashMap::new(),
+            silvers: HashMap::new(),
+            rules: Box::new(hindi::Hindi),
+            numbers: Box::new(hindi::HindiNumbers),
+            tokenizer: None,
+            number_locale: NumberLocale::English,
+        }),
+        #[cfg(feature = "vi")]
+        Language::Vietnamese => Some(LanguagePack {
+            golds: HashMap::new(),
+            silvers: HashMap::new(),
+            rules: Box::new(vietnamese::Vietnamese),
+            numbers: Box::new(vietnamese::VietnameseNumbers),
+            tokenizer: None,
+            number_locale: NumberLocale::English,
+        }),
+        Language::Other(name) => {
+            // Release the lock before the factory runs, in case it registers packs
+            let factory = REGISTRY.read().unwrap().get(name).cloned()?;
+            Some(factory())
+        }
+    }
 }
 
 pub mod english;
+#[cfg(feature = "french")]
+pub mod french;
+#[cfg(feature = "german")]
+pub mod german;
+#[cfg(feature = "hi")]
+pub mod hindi;
+#[cfg(feature = "ja")]
+pub mod japanese;
+#[cfg(feature = "ko")]
+pub mod korean;
+#[cfg(feature = "zh")]
+pub mod mandarin;
+#[cfg(feature = "portuguese")]
+pub mod portuguese;
+#[cfg(feature = "spanish")]
+pub mod spanish;
+#[cfg(feature = "vi")]
+pub mod vietnamese;
 // pub mod italian;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::{G2P, G2PError};
+
+    struct NoRules;
+
+    impl LanguageRules for NoRules {
+        fn apply_rules(&self, _word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+            None
+        }
+    }
+
+    struct TokiNumbers;
+
+    impl NumberVerbalizer for TokiNumbers {
+        fn cardinal(&self, n: i64) -> Option<String> {
+            ["ala", "wan", "tu"].get(n as usize).map(|w| w.to_string())
+        }
+
+        fn ordinal(&self, n: i64) -> Option<String> {
+            self.cardinal(n).map(|w| format!("nanpa {}", w))
+        }
+    }
+
+    #[test]
+    fn test_english_numbers() {
+        assert_eq!(EnglishNumbers.decimal("3", "14").as_deref(), Some("three point one four"));
+        assert_eq!(EnglishNumbers.decimal("", "5").as_deref(), Some("point five"));
+        assert_eq!(EnglishNumbers.negative("five", NegativeStyle::Negative).as_deref(), Some("negative five"));
+        assert_eq!(
+            EnglishNumbers.large_cardinal("12000000000000000000000").as_deref(),
+            Some("twelve sextillion")
+        );
+        assert_eq!(
+            EnglishNumbers.large_cardinal("10000000000000000000010").as_deref(),
+            Some("ten sextillion and ten")
+        );
+        assert_eq!(EnglishNumbers.currency("2.50", "$").as_deref(), Some("two dollars and fifty cents"));
+    }
+
+    #[test]
+    fn test_default_verbalizer() {
+        assert_eq!(TokiNumbers.digits("120").as_deref(), Some("wan tu ala"));
+        assert_eq!(TokiNumbers.large_cardinal("12").as_deref(), Some("wan tu"));
+        assert_eq!(TokiNumbers.digits("13"), None);
+        assert_eq!(TokiNumbers.decimal("1", "2"), None);
+        assert_eq!(TokiNumbers.negative("wan", NegativeStyle::Minus), None);
+        assert_eq!(read_decimal(&TokiNumbers, "", "12", "pi", " ").as_deref(), Some("ala pi wan tu"));
+    }
+
+    #[test]
+    fn test_register() {
+        assert!(pack(&Language::Other("toki-test".to_string())).is_none());
+        assert!(matches!(
+            G2P::try_new(Language::Other("toki-test".to_string())),
+            Err(G2PError::NoLanguagePack { .. })
+        ));
+        register("toki-test", || LanguagePack {
+            golds: [("toki", "tˈoki"), ("pona", "pˈona"), ("wan", "wˈan"), ("tu", "tˈu")]
+                .into_iter()
+                .map(|(w, ps)| (w.to_string(), PhonemeEntry::Simple(ps.to_string())))
+                .collect(),
+            silvers: HashMap::new(),
+            rules: Box::new(NoRules),
+            numbers: Box::new(TokiNumbers),
+            // Words are split at "+" as well as spaces
+            tokenizer: Some(Box::new(|text: &str| {
+                let mut words = Vec::new();
+                let mut start = 0;
+                for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+                    if c == ' ' || c == '+' {
+                        if i > start {
+                            words.push((start, i));
+                        }
+                        start = i + c.len_utf8();
+                    }
+                }
+                words
+            })),
+            number_locale: NumberLocale::English,
+        });
+        assert!(is_registered("toki-test"));
+
+        let g2p = G2P::new(Language::Other("toki-test".to_string()));
+        let (_, tokens) = g2p.g2p("toki+pona 2").unwrap();
+        let phonemes: Vec<&str> = tokens.iter().filter_map(|t| t.phonemes.as_deref()).collect();
+        assert_eq!(phonemes, ["tˈoki", "pˈona", "tˈu"]);
+    }
+}
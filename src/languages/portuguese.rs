@@ -0,0 +1,519 @@
+//! Rule-based Portuguese for both Brazil and Portugal, in the notation of
+//! espeak-ng's "pt-br" and "pt" voices: the stress mark before the stressed
+//! vowel, nasal vowels with a combining tilde, and "ão" as "ɐ̃w". The two
+//! share the spelling rules; `Portuguese` switches what differs.
+
+use crate::lexicon::Lexicon;
+use crate::options::NegativeStyle;
+use super::{LanguageRules, NumberVerbalizer, read_decimal};
+
+/// Where Brazilian and European Portuguese part ways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Portuguese {
+    /// Unstressed vowels reduce everywhere ("a" → "ɐ", "e" → "ɨ", "o" → "u")
+    /// rather than only at the end of the word ("e" → "i", "o" → "u").
+    pub reduce_vowels: bool,
+    /// "s" and "z" closing a syllable are "ʃ" ("ʒ" before a voiced
+    /// consonant) rather than "s" ("z").
+    pub palatalize_s: bool,
+    /// "t" and "d" before an "i" sound are "ʧ" and "ʤ", "l" closing a
+    /// syllable is "w", and a strong "r" is "x" rather than "ʁ".
+    pub brazilian_consonants: bool,
+}
+
+impl Portuguese {
+    pub const BRAZILIAN: Portuguese = Portuguese { reduce_vowels: false, palatalize_s: false, brazilian_consonants: true };
+    pub const EUROPEAN: Portuguese = Portuguese { reduce_vowels: true, palatalize_s: true, brazilian_consonants: false };
+}
+
+impl LanguageRules for Portuguese {
+    fn apply_rules(&self, word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+        self.phonemize(word)
+    }
+}
+
+/// Function words read without stress.
+const UNSTRESSED: &[&str] = &[
+    "a", "as", "com", "da", "das", "de", "do", "dos", "e", "em", "lhe", "mas", "me", "na", "nas", "nem", "no", "nos",
+    "o", "os", "ou", "por", "que", "se", "sem", "te",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mark {
+    None,
+    Acute,
+    Circumflex,
+    Tilde,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    /// `nasal` when an "m" or "n" closes its syllable.
+    Vowel { vowel: char, mark: Mark, nasal: bool },
+    /// "S" stands for an "s" or "z" closing a syllable, "R" for a strong "r".
+    Consonant(&'static str),
+}
+
+use Segment::*;
+
+fn is_front(c: Option<&char>) -> bool {
+    matches!(c, Some('e' | 'i' | 'é' | 'ê' | 'í'))
+}
+
+fn is_vowel_letter(c: Option<&char>) -> bool {
+    matches!(c, Some('a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'à' | 'â' | 'ã' | 'é' | 'ê' | 'í' | 'ó' | 'ô' | 'õ' | 'ú' | 'ü'))
+}
+
+/// Spells `word` as sounds, before stress and the dialect's reductions.
+fn segments(word: &[char]) -> Option<Vec<Segment>> {
+    let mut segs = Vec::new();
+    let mut i = 0;
+    while i < word.len() {
+        let prev = i.checked_sub(1).map(|j| &word[j]);
+        let next = word.get(i + 1);
+        let mut skip = 1;
+        let vowel = |vowel, mark| Vowel { vowel, mark, nasal: false };
+        match word[i] {
+            c @ ('a' | 'e' | 'i' | 'o' | 'u') => segs.push(vowel(c, Mark::None)),
+            'à' => segs.push(vowel('a', Mark::None)),
+            'ü' => segs.push(vowel('u', Mark::None)),
+            'á' => segs.push(vowel('a', Mark::Acute)),
+            'é' => segs.push(vowel('e', Mark::Acute)),
+            'í' => segs.push(vowel('i', Mark::Acute)),
+            'ó' => segs.push(vowel('o', Mark::Acute)),
+            'ú' => segs.push(vowel('u', Mark::Acute)),
+            'â' => segs.push(vowel('a', Mark::Circumflex)),
+            'ê' => segs.push(vowel('e', Mark::Circumflex)),
+            'ô' => segs.push(vowel('o', Mark::Circumflex)),
+            'ã' => segs.push(vowel('a', Mark::Tilde)),
+            'õ' => segs.push(vowel('o', Mark::Tilde)),
+            'c' if next == Some(&'h') => {
+                segs.push(Consonant("ʃ"));
+                skip = 2;
+            }
+            'c' if is_front(next) => segs.push(Consonant("s")),
+            'ç' => segs.push(Consonant("s")),
+            'c' | 'k' => segs.push(Consonant("k")),
+            'l' if next == Some(&'h') => {
+                segs.push(Consonant("ʎ"));
+                skip = 2;
+            }
+            'n' if next == Some(&'h') => {
+                segs.push(Consonant("ɲ"));
+                skip = 2;
+            }
+            // A nasal closing a syllable nasalizes the vowel before it
+            'm' | 'n' if !is_vowel_letter(next) && matches!(segs.last(), Some(Vowel { .. })) => {
+                if let Some(Vowel { nasal, .. }) = segs.last_mut() {
+                    *nasal = true;
+                }
+            }
+            'm' => segs.push(Consonant("m")),
+            'n' => segs.push(Consonant("n")),
+            'q' if next == Some(&'u') => {
+                segs.push(Consonant("k"));
+                // The "u" is silent before "e" and "i"
+                if !is_front(word.get(i + 2)) {
+                    segs.push(Consonant("w"));
+                }
+                skip = 2;
+            }
+            'q' => segs.push(Consonant("k")),
+            'g' if next == Some(&'u') && is_front(word.get(i + 2)) => {
+                segs.push(Consonant("ɡ"));
+                skip = 2;
+            }
+            'g' if is_front(next) => segs.push(Consonant("ʒ")),
+            'g' => segs.push(Consonant("ɡ")),
+            'j' => segs.push(Consonant("ʒ")),
+            'h' => {}
+            'x' => segs.push(Consonant("ʃ")),
+            's' if next == Some(&'s') => {
+                segs.push(Consonant("s"));
+                skip = 2;
+            }
+            's' | 'z' if is_vowel_letter(next) => {
+                let between_vowels = is_vowel_letter(prev);
+                segs.push(Consonant(if between_vowels || word[i] == 'z' { "z" } else { "s" }));
+            }
+            's' | 'z' => segs.push(Consonant("S")),
+            'r' if next == Some(&'r') => {
+                segs.push(Consonant("R"));
+                skip = 2;
+            }
+            'r' if i == 0 || matches!(prev, Some('l' | 'n' | 's')) => segs.push(Consonant("R")),
+            'r' => segs.push(Consonant("ɾ")),
+            'b' => segs.push(Consonant("b")),
+            'd' => segs.push(Consonant("d")),
+            'f' => segs.push(Consonant("f")),
+            'l' => segs.push(Consonant("l")),
+            'p' => segs.push(Consonant("p")),
+            't' => segs.push(Consonant("t")),
+            'v' => segs.push(Consonant("v")),
+            'w' => segs.push(Consonant("w")),
+            'y' => segs.push(vowel('i', Mark::None)),
+            _ => return None,
+        }
+        i += skip;
+    }
+    Some(segs)
+}
+
+fn nasal(vowel: char) -> &'static str {
+    match vowel {
+        'a' => "ɐ̃",
+        'e' => "ẽ",
+        'i' => "ĩ",
+        'o' => "õ",
+        _ => "ũ",
+    }
+}
+
+impl Portuguese {
+    /// Phonemes for a Portuguese word, or `None` if it has letters
+    /// Portuguese doesn't use.
+    pub fn phonemize(&self, word: &str) -> Option<String> {
+        let lower: Vec<char> = word.to_lowercase().chars().collect();
+        let text: String = lower.iter().collect();
+        let segs = segments(&lower)?;
+
+        // Falling diphthongs ("ai", "eu") share a syllable, as do "ão",
+        // "ãe" and "õe"; a weak vowel before another starts its own
+        let weak = |i: usize| match segs[i] {
+            Vowel { vowel: 'i' | 'u', mark: Mark::None, nasal: false } => true,
+            Vowel { vowel: 'o' | 'e', mark: Mark::None, nasal: false } => {
+                i > 0 && matches!(segs[i - 1], Vowel { mark: Mark::Tilde, .. })
+            }
+            _ => false,
+        };
+        let mut nuclei: Vec<Vec<usize>> = Vec::new();
+        for (i, seg) in segs.iter().enumerate() {
+            if !matches!(seg, Vowel { .. }) {
+                continue;
+            }
+            match nuclei.last_mut() {
+                Some(nucleus) if *nucleus.last().unwrap() == i - 1 && weak(i) && nucleus.len() == 1 => nucleus.push(i),
+                _ => nuclei.push(vec![i]),
+            }
+        }
+        if nuclei.is_empty() {
+            return None;
+        }
+
+        // Written accents mark the stress, then a tilde; otherwise it falls
+        // on the second to last syllable of words ending in "a", "e", "o",
+        // "am", "em" or "ens" (plural "s" aside), and the last of others
+        let marked = |marks: &[Mark]| {
+            nuclei.iter().position(|n| n.iter().any(|&i| matches!(segs[i], Vowel { mark, .. } if marks.contains(&mark))))
+        };
+        let stem = text.strip_suffix('s').unwrap_or(&text);
+        let stressed = if let Some(n) = marked(&[Mark::Acute, Mark::Circumflex]).or_else(|| marked(&[Mark::Tilde])) {
+            Some(n)
+        } else if nuclei.len() == 1 {
+            (!UNSTRESSED.contains(&text.as_str())).then_some(0)
+        } else if stem.ends_with(['a', 'e', 'o']) || ["am", "em", "en"].iter().any(|e| stem.ends_with(e)) {
+            Some(nuclei.len() - 2)
+        } else {
+            Some(nuclei.len() - 1)
+        };
+
+        // How each vowel is read
+        let mut vowels: Vec<Option<String>> = vec![None; segs.len()];
+        let final_nucleus = nuclei.len() - 1;
+        for (n, nucleus) in nuclei.iter().enumerate() {
+            let peak = nucleus.iter().copied().find(|&i| !weak(i)).unwrap_or(nucleus[0]);
+            for &i in nucleus {
+                let Vowel { vowel, mark, nasal: is_nasal } = segs[i] else { continue };
+                let ends_word = !segs[i + 1..].iter().any(|s| matches!(s, Vowel { .. } | Consonant("l" | "ɾ" | "R")));
+                let ps = if i != peak {
+                    (if vowel == 'i' || vowel == 'e' { "j" } else { "w" }).to_string()
+                } else if is_nasal && ends_word && vowel == 'e' {
+                    "ẽj".to_string()
+                } else if is_nasal && ends_word && vowel == 'a' && stressed != Some(n) {
+                    "ɐ̃w".to_string()
+                } else if is_nasal || mark == Mark::Tilde {
+                    nasal(vowel).to_string()
+                } else if stressed == Some(n) {
+                    match (vowel, mark) {
+                        ('a', Mark::Circumflex) => "ɐ".to_string(),
+                        ('e', Mark::Acute) => "ɛ".to_string(),
+                        ('o', Mark::Acute) => "ɔ".to_string(),
+                        (v, _) => v.to_string(),
+                    }
+                } else if n == final_nucleus && ends_word {
+                    match vowel {
+                        'a' => "ɐ",
+                        'e' if self.reduce_vowels => "ɨ",
+                        'e' | 'i' => "i",
+                        'o' | 'u' => "u",
+                        _ => unreachable!(),
+                    }
+                    .to_string()
+                } else if self.reduce_vowels && mark == Mark::None {
+                    match vowel {
+                        'a' => "ɐ".to_string(),
+                        'e' => "ɨ".to_string(),
+                        'o' => "u".to_string(),
+                        v => v.to_string(),
+                    }
+                } else {
+                    vowel.to_string()
+                };
+                if stressed == Some(n) && i == peak {
+                    vowels[i] = Some(format!("ˈ{}", ps));
+                } else {
+                    vowels[i] = Some(ps);
+                }
+            }
+        }
+
+        let mut out = String::new();
+        for (i, seg) in segs.iter().enumerate() {
+            let next = segs.get(i + 1);
+            let next_vowel = vowels.get(i + 1).cloned().flatten();
+            let before_i = next_vowel.as_deref().is_some_and(|v| v.trim_start_matches('ˈ').starts_with(['i', 'ĩ', 'j']));
+            let voiced = matches!(next, Some(Consonant("b" | "d" | "ɡ" | "v" | "z" | "ʒ" | "m" | "n" | "l" | "ɾ" | "R" | "ʎ" | "ɲ")));
+            match *seg {
+                Vowel { .. } => out.push_str(vowels[i].as_deref().unwrap_or_default()),
+                Consonant(c) => out.push_str(match c {
+                    "S" => match (self.palatalize_s, voiced) {
+                        (true, true) => "ʒ",
+                        (true, false) => "ʃ",
+                        (false, true) => "z",
+                        (false, false) => "s",
+                    },
+                    "R" if self.brazilian_consonants => "x",
+                    "R" => "ʁ",
+                    "l" if self.brazilian_consonants && !matches!(next, Some(Vowel { .. })) => "w",
+                    "t" if self.brazilian_consonants && before_i => "ʧ",
+                    "d" if self.brazilian_consonants && before_i => "ʤ",
+                    c => c,
+                }),
+            }
+        }
+        Some(out)
+    }
+}
+
+const UNITS: [&str; 20] = [
+    "zero", "um", "dois", "três", "quatro", "cinco", "seis", "sete", "oito", "nove", "dez", "onze", "doze", "treze",
+    "catorze", "quinze", "dezasseis", "dezassete", "dezoito", "dezanove",
+];
+const TENS: [&str; 10] =
+    ["", "", "vinte", "trinta", "quarenta", "cinquenta", "sessenta", "setenta", "oitenta", "noventa"];
+const HUNDREDS: [&str; 10] = [
+    "", "cento", "duzentos", "trezentos", "quatrocentos", "quinhentos", "seiscentos", "setecentos", "oitocentos",
+    "novecentos",
+];
+
+const ORDINAL_UNITS: [&str; 10] =
+    ["", "primeiro", "segundo", "terceiro", "quarto", "quinto", "sexto", "sétimo", "oitavo", "nono"];
+const ORDINAL_TENS: [&str; 10] = [
+    "", "décimo", "vigésimo", "trigésimo", "quadragésimo", "quinquagésimo", "sexagésimo", "septuagésimo",
+    "octogésimo", "nonagésimo",
+];
+
+/// Portuguese numbers: Brazil's short scale ("bilhão" is 10⁹) or Portugal's
+/// long one ("mil milhões" is 10⁹, "bilião" 10¹²), and their spellings of
+/// the teens.
+pub struct PortugueseNumbers {
+    pub brazilian: bool,
+}
+
+impl PortugueseNumbers {
+    /// 1 to 99.
+    fn below_hundred(&self, n: u64) -> String {
+        let unit = |n: usize| match (self.brazilian, n) {
+            (true, 14) => "quatorze",
+            (true, 16) => "dezesseis",
+            (true, 17) => "dezessete",
+            (true, 19) => "dezenove",
+            _ => UNITS[n],
+        };
+        match n {
+            0..20 => unit(n as usize).to_string(),
+            _ if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+            _ => format!("{} e {}", TENS[n as usize / 10], unit(n as usize % 10)),
+        }
+    }
+
+    /// 1 to 999.
+    fn below_thousand(&self, n: u64) -> String {
+        if n == 100 {
+            return "cem".to_string();
+        }
+        let mut parts = Vec::new();
+        if n >= 100 {
+            parts.push(HUNDREDS[n as usize / 100].to_string());
+        }
+        if !n.is_multiple_of(100) {
+            parts.push(self.below_hundred(n % 100));
+        }
+        parts.join(" e ")
+    }
+
+    /// 1 to 999 999.
+    fn below_million(&self, n: u64) -> String {
+        let mut groups = Vec::new();
+        match n / 1000 {
+            0 => {}
+            1 => groups.push((1, "mil".to_string())),
+            thousands => groups.push((thousands, format!("{} mil", self.below_thousand(thousands)))),
+        }
+        if !n.is_multiple_of(1000) {
+            groups.push((n % 1000, self.below_thousand(n % 1000)));
+        }
+        join(groups)
+    }
+}
+
+/// Joins groups of thousands, with "e" before the last when it is below a
+/// hundred or a round number of hundreds ("mil e cem", "mil duzentos e um").
+fn join(groups: Vec<(u64, String)>) -> String {
+    let mut words = String::new();
+    let last = groups.len().saturating_sub(1);
+    for (i, (value, group)) in groups.into_iter().enumerate() {
+        if i > 0 {
+            words += if i == last && (value % 1000 < 100 || value.is_multiple_of(100)) { " e " } else { " " };
+        }
+        words += &group;
+    }
+    words
+}
+
+impl NumberVerbalizer for PortugueseNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("menos {}", words));
+        }
+        if n == 0 {
+            return Some(UNITS[0].to_string());
+        }
+        let scales: &[(u64, &str, &str)] = if self.brazilian {
+            &[
+                (1_000_000_000_000_000_000, "quintilhão", "quintilhões"),
+                (1_000_000_000_000_000, "quatrilhão", "quatrilhões"),
+                (1_000_000_000_000, "trilhão", "trilhões"),
+                (1_000_000_000, "bilhão", "bilhões"),
+                (1_000_000, "milhão", "milhões"),
+            ]
+        } else {
+            &[
+                (1_000_000_000_000_000_000, "trilião", "triliões"),
+                (1_000_000_000_000, "bilião", "biliões"),
+                (1_000_000, "milhão", "milhões"),
+            ]
+        };
+        let mut n = n as u64;
+        let mut groups = Vec::new();
+        for &(scale, one, many) in scales {
+            match n / scale {
+                0 => {}
+                1 => groups.push((1, format!("um {}", one))),
+                count => groups.push((count, format!("{} {}", self.below_million(count), many))),
+            }
+            n %= scale;
+        }
+        match n / 1000 {
+            0 => {}
+            1 => groups.push((1, "mil".to_string())),
+            thousands => groups.push((thousands, format!("{} mil", self.below_thousand(thousands)))),
+        }
+        if !n.is_multiple_of(1000) {
+            groups.push((n % 1000, self.below_thousand(n % 1000)));
+        }
+        Some(join(groups))
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        match n {
+            1..10 => Some(ORDINAL_UNITS[n as usize].to_string()),
+            10..100 if n % 10 == 0 => Some(ORDINAL_TENS[n as usize / 10].to_string()),
+            10..100 => Some(format!("{} {}", ORDINAL_TENS[n as usize / 10], ORDINAL_UNITS[n as usize % 10])),
+            100 => Some("centésimo".to_string()),
+            // Larger ordinals are usually read as cardinals
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "vírgula", " ")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("menos {}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize() {
+        // (word, Brazilian, European)
+        let cases = [
+            ("cidade", "sidˈaʤi", "sidˈadɨ"),
+            ("obrigado", "obɾiɡˈadu", "ubɾiɡˈadu"),
+            ("pão", "pˈɐ̃w", "pˈɐ̃w"),
+            ("mãe", "mˈɐ̃j", "mˈɐ̃j"),
+            ("coração", "koɾasˈɐ̃w", "kuɾɐsˈɐ̃w"),
+            ("rio", "xˈiu", "ʁˈiu"),
+            ("brasil", "bɾazˈiw", "bɾɐzˈil"),
+            ("português", "poɾtuɡˈes", "puɾtuɡˈeʃ"),
+            ("filho", "fˈiʎu", "fˈiʎu"),
+            ("casa", "kˈazɐ", "kˈazɐ"),
+            ("leite", "lˈejʧi", "lˈejtɨ"),
+            ("também", "tɐ̃bˈẽj", "tɐ̃bˈẽj"),
+            ("quatro", "kwˈatɾu", "kwˈatɾu"),
+            ("mesmo", "mˈezmu", "mˈeʒmu"),
+            ("falam", "fˈalɐ̃w", "fˈalɐ̃w"),
+            ("de", "ʤi", "dɨ"),
+        ];
+        for (word, br, pt) in cases {
+            assert_eq!(Portuguese::BRAZILIAN.phonemize(word).as_deref(), Some(br), "{}", word);
+            assert_eq!(Portuguese::EUROPEAN.phonemize(word).as_deref(), Some(pt), "{}", word);
+        }
+        assert_eq!(Portuguese::BRAZILIAN.phonemize("straße"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let (br, pt) = (PortugueseNumbers { brazilian: true }, PortugueseNumbers { brazilian: false });
+        let cases = [
+            (0, "zero"),
+            (21, "vinte e um"),
+            (100, "cem"),
+            (123, "cento e vinte e três"),
+            (1_001, "mil e um"),
+            (1_100, "mil e cem"),
+            (1_234, "mil duzentos e trinta e quatro"),
+            (2_000_300, "dois milhões e trezentos"),
+            (-7, "menos sete"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(br.cardinal(num).as_deref(), Some(words), "{}", num);
+            assert_eq!(pt.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(br.cardinal(16).as_deref(), Some("dezesseis"));
+        assert_eq!(pt.cardinal(16).as_deref(), Some("dezasseis"));
+        assert_eq!(br.cardinal(1_000_000_000).as_deref(), Some("um bilhão"));
+        assert_eq!(pt.cardinal(1_000_000_000).as_deref(), Some("mil milhões"));
+        assert_eq!(br.ordinal(21).as_deref(), Some("vigésimo primeiro"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let (br, pt) = (G2P::new(Language::PortugueseBR), G2P::new(Language::PortuguesePT));
+        let words = |g2p: &G2P| g2p.g2p("Eu tenho 3 gatos.").unwrap().0.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert_eq!(words(&br), ["ˈew", "tˈeɲu", "tɾˈes", "ɡˈatus"]);
+        assert_eq!(words(&pt), ["ˈew", "tˈeɲu", "tɾˈeʃ", "ɡˈatuʃ"]);
+
+        // Decimal commas and thousands dots
+        for (text, spoken) in [("3,14", "três vírgula um quatro"), ("1.000 pessoas", "mil pessoas")] {
+            assert_eq!(br.g2p(text).unwrap().0, br.g2p(spoken).unwrap().0, "{}", text);
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! Rule-based Spanish. The spelling is close enough to phonemic that every
+//! word can be read from its letters, in the notation espeak-ng's "es" voice
+//! gives Kokoro: Castilian "θ" for "c" and "z", fricative "β ð ɣ" between
+//! vowels, and the stress mark before the stressed vowel.
+
+use crate::lexicon::Lexicon;
+use crate::options::NegativeStyle;
+use super::{LanguageRules, NumberVerbalizer, read_decimal};
+
+pub struct Spanish;
+
+impl LanguageRules for Spanish {
+    fn apply_rules(&self, word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+        match word {
+            // Opening marks aren't read; the closing one sets the intonation
+            "¿" | "¡" => Some(String::new()),
+            _ => phonemize(word),
+        }
+    }
+}
+
+/// Function words read without stress.
+const UNSTRESSED: &[&str] = &[
+    "a", "al", "con", "de", "del", "e", "el", "en", "la", "las", "le", "les", "lo", "los", "me", "mi", "mis", "ni",
+    "nos", "o", "os", "por", "que", "se", "sin", "su", "sus", "te", "tu", "tus", "u", "un", "y",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Segment {
+    Vowel { vowel: char, accent: bool },
+    Consonant(&'static str),
+}
+
+impl Segment {
+    /// "i" and "u" without an accent, which glide into a neighbouring vowel.
+    fn is_weak(self) -> bool {
+        matches!(self, Segment::Vowel { vowel: 'i' | 'u', accent: false })
+    }
+}
+
+fn is_front(c: Option<&char>) -> bool {
+    matches!(c, Some('e' | 'i' | 'é' | 'í'))
+}
+
+fn is_vowel_letter(c: Option<&char>) -> bool {
+    matches!(c, Some('a' | 'e' | 'i' | 'o' | 'u' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'ü'))
+}
+
+/// Spells `word` as sounds, before stress and allophones.
+fn segments(word: &[char]) -> Option<Vec<Segment>> {
+    use Segment::*;
+    let mut segs = Vec::new();
+    let mut i = 0;
+    while i < word.len() {
+        let next = word.get(i + 1);
+        let mut skip = 1;
+        match word[i] {
+            c @ ('a' | 'e' | 'i' | 'o' | 'u') => segs.push(Vowel { vowel: c, accent: false }),
+            'ü' => segs.push(Vowel { vowel: 'u', accent: false }),
+            'á' => segs.push(Vowel { vowel: 'a', accent: true }),
+            'é' => segs.push(Vowel { vowel: 'e', accent: true }),
+            'í' => segs.push(Vowel { vowel: 'i', accent: true }),
+            'ó' => segs.push(Vowel { vowel: 'o', accent: true }),
+            'ú' => segs.push(Vowel { vowel: 'u', accent: true }),
+            'b' | 'v' => segs.push(Consonant("b")),
+            'c' if next == Some(&'h') => {
+                segs.push(Consonant("ʧ"));
+                skip = 2;
+            }
+            'c' if is_front(next) => segs.push(Consonant("θ")),
+            'c' | 'k' => segs.push(Consonant("k")),
+            'd' => segs.push(Consonant("d")),
+            'f' => segs.push(Consonant("f")),
+            'g' if is_front(next) => segs.push(Consonant("x")),
+            'g' => {
+                segs.push(Consonant("ɡ"));
+                // The "u" of "gue" and "gui" is silent
+                if next == Some(&'u') && is_front(word.get(i + 2)) {
+                    skip = 2;
+                }
+            }
+            'h' => {}
+            'j' => segs.push(Consonant("x")),
+            'l' if next == Some(&'l') => {
+                segs.push(Consonant("ʎ"));
+                skip = 2;
+            }
+            'l' => segs.push(Consonant("l")),
+            'm' => segs.push(Consonant("m")),
+            'n' => segs.push(Consonant("n")),
+            'ñ' => segs.push(Consonant("ɲ")),
+            'p' => segs.push(Consonant("p")),
+            'q' => {
+                segs.push(Consonant("k"));
+                if next == Some(&'u') {
+                    skip = 2;
+                }
+            }
+            'r' if next == Some(&'r') => {
+                segs.push(Consonant("r"));
+                skip = 2;
+            }
+            // Trilled at the start of a word and after "l", "n" and "s"
+            'r' if i == 0 || matches!(word[i - 1], 'l' | 'n' | 's') => segs.push(Consonant("r")),
+            'r' => segs.push(Consonant("ɾ")),
+            's' => segs.push(Consonant("s")),
+            't' => segs.push(Consonant("t")),
+            'w' => segs.push(Consonant("w")),
+            'x' => segs.extend([Consonant("k"), Consonant("s")]),
+            'y' if is_vowel_letter(next) => segs.push(Consonant("ʝ")),
+            'y' => segs.push(Vowel { vowel: 'i', accent: false }),
+            'z' => segs.push(Consonant("θ")),
+            _ => return None,
+        }
+        i += skip;
+    }
+    Some(segs)
+}
+
+/// Phonemes for a Spanish word, or `None` if it has letters Spanish doesn't
+/// use.
+pub fn phonemize(word: &str) -> Option<String> {
+    use Segment::*;
+    let lower: Vec<char> = word.to_lowercase().chars().collect();
+    let mut segs = segments(&lower)?;
+
+    // Adjacent vowels share a syllable unless both are strong
+    let mut nuclei: Vec<Vec<usize>> = Vec::new();
+    for (i, seg) in segs.iter().enumerate() {
+        if !matches!(seg, Vowel { .. }) {
+            continue;
+        }
+        match nuclei.last_mut() {
+            Some(nucleus) if *nucleus.last().unwrap() == i - 1 && (seg.is_weak() || segs[i - 1].is_weak()) => {
+                nucleus.push(i)
+            }
+            _ => nuclei.push(vec![i]),
+        }
+    }
+    if nuclei.is_empty() {
+        return None;
+    }
+
+    // An accent marks the stress, otherwise it falls on the second to last
+    // syllable of words ending in a vowel, "n" or "s", and the last of others
+    let accented = nuclei.iter().position(|n| n.iter().any(|&i| matches!(segs[i], Vowel { accent: true, .. })));
+    let stressed = if let Some(n) = accented {
+        Some(n)
+    } else if nuclei.len() == 1 {
+        (!UNSTRESSED.contains(&lower.iter().collect::<String>().as_str())).then_some(0)
+    } else if matches!(lower.last(), Some('a' | 'e' | 'i' | 'o' | 'u' | 'n' | 's')) {
+        Some(nuclei.len() - 2)
+    } else {
+        Some(nuclei.len() - 1)
+    };
+
+    // The peak of a syllable is its strong or accented vowel, or the second
+    // of two weak ones; the others glide
+    let mut peaks = Vec::new();
+    for nucleus in &nuclei {
+        let peak = nucleus.iter().copied().find(|&i| !segs[i].is_weak()).unwrap_or(*nucleus.last().unwrap());
+        for &i in nucleus.iter().filter(|&&i| i < peak) {
+            if let Vowel { vowel, .. } = segs[i] {
+                segs[i] = Consonant(if vowel == 'i' { "j" } else { "w" });
+            }
+        }
+        peaks.push(peak);
+    }
+
+    let mut out = String::new();
+    for i in 0..segs.len() {
+        match segs[i] {
+            Vowel { vowel, .. } => {
+                if stressed.is_some_and(|n| peaks[n] == i) {
+                    out.push('ˈ');
+                }
+                out.push(vowel);
+            }
+            Consonant(c) => {
+                let next = segs.get(i + 1);
+                let prev = i.checked_sub(1).map(|j| segs[j]);
+                let after_nasal = matches!(prev, Some(Consonant("m" | "n" | "ɲ")));
+                let c = match c {
+                    // Nasals take the place of the consonant after them
+                    "n" if matches!(next, Some(Consonant("b" | "p" | "m"))) => "m",
+                    "n" if matches!(next, Some(Consonant("k" | "ɡ" | "x"))) => "ŋ",
+                    // Voiced stops soften except after a pause or a nasal, and
+                    // "d" after "l"
+                    "b" if prev.is_some() && !after_nasal => "β",
+                    "d" if prev.is_some() && !after_nasal && prev != Some(Consonant("l")) => "ð",
+                    "ɡ" if prev.is_some() && !after_nasal => "ɣ",
+                    c => c,
+                };
+                out.push_str(c);
+            }
+        }
+    }
+    Some(out)
+}
+
+const UNITS: [&str; 30] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez", "once", "doce",
+    "trece", "catorce", "quince", "dieciséis", "diecisiete", "dieciocho", "diecinueve", "veinte", "veintiuno",
+    "veintidós", "veintitrés", "veinticuatro", "veinticinco", "veintiséis", "veintisiete", "veintiocho",
+    "veintinueve",
+];
+const TENS: [&str; 10] = ["", "", "", "treinta", "cuarenta", "cincuenta", "sesenta", "setenta", "ochenta", "noventa"];
+const HUNDREDS: [&str; 10] = [
+    "", "ciento", "doscientos", "trescientos", "cuatrocientos", "quinientos", "seiscientos", "setecientos",
+    "ochocientos", "novecientos",
+];
+
+const ORDINAL_UNITS: [&str; 10] =
+    ["", "primero", "segundo", "tercero", "cuarto", "quinto", "sexto", "séptimo", "octavo", "noveno"];
+const ORDINAL_TENS: [&str; 10] = [
+    "", "décimo", "vigésimo", "trigésimo", "cuadragésimo", "quincuagésimo", "sexagésimo", "septuagésimo",
+    "octogésimo", "nonagésimo",
+];
+
+/// 1 to 999, with "uno" cut to "un" when `apocope` (before "mil", "millón").
+fn below_thousand(n: u64, apocope: bool) -> String {
+    let words = match n {
+        100 => "cien".to_string(),
+        0..30 => UNITS[n as usize].to_string(),
+        30..100 if n.is_multiple_of(10) => TENS[n as usize / 10].to_string(),
+        30..100 => format!("{} y {}", TENS[n as usize / 10], UNITS[n as usize % 10]),
+        _ if n.is_multiple_of(100) => HUNDREDS[n as usize / 100].to_string(),
+        _ => format!("{} {}", HUNDREDS[n as usize / 100], below_thousand(n % 100, false)),
+    };
+    if !apocope {
+        words
+    } else if let Some(stem) = words.strip_suffix("veintiuno") {
+        stem.to_string() + "veintiún"
+    } else if let Some(stem) = words.strip_suffix("uno") {
+        stem.to_string() + "un"
+    } else {
+        words
+    }
+}
+
+/// 1 to 999 999, apocopated as `below_thousand`.
+fn below_million(n: u64, apocope: bool) -> String {
+    let mut words = Vec::new();
+    match n / 1000 {
+        0 => {}
+        1 => words.push("mil".to_string()),
+        thousands => words.push(format!("{} mil", below_thousand(thousands, true))),
+    }
+    if !n.is_multiple_of(1000) {
+        words.push(below_thousand(n % 1000, apocope));
+    }
+    words.join(" ")
+}
+
+/// Spanish numbers, with the long scale ("billón" is 10¹²).
+pub struct SpanishNumbers;
+
+impl NumberVerbalizer for SpanishNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n < 0 {
+            return self.cardinal(n.checked_neg()?).map(|words| format!("menos {}", words));
+        }
+        if n == 0 {
+            return Some(UNITS[0].to_string());
+        }
+        let mut n = n as u64;
+        let mut words = Vec::new();
+        for (scale, one, many) in [
+            (1_000_000_000_000_000_000, "trillón", "trillones"),
+            (1_000_000_000_000, "billón", "billones"),
+            (1_000_000, "millón", "millones"),
+        ] {
+            match n / scale {
+                0 => {}
+                1 => words.push(format!("un {}", one)),
+                count => words.push(format!("{} {}", below_million(count, true), many)),
+            }
+            n %= scale;
+        }
+        if n > 0 {
+            words.push(below_million(n, false));
+        }
+        Some(words.join(" "))
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        match n {
+            1..10 => Some(ORDINAL_UNITS[n as usize].to_string()),
+            11 => Some("undécimo".to_string()),
+            12 => Some("duodécimo".to_string()),
+            13..20 => Some(format!("decimo{}", ORDINAL_UNITS[n as usize - 10]).replace("oo", "o")),
+            10..100 if n % 10 == 0 => Some(ORDINAL_TENS[n as usize / 10].to_string()),
+            10..100 => Some(format!("{} {}", ORDINAL_TENS[n as usize / 10], ORDINAL_UNITS[n as usize % 10])),
+            100 => Some("centésimo".to_string()),
+            // Larger ordinals are usually read as cardinals
+            _ => self.cardinal(n),
+        }
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "coma", " ")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("menos {}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize() {
+        let cases = [
+            ("hola", "ˈola"),
+            ("mundo", "mˈundo"),
+            ("niño", "nˈiɲo"),
+            ("ciudad", "θjuðˈað"),
+            ("canción", "kanθjˈon"),
+            ("guerra", "ɡˈera"),
+            ("lluvia", "ʎˈuβja"),
+            ("tengo", "tˈeŋɡo"),
+            ("pingüino", "piŋɡwˈino"),
+            ("día", "dˈia"),
+            ("hay", "ˈai"),
+            ("yo", "ʝˈo"),
+            ("queso", "kˈeso"),
+            ("jirafa", "xiɾˈafa"),
+            ("honrado", "onrˈaðo"),
+            ("de", "de"),
+        ];
+        for (word, phonemes) in cases {
+            assert_eq!(phonemize(word).as_deref(), Some(phonemes), "{}", word);
+        }
+        assert_eq!(phonemize("Hola").as_deref(), Some("ˈola"));
+        assert_eq!(phonemize("straße"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let n = SpanishNumbers;
+        let cases = [
+            (0, "cero"),
+            (16, "dieciséis"),
+            (21, "veintiuno"),
+            (45, "cuarenta y cinco"),
+            (100, "cien"),
+            (101, "ciento uno"),
+            (555, "quinientos cincuenta y cinco"),
+            (1000, "mil"),
+            (21_000, "veintiún mil"),
+            (31_001, "treinta y un mil uno"),
+            (1_999, "mil novecientos noventa y nueve"),
+            (1_000_000, "un millón"),
+            (21_500_000, "veintiún millones quinientos mil"),
+            (-3, "menos tres"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(n.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(n.ordinal(3).as_deref(), Some("tercero"));
+        assert_eq!(n.ordinal(17).as_deref(), Some("decimoséptimo"));
+        assert_eq!(n.decimal("3", "14").as_deref(), Some("tres coma uno cuatro"));
+        assert_eq!(n.ordinal(18).as_deref(), Some("decimoctavo"));
+        assert_eq!(n.ordinal(42).as_deref(), Some("cuadragésimo segundo"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::Spanish);
+        let (_, tokens) = g2p.g2p("¿Quién tiene 21 años?").unwrap();
+        let phonemes: Vec<&str> = tokens.iter().filter_map(|t| t.phonemes.as_deref()).filter(|p| !p.trim().is_empty()).collect();
+        assert_eq!(phonemes, ["kjˈen", "tjˈene", "beintjˈuno", "ˈaɲos"]);
+
+        // Decimal commas and thousands dots
+        for (text, spoken) in [("3,14", "tres coma uno cuatro"), ("1.000 personas", "mil personas")] {
+            assert_eq!(g2p.g2p(text).unwrap().0, g2p.g2p(spoken).unwrap().0, "{}", text);
+        }
+    }
+}
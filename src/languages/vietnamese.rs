@@ -0,0 +1,306 @@
+//! Vietnamese read by rule, in the Northern (Hanoi) standard. Each word is a
+//! syllable: an initial, an optional "w" glide, a nucleus and a coda, with
+//! its tone taken off the vowel and written after it as a digit, numbered
+//! as in viphoneme: 1 ngang, 2 huyền, 3 ngã, 4 hỏi, 5 sắc, 6 nặng.
+
+use crate::lexicon::Lexicon;
+use crate::options::NegativeStyle;
+use unicode_normalization::UnicodeNormalization;
+use super::{LanguageRules, NumberVerbalizer, read_decimal};
+
+pub struct Vietnamese;
+
+impl LanguageRules for Vietnamese {
+    fn apply_rules(&self, word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+        phonemize(word)
+    }
+}
+
+/// Initials, longest first.
+const INITIALS: [(&str, &str); 27] = [
+    ("ngh", "ŋ"),
+    ("ng", "ŋ"),
+    ("nh", "ɲ"),
+    ("ch", "c"),
+    ("tr", "c"),
+    ("th", "tʰ"),
+    ("kh", "x"),
+    ("gh", "ɣ"),
+    ("gi", "z"),
+    ("ph", "f"),
+    ("qu", "kw"),
+    ("b", "ɓ"),
+    ("c", "k"),
+    ("d", "z"),
+    ("đ", "ɗ"),
+    ("g", "ɣ"),
+    ("h", "h"),
+    ("k", "k"),
+    ("l", "l"),
+    ("m", "m"),
+    ("n", "n"),
+    ("p", "p"),
+    ("r", "z"),
+    ("s", "s"),
+    ("t", "t"),
+    ("v", "v"),
+    ("x", "s"),
+];
+
+const CODAS: [(&str, &str); 8] = [
+    ("ng", "ŋ"),
+    ("nh", "ɲ"),
+    ("ch", "c"),
+    ("c", "k"),
+    ("m", "m"),
+    ("n", "n"),
+    ("p", "p"),
+    ("t", "t"),
+];
+
+fn nucleus(v: &str) -> Option<&'static str> {
+    Some(match v {
+        "a" => "aː",
+        "ă" => "a",
+        "â" => "ə",
+        "e" => "ɛ",
+        "ê" => "e",
+        "i" | "y" => "i",
+        "o" => "ɔ",
+        "ô" => "o",
+        "ơ" => "əː",
+        "u" => "u",
+        "ư" => "ɨ",
+        "ia" | "iê" | "ya" | "yê" => "iə",
+        "ua" | "uô" => "uə",
+        "ưa" | "ươ" => "ɨə",
+        "oo" => "ɔː",
+        "ôô" => "oː",
+        _ => return None,
+    })
+}
+
+fn is_vowel(c: char) -> bool {
+    "aăâeêioôơuưy".contains(c)
+}
+
+/// Takes the tone mark off a syllable, leaving the other diacritics.
+fn split_tone(word: &str) -> (String, u8) {
+    let mut tone = 1;
+    let bare: String = word
+        .nfd()
+        .filter(|&c| {
+            let mark = match c {
+                '\u{0300}' => 2,
+                '\u{0303}' => 3,
+                '\u{0309}' => 4,
+                '\u{0301}' => 5,
+                '\u{0323}' => 6,
+                _ => return true,
+            };
+            tone = mark;
+            false
+        })
+        .nfc()
+        .collect();
+    (bare, tone)
+}
+
+/// Phonemes for one syllable, `None` if it isn't spelled as Vietnamese.
+pub fn phonemize(word: &str) -> Option<String> {
+    let (bare, tone) = split_tone(&word.to_lowercase());
+    let mut rest = bare.as_str();
+
+    let (mut onset, mut initial) = ("", "");
+    if let Some(&(spelling, ps)) = INITIALS.iter().find(|(s, _)| rest.starts_with(s)) {
+        (onset, initial) = (spelling, ps);
+    }
+    rest = &rest[onset.len()..];
+    // "gi" keeps its "i" when nothing else spells the vowel ("gì", "giếng")
+    if onset == "gi" && !rest.starts_with(|c: char| is_vowel(c) && c != 'ê') {
+        rest = &bare[1..];
+    }
+    if !rest.starts_with(is_vowel) {
+        return None;
+    }
+
+    // "o" before "a", "ă", "e" and "u" before "â", "ê", "y", "ơ" are a glide
+    let mut chars = rest.chars();
+    let glide = match (chars.next(), chars.next()) {
+        (Some('o'), Some('a' | 'ă' | 'e')) | (Some('u'), Some('â' | 'ê' | 'y' | 'ơ')) => {
+            rest = &rest[1..];
+            "w"
+        }
+        _ => "",
+    };
+
+    let (mut spelled, mut coda) = (rest, "");
+    if let Some(&(s, ps)) = CODAS.iter().find(|(s, _)| rest.ends_with(s)) {
+        (spelled, coda) = (&rest[..rest.len() - s.len()], ps);
+    } else if let Some(last) = rest.chars().last()
+        && rest.len() > last.len_utf8()
+    {
+        let offglide = match last {
+            'i' | 'y' => Some("j"),
+            'o' | 'u' => Some("w"),
+            _ => None,
+        };
+        if let Some(ps) = offglide
+            && nucleus(&rest[..rest.len() - last.len_utf8()]).is_some()
+        {
+            (spelled, coda) = (&rest[..rest.len() - last.len_utf8()], ps);
+        }
+    }
+    // "a" is short before "y", "u", "nh" and "ch"
+    let vowel = match (spelled, rest.chars().last()) {
+        ("a", Some('y' | 'u' | 'h')) => "a",
+        _ => nucleus(spelled)?,
+    };
+    Some(format!("{}{}{}{}{}", initial, glide, vowel, coda, tone))
+}
+
+const DIGITS: [&str; 10] = ["không", "một", "hai", "ba", "bốn", "năm", "sáu", "bảy", "tám", "chín"];
+
+/// Vietnamese numbers with the Northern "nghìn" and "linh".
+pub struct VietnameseNumbers;
+
+impl VietnameseNumbers {
+    fn below_hundred(n: u64) -> String {
+        if n < 10 {
+            return DIGITS[n as usize].to_string();
+        }
+        let (tens, units) = (n / 10, n % 10);
+        let mut words = if tens == 1 { "mười".to_string() } else { format!("{} mươi", DIGITS[tens as usize]) };
+        let unit = match units {
+            0 => return words,
+            1 if tens > 1 => "mốt",
+            4 if tens > 1 => "tư",
+            5 => "lăm",
+            _ => DIGITS[units as usize],
+        };
+        words.push(' ');
+        words += unit;
+        words
+    }
+
+    /// Below a thousand; `full` reads the hundreds even when there are none
+    /// ("không trăm"), as inside a larger number.
+    fn below_thousand(n: u64, full: bool) -> String {
+        let (hundreds, rest) = (n / 100, n % 100);
+        let mut words = Vec::new();
+        if hundreds > 0 || full {
+            words.push(format!("{} trăm", DIGITS[hundreds as usize]));
+        }
+        if rest > 0 {
+            if rest < 10 && !words.is_empty() {
+                words.push("linh".to_string());
+            }
+            words.push(Self::below_hundred(rest));
+        }
+        words.join(" ")
+    }
+
+    fn read(n: u64, full: bool) -> String {
+        let Some(&(scale, name)) = [(1_000_000_000, "tỷ"), (1_000_000, "triệu"), (1_000, "nghìn")]
+            .iter()
+            .find(|(scale, _)| n >= *scale)
+        else {
+            return Self::below_thousand(n, full);
+        };
+        let head = format!("{} {}", Self::read(n / scale, false), name);
+        match n % scale {
+            0 => head,
+            rest => format!("{} {}", head, Self::read(rest, true)),
+        }
+    }
+}
+
+impl NumberVerbalizer for VietnameseNumbers {
+    fn cardinal(&self, n: i64) -> Option<String> {
+        if n == 0 {
+            return Some(DIGITS[0].to_string());
+        }
+        if n < 0 {
+            return Some(format!("âm {}", Self::read(n.unsigned_abs(), false)));
+        }
+        Some(Self::read(n as u64, false))
+    }
+
+    fn ordinal(&self, n: i64) -> Option<String> {
+        match n {
+            1 => Some("thứ nhất".to_string()),
+            4 => Some("thứ tư".to_string()),
+            _ => self.cardinal(n).map(|words| format!("thứ {}", words)),
+        }
+    }
+
+    fn decimal(&self, whole: &str, fraction: &str) -> Option<String> {
+        read_decimal(self, whole, fraction, "phẩy", " ")
+    }
+
+    fn negative(&self, words: &str, _style: NegativeStyle) -> Option<String> {
+        Some(format!("âm {}", words))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::g2p::G2P;
+    use crate::language::Language;
+
+    #[test]
+    fn test_phonemize() {
+        let cases = [
+            ("Việt", "viət6"),
+            ("Nam", "naːm1"),
+            ("chào", "caːw2"),
+            ("người", "ŋɨəj2"),
+            ("hoa", "hwaː1"),
+            ("quốc", "kwok5"),
+            ("gì", "zi2"),
+            ("giường", "zɨəŋ2"),
+            ("đẹp", "ɗɛp6"),
+            ("tay", "taj1"),
+            ("anh", "aɲ1"),
+            ("cảm", "kaːm4"),
+            ("ơn", "əːn1"),
+            ("trường", "cɨəŋ2"),
+            ("khuya", "xwiə1"),
+            ("yêu", "iəw1"),
+            ("nghĩ", "ŋi3"),
+        ];
+        for (word, ps) in cases {
+            assert_eq!(phonemize(word).as_deref(), Some(ps), "{}", word);
+        }
+        assert_eq!(phonemize("xyz"), None);
+        assert_eq!(phonemize("strong"), None);
+    }
+
+    #[test]
+    fn test_numbers() {
+        let cases = [
+            (0, "không"),
+            (15, "mười lăm"),
+            (21, "hai mươi mốt"),
+            (24, "hai mươi tư"),
+            (105, "một trăm linh năm"),
+            (1_005, "một nghìn không trăm linh năm"),
+            (2_024, "hai nghìn không trăm hai mươi tư"),
+            (1_500_000, "một triệu năm trăm nghìn"),
+            (-3, "âm ba"),
+        ];
+        for (num, words) in cases {
+            assert_eq!(VietnameseNumbers.cardinal(num).as_deref(), Some(words), "{}", num);
+        }
+        assert_eq!(VietnameseNumbers.ordinal(1).as_deref(), Some("thứ nhất"));
+        assert_eq!(VietnameseNumbers.ordinal(2).as_deref(), Some("thứ hai"));
+    }
+
+    #[test]
+    fn test_g2p() {
+        let g2p = G2P::new(Language::Vietnamese);
+        let (ps, _) = g2p.g2p("xin chào 5 người").unwrap();
+        assert_eq!(ps, "sin1 caːw2 nam1 ŋɨəj2");
+    }
+}
@@ -1,75 +1,475 @@
-use crate::data;
 use crate::language::Language;
+use crate::languages;
+use crate::normalize;
+use crate::phoneme::{PhonemeError, PhonemeString};
+use crate::special_cases::{SpecialCases, Then};
+use crate::stress::Stress;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
 
 // Constants matching Python implementation
-const LEXICON_ORDS: &[u32] = &[
-    39, 45, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75, 76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86,
-    87, 88, 89, 90, 91, 97, 98, 99, 100, 101, 102, 103, 104, 105, 106, 107, 108, 109, 110, 111,
-    112, 113, 114, 115, 116, 117, 118, 119, 120, 121, 122,
-];
 const US_TAUS: &str = "AIOWYiuæɑəɛɪɹʊʌ";
 
-fn get_add_symbols() -> HashMap<&'static str, &'static str> {
-    let mut m = HashMap::new();
-    m.insert(".", "dot");
-    m.insert("/", "slash");
-    m
+/// Python's LEXICON_ORDS (apostrophe, hyphen and ASCII letters) less the
+/// apostrophe and hyphen, which `is_known` rules out as not alphabetic.
+fn is_lexicon_word(word: &str) -> bool {
+    !word.is_empty() && word.bytes().all(|b| b.is_ascii_alphabetic())
 }
 
-fn get_symbols() -> HashMap<&'static str, &'static str> {
-    let mut m = HashMap::new();
-    m.insert("%", "percent");
-    m.insert("&", "and");
-    m.insert("+", "plus");
-    m.insert("@", "at");
-    m
+/// The word read for a symbol tagged ADD (part of an address).
+fn add_symbol(word: &str) -> Option<&'static str> {
+    Some(match word {
+        "." => "dot",
+        "/" => "slash",
+        _ => return None,
+    })
+}
+
+/// The word read for a symbol standing alone.
+fn symbol(word: &str) -> Option<&'static str> {
+    Some(match word {
+        "%" => "percent",
+        "&" => "and",
+        "+" => "plus",
+        "@" => "at",
+        _ => return None,
+    })
+}
+
+/// `word` lowercased, borrowed when it already is.
+fn lowercase(word: &str) -> Cow<'_, str> {
+    if word.is_ascii() {
+        if word.bytes().any(|b| b.is_ascii_uppercase()) {
+            return Cow::Owned(word.to_ascii_lowercase());
+        }
+        return Cow::Borrowed(word);
+    }
+    let lower = word.to_lowercase();
+    if lower == word { Cow::Borrowed(word) } else { Cow::Owned(lower) }
+}
+
+/// `word == word.to_uppercase()`, without allocating for ASCII words.
+fn is_uppercase(word: &str) -> bool {
+    if word.is_ascii() {
+        return !word.bytes().any(|b| b.is_ascii_lowercase());
+    }
+    word == word.to_uppercase()
+}
+
+/// `word == word.to_lowercase()`, without allocating for ASCII words.
+fn is_lowercase(word: &str) -> bool {
+    if word.is_ascii() {
+        return !word.bytes().any(|b| b.is_ascii_uppercase());
+    }
+    word == word.to_lowercase()
+}
+
+/// `word` without its last `n` chars, as Python's `word[:-n]`.
+fn drop_last(word: &str, n: usize) -> &str {
+    word.char_indices().rev().nth(n - 1).map_or("", |(i, _)| &word[..i])
+}
+
+#[derive(Error, Debug)]
+pub enum LexiconError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("invalid dictionary json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid phonemes for '{word}': {error}")]
+    InvalidPhonemes { word: String, error: PhonemeError },
+}
+
+/// How much of a corpus the lexicon covers, from `Lexicon::coverage`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CoverageReport {
+    /// Words counted (tokens with at least one letter).
+    pub tokens: usize,
+    /// Found in the gold or user dictionary.
+    pub gold: usize,
+    /// Found only in the silver dictionary.
+    pub silver: usize,
+    /// Found by stripping -s, -ed or -ing from a known word.
+    pub stemmed: usize,
+    pub missing: usize,
+    /// Lowercased missing words with their counts, most frequent first.
+    pub missing_words: Vec<(String, usize)>,
+}
+
+impl CoverageReport {
+    fn fraction(&self, count: usize) -> f64 {
+        if self.tokens == 0 { 0.0 } else { count as f64 / self.tokens as f64 }
+    }
+
+    pub fn gold_fraction(&self) -> f64 {
+        self.fraction(self.gold)
+    }
+
+    pub fn silver_fraction(&self) -> f64 {
+        self.fraction(self.silver)
+    }
+
+    pub fn stemmed_fraction(&self) -> f64 {
+        self.fraction(self.stemmed)
+    }
+
+    pub fn missing_fraction(&self) -> f64 {
+        self.fraction(self.missing)
+    }
+
+    /// The `n` most frequent missing words.
+    pub fn top_missing(&self, n: usize) -> &[(String, usize)] {
+        &self.missing_words[..n.min(self.missing_words.len())]
+    }
+}
+
+/// One of the lexicon's dictionaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    User,
+    Gold,
+    Silver,
+}
+
+/// An entry that differs between two lexicons, from `Lexicon::diff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    pub tier: Tier,
+    pub word: String,
+    /// The entry in the lexicon diffed against; `None` if it was added.
+    pub old: Option<PhonemeEntry>,
+    /// The entry in the lexicon diffed; `None` if it was removed.
+    pub new: Option<PhonemeEntry>,
+}
+
+/// What changed from one lexicon to another, each list sorted by tier and
+/// word.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LexiconDiff {
+    pub added: Vec<DiffEntry>,
+    pub removed: Vec<DiffEntry>,
+    pub changed: Vec<DiffEntry>,
+}
+
+impl LexiconDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Quality of a pronunciation, ordered from worst to best.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rating {
+    /// Replaced by the unknown marker.
+    Unknown,
+    /// Guessed by the OOV fallback or spelled out.
+    Fallback,
+    /// Read as the dictionary word one edit away, taken for a typo (see
+    /// `G2POptions::correct_typos`).
+    Corrected,
+    /// Derived from known words: by an -s, -ed or -ing suffix, or as a
+    /// compound of two.
+    Stemmed,
+    Silver,
+    Gold,
+}
+
+/// Which part of the lexicon a pronunciation came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    User,
+    Gold,
+    Silver,
+    /// Rules for function words ("a", "the", "to"), from
+    /// `Lexicon::special_cases` or symbols and initialisms.
+    SpecialCase,
+    /// Read letter by letter, as for initialisms.
+    Letters,
+}
+
+/// A lexicon result: the phonemes with how much to trust them and where they
+/// came from. Stemmed words keep the source of their stem. The phonemes are
+/// borrowed from the lexicon unless stress or stemming changed them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pronunciation<'a> {
+    pub phonemes: Cow<'a, str>,
+    pub rating: Rating,
+    pub source: Source,
+}
+
+impl<'a> Pronunciation<'a> {
+    fn special(phonemes: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            phonemes: phonemes.into(),
+            rating: Rating::Gold,
+            source: Source::SpecialCase,
+        }
+    }
+
+    fn stemmed(self, phonemes: String) -> Self {
+        Self {
+            phonemes: Cow::Owned(phonemes),
+            rating: Rating::Stemmed,
+            ..self
+        }
+    }
+
+    /// The pronunciation with its phonemes copied out of the lexicon.
+    pub fn into_owned(self) -> Pronunciation<'static> {
+        Pronunciation {
+            phonemes: Cow::Owned(self.phonemes.into_owned()),
+            rating: self.rating,
+            source: self.source,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct TokenContext {
     pub future_vowel: Option<bool>,
     pub future_to: bool,
+    /// The token's tag may well be wrong, so heteronyms take their DEFAULT
+    /// reading rather than the tag's.
+    pub uncertain_tag: bool,
+    /// The next token's text, lowercased, for entries read differently
+    /// before some words.
+    pub next_word: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// A dictionary entry: one reading, or readings by POS tag. Tagged keys are
+/// Penn tags ("VBD"), parent tags ("VERB", see `Lexicon::get_parent_tag`),
+/// "DEFAULT", "None" (read when the next word is unknown), or several tags
+/// joined with "|" ("VBD|VBN") that share a reading.
+///
+/// Keys starting "next:" read the word by what follows it, and win over its
+/// tag: "next:to" before the word "to", "next:VOWEL" and "next:CONSONANT"
+/// before a word starting with one. With them, a dictionary entry can say
+/// what `Lexicon::special_cases` says for "the" and "used".
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum PhonemeEntry {
     Simple(String),
     Tagged(HashMap<String, Option<String>>),
 }
 
+impl PhonemeEntry {
+    /// The reading `map` gives `tag`: under a key that is the tag alone,
+    /// or failing that the alphabetically first "|" key listing it.
+    fn for_tag<'a>(map: &'a HashMap<String, Option<String>>, tag: &str) -> Option<&'a String> {
+        map.get(Self::claimant(map, tag)?)?.as_ref()
+    }
+
+    /// "|" keys of a tagged entry that no tag can reach, every tag they list
+    /// having its own key or an alphabetically earlier "|" key listing it.
+    pub fn unreachable_keys(&self) -> Vec<&str> {
+        let PhonemeEntry::Tagged(map) = self else {
+            return Vec::new();
+        };
+        let mut keys: Vec<&str> = map
+            .keys()
+            .map(String::as_str)
+            .filter(|key| key.contains('|') && key.split('|').all(|tag| Self::claimant(map, tag) != Some(*key)))
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// The reading for what follows the word, from its "next:" keys.
+    fn for_next<'a>(map: &'a HashMap<String, Option<String>>, ctx: &TokenContext) -> Option<&'a String> {
+        let by_word = ctx.next_word.as_ref().and_then(|w| map.get(&format!("next:{}", w)));
+        let by_sound = match ctx.future_vowel {
+            Some(true) => map.get("next:VOWEL"),
+            Some(false) => map.get("next:CONSONANT"),
+            None => None,
+        };
+        by_word.or(by_sound)?.as_ref()
+    }
+
+    /// The key `tag` resolves to.
+    fn claimant<'a>(map: &'a HashMap<String, Option<String>>, tag: &str) -> Option<&'a str> {
+        if let Some((key, _)) = map.get_key_value(tag) {
+            return Some(key);
+        }
+        map.keys().filter(|key| key.contains('|') && key.split('|').any(|t| t == tag)).min().map(String::as_str)
+    }
+}
+
 pub struct Lexicon {
     pub lang: Language,
-    pub cap_stresses: (f64, f64),
+    /// Stress for capitalized and all-caps words the link syntax doesn't set.
+    pub cap_stresses: (Stress, Stress),
     pub golds: HashMap<String, PhonemeEntry>,
     pub silvers: HashMap<String, PhonemeEntry>,
+    /// User-supplied entries, consulted before golds.
+    pub users: HashMap<String, PhonemeEntry>,
+    /// Rules for words read by context ("a", "the", "used"), tried before
+    /// the dictionaries. English lexicons start with the bundled table,
+    /// others with none.
+    pub special_cases: SpecialCases,
+    /// Keys of each dictionary that are casing variants `grow_dictionary`
+    /// derived rather than entries as loaded.
+    grown: HashMap<Tier, HashSet<String>>,
 }
 
 impl Lexicon {
+    /// The lexicon of `lang`'s pack (see `languages::pack`).
+    ///
+    /// Panics if `lang` is a `Language::Other` that was never registered.
     pub fn new(lang: Language) -> Self {
-        let (golds_raw, silvers_raw) = match lang {
-            Language::EnglishGB => (data::load_gb_gold(), data::load_gb_silver()),
-            Language::EnglishUS => (data::load_us_gold(), data::load_us_silver()),
-            // Language::Italian => (data::load_it_gold(), data::load_it_silver()),
-        };
+        let pack = languages::pack(&lang).unwrap_or_else(|| panic!("no language pack registered for {:?}", lang));
+        Self::from_entries(lang, pack.golds, pack.silvers)
+    }
 
-        let golds = Lexicon::grow_dictionary(golds_raw);
-        let silvers = Lexicon::grow_dictionary(silvers_raw);
+    pub fn from_entries(
+        lang: Language,
+        golds: HashMap<String, PhonemeEntry>,
+        silvers: HashMap<String, PhonemeEntry>,
+    ) -> Self {
+        let (golds, grown_golds) = Lexicon::grow_dictionary(golds);
+        let (silvers, grown_silvers) = Lexicon::grow_dictionary(silvers);
 
         Self {
+            special_cases: if lang.is_english() { SpecialCases::english() } else { SpecialCases::default() },
             lang,
-            cap_stresses: (0.5, 2.0),
+            cap_stresses: (Stress::Weight(0.5), Stress::Promote),
             golds,
             silvers,
+            users: HashMap::new(),
+            grown: HashMap::from([(Tier::Gold, grown_golds), (Tier::Silver, grown_silvers)]),
+        }
+    }
+
+    /// Adds a single entry to the user layer, along with the casing variants
+    /// `grow_dictionary` would derive for it.
+    pub fn add_user_entry(&mut self, word: &str, entry: PhonemeEntry) {
+        let mut d = HashMap::new();
+        d.insert(word.to_string(), entry);
+        self.extend_users(d);
+    }
+
+    /// Adds `d` and its casing variants to the user layer. Entries of `d`
+    /// replace any with the same key; variants replace only other variants.
+    fn extend_users(&mut self, d: HashMap<String, PhonemeEntry>) {
+        let grown = self.grown.entry(Tier::User).or_default();
+        for word in d.keys() {
+            grown.remove(word);
+        }
+        let (d, variants) = Lexicon::grow_dictionary(d);
+        for (word, entry) in d {
+            if !variants.contains(&word) {
+                self.users.insert(word, entry);
+            } else if !self.users.contains_key(&word) || grown.contains(&word) {
+                grown.insert(word.clone());
+                self.users.insert(word, entry);
+            }
+        }
+    }
+
+    /// Loads a JSON dictionary (same schema as the embedded gold/silver data)
+    /// into the user layer. Existing user entries with the same key are replaced.
+    /// Fails without loading anything if an entry uses a symbol outside the
+    /// phoneme inventory.
+    pub fn load_user_dictionary(&mut self, path: impl AsRef<Path>) -> Result<(), LexiconError> {
+        self.load_user_dictionary_json(&fs::read_to_string(path)?)
+    }
+
+    /// `load_user_dictionary` from the JSON itself, as where there are no
+    /// files to read (WebAssembly).
+    pub fn load_user_dictionary_json(&mut self, json: &str) -> Result<(), LexiconError> {
+        let raw: HashMap<String, PhonemeEntry> = serde_json::from_str(json)?;
+        for (word, entry) in &raw {
+            let phonemes: Vec<&String> = match entry {
+                PhonemeEntry::Simple(ps) => vec![ps],
+                PhonemeEntry::Tagged(tagged) => tagged.values().flatten().collect(),
+            };
+            for ps in phonemes {
+                PhonemeString::parse(ps).map_err(|error| LexiconError::InvalidPhonemes {
+                    word: word.clone(),
+                    error,
+                })?;
+            }
+        }
+        for (word, entry) in &raw {
+            for key in entry.unreachable_keys() {
+                tracing::warn!("'{}': tag key '{}' is unreachable, its tags all resolve to other keys", word, key);
+            }
+        }
+        self.extend_users(raw);
+        Ok(())
+    }
+
+    /// Writes the user layer back as JSON in the schema `load_user_dictionary` reads.
+    /// Keys are emitted sorted so exported files diff cleanly.
+    pub fn export_user_layer(&self, path: impl AsRef<Path>) -> Result<(), LexiconError> {
+        fs::write(path, self.export_json(Tier::User)?)?;
+        Ok(())
+    }
+
+    fn tier(&self, tier: Tier) -> &HashMap<String, PhonemeEntry> {
+        match tier {
+            Tier::User => &self.users,
+            Tier::Gold => &self.golds,
+            Tier::Silver => &self.silvers,
+        }
+    }
+
+    /// A dictionary's entries as loaded, without the casing variants
+    /// `grow_dictionary` derived from them.
+    fn source(&self, tier: Tier) -> HashMap<&String, &PhonemeEntry> {
+        let grown = self.grown.get(&tier);
+        self.tier(tier).iter().filter(|(word, _)| !grown.is_some_and(|g| g.contains(*word))).collect()
+    }
+
+    /// One dictionary's entries as loaded, as pretty-printed JSON in the
+    /// schema of the embedded data, keys sorted.
+    pub fn export_json(&self, tier: Tier) -> Result<String, LexiconError> {
+        let value = serde_json::to_value(self.source(tier))?;
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// The entries of every dictionary that `self` adds, removes or changes
+    /// relative to `old`, as between two releases of the data. Casing
+    /// variants derived from the entries aren't listed.
+    pub fn diff(&self, old: &Lexicon) -> LexiconDiff {
+        let mut diff = LexiconDiff::default();
+        for tier in [Tier::User, Tier::Gold, Tier::Silver] {
+            let (old_dict, new_dict) = (old.source(tier), self.source(tier));
+            let entry = |word: &String, old: Option<&PhonemeEntry>, new: Option<&PhonemeEntry>| DiffEntry {
+                tier,
+                word: word.clone(),
+                old: old.cloned(),
+                new: new.cloned(),
+            };
+            for (&word, &new) in &new_dict {
+                match old_dict.get(word).copied() {
+                    None => diff.added.push(entry(word, None, Some(new))),
+                    Some(old) if old != new => diff.changed.push(entry(word, Some(old), Some(new))),
+                    Some(_) => {}
+                }
+            }
+            for (&word, &old) in old_dict.iter().filter(|(word, _)| !new_dict.contains_key(*word)) {
+                diff.removed.push(entry(word, Some(old), None));
+            }
+        }
+        for list in [&mut diff.added, &mut diff.removed, &mut diff.changed] {
+            list.sort_by(|a, b| (a.tier, &a.word).cmp(&(b.tier, &b.word)));
         }
+        diff
     }
 
-    fn grow_dictionary(d: HashMap<String, PhonemeEntry>) -> HashMap<String, PhonemeEntry> {
+    /// `d` with the lowercase or capitalized variant of each key it lacks,
+    /// and the keys of those variants. Entries of `d` win over variants, as
+    /// in misaki's `{**e, **d}`.
+    fn grow_dictionary(d: HashMap<String, PhonemeEntry>) -> (HashMap<String, PhonemeEntry>, HashSet<String>) {
         let mut e = HashMap::new();
         for (k, v) in d.iter() {
-            if k.len() < 2 {
+            if k.chars().count() < 2 {
                 continue;
             }
             let lower = k.to_lowercase();
@@ -89,38 +489,52 @@ impl Lexicon {
                 e.insert(lower, v.clone());
             }
         }
+        e.retain(|k, _| !d.contains_key(k));
+        let grown = e.keys().cloned().collect();
         let mut result = d;
         result.extend(e);
-        result
+        (result, grown)
     }
 
     // Helper to get phoneme string based on tag from entry
-    fn resolve_phonemes(
+    fn resolve_phonemes<'a>(
         &self,
-        entry: &PhonemeEntry,
+        entry: &'a PhonemeEntry,
         tag: &str,
         ctx: Option<&TokenContext>,
-    ) -> Option<String> {
+    ) -> Option<&'a str> {
         match entry {
-            PhonemeEntry::Simple(ps) => Some(ps.clone()),
+            PhonemeEntry::Simple(ps) => Some(ps),
             PhonemeEntry::Tagged(map) => {
+                if let Some(ps) = ctx.and_then(|c| PhonemeEntry::for_next(map, c)) {
+                    return Some(ps);
+                }
+
                 // Python: if ctx and ctx.future_vowel is None and 'None' in ps: tag = 'None'
                 let mut current_tag = tag;
-                if let Some(context) = ctx {
-                    if context.future_vowel.is_none() && map.contains_key("None") {
-                        current_tag = "None";
-                    }
+                if let Some(context) = ctx
+                    && context.future_vowel.is_none()
+                    && map.contains_key("None")
+                {
+                    current_tag = "None";
+                }
+
+                if ctx.is_some_and(|c| c.uncertain_tag)
+                    && let Some(Some(ps)) = map.get("DEFAULT")
+                {
+                    return Some(ps);
                 }
 
-                // Try specific tag, then parent tag, then DEFAULT
-                if let Some(Some(ps)) = map.get(current_tag) {
-                    return Some(ps.clone());
+                // Try specific tag, then parent tag, then DEFAULT; each of the
+                // first two by its own key or a "|" key listing it
+                if let Some(ps) = PhonemeEntry::for_tag(map, current_tag) {
+                    return Some(ps);
                 }
                 let parent = Lexicon::get_parent_tag(current_tag);
-                if let Some(Some(ps)) = map.get(parent) {
-                    return Some(ps.clone());
+                if let Some(ps) = PhonemeEntry::for_tag(map, parent) {
+                    return Some(ps);
                 }
-                map.get("DEFAULT").and_then(|opt| opt.clone())
+                map.get("DEFAULT").and_then(|opt| opt.as_deref())
             }
         }
     }
@@ -143,32 +557,47 @@ impl Lexicon {
         &self,
         word: &str,
         tag: &str,
-        stress: Option<f64>,
+        stress: Option<Stress>,
         ctx: Option<&TokenContext>,
-    ) -> Option<(String, i32)> {
-        let mut current_word = word.to_string();
+    ) -> Option<Pronunciation<'_>> {
+        let mut current_word = Cow::Borrowed(word);
         let mut is_nnp = false;
 
-        if word == word.to_uppercase() && !self.golds.contains_key(word) {
-            current_word = word.to_lowercase();
+        if is_uppercase(word)
+            && !self.golds.contains_key(word)
+            && !self.users.contains_key(word)
+        {
+            current_word = lowercase(word);
             is_nnp = tag == "NNP";
         }
+        let current_word = current_word.as_ref();
+
+        // Borrowed from the dictionaries until stress changes it
+        let mut ps: Option<Cow<'_, str>> = None;
+        let mut rating = Rating::Gold;
+        let mut source = Source::User;
 
-        let mut ps = None;
-        let mut rating = 0;
+        // User entries override the bundled dictionaries
+        if let Some(entry) = self.users.get(current_word) {
+            ps = self.resolve_phonemes(entry, tag, ctx).map(Cow::Borrowed);
+        }
 
         // Try golds first
-        if let Some(entry) = self.golds.get(&current_word) {
-            ps = self.resolve_phonemes(entry, tag, ctx);
-            rating = 4;
+        if ps.is_none()
+            && let Some(entry) = self.golds.get(current_word)
+        {
+            ps = self.resolve_phonemes(entry, tag, ctx).map(Cow::Borrowed);
+            source = Source::Gold;
         }
 
         // Try silvers only if not NNP (Python behavior)
-        if ps.is_none() && !is_nnp {
-            if let Some(entry) = self.silvers.get(&current_word) {
-                ps = self.resolve_phonemes(entry, tag, ctx);
-                rating = 3;
-            }
+        if ps.is_none()
+            && !is_nnp
+            && let Some(entry) = self.silvers.get(current_word)
+        {
+            ps = self.resolve_phonemes(entry, tag, ctx).map(Cow::Borrowed);
+            rating = Rating::Silver;
+            source = Source::Silver;
         }
 
         if ps.is_none() && (word == "three" || word == "one") {
@@ -181,19 +610,29 @@ impl Lexicon {
         }
 
         // Special NNP handling if not found or no primary stress
-        if ps.is_none() || (is_nnp && !ps.as_ref()?.contains('ˈ')) {
-            if is_nnp {
-                if let Some((nnp_ps, nnp_rating)) = self.get_nnp(&current_word) {
-                    ps = Some(nnp_ps);
-                    rating = nnp_rating;
-                }
-            }
+        if is_nnp
+            && ps.as_ref().is_none_or(|p| !p.contains('ˈ'))
+            && let Some(nnp) = self.get_nnp(current_word)
+        {
+            ps = Some(nnp.phonemes);
+            rating = nnp.rating;
+            source = nnp.source;
         }
 
-        ps.map(|p| (self.apply_stress(&p, stress), rating))
+        ps.map(|p| {
+            let stressed = match self.apply_stress(&p, stress) {
+                Cow::Owned(stressed) => Some(stressed),
+                Cow::Borrowed(_) => None,
+            };
+            Pronunciation {
+                phonemes: stressed.map_or(p, Cow::Owned),
+                rating,
+                source,
+            }
+        })
     }
 
-    fn get_nnp(&self, word: &str) -> Option<(String, i32)> {
+    fn get_nnp(&self, word: &str) -> Option<Pronunciation<'static>> {
         let mut ps_parts = Vec::new();
         for c in word.chars() {
             if c.is_alphabetic() {
@@ -211,53 +650,61 @@ impl Lexicon {
         }
 
         let combined = ps_parts.join("");
-        let stressed = self.apply_stress(&combined, Some(0.0));
+        let stressed = self.apply_stress(&combined, Some(Stress::Secondary));
 
         // Python: ps = ps.rsplit(SECONDARY_STRESS, 1) -> return PRIMARY_STRESS.join(ps), 3
         let secondary = 'ˌ';
         let primary = 'ˈ';
-        if let Some(idx) = stressed.rfind(secondary) {
-            let mut result = stressed.clone();
-            result.replace_range(idx..idx + secondary.len_utf8(), &primary.to_string());
-            Some((result, 3))
-        } else {
-            Some((stressed, 3))
+        let mut phonemes = stressed.into_owned();
+        if let Some(idx) = phonemes.rfind(secondary) {
+            phonemes.replace_range(idx..idx + secondary.len_utf8(), &primary.to_string());
         }
+        Some(Pronunciation {
+            phonemes: Cow::Owned(phonemes),
+            rating: Rating::Silver,
+            source: Source::Letters,
+        })
     }
 
-    pub fn apply_stress(&self, ps: &str, stress: Option<f64>) -> String {
+    /// `ps` with its stress adjusted to `stress`, borrowed when that
+    /// changes nothing.
+    pub fn apply_stress<'a>(&self, ps: &'a str, stress: Option<Stress>) -> Cow<'a, str> {
         let primary = 'ˈ';
         let secondary = 'ˌ';
         let vowels = "AIOQWYaiuæɑɒɔəɛɜɪʊʌᵻ";
 
-        if stress.is_none() {
-            return ps.to_string();
-        }
-        let s = stress.unwrap();
+        let Some(s) = stress.map(f64::from) else {
+            return Cow::Borrowed(ps);
+        };
 
         if s < -1.0 {
-            return ps.replace(primary, "").replace(secondary, "");
-        } else if s == -1.0 || (s >= -0.5 && s <= 0.0 && ps.contains(primary)) {
-            return ps
-                .replace(secondary, "")
-                .replace(primary, &secondary.to_string());
+            if ps.contains([primary, secondary]) {
+                return Cow::Owned(ps.replace([primary, secondary], ""));
+            }
+        } else if s == -1.0 || ((-0.5..=0.0).contains(&s) && ps.contains(primary)) {
+            if ps.contains(primary) {
+                return Cow::Owned(ps.replace(secondary, "").replace(primary, &secondary.to_string()));
+            }
+            if ps.contains(secondary) {
+                return Cow::Owned(ps.replace(secondary, ""));
+            }
         } else if (s == 0.0 || s == 0.5 || s == 1.0)
             && !ps.contains(primary)
             && !ps.contains(secondary)
         {
-            if !ps.chars().any(|c| vowels.contains(c)) {
-                return ps.to_string();
+            if ps.chars().any(|c| vowels.contains(c)) {
+                return Cow::Owned(self.restress(&format!("{}{}", secondary, ps)));
             }
-            return self.restress(&format!("{}{}", secondary, ps));
         } else if s >= 1.0 && !ps.contains(primary) && ps.contains(secondary) {
-            return ps.replace(secondary, &primary.to_string());
-        } else if s > 1.0 && !ps.contains(primary) && !ps.contains(secondary) {
-            if !ps.chars().any(|c| vowels.contains(c)) {
-                return ps.to_string();
-            }
-            return self.restress(&format!("{}{}", primary, ps));
+            return Cow::Owned(ps.replace(secondary, &primary.to_string()));
+        } else if s > 1.0
+            && !ps.contains(primary)
+            && !ps.contains(secondary)
+            && ps.chars().any(|c| vowels.contains(c))
+        {
+            return Cow::Owned(self.restress(&format!("{}{}", primary, ps)));
         }
-        ps.to_string()
+        Cow::Borrowed(ps)
     }
 
     fn restress(&self, ps: &str) -> String {
@@ -270,10 +717,10 @@ impl Lexicon {
         let mut stresses = Vec::new();
 
         for (i, &(_, c)) in parts.iter().enumerate() {
-            if c == primary || c == secondary {
-                if let Some(j) = parts[i..].iter().position(|&(_, vc)| vowels.contains(vc)) {
-                    stresses.push((i, i + j));
-                }
+            if (c == primary || c == secondary)
+                && let Some(j) = parts[i..].iter().position(|&(_, vc)| vowels.contains(vc))
+            {
+                stresses.push((i, i + j));
             }
         }
 
@@ -286,44 +733,42 @@ impl Lexicon {
         parts.into_iter().map(|(_, c)| c).collect()
     }
 
-    // Stemming logic
+    // Stemming logic, branch for branch as Python misaki's stem_s, stem_ed
+    // and stem_ing, counting chars as Python's slices do
+
     pub fn stem_s(
         &self,
         word: &str,
         tag: &str,
-        stress: Option<f64>,
+        stress: Option<Stress>,
         ctx: Option<&TokenContext>,
-    ) -> Option<(String, i32)> {
-        let lower = word.to_lowercase();
-        if lower.len() < 3 || !lower.ends_with('s') {
+    ) -> Option<Pronunciation<'_>> {
+        let len = word.chars().count();
+        if len < 3 || !word.ends_with('s') {
             return None;
         }
 
-        let stem = if !lower.ends_with("ss") && self.is_known(&lower[..lower.len() - 1], tag) {
-            &lower[..lower.len() - 1]
-        } else if (lower.ends_with("'s")
-            || (lower.len() > 4 && lower.ends_with("es") && !lower.ends_with("ies")))
-            && self.is_known(&lower[..lower.len() - 2], tag)
+        let stem = if !word.ends_with("ss") && self.is_known(drop_last(word, 1), tag) {
+            drop_last(word, 1).to_string()
+        } else if (word.ends_with("'s") || (len > 4 && word.ends_with("es") && !word.ends_with("ies")))
+            && self.is_known(drop_last(word, 2), tag)
         {
-            &lower[..lower.len() - 2]
-        } else if lower.len() > 4
-            && lower.ends_with("ies")
-            && self.is_known(&(lower[..lower.len() - 3].to_string() + "y"), tag)
-        {
-            &(lower[..lower.len() - 3].to_string() + "y")
+            drop_last(word, 2).to_string()
+        } else if len > 4 && word.ends_with("ies") && self.is_known(&format!("{}y", drop_last(word, 3)), tag) {
+            format!("{}y", drop_last(word, 3))
         } else {
             return None;
         };
 
-        let (stem_ps, rating) = self.lookup(stem, tag, stress, ctx)?;
-        Some((self.append_s(&stem_ps), rating))
+        let stem = self.lookup(&stem, tag, stress, ctx)?;
+        let phonemes = self.append_s(&stem.phonemes);
+        Some(stem.stemmed(phonemes))
     }
 
     pub fn append_s(&self, stem: &str) -> String {
-        if stem.is_empty() {
+        let Some(last) = stem.chars().last() else {
             return String::new();
-        }
-        let last = stem.chars().last().unwrap();
+        };
         let british = matches!(self.lang, Language::EnglishGB);
         if "ptkfθ".contains(last) {
             format!("{}s", stem)
@@ -338,84 +783,63 @@ impl Lexicon {
         &self,
         word: &str,
         tag: &str,
-        stress: Option<f64>,
+        stress: Option<Stress>,
         ctx: Option<&TokenContext>,
-    ) -> Option<(String, i32)> {
-        let lower = word.to_lowercase();
-        if lower.len() < 4 || !lower.ends_with('d') {
+    ) -> Option<Pronunciation<'_>> {
+        let len = word.chars().count();
+        if len < 4 || !word.ends_with('d') {
             return None;
         }
-        let stem = if !lower.ends_with("dd") && self.is_known(&lower[..lower.len() - 1], tag) {
-            &lower[..lower.len() - 1]
-        } else if lower.len() > 4
-            && lower.ends_with("ed")
-            && !lower.ends_with("eed")
-            && self.is_known(&lower[..lower.len() - 2], tag)
+
+        let stem = if !word.ends_with("dd") && self.is_known(drop_last(word, 1), tag) {
+            drop_last(word, 1)
+        } else if len > 4
+            && word.ends_with("ed")
+            && !word.ends_with("eed")
+            && self.is_known(drop_last(word, 2), tag)
         {
-            &lower[..lower.len() - 2]
+            drop_last(word, 2)
         } else {
             return None;
         };
 
-        let (stem_ps, rating) = self.lookup(stem, tag, stress, ctx)?;
-        Some((self.append_ed(&stem_ps), rating))
+        let stem = self.lookup(stem, tag, stress, ctx)?;
+        let phonemes = self.append_ed(&stem.phonemes);
+        Some(stem.stemmed(phonemes))
     }
 
     pub fn append_ed(&self, stem: &str) -> String {
-        if stem.is_empty() {
+        let chars: Vec<char> = stem.chars().collect();
+        let Some(&last) = chars.last() else {
             return String::new();
-        }
+        };
         let british = matches!(self.lang, Language::EnglishGB);
-        let last = stem.chars().last().unwrap();
         if "pkfθʃsʧ".contains(last) {
             format!("{}t", stem)
         } else if last == 'd' {
             format!("{}{}d", stem, if british { "ɪ" } else { "ᵻ" })
         } else if last != 't' {
             format!("{}d", stem)
-        } else if british || stem.len() < 2 {
+        } else if british || chars.len() < 2 {
             format!("{}ɪd", stem)
+        } else if US_TAUS.contains(chars[chars.len() - 2]) {
+            format!("{}ɾᵻd", drop_last(stem, 1))
         } else {
-            // Check if second-to-last char is in US_TAUS
-            let chars: Vec<char> = stem.chars().collect();
-            if chars.len() >= 2 && US_TAUS.contains(chars[chars.len() - 2]) {
-                format!(
-                    "{}ɾᵻd",
-                    &stem[..stem.len() - chars[chars.len() - 1].len_utf8()]
-                )
-            } else {
-                format!("{}ᵻd", stem)
-            }
+            format!("{}ᵻd", stem)
         }
     }
 
     pub fn append_ing(&self, stem: &str) -> Option<String> {
-        if stem.is_empty() {
-            return None;
-        }
+        let chars: Vec<char> = stem.chars().collect();
+        let &last = chars.last()?;
         let british = matches!(self.lang, Language::EnglishGB);
-
         if british {
-            let last = stem.chars().last().unwrap();
             if last == 'ə' || last == 'ː' {
                 return None;
             }
+        } else if chars.len() > 1 && last == 't' && US_TAUS.contains(chars[chars.len() - 2]) {
+            return Some(format!("{}ɾɪŋ", drop_last(stem, 1)));
         }
-
-        // US: check for 't' followed by US_TAUS vowel
-        if !british && stem.len() > 1 {
-            let chars: Vec<char> = stem.chars().collect();
-            if chars[chars.len() - 1] == 't'
-                && chars.len() >= 2
-                && US_TAUS.contains(chars[chars.len() - 2])
-            {
-                return Some(format!(
-                    "{}ɾɪŋ",
-                    &stem[..stem.len() - chars[chars.len() - 1].len_utf8()]
-                ));
-            }
-        }
-
         Some(format!("{}ɪŋ", stem))
     }
 
@@ -423,165 +847,153 @@ impl Lexicon {
         &self,
         word: &str,
         tag: &str,
-        stress: Option<f64>,
+        stress: Option<Stress>,
         ctx: Option<&TokenContext>,
-    ) -> Option<(String, i32)> {
-        let lower = word.to_lowercase();
-        if lower.len() < 5 || !lower.ends_with("ing") {
+    ) -> Option<Pronunciation<'_>> {
+        let chars: Vec<char> = word.chars().collect();
+        let len = chars.len();
+        if len < 5 || !word.ends_with("ing") {
             return None;
         }
-
-        let stem = if lower.len() > 5 && self.is_known(&lower[..lower.len() - 3], tag) {
-            lower[..lower.len() - 3].to_string()
-        } else if self.is_known(&(lower[..lower.len() - 3].to_string() + "e"), tag) {
-            lower[..lower.len() - 3].to_string() + "e"
-        } else if lower.len() > 5 {
-            // Python regex: r'([bcdgklmnprstvxz])\1ing$|cking$'
-            let stem_candidate = &lower[..lower.len() - 4];
-            if self.is_known(stem_candidate, tag) {
-                // Check for doubled consonants or 'ck'
-                let chars: Vec<char> = stem_candidate.chars().collect();
-                if chars.len() >= 2 {
-                    let last = chars[chars.len() - 1];
-                    let second_last = chars[chars.len() - 2];
-                    if (last == second_last && "bcdgklmnprstvxz".contains(last))
-                        || (last == 'k' && second_last == 'c')
-                    {
-                        return Some((
-                            self.append_ing(stem_candidate)?,
-                            self.lookup(stem_candidate, tag, stress, ctx)?.1,
-                        ));
-                    }
-                }
-            }
-            return None;
+        // Python: re.search(r'([bcdgklmnprstvxz])\1ing$|cking$', word)
+        let doubled = (chars[len - 5] == chars[len - 4] && "bcdgklmnprstvxz".contains(chars[len - 4]))
+            || word.ends_with("cking");
+
+        let stem = if len > 5 && self.is_known(drop_last(word, 3), tag) {
+            drop_last(word, 3).to_string()
+        } else if self.is_known(&format!("{}e", drop_last(word, 3)), tag) {
+            format!("{}e", drop_last(word, 3))
+        } else if len > 5 && doubled && self.is_known(drop_last(word, 4), tag) {
+            drop_last(word, 4).to_string()
         } else {
             return None;
         };
 
-        let (stem_ps, rating) = self.lookup(&stem, tag, stress, ctx)?;
-        Some((self.append_ing(&stem_ps)?, rating))
+        let stem = self.lookup(&stem, tag, stress, ctx)?;
+        let phonemes = self.append_ing(&stem.phonemes)?;
+        Some(stem.stemmed(phonemes))
+    }
+
+    /// Reads an unknown word as two dictionary words run together
+    /// ("smartwatch" is "smart" + "watch"), taking the longest first word
+    /// that leaves a known second. The second word's primary stress becomes
+    /// secondary, as in "bookshelf".
+    pub fn split_compound(&self, word: &str, tag: &str, ctx: Option<&TokenContext>) -> Option<Pronunciation<'_>> {
+        // Two-letter words ("an", "is") would split far too eagerly
+        const MIN_PART: usize = 3;
+
+        let lower = word.to_lowercase();
+        if !lower.chars().all(char::is_alphabetic) {
+            return None;
+        }
+        let known = |w: &str| self.golds.contains_key(w) || self.silvers.contains_key(w);
+        let splits: Vec<usize> = lower.char_indices().map(|(i, _)| i).collect();
+        splits.into_iter().rev().find_map(|i| {
+            let (first, second) = lower.split_at(i);
+            if first.chars().count() < MIN_PART || second.chars().count() < MIN_PART || !known(first) || !known(second) {
+                return None;
+            }
+            let first = self.lookup(first, tag, None, ctx)?;
+            let second = self.lookup(second, tag, Some(Stress::Demote), None)?;
+            let phonemes = format!("{}{}", first.phonemes, second.phonemes);
+            Some(first.stemmed(phonemes))
+        })
+    }
+
+    /// The dictionary word one edit (a letter deleted, inserted, replaced,
+    /// or two swapped) from `word`, taken for what a typo like "restirction"
+    /// meant. Gold words win over silver ones, then the alphabetically first.
+    /// Words under five letters have too many neighbours to guess from.
+    pub fn nearest_word(&self, word: &str) -> Option<String> {
+        const MIN_LEN: usize = 5;
+
+        let lower = word.to_lowercase();
+        let chars: Vec<char> = lower.chars().collect();
+        if chars.len() < MIN_LEN || !chars.iter().all(char::is_ascii_lowercase) {
+            return None;
+        }
+
+        let mut candidates = Vec::new();
+        for i in 0..=chars.len() {
+            let (head, tail) = chars.split_at(i);
+            if let Some((_, rest)) = tail.split_first() {
+                candidates.push(head.iter().chain(rest).collect::<String>());
+            }
+            if let [a, b, rest @ ..] = tail {
+                candidates.push(head.iter().chain([b, a]).chain(rest).collect());
+            }
+            for c in 'a'..='z' {
+                candidates.push(head.iter().chain([&c]).chain(tail).collect());
+                if let Some((_, rest)) = tail.split_first() {
+                    candidates.push(head.iter().chain([&c]).chain(rest).collect());
+                }
+            }
+        }
+        candidates.retain(|w| *w != lower);
+        candidates.sort();
+        let find = |dict: &HashMap<String, PhonemeEntry>| candidates.iter().find(|w| dict.contains_key(*w)).cloned();
+        find(&self.golds).or_else(|| find(&self.silvers))
     }
 
     pub fn get_special_case(
         &self,
         word: &str,
         tag: &str,
-        stress: Option<f64>,
+        stress: Option<Stress>,
         ctx: Option<&TokenContext>,
-    ) -> Option<(String, i32)> {
-        let add_symbols = get_add_symbols();
-        let symbols = get_symbols();
-
-        if tag == "ADD" && add_symbols.contains_key(word) {
-            return self.lookup(add_symbols[word], "NN", Some(-0.5), ctx);
-        } else if symbols.contains_key(word) {
-            return self.lookup(symbols[word], "NN", None, ctx);
-        } else if word.contains('.') && word.replace('.', "").chars().all(|c| c.is_alphabetic()) {
-            let max_len = word.split('.').map(|s| s.len()).max().unwrap_or(0);
-            if max_len < 3 {
-                return self.get_nnp(word);
-            }
-        } else if word == "a" || word == "A" {
-            return Some((
-                if tag == "DT" {
-                    "ɐ".to_string()
-                } else {
-                    "ˈA".to_string()
-                },
-                4,
-            ));
-        } else if word == "am" || word == "Am" || word == "AM" {
-            if tag.starts_with("NN") {
-                return self.get_nnp(word);
-            } else if ctx.is_none()
-                || ctx.and_then(|c| c.future_vowel).is_none()
-                || word != "am"
-                || stress.map(|s| s > 0.0).unwrap_or(false)
-            {
-                if let Some(PhonemeEntry::Simple(ps)) = self.golds.get("am") {
-                    return Some((ps.clone(), 4));
-                }
-            }
-            return Some(("ɐm".to_string(), 4));
-        } else if word == "an" || word == "An" || word == "AN" {
-            if word == "AN" && tag.starts_with("NN") {
-                return self.get_nnp(word);
-            }
-            return Some(("ɐn".to_string(), 4));
-        } else if word == "I" && tag == "PRP" {
-            return Some(("ˌI".to_string(), 4));
-        } else if (word == "by" || word == "By" || word == "BY")
-            && Lexicon::get_parent_tag(tag) == "ADV"
+    ) -> Option<Pronunciation<'_>> {
+        if tag == "ADD"
+            && let Some(name) = add_symbol(word)
         {
-            return Some(("bˈI".to_string(), 4));
-        } else if word == "to" || word == "To" || (word == "TO" && (tag == "TO" || tag == "IN")) {
-            let future_vowel = ctx.and_then(|c| c.future_vowel);
-            if let Some(PhonemeEntry::Simple(ps)) = self.golds.get("to") {
-                return Some((
-                    match future_vowel {
-                        None => ps.clone(),
-                        Some(false) => "tə".to_string(),
-                        Some(true) => "tʊ".to_string(),
-                    },
-                    4,
-                ));
-            }
-        } else if word == "in" || word == "In" || (word == "IN" && tag != "NNP") {
-            let future_vowel = ctx.and_then(|c| c.future_vowel);
-            let stress_mark = if future_vowel.is_none() || tag != "IN" {
-                "ˈ"
-            } else {
-                ""
-            };
-            return Some((format!("{}{}", stress_mark, "ɪn"), 4));
-        } else if word == "the" || word == "The" || (word == "THE" && tag == "DT") {
-            let future_vowel = ctx.and_then(|c| c.future_vowel);
-            return Some((
-                if future_vowel == Some(true) {
-                    "ði".to_string()
-                } else {
-                    "ðə".to_string()
-                },
-                4,
-            ));
-        } else if tag == "IN" && (word.to_lowercase() == "vs" || word.to_lowercase() == "vs.") {
-            return self.lookup("versus", "NN", None, ctx);
-        } else if word == "used" || word == "Used" || word == "USED" {
-            if (tag == "VBD" || tag == "JJ") && ctx.map(|c| c.future_to).unwrap_or(false) {
-                if let Some(PhonemeEntry::Tagged(map)) = self.golds.get("used") {
-                    if let Some(Some(ps)) = map.get("VBD") {
-                        return Some((ps.clone(), 4));
+            return self.lookup(name, "NN", Some(Stress::Weight(-0.5)), ctx);
+        } else if let Some(name) = symbol(word) {
+            return self.lookup(name, "NN", None, ctx);
+        } else if normalize::is_initialism(word) {
+            return self.get_nnp(word);
+        }
+        self.read_special_case(word, tag, stress, ctx)
+    }
+
+    /// The reading `special_cases` gives the word, if any.
+    fn read_special_case(
+        &self,
+        word: &str,
+        tag: &str,
+        stress: Option<Stress>,
+        ctx: Option<&TokenContext>,
+    ) -> Option<Pronunciation<'_>> {
+        for then in self.special_cases.actions(word, tag, stress, ctx) {
+            match then {
+                Then::Say(ps) => return Some(Pronunciation::special(ps.as_str())),
+                Then::Gold { word, key } => {
+                    let ps = match (self.golds.get(word), key) {
+                        (Some(PhonemeEntry::Simple(ps)), None) => Some(ps),
+                        (Some(PhonemeEntry::Tagged(map)), Some(key)) => map.get(key).and_then(Option::as_ref),
+                        _ => None,
+                    };
+                    if let Some(ps) = ps {
+                        return Some(Pronunciation::special(ps.as_str()));
                     }
                 }
-            }
-            if let Some(PhonemeEntry::Tagged(map)) = self.golds.get("used") {
-                if let Some(Some(ps)) = map.get("DEFAULT") {
-                    return Some((ps.clone(), 4));
-                }
+                Then::Lookup { word, tag } => return self.lookup(word, tag, None, ctx),
+                Then::Letters => return self.get_nnp(word),
+                Then::Nothing => return None,
             }
         }
         None
     }
 
     pub fn is_known(&self, word: &str, _tag: &str) -> bool {
-        let symbols = get_symbols();
-
-        if self.golds.contains_key(word)
-            || symbols.contains_key(word)
+        if self.users.contains_key(word)
+            || self.golds.contains_key(word)
+            || symbol(word).is_some()
             || self.silvers.contains_key(word)
         {
             return true;
         }
 
-        if !word.chars().all(|c| c.is_alphabetic()) {
-            return false;
-        }
-
-        if !word.chars().all(|c| {
-            let ord = c as u32;
-            LEXICON_ORDS.contains(&ord)
-        }) {
+        // Only ASCII letters from here, so bytes are chars
+        if !is_lexicon_word(word) {
             return false;
         }
 
@@ -589,54 +1001,115 @@ impl Lexicon {
             return true;
         }
 
-        if word == word.to_uppercase() && self.golds.contains_key(&word.to_lowercase()) {
-            return true;
+        if is_uppercase(word) {
+            // Lowercase into a buffer rather than a new String when it fits
+            let mut buf = [0u8; 32];
+            let in_golds = match buf.get_mut(..word.len()) {
+                Some(lower) => {
+                    lower.copy_from_slice(word.as_bytes());
+                    lower.make_ascii_lowercase();
+                    std::str::from_utf8(lower).is_ok_and(|lower| self.golds.contains_key(lower))
+                }
+                None => self.golds.contains_key(&word.to_ascii_lowercase()),
+            };
+            if in_golds {
+                return true;
+            }
         }
 
         // Check for mixed case like "iPhone" (word[1:] == word[1:].upper())
-        if word.len() > 1 {
-            let rest: String = word.chars().skip(1).collect();
-            if rest == rest.to_uppercase() {
-                return true;
+        is_uppercase(&word[1..])
+    }
+
+    /// Measures how many words of `corpus` (lines or documents of plain
+    /// text) the dictionaries cover, to judge whether a domain needs a
+    /// supplemental dictionary. Words are split on whitespace and stripped of
+    /// surrounding punctuation; tokens without letters are skipped.
+    pub fn coverage<'a>(&self, corpus: impl Iterator<Item = &'a str>) -> CoverageReport {
+        let mut report = CoverageReport::default();
+        let mut missing: HashMap<String, usize> = HashMap::new();
+        for text in corpus {
+            for raw in text.split_whitespace() {
+                let word = raw.trim_matches(|c: char| !c.is_alphanumeric());
+                if !word.chars().any(char::is_alphabetic) {
+                    continue;
+                }
+                report.tokens += 1;
+                let lower = word.to_lowercase();
+                let in_dict = |d: &HashMap<String, PhonemeEntry>| d.contains_key(word) || d.contains_key(&lower);
+                if in_dict(&self.users) || in_dict(&self.golds) {
+                    report.gold += 1;
+                } else if in_dict(&self.silvers) {
+                    report.silver += 1;
+                } else if self.stem_s(&lower, "NN", None, None).is_some()
+                    || self.stem_ed(&lower, "VBD", None, None).is_some()
+                    || self.stem_ing(&lower, "VBG", None, None).is_some()
+                {
+                    report.stemmed += 1;
+                } else {
+                    report.missing += 1;
+                    *missing.entry(lower).or_default() += 1;
+                }
             }
         }
-
-        false
+        report.missing_words = missing.into_iter().collect();
+        report.missing_words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        report
     }
 
     pub fn get_word(
         &self,
         word: &str,
         tag: &str,
-        stress: Option<f64>,
+        stress: Option<Stress>,
         ctx: Option<&TokenContext>,
-    ) -> Option<(String, i32)> {
-        // First try special cases
-        if let Some(result) = self.get_special_case(word, tag, stress, ctx) {
+    ) -> Option<Pronunciation<'_>> {
+        // The dictionaries spell "don't" with the ASCII apostrophe, not "’"
+        let word = if word.contains('’') { Cow::Owned(word.replace('’', "'")) } else { Cow::Borrowed(word) };
+        let word = word.as_ref();
+
+        // Casing and stemming are English; other languages only look words
+        // up, after any special cases of their own
+        if !self.lang.is_english() {
+            if !self.users.contains_key(word)
+                && let Some(result) = self.read_special_case(word, tag, stress, ctx)
+            {
+                return Some(result);
+            }
+            let wl = lowercase(word);
+            let known = |w: &str| self.users.contains_key(w) || self.golds.contains_key(w) || self.silvers.contains_key(w);
+            return [word, wl.as_ref()].into_iter().find(|w| known(w)).and_then(|w| self.lookup(w, tag, stress, ctx));
+        }
+
+        // First try special cases, unless a user entry replaces them
+        if !self.users.contains_key(word)
+            && let Some(result) = self.get_special_case(word, tag, stress, ctx)
+        {
             return Some(result);
         }
 
-        let wl = word.to_lowercase();
         let mut current_word = word;
 
         // Python logic: convert to lowercase if conditions met
-        if word.len() > 1
-            && word.replace("'", "").chars().all(|c| c.is_alphabetic())
+        let len = word.chars().count();
+        let wl = lowercase(word);
+        let wl = wl.as_ref();
+        if len > 1
+            && word.chars().all(|c| c == '\'' || c.is_alphabetic())
             && word != wl
-            && (tag != "NNP" || word.len() > 7)
+            && (tag != "NNP" || len > 7)
+            && !self.users.contains_key(word)
             && !self.golds.contains_key(word)
             && !self.silvers.contains_key(word)
-            && (word == word.to_uppercase() || {
-                let rest: String = word.chars().skip(1).collect();
-                rest == rest.to_lowercase()
-            })
-            && (self.golds.contains_key(&wl)
-                || self.silvers.contains_key(&wl)
-                || self.stem_s(&wl, tag, stress, ctx).is_some()
-                || self.stem_ed(&wl, tag, stress, ctx).is_some()
-                || self.stem_ing(&wl, tag, stress, ctx).is_some())
+            && (is_uppercase(word) || word.chars().next().is_some_and(|c| is_lowercase(&word[c.len_utf8()..])))
+            && (self.users.contains_key(wl)
+                || self.golds.contains_key(wl)
+                || self.silvers.contains_key(wl)
+                || self.stem_s(wl, tag, stress, ctx).is_some()
+                || self.stem_ed(wl, tag, stress, ctx).is_some()
+                || self.stem_ing(wl, tag, stress, ctx).is_some())
         {
-            current_word = &wl;
+            current_word = wl;
         }
 
         if self.is_known(current_word, tag) {
@@ -644,20 +1117,11 @@ impl Lexicon {
         }
 
         // Handle possessive forms
-        if current_word.ends_with("s'")
-            && self.is_known(&current_word[..current_word.len() - 2], tag)
-        {
-            return self.lookup(
-                &format!("{}'s", &current_word[..current_word.len() - 2]),
-                tag,
-                stress,
-                ctx,
-            );
+        if current_word.ends_with("s'") && self.is_known(drop_last(current_word, 2), tag) {
+            return self.lookup(&format!("{}'s", drop_last(current_word, 2)), tag, stress, ctx);
         }
-        if current_word.ends_with("'")
-            && self.is_known(&current_word[..current_word.len() - 1], tag)
-        {
-            return self.lookup(&current_word[..current_word.len() - 1], tag, stress, ctx);
+        if current_word.ends_with('\'') && self.is_known(drop_last(current_word, 1), tag) {
+            return self.lookup(drop_last(current_word, 1), tag, stress, ctx);
         }
 
         // Try stemming
@@ -667,10 +1131,336 @@ impl Lexicon {
         if let Some(result) = self.stem_ed(current_word, tag, stress, ctx) {
             return Some(result);
         }
-        if let Some(result) = self.stem_ing(current_word, tag, Some(0.5).or(stress), ctx) {
+        if let Some(result) = self.stem_ing(current_word, tag, Some(Stress::Weight(0.5)).or(stress), ctx) {
             return Some(result);
         }
 
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_layer_round_trip() {
+        let mut lexicon = Lexicon::new(Language::EnglishUS);
+        lexicon.add_user_entry("Kokoro", PhonemeEntry::Simple("kˈOkəɹO".to_string()));
+        let mut tagged = HashMap::new();
+        tagged.insert("DEFAULT".to_string(), Some("ɹˈɛkɔɹd".to_string()));
+        tagged.insert("VERB".to_string(), Some("ɹᵻkˈɔɹd".to_string()));
+        tagged.insert("None".to_string(), None);
+        lexicon.add_user_entry("rekord", PhonemeEntry::Tagged(tagged));
+
+        // grow_dictionary adds the lowercase/capitalized variants
+        assert!(lexicon.users.contains_key("kokoro"));
+        assert!(lexicon.users.contains_key("Rekord"));
+
+        let path = std::env::temp_dir().join(format!("misaki_user_layer_{}.json", std::process::id()));
+        lexicon.export_user_layer(&path).unwrap();
+
+        let mut reloaded = Lexicon::new(Language::EnglishUS);
+        reloaded.load_user_dictionary(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&lexicon.users).unwrap(),
+            serde_json::to_value(&reloaded.users).unwrap()
+        );
+        assert_eq!(
+            reloaded.lookup("Kokoro", "NNP", None, None),
+            Some(Pronunciation {
+                phonemes: Cow::Borrowed("kˈOkəɹO"),
+                rating: Rating::Gold,
+                source: Source::User,
+            })
+        );
+        assert_eq!(
+            reloaded.lookup("rekord", "VB", None, None).map(|p| p.phonemes).as_deref(),
+            Some("ɹᵻkˈɔɹd")
+        );
+    }
+
+    #[test]
+    fn test_phoneme_inventory() {
+        let path = std::env::temp_dir().join(format!("misaki_bad_user_{}.json", std::process::id()));
+        fs::write(&path, r#"{"zorb": "zˈɔɹb", "blorf": "blˈɔːrf❓"}"#).unwrap();
+        let mut lexicon = Lexicon::new(Language::EnglishUS);
+        let err = lexicon.load_user_dictionary(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(err, LexiconError::InvalidPhonemes { ref word, .. } if word == "blorf"));
+        assert!(lexicon.users.is_empty());
+
+        // The bundled dictionaries stay within the inventory
+        for lang in [Language::EnglishUS, Language::EnglishGB] {
+            let lexicon = Lexicon::new(lang);
+            for (word, entry) in lexicon.golds.iter().chain(lexicon.silvers.iter()) {
+                let phonemes: Vec<&String> = match entry {
+                    PhonemeEntry::Simple(ps) => vec![ps],
+                    PhonemeEntry::Tagged(tagged) => tagged.values().flatten().collect(),
+                };
+                for ps in phonemes {
+                    assert!(PhonemeString::parse(ps).is_ok(), "{}: {}", word, ps);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_stress() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        assert_eq!(lexicon.apply_stress("hˈɛlO", Some(Stress::Remove)), "hɛlO");
+        assert_eq!(lexicon.apply_stress("hˈɛlO", Some(Stress::Demote)), "hˌɛlO");
+        assert_eq!(lexicon.apply_stress("hˌɛlO", Some(Stress::Primary)), "hˈɛlO");
+        assert_eq!(lexicon.apply_stress("kæt", Some(Stress::Promote)), "kˈæt");
+        assert_eq!(lexicon.apply_stress("kæt", Some(Stress::Secondary)), "kˌæt");
+        assert_eq!(lexicon.apply_stress("kæt", None), "kæt");
+        // Only a change allocates
+        assert!(matches!(lexicon.apply_stress("hˈɛlO", Some(Stress::Primary)), Cow::Borrowed(_)));
+        assert!(matches!(lexicon.apply_stress("hɛlO", Some(Stress::Remove)), Cow::Borrowed(_)));
+        assert!(matches!(lexicon.apply_stress("hˈɛlO", Some(Stress::Remove)), Cow::Owned(_)));
+        // The float shim reads the same
+        for value in [-2.0, -1.0, -0.5, 0.0, 0.5, 1.0, 2.0] {
+            for ps in ["hˈɛlO", "hˌɛlO", "kæt"] {
+                let stress = Stress::from(value);
+                assert_eq!(lexicon.apply_stress(ps, Some(stress)), lexicon.apply_stress(ps, Some(Stress::Weight(value))));
+            }
+        }
+    }
+
+    #[test]
+    fn test_coverage() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        let corpus = ["The cats jumped over 42 fences.", "Zorbington met Zorbington's flurbs, zorbington!"];
+        let report = lexicon.coverage(corpus.into_iter());
+        assert_eq!(report.tokens, 10);
+        assert!(report.gold >= 3);
+        assert_eq!(report.gold + report.silver + report.stemmed + report.missing, report.tokens);
+        assert_eq!(report.top_missing(1), [("zorbington".to_string(), 2)]);
+        assert!((report.missing_fraction() - report.missing as f64 / 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pronunciation_rating() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+
+        let cat = lexicon.get_word("cat", "NN", None, None).unwrap();
+        assert_eq!((cat.rating, cat.source), (Rating::Gold, Source::Gold));
+        // Borrowed from the dictionary unless stress changes it
+        assert!(matches!(cat.phonemes, Cow::Borrowed(_)));
+        let demoted = lexicon.get_word("cat", "NN", Some(Stress::Demote), None).unwrap();
+        assert!(matches!(demoted.phonemes, Cow::Owned(_)));
+
+        let cats = lexicon.stem_s("cats", "NNS", None, None).unwrap();
+        assert_eq!((cats.rating, cats.source), (Rating::Stemmed, Source::Gold));
+        assert!(cats.phonemes.starts_with(&*cat.phonemes));
+
+        let the = lexicon.get_word("the", "DT", None, None).unwrap();
+        assert_eq!(the.source, Source::SpecialCase);
+
+        let fbi = lexicon.get_word("FBI", "NNP", None, None).unwrap();
+        assert_eq!((fbi.rating, fbi.source), (Rating::Silver, Source::Letters));
+        assert!(Rating::Gold > Rating::Stemmed && Rating::Fallback > Rating::Unknown);
+    }
+
+    #[test]
+    fn test_split_compound() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        let word = |w: &str| lexicon.get_word(w, "NN", None, None).unwrap().phonemes.into_owned();
+
+        let smartwatch = lexicon.split_compound("smartwatch", "NN", None).unwrap();
+        assert_eq!(smartwatch.phonemes, word("smart") + &word("watch").replace('ˈ', "ˌ"));
+        assert_eq!((smartwatch.rating, smartwatch.source), (Rating::Stemmed, Source::Gold));
+        assert!(lexicon.split_compound("zorblington", "NN", None).is_none());
+        assert!(lexicon.split_compound("smart-watch", "NN", None).is_none());
+    }
+
+    #[test]
+    fn test_nearest_word() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        assert_eq!(lexicon.nearest_word("restirction").as_deref(), Some("restriction"));
+        assert_eq!(lexicon.nearest_word("Beautifull").as_deref(), Some("beautiful"));
+        assert_eq!(lexicon.nearest_word("recieve").as_deref(), Some("receive"));
+        assert_eq!(lexicon.nearest_word("zorblington"), None);
+        // Too short to guess
+        assert_eq!(lexicon.nearest_word("teh"), None);
+    }
+
+    #[test]
+    fn test_stem_parity() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        let cases = include_str!("../data/stem_parity.tsv");
+        let mut checked = 0;
+        for line in cases.lines().filter(|l| !l.starts_with('#')) {
+            let [word, suffix, stem] = line.split('\t').collect::<Vec<_>>()[..] else {
+                panic!("bad case: {:?}", line);
+            };
+            let stem = (stem != "-").then(|| lexicon.lookup(stem, "NN", None, None)).flatten();
+            let (actual, expected) = match suffix {
+                "s" => (lexicon.stem_s(word, "NN", None, None), stem.map(|p| lexicon.append_s(&p.phonemes))),
+                "ed" => (lexicon.stem_ed(word, "NN", None, None), stem.map(|p| lexicon.append_ed(&p.phonemes))),
+                _ => (lexicon.stem_ing(word, "NN", None, None), stem.and_then(|p| lexicon.append_ing(&p.phonemes))),
+            };
+            assert_eq!(actual.map(|p| p.phonemes.into_owned()), expected, "{} (-{})", word, suffix);
+            checked += 1;
+        }
+        assert!(checked > 500);
+    }
+
+    #[test]
+    fn test_append_suffixes() {
+        let us = Lexicon::new(Language::EnglishUS);
+        assert_eq!(us.append_ed("hˈIt"), "hˈIɾᵻd");
+        assert_eq!(us.append_ed("t"), "tɪd");
+        assert_eq!(us.append_ing("sˈIt").as_deref(), Some("sˈIɾɪŋ"));
+        assert_eq!(us.append_s("wˈɪʃ"), "wˈɪʃᵻz");
+        assert_eq!(us.append_ing(""), None);
+        let stem = us.stem_ing("running", "VBG", None, None).unwrap();
+        assert!(stem.phonemes.ends_with("nɪŋ") && !stem.phonemes.starts_with("run"));
+    }
+
+    #[test]
+    fn test_non_ascii_words() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        assert_eq!(lexicon.get_word("don’t", "VB", None, None), lexicon.get_word("don't", "VB", None, None));
+        assert_eq!(lexicon.get_word("dogs’", "NNS", None, None), lexicon.get_word("dogs'", "NNS", None, None));
+
+        // Words mixing multi-byte chars into every position suffixes are cut at
+        let pieces = [
+            "a", "s", "'", "’", "d", "e", "i", "n", "g", "es", "ed", "ing", "ies", "ck", "é", "ï", "ß", "ﬁ",
+            "日", "ğ", "e\u{301}", "🎉", "S", "İ",
+        ];
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |n: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % n as u64) as usize
+        };
+        for _ in 0..2000 {
+            let len = 1 + next(6);
+            let word: String = (0..len).map(|_| pieces[next(pieces.len())]).collect();
+            for tag in ["NN", "NNP", "VBD", "VBG"] {
+                lexicon.get_word(&word, tag, None, None);
+                lexicon.stem_s(&word, tag, None, None);
+                lexicon.stem_ed(&word, tag, None, None);
+                lexicon.stem_ing(&word, tag, None, None);
+                lexicon.split_compound(&word, tag, None);
+            }
+            lexicon.nearest_word(&word);
+            lexicon.append_ed(&word);
+            lexicon.append_ing(&word);
+        }
+        lexicon.coverage(["café’s naïve résumés İstanbul ﬁnding"].into_iter());
+    }
+
+    #[test]
+    fn test_diff() {
+        let dict = |entries: &[(&str, &str)]| -> HashMap<String, PhonemeEntry> {
+            entries.iter().map(|&(w, ps)| (w.to_string(), PhonemeEntry::Simple(ps.to_string()))).collect()
+        };
+        let old = Lexicon::from_entries(Language::EnglishUS, dict(&[("cat", "kˈæt"), ("dog", "dˈɔɡ")]), dict(&[("zeb", "zˈɛb")]));
+        let new = Lexicon::from_entries(Language::EnglishUS, dict(&[("cat", "kˈæt"), ("dog", "dˈɑɡ"), ("owl", "ˈWl")]), HashMap::new());
+
+        let diff = new.diff(&old);
+        let words = |entries: &[DiffEntry]| entries.iter().map(|e| (e.tier, e.word.clone())).collect::<Vec<_>>();
+        // Casing variants grown from the entries are left out
+        assert_eq!(words(&diff.added), [(Tier::Gold, "owl".to_string())]);
+        assert_eq!(words(&diff.removed), [(Tier::Silver, "zeb".to_string())]);
+        assert_eq!(words(&diff.changed), [(Tier::Gold, "dog".to_string())]);
+        assert_eq!(diff.changed[0].old, Some(PhonemeEntry::Simple("dˈɔɡ".to_string())));
+        assert!(new.diff(&new).is_empty());
+        assert!(new.golds.contains_key("Owl"));
+
+        let json = new.export_json(Tier::Gold).unwrap();
+        assert!(json.find("\"cat\"").unwrap() < json.find("\"dog\"").unwrap());
+        let back: HashMap<String, PhonemeEntry> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, dict(&[("cat", "kˈæt"), ("dog", "dˈɑɡ"), ("owl", "ˈWl")]));
+
+        // An entry wins over the variant grown from another casing of it
+        let lexicon = Lexicon::from_entries(Language::EnglishUS, dict(&[("polish", "pˈɑlɪʃ"), ("Polish", "pˈOlɪʃ")]), HashMap::new());
+        assert_eq!(lexicon.golds["Polish"], PhonemeEntry::Simple("pˈOlɪʃ".to_string()));
+        let mut lexicon = Lexicon::from_entries(Language::EnglishUS, HashMap::new(), HashMap::new());
+        lexicon.add_user_entry("Kokoro", PhonemeEntry::Simple("kˈOkəɹO".to_string()));
+        lexicon.add_user_entry("kokoro", PhonemeEntry::Simple("kəkˈɔɹO".to_string()));
+        lexicon.add_user_entry("Kokoro", PhonemeEntry::Simple("kˈOkəɹO".to_string()));
+        assert_eq!(lexicon.users["kokoro"], PhonemeEntry::Simple("kəkˈɔɹO".to_string()));
+        let back: HashMap<String, PhonemeEntry> = serde_json::from_str(&lexicon.export_json(Tier::User).unwrap()).unwrap();
+        assert_eq!(back.len(), 2);
+    }
+
+    #[test]
+    fn test_multi_tag_keys() {
+        let tagged = |keys: &[(&str, &str)]| {
+            PhonemeEntry::Tagged(keys.iter().map(|&(k, ps)| (k.to_string(), Some(ps.to_string()))).collect())
+        };
+        let mut lexicon = Lexicon::from_entries(Language::EnglishUS, HashMap::new(), HashMap::new());
+        let entry = tagged(&[("DEFAULT", "ɹˈid"), ("VBD|VBN", "ɹˈɛd"), ("VBN", "ɹˈɛːd"), ("VBZ", "ɹˈidz"), ("VBD|VBZ", "ɹˈAd")]);
+        assert_eq!(entry.unreachable_keys(), ["VBD|VBZ"]);
+        lexicon.add_user_entry("reed", entry);
+
+        let read = |tag: &str| lexicon.lookup("reed", tag, None, None).unwrap().phonemes;
+        assert_eq!(read("VBD"), "ɹˈɛd");
+        // Its own key wins over a "|" key listing it
+        assert_eq!(read("VBN"), "ɹˈɛːd");
+        assert_eq!(read("VB"), "ɹˈid");
+
+        // Reachable for one of its tags is reachable
+        assert!(tagged(&[("VBD|VBN", "a"), ("VBN", "b")]).unreachable_keys().is_empty());
+        assert!(PhonemeEntry::Simple("a".to_string()).unreachable_keys().is_empty());
+    }
+
+    #[test]
+    fn test_next_word_keys() {
+        let mut lexicon = Lexicon::from_entries(Language::EnglishUS, HashMap::new(), HashMap::new());
+        let entry = |keys: &[(&str, &str)]| {
+            PhonemeEntry::Tagged(keys.iter().map(|&(k, ps)| (k.to_string(), Some(ps.to_string()))).collect())
+        };
+        lexicon.add_user_entry("use", entry(&[("DEFAULT", "jˈuz"), ("VB", "jˈuːz"), ("next:to", "jˈus")]));
+        lexicon.add_user_entry("thee", entry(&[("DEFAULT", "ðə"), ("next:VOWEL", "ði")]));
+        lexicon.add_user_entry("the", entry(&[("DEFAULT", "ðə"), ("next:VOWEL", "ðiː")]));
+
+        let before = |next: &str| TokenContext {
+            future_vowel: Some(next.starts_with(['a', 'e', 'i', 'o', 'u'])),
+            next_word: Some(next.to_string()),
+            ..Default::default()
+        };
+        let read = |word: &str, tag: &str, ctx: Option<&TokenContext>| lexicon.get_word(word, tag, None, ctx).unwrap().phonemes;
+        // The next word wins over the tag
+        assert_eq!(read("use", "VB", Some(&before("to"))), "jˈus");
+        assert_eq!(read("use", "VB", Some(&before("it"))), "jˈuːz");
+        assert_eq!(read("use", "VB", None), "jˈuːz");
+        assert_eq!(read("thee", "DT", Some(&before("apple"))), "ði");
+        assert_eq!(read("thee", "DT", Some(&before("cat"))), "ðə");
+
+        // A user entry replaces the special case for its word
+        assert_eq!(read("the", "DT", Some(&before("apple"))), "ðiː");
+    }
+
+    #[test]
+    fn test_special_cases_table() {
+        let lexicon = Lexicon::new(Language::EnglishUS);
+        let read = |word: &str, tag: &str, future_vowel| {
+            let ctx = TokenContext { future_vowel, ..Default::default() };
+            lexicon.get_word(word, tag, None, Some(&ctx)).map(|p| p.phonemes)
+        };
+        assert_eq!(read("a", "DT", None).as_deref(), Some("ɐ"));
+        assert_eq!(read("The", "DT", Some(true)).as_deref(), Some("ði"));
+        assert_eq!(read("to", "TO", Some(false)).as_deref(), Some("tə"));
+        assert_eq!(read("in", "IN", Some(false)).as_deref(), Some("ɪn"));
+        assert_eq!(read("in", "RP", Some(false)).as_deref(), Some("ˈɪn"));
+        assert_eq!(read("vs.", "IN", None), read("versus", "NN", None));
+
+        // New special cases and other languages need only a table
+        let mut lexicon = Lexicon::from_entries(Language::Other("es".to_string()), HashMap::new(), HashMap::new());
+        assert!(lexicon.special_cases.is_empty());
+        lexicon.special_cases = SpecialCases::from_json(
+            r#"[{"words": ["y"], "rules": [{"when": {"next": "vowel"}, "then": {"say": "i"}}]}]"#,
+        )
+        .unwrap();
+        let ctx = TokenContext { future_vowel: Some(true), ..Default::default() };
+        assert_eq!(lexicon.get_word("y", "CC", None, Some(&ctx)).map(|p| p.phonemes).as_deref(), Some("i"));
+        assert_eq!(lexicon.get_word("y", "CC", None, None), None);
+    }
+}
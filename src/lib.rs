@@ -4,11 +4,76 @@ pub mod languages;
 pub mod lexicon;
 pub mod data;
 pub mod tagger;
+mod tagger_format;
 pub mod g2p;
 pub mod fallback;
+pub mod lts;
+pub mod options;
+pub mod normalize;
+pub mod oov;
+pub mod transliterate;
+pub mod script;
+pub mod stress;
+pub mod align;
+pub mod timing;
+pub mod phoneme;
+pub mod phonology;
+pub mod markup;
+pub mod spacy;
+pub mod result;
+pub mod post_process;
+pub mod pipeline;
+pub mod special_cases;
+pub mod stream;
+pub mod integrations;
+#[cfg(feature = "homograph-ml")]
+pub mod homograph;
+#[cfg(feature = "neural-fallback")]
+pub mod neural;
+#[cfg(feature = "parity")]
+pub mod parity;
+#[cfg(feature = "auto-detect")]
+pub mod multilingual;
+#[cfg(feature = "async")]
+pub mod async_g2p;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "config")]
+pub mod config;
 
-pub use g2p::G2P;
-pub use token::MToken;
-pub use lexicon::Lexicon;
-pub use language::Language;
-pub use fallback::{Fallback, EspeakFallback};
+pub use g2p::{G2P, G2PBuilder};
+pub use result::{G2PResult, G2PWarning, SentenceResult};
+pub use stream::SentenceStream;
+pub use token::{Intonation, MToken};
+pub use lexicon::{CoverageReport, DiffEntry, Lexicon, LexiconDiff, Pronunciation, Rating, Source, Tier};
+pub use oov::{OovEntry, OovRecorder, OovSource};
+pub use transliterate::Transliterator;
+pub use script::Script;
+pub use stress::Stress;
+pub use post_process::PostProcess;
+pub use pipeline::{BuiltinStage, PipelineCtx, Stage};
+pub use special_cases::SpecialCases;
+pub use phoneme::{Phoneme, PhonemeString};
+pub use integrations::kokoro;
+pub use align::PhonemeAlignment;
+pub use language::{Dialect, Language};
+pub use languages::{LanguagePack, LanguageRules, NumberVerbalizer};
+pub use fallback::{CachedFallback, Fallback, FallbackChain, PhonemeMap};
+#[cfg(feature = "espeak")]
+pub use fallback::EspeakFallback;
+pub use lts::LtsFallback;
+#[cfg(feature = "auto-detect")]
+pub use multilingual::{MultilingualG2P, Segment};
+#[cfg(feature = "async")]
+pub use async_g2p::AsyncG2P;
+#[cfg(feature = "config")]
+pub use config::{ConfigError, G2PConfig};
+pub use tagger::{PerceptronTagger, TagStream, TaggedWord, Tagger, TaggerError, TaggerOptions, TieBreak};
+pub use options::{
+    DateOrder, EmojiPolicy, FractionStyle, G2POptions, InputFormat, NegativeStyle, NumberLocale,
+    NumberStyle, OovPolicy, OutputFormat, PauseMarkers, ScriptPolicy, TokenizerKind, UnicodeForm,
+};
@@ -0,0 +1,668 @@
+//! Rule-based English letter-to-sound fallback.
+//!
+//! A port of the NRL rules (Elovitz et al., 1976): each letter has an ordered
+//! list of `(left context, letters, right context, phonemes)` rules and the
+//! first one that matches wins. Phonemes are written as ARPAbet-like codes,
+//! two uppercase letters for vowels and digraph consonants and one lowercase
+//! letter for the rest, then mapped to the lexicon's IPA.
+//!
+//! Context patterns use these classes besides literal letters:
+//!
+//! | char | matches                                       |
+//! |------|-----------------------------------------------|
+//! | ` `  | a word boundary                               |
+//! | `#`  | one or more vowels                            |
+//! | `:`  | zero or more consonants                       |
+//! | `^`  | one consonant                                 |
+//! | `.`  | one voiced consonant (B D V G J L M N R W Z)  |
+//! | `+`  | one front vowel (E I Y)                       |
+//! | `%`  | a suffix (ER E ES ED ING ELY), right side only |
+
+use crate::fallback::{Fallback, FallbackError};
+
+type Rule = (&'static str, &'static str, &'static str, &'static str);
+
+#[rustfmt::skip]
+const RULES: &[Rule] = &[
+    // A
+    ("", "A", " ", "AX"),
+    (" ", "ARE", " ", "AAr"),
+    (" ", "AR", "O", "AXr"),
+    ("", "AR", "#", "EHr"),
+    ("^", "AS", "#", "EYs"),
+    ("", "A", "WA", "AX"),
+    ("", "AW", "", "AO"),
+    (" :", "ANY", "", "EHnIY"),
+    ("", "A", "^+#", "EY"),
+    ("#:", "ALLY", "", "AXlIY"),
+    (" ", "AL", "#", "AXl"),
+    ("", "AGAIN", "", "AXgEHn"),
+    ("#:", "AG", "E", "IHj"),
+    ("", "A", "^+:#", "AE"),
+    (" :", "A", "^+ ", "EY"),
+    ("", "A", "^%", "EY"),
+    (" ", "ARR", "", "AXr"),
+    ("", "ARR", "", "AEr"),
+    (" :", "AR", " ", "AAr"),
+    ("", "AR", " ", "ER"),
+    ("", "AR", "", "AAr"),
+    ("", "AIR", "", "EHr"),
+    ("", "AI", "", "EY"),
+    ("", "AY", "", "EY"),
+    ("", "AU", "", "AO"),
+    ("#:", "AL", " ", "AXl"),
+    ("#:", "ALS", " ", "AXlz"),
+    ("", "ALK", "", "AOk"),
+    ("", "AL", "^", "AOl"),
+    (" :", "ABLE", "", "EYbAXl"),
+    ("", "ABLE", "", "AXbAXl"),
+    ("", "ANG", "+", "EYnj"),
+    ("", "A", "", "AE"),
+    // B
+    (" ", "BE", "^#", "bIH"),
+    ("", "BEING", "", "bIYIHNG"),
+    (" ", "BOTH", " ", "bOWTH"),
+    (" ", "BUS", "#", "bIHz"),
+    ("", "BUIL", "", "bIHl"),
+    ("", "B", "", "b"),
+    // C
+    (" ", "CH", "^", "k"),
+    ("^E", "CH", "", "k"),
+    ("", "CH", "", "CH"),
+    (" S", "CI", "#", "sAY"),
+    ("", "CI", "A", "SH"),
+    ("", "CI", "O", "SH"),
+    ("", "CI", "EN", "SH"),
+    ("", "C", "+", "s"),
+    ("", "CK", "", "k"),
+    ("", "COM", "%", "kAHm"),
+    ("", "C", "", "k"),
+    // D
+    ("#:", "DED", " ", "dIHd"),
+    (".E", "D", " ", "d"),
+    ("#:^E", "D", " ", "t"),
+    (" ", "DE", "^#", "dIH"),
+    (" ", "DO", " ", "dUW"),
+    (" ", "DOES", "", "dAHz"),
+    (" ", "DOING", "", "dUWIHNG"),
+    (" ", "DOW", "", "dAW"),
+    ("", "DU", "A", "jUW"),
+    ("", "D", "", "d"),
+    // E
+    ("#:", "E", " ", ""),
+    ("'^:", "E", " ", ""),
+    (" :", "E", " ", "IY"),
+    ("#", "ED", " ", "d"),
+    ("#:", "E", "D ", ""),
+    ("", "EV", "ER", "EHv"),
+    ("", "E", "^%", "IY"),
+    ("", "ERI", "#", "IYrIY"),
+    ("", "ERI", "", "EHrIH"),
+    ("#:", "ER", "#", "ER"),
+    ("", "ER", "#", "EHr"),
+    ("", "ER", "", "ER"),
+    (" ", "EVEN", "", "IYvEHn"),
+    ("#:", "E", "W", ""),
+    ("T", "EW", "", "UW"),
+    ("S", "EW", "", "UW"),
+    ("R", "EW", "", "UW"),
+    ("D", "EW", "", "UW"),
+    ("L", "EW", "", "UW"),
+    ("Z", "EW", "", "UW"),
+    ("N", "EW", "", "UW"),
+    ("J", "EW", "", "UW"),
+    ("TH", "EW", "", "UW"),
+    ("CH", "EW", "", "UW"),
+    ("SH", "EW", "", "UW"),
+    ("", "EW", "", "yUW"),
+    ("", "E", "O", "IY"),
+    ("#:S", "ES", " ", "IHz"),
+    ("#:C", "ES", " ", "IHz"),
+    ("#:G", "ES", " ", "IHz"),
+    ("#:Z", "ES", " ", "IHz"),
+    ("#:X", "ES", " ", "IHz"),
+    ("#:J", "ES", " ", "IHz"),
+    ("#:CH", "ES", " ", "IHz"),
+    ("#:SH", "ES", " ", "IHz"),
+    ("#:", "E", "S ", ""),
+    ("#:", "ELY", " ", "lIY"),
+    ("#:", "EMENT", "", "mEHnt"),
+    ("", "EFUL", "", "fUHl"),
+    ("", "EE", "", "IY"),
+    ("", "EARN", "", "ERn"),
+    (" ", "EAR", "^", "ER"),
+    ("", "EAD", "", "EHd"),
+    ("#:", "EA", " ", "IYAX"),
+    ("", "EA", "SU", "EH"),
+    ("", "EA", "", "IY"),
+    ("", "EIGH", "", "EY"),
+    ("", "EI", "", "IY"),
+    (" ", "EYE", "", "AY"),
+    ("", "EY", "", "IY"),
+    ("", "EU", "", "yUW"),
+    ("", "E", "", "EH"),
+    // F
+    ("", "FUL", "", "fUHl"),
+    ("", "F", "", "f"),
+    // G
+    ("", "GIV", "", "gIHv"),
+    (" ", "G", "I^", "g"),
+    ("", "GE", "T", "gEH"),
+    ("SU", "GGES", "", "gjEHs"),
+    ("", "GG", "", "g"),
+    (" B#", "G", "", "g"),
+    ("", "G", "+", "j"),
+    ("", "GREAT", "", "grEYt"),
+    ("#", "GH", "", ""),
+    ("", "G", "", "g"),
+    // H
+    (" ", "HAV", "", "hAEv"),
+    (" ", "HERE", "", "hIYr"),
+    (" ", "HOUR", "", "AWER"),
+    ("", "HOW", "", "hAW"),
+    ("", "H", "#", "h"),
+    ("", "H", "", ""),
+    // I
+    (" ", "IN", "", "IHn"),
+    (" ", "I", " ", "AY"),
+    ("", "IN", "D", "AYn"),
+    ("", "IER", "", "IYER"),
+    ("#:R", "IED", "", "IYd"),
+    ("", "IED", " ", "AYd"),
+    ("", "IEN", "", "IYEHn"),
+    ("", "IE", "T", "AYEH"),
+    (" :", "I", "%", "AY"),
+    ("", "I", "%", "IY"),
+    ("", "IE", "", "IY"),
+    ("", "I", "^+:#", "IH"),
+    ("", "IR", "#", "AYr"),
+    ("", "IZ", "%", "AYz"),
+    ("", "IS", "%", "AYz"),
+    ("", "I", "D%", "AY"),
+    ("+^", "I", "^+", "IH"),
+    ("", "I", "T%", "AY"),
+    ("#:^", "I", "^+", "IH"),
+    ("", "I", "^+", "AY"),
+    ("", "IR", "", "ER"),
+    ("", "IGH", "", "AY"),
+    ("", "ILD", "", "AYld"),
+    ("", "IGN", " ", "AYn"),
+    ("", "IGN", "^", "AYn"),
+    ("", "IGN", "%", "AYn"),
+    ("", "IQUE", "", "IYk"),
+    ("", "I", "", "IH"),
+    // J
+    ("", "J", "", "j"),
+    // K
+    (" ", "K", "N", ""),
+    ("", "K", "", "k"),
+    // L
+    ("", "LO", "C#", "lOW"),
+    ("L", "L", "", ""),
+    ("#:^", "L", "%", "AXl"),
+    ("", "LEAD", "", "lIYd"),
+    ("", "L", "", "l"),
+    // M
+    ("", "MOV", "", "mUWv"),
+    ("", "M", "", "m"),
+    // N
+    ("E", "NG", "+", "nj"),
+    ("", "NG", "R", "NGg"),
+    ("", "NG", "#", "NGg"),
+    ("", "NGL", "%", "NGgAXl"),
+    ("", "NG", "", "NG"),
+    ("", "NK", "", "NGk"),
+    (" ", "NOW", " ", "nAW"),
+    ("", "N", "", "n"),
+    // O
+    ("", "OF", " ", "AXv"),
+    ("", "OROUGH", "", "EROW"),
+    ("#:", "OR", " ", "ER"),
+    ("#:", "ORS", " ", "ERz"),
+    ("", "OR", "", "AOr"),
+    (" ", "ONE", "", "wAHn"),
+    ("", "OW", "", "OW"),
+    (" ", "OVER", "", "OWvER"),
+    ("", "OV", "", "AHv"),
+    ("", "O", "^%", "OW"),
+    ("", "O", "^EN", "OW"),
+    ("", "O", "^I#", "OW"),
+    ("", "OL", "D", "OWl"),
+    ("", "OUGHT", "", "AOt"),
+    ("", "OUGH", "", "AHf"),
+    (" ", "OU", "", "AW"),
+    ("H", "OU", "S#", "AW"),
+    ("", "OUS", "", "AXs"),
+    ("", "OUR", "", "AOr"),
+    ("", "OULD", "", "UHd"),
+    ("^", "OU", "^L", "AH"),
+    ("", "OUP", "", "UWp"),
+    ("", "OU", "", "AW"),
+    ("", "OY", "", "OY"),
+    ("", "OING", "", "OWIHNG"),
+    ("", "OI", "", "OY"),
+    ("", "OOR", "", "AOr"),
+    ("", "OOK", "", "UHk"),
+    ("", "OOD", "", "UHd"),
+    ("", "OO", "", "UW"),
+    ("", "O", "E", "OW"),
+    ("", "O", " ", "OW"),
+    ("", "OA", "", "OW"),
+    (" ", "ONLY", "", "OWnlIY"),
+    (" ", "ONCE", "", "wAHns"),
+    ("", "ON'T", "", "OWnt"),
+    ("C", "O", "N", "AA"),
+    ("", "O", "NG", "AO"),
+    (" :^", "O", "N", "AH"),
+    ("I", "ON", "", "AXn"),
+    ("#:", "ON", " ", "AXn"),
+    ("#^", "ON", "", "AXn"),
+    ("", "O", "ST ", "OW"),
+    ("", "OF", "^", "AOf"),
+    ("", "OTHER", "", "AHDHER"),
+    ("", "OSS", " ", "AOs"),
+    ("#:^", "OM", "", "AHm"),
+    ("", "O", "", "AA"),
+    // P
+    ("", "PH", "", "f"),
+    ("", "PEOP", "", "pIYp"),
+    ("", "POW", "", "pAW"),
+    ("", "PUT", " ", "pUHt"),
+    ("", "P", "", "p"),
+    // Q
+    ("", "QUAR", "", "kwAOr"),
+    ("", "QU", "", "kw"),
+    ("", "Q", "", "k"),
+    // R
+    (" ", "RE", "^#", "rIY"),
+    ("", "R", "", "r"),
+    // S
+    ("", "SH", "", "SH"),
+    ("#", "SION", "", "ZHAXn"),
+    ("", "SOME", "", "sAHm"),
+    ("#", "SUR", "#", "ZHER"),
+    ("", "SUR", "#", "SHER"),
+    ("#", "SU", "#", "ZHUW"),
+    ("#", "SSU", "#", "SHUW"),
+    ("#", "SED", " ", "zd"),
+    ("#", "S", "#", "z"),
+    ("", "SAID", "", "sEHd"),
+    ("^", "SION", "", "SHAXn"),
+    ("", "S", "S", ""),
+    (".", "S", " ", "z"),
+    ("#:.E", "S", " ", "z"),
+    ("#:^##", "S", " ", "z"),
+    ("#:^#", "S", " ", "s"),
+    ("U", "S", " ", "s"),
+    (" :#", "S", " ", "z"),
+    (" ", "SCH", "", "sk"),
+    ("", "S", "C+", ""),
+    ("#", "SM", "", "zm"),
+    ("#", "SN", "'", "zAXn"),
+    ("", "S", "", "s"),
+    // T
+    (" ", "THE", " ", "DHAX"),
+    ("", "TO", " ", "tUW"),
+    ("", "THAT", " ", "DHAEt"),
+    (" ", "THIS", " ", "DHIHs"),
+    (" ", "THEY", "", "DHEY"),
+    (" ", "THERE", "", "DHEHr"),
+    ("", "THER", "", "DHER"),
+    ("", "THEIR", "", "DHEHr"),
+    (" ", "THAN", " ", "DHAEn"),
+    (" ", "THEM", " ", "DHEHm"),
+    ("", "THESE", " ", "DHIYz"),
+    (" ", "THEN", "", "DHEHn"),
+    ("", "THROUGH", "", "THrUW"),
+    ("", "THOSE", "", "DHOWz"),
+    ("", "THOUGH", " ", "DHOW"),
+    (" ", "THUS", "", "DHAHs"),
+    ("", "TH", "", "TH"),
+    ("#:", "TED", " ", "tIHd"),
+    ("S", "TI", "#N", "CH"),
+    ("", "TI", "O", "SH"),
+    ("", "TI", "A", "SH"),
+    ("", "TIEN", "", "SHAXn"),
+    ("", "TUR", "#", "CHER"),
+    ("", "TU", "A", "CHUW"),
+    (" ", "TWO", "", "tUW"),
+    ("", "T", "", "t"),
+    // U
+    (" ", "UN", "I", "yUWn"),
+    (" ", "UN", "", "AHn"),
+    (" ", "UPON", "", "AXpAOn"),
+    ("T", "UR", "#", "UHr"),
+    ("S", "UR", "#", "UHr"),
+    ("R", "UR", "#", "UHr"),
+    ("D", "UR", "#", "UHr"),
+    ("L", "UR", "#", "UHr"),
+    ("Z", "UR", "#", "UHr"),
+    ("N", "UR", "#", "UHr"),
+    ("J", "UR", "#", "UHr"),
+    ("TH", "UR", "#", "UHr"),
+    ("CH", "UR", "#", "UHr"),
+    ("SH", "UR", "#", "UHr"),
+    ("", "UR", "#", "yUHr"),
+    ("", "UR", "", "ER"),
+    ("", "U", "^ ", "AH"),
+    ("", "U", "^^", "AH"),
+    ("", "UY", "", "AY"),
+    (" G", "U", "#", ""),
+    ("G", "U", "%", ""),
+    ("G", "U", "#", "w"),
+    ("#N", "U", "", "yUW"),
+    ("T", "U", "", "UW"),
+    ("S", "U", "", "UW"),
+    ("R", "U", "", "UW"),
+    ("D", "U", "", "UW"),
+    ("L", "U", "", "UW"),
+    ("Z", "U", "", "UW"),
+    ("N", "U", "", "UW"),
+    ("J", "U", "", "UW"),
+    ("TH", "U", "", "UW"),
+    ("CH", "U", "", "UW"),
+    ("SH", "U", "", "UW"),
+    ("", "U", "", "yUW"),
+    // V
+    ("", "VIEW", "", "vyUW"),
+    ("", "V", "", "v"),
+    // W
+    (" ", "WERE", "", "wER"),
+    ("", "WA", "S", "wAA"),
+    ("", "WA", "T", "wAA"),
+    ("", "WHERE", "", "WHEHr"),
+    ("", "WHAT", "", "WHAAt"),
+    ("", "WHOL", "", "hOWl"),
+    ("", "WHO", "", "hUW"),
+    ("", "WH", "", "WH"),
+    ("", "WAR", "", "wAOr"),
+    ("", "WOR", "^", "wER"),
+    ("", "WR", "", "r"),
+    ("", "W", "", "w"),
+    // X
+    ("", "X", "", "ks"),
+    // Y
+    ("", "YOUNG", "", "yAHNG"),
+    (" ", "YOU", "", "yUW"),
+    (" ", "YES", "", "yEHs"),
+    (" ", "Y", "", "y"),
+    ("#:^", "Y", " ", "IY"),
+    ("#:^", "Y", "I", "IY"),
+    (" :", "Y", " ", "AY"),
+    (" :", "Y", "#", "AY"),
+    (" :", "Y", "^+:#", "IH"),
+    (" :", "Y", "^#", "AY"),
+    ("", "Y", "", "IH"),
+    // Z
+    ("", "Z", "", "z"),
+];
+
+const SUFFIXES: &[&str] = &["ING", "ELY", "ER", "ES", "ED", "E"];
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'A' | b'E' | b'I' | b'O' | b'U')
+}
+
+fn is_consonant(c: u8) -> bool {
+    c.is_ascii_uppercase() && !is_vowel(c)
+}
+
+/// Whether `c` matches a single-character pattern class or literal.
+fn matches_class(pattern: u8, c: u8) -> bool {
+    match pattern {
+        b' ' => !c.is_ascii_alphabetic(),
+        b'^' => is_consonant(c),
+        b'.' => matches!(c, b'B' | b'D' | b'V' | b'G' | b'J' | b'L' | b'M' | b'N' | b'R' | b'W' | b'Z'),
+        b'+' => matches!(c, b'E' | b'I' | b'Y'),
+        _ => pattern == c,
+    }
+}
+
+/// Matches `pattern` against the text ending at `end`, right to left.
+fn matches_left(text: &[u8], end: usize, pattern: &str) -> bool {
+    let mut i = end;
+    for &p in pattern.as_bytes().iter().rev() {
+        match p {
+            b'#' => {
+                let start = i;
+                while i > 0 && is_vowel(text[i - 1]) {
+                    i -= 1;
+                }
+                if i == start {
+                    return false;
+                }
+            }
+            b':' => {
+                while i > 0 && is_consonant(text[i - 1]) {
+                    i -= 1;
+                }
+            }
+            _ => {
+                if i == 0 || !matches_class(p, text[i - 1]) {
+                    return false;
+                }
+                i -= 1;
+            }
+        }
+    }
+    true
+}
+
+/// Matches `pattern` against the text starting at `start`, left to right.
+fn matches_right(text: &[u8], start: usize, pattern: &str) -> bool {
+    let mut i = start;
+    for &p in pattern.as_bytes() {
+        match p {
+            b'#' => {
+                let from = i;
+                while i < text.len() && is_vowel(text[i]) {
+                    i += 1;
+                }
+                if i == from {
+                    return false;
+                }
+            }
+            b':' => {
+                while i < text.len() && is_consonant(text[i]) {
+                    i += 1;
+                }
+            }
+            b'%' => match SUFFIXES.iter().find(|s| text[i..].starts_with(s.as_bytes())) {
+                Some(suffix) => i += suffix.len(),
+                None => return false,
+            },
+            _ => {
+                if i >= text.len() || !matches_class(p, text[i]) {
+                    return false;
+                }
+                i += 1;
+            }
+        }
+    }
+    true
+}
+
+/// Phoneme codes for a word, or `None` if it has no letters.
+fn to_codes(word: &str) -> Option<String> {
+    // Pad with boundaries; anything that isn't a letter or apostrophe splits the word
+    let mut text = vec![b' '];
+    text.extend(word.chars().map(|c| match c.to_ascii_uppercase() {
+        c @ ('A'..='Z' | '\'') => c as u8,
+        _ => b' ',
+    }));
+    text.push(b' ');
+    if !text.iter().any(u8::is_ascii_uppercase) {
+        return None;
+    }
+
+    let mut codes = String::new();
+    let mut i = 1;
+    while i < text.len() - 1 {
+        let c = text[i];
+        if !c.is_ascii_uppercase() {
+            i += 1;
+            continue;
+        }
+        let rule = RULES.iter().find(|(left, target, right, _)| {
+            target.as_bytes()[0] == c
+                && text[i..].starts_with(target.as_bytes())
+                && matches_left(&text, i, left)
+                && matches_right(&text, i + target.len(), right)
+        });
+        match rule {
+            Some((_, target, _, phonemes)) => {
+                codes.push_str(phonemes);
+                i += target.len();
+            }
+            None => i += 1,
+        }
+    }
+    Some(codes)
+}
+
+fn is_vowel_code(code: &str) -> bool {
+    matches!(
+        code,
+        "IY" | "IH" | "EY" | "EH" | "AE" | "AA" | "AO" | "OW" | "UH" | "UW" | "ER" | "AX" | "AH" | "AY" | "AW" | "OY"
+    )
+}
+
+/// IPA for a code, in the lexicon's notation for the dialect.
+fn code_to_ipa(code: &str, stressed: bool, british: bool) -> &'static str {
+    match code {
+        "IY" if stressed => "iː",
+        "IY" => "i",
+        "IH" => "ɪ",
+        "EY" => "e‍ɪ",
+        "EH" => "ɛ",
+        "AE" if british => "a",
+        "AE" => "æ",
+        "AA" if british => "ɒ",
+        "AA" => "ɑː",
+        "AO" => "ɔː",
+        "OW" if british => "ə‍ʊ",
+        "OW" => "o‍ʊ",
+        "UH" => "ʊ",
+        "UW" => "uː",
+        "ER" if stressed => "ɜː",
+        "ER" if british => "ə",
+        "ER" => "ɚ",
+        "AX" => "ə",
+        "AH" => "ʌ",
+        "AY" => "a‍ɪ",
+        "AW" => "a‍ʊ",
+        "OY" => "ɔ‍ɪ",
+        "TH" => "θ",
+        "DH" => "ð",
+        "SH" => "ʃ",
+        "ZH" => "ʒ",
+        "NG" => "ŋ",
+        "CH" => "t‍ʃ",
+        "WH" => "w",
+        "j" => "d‍ʒ",
+        "y" => "j",
+        "r" => "ɹ",
+        "g" => "ɡ",
+        "p" => "p",
+        "b" => "b",
+        "t" => "t",
+        "d" => "d",
+        "k" => "k",
+        "f" => "f",
+        "v" => "v",
+        "s" => "s",
+        "z" => "z",
+        "h" => "h",
+        "m" => "m",
+        "n" => "n",
+        "l" => "l",
+        "w" => "w",
+        _ => "",
+    }
+}
+
+/// Converts phoneme codes to IPA, stressing the first full vowel.
+fn codes_to_ipa(codes: &str, british: bool) -> String {
+    let mut parsed = Vec::new();
+    let mut chars = codes.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let len = if c.is_ascii_uppercase() && chars.next().is_some() { 2 } else { 1 };
+        parsed.push(&codes[i..i + len]);
+    }
+
+    let stress = parsed
+        .iter()
+        .position(|&code| is_vowel_code(code) && code != "AX")
+        .or_else(|| parsed.iter().position(|&code| is_vowel_code(code)));
+    let mut ipa = String::new();
+    for (i, &code) in parsed.iter().enumerate() {
+        // Non-rhotic: British English drops an r that isn't followed by a vowel
+        if british && code == "r" && !parsed.get(i + 1).is_some_and(|&next| is_vowel_code(next)) {
+            continue;
+        }
+        if Some(i) == stress {
+            ipa.push('ˈ');
+        }
+        // The British "o" in "hot" is ɒ, but "ar" is still the long ɑː
+        if british && code == "AA" && parsed.get(i + 1) == Some(&"r") {
+            ipa.push_str("ɑː");
+        } else {
+            ipa.push_str(code_to_ipa(code, Some(i) == stress, british));
+        }
+    }
+    ipa
+}
+
+/// Pure-Rust letter-to-sound fallback for English, needing no system library.
+pub struct LtsFallback {
+    british: bool,
+}
+
+impl LtsFallback {
+    pub fn new(british: bool) -> Self {
+        Self { british }
+    }
+}
+
+impl Fallback for LtsFallback {
+    fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+        match to_codes(word) {
+            Some(codes) if !codes.is_empty() => Ok(codes_to_ipa(&codes, self.british)),
+            _ => Err(FallbackError::NoPhonemes {
+                word: word.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes() {
+        assert_eq!(to_codes("cat").as_deref(), Some("kAEt"));
+        assert_eq!(to_codes("phone").as_deref(), Some("fOWn"));
+        assert_eq!(to_codes("light").as_deref(), Some("lAYt"));
+        assert_eq!(to_codes("ship").as_deref(), Some("SHIHp"));
+        assert_eq!(to_codes("123"), None);
+    }
+
+    #[test]
+    fn test_us_phonemes() {
+        let lts = LtsFallback::new(false);
+        assert_eq!(lts.phonemize("cat").unwrap(), "kˈæt");
+        assert_eq!(lts.phonemize("phone").unwrap(), "fˈo‍ʊn");
+        assert_eq!(lts.phonemize("light").unwrap(), "lˈa‍ɪt");
+        assert_eq!(lts.phonemize("Zorbington").unwrap().chars().next(), Some('z'));
+        assert!(lts.phonemize("!?").is_err());
+    }
+
+    #[test]
+    fn test_gb_phonemes() {
+        let lts = LtsFallback::new(true);
+        assert_eq!(lts.phonemize("cat").unwrap(), "kˈat");
+        assert_eq!(lts.phonemize("phone").unwrap(), "fˈə‍ʊn");
+        assert_eq!(lts.phonemize("car").unwrap(), "kˈɑː");
+    }
+}
@@ -0,0 +1,219 @@
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+
+/// What code blocks are read as.
+pub const CODE_BLOCK_OMITTED: &str = "Code block omitted.";
+
+static MD_HEADER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s{0,3}#{1,6}\s+(.*?)(?:\s+#+)?\s*$").unwrap());
+static MD_LIST_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(?:[-*+]|\d+[.)])\s+(.*)$").unwrap());
+static MD_QUOTE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s{0,3}(?:>\s?)+").unwrap());
+static MD_RULE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s{0,3}(?:(?:-\s*){3,}|(?:\*\s*){3,}|(?:_\s*){3,})$").unwrap());
+static MD_IMAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap());
+static MD_LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\]]+)\]\(([^)]*)\)").unwrap());
+static MD_AUTOLINK_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<((?:https?://|mailto:)[^>\s]+)>").unwrap());
+static MD_CODE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"`+([^`]+)`+").unwrap());
+static MD_EMPHASIS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__|~~([^~]+)~~|\*([^*\s][^*]*)\*|\b_([^_\s][^_]*)_\b").unwrap()
+});
+
+static HTML_DROP_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<!--.*?-->|<(script|style|head)\b[^>]*>.*?</(?:script|style|head)\s*>").unwrap());
+static HTML_PRE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?is)<pre\b[^>]*>.*?</pre\s*>").unwrap());
+static HTML_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<(/?)([a-zA-Z][a-zA-Z0-9]*)\b[^>]*>").unwrap());
+static HTML_ENTITY_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&(#[0-9]+|#[xX][0-9a-fA-F]+|[a-zA-Z]+);").unwrap());
+
+/// Ends `text` with a period unless it already ends in punctuation, so a
+/// heading or list item is read as a sentence of its own.
+fn end_sentence(text: &mut String) {
+    let trimmed = text.trim_end().len();
+    if text[..trimmed].ends_with(|c: char| c.is_alphanumeric()) {
+        text.insert(trimmed, '.');
+    }
+}
+
+/// Whether a link target is Python misaki's `[text](feature)` syntax, which
+/// is left for `G2P::preprocess` rather than stripped as a hyperlink.
+fn is_misaki_feature(target: &str) -> bool {
+    let unsigned = target.strip_prefix(['-', '+']).unwrap_or(target);
+    (!unsigned.is_empty() && unsigned.chars().all(|c| c.is_ascii_digit()) || unsigned == "0.5")
+        || (target.len() > 2 && target.starts_with('/') && target.ends_with('/') && !target[1..target.len() - 1].contains('/'))
+        || (target.len() > 2 && target.starts_with('#') && target.ends_with('#'))
+}
+
+fn strip_inline_markdown(line: &str) -> String {
+    let line = MD_IMAGE_REGEX.replace_all(line, "$1");
+    let line = MD_LINK_REGEX.replace_all(&line, |caps: &Captures| {
+        if is_misaki_feature(&caps[2]) { caps[0].to_string() } else { caps[1].to_string() }
+    });
+    let line = MD_AUTOLINK_REGEX.replace_all(&line, "$1");
+    // Code spans are literal text, so escape what `strip_html` would take for markup
+    let line = MD_CODE_REGEX.replace_all(&line, |caps: &Captures| {
+        caps[1].replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+    });
+    let line = MD_EMPHASIS_REGEX.replace_all(&line, |caps: &Captures| {
+        caps.iter().skip(1).flatten().next().map_or("", |m| m.as_str()).to_string()
+    });
+    line.into_owned()
+}
+
+/// Reduces Markdown to the text a listener should hear: headings and list
+/// items become sentences, emphasis, quote and list markers are dropped,
+/// links and images are read by their text, and fenced code blocks are read
+/// as "Code block omitted." Inline HTML outside code spans is stripped as by
+/// `strip_html`. Misaki's own `[text](/phonemes/)` links are kept.
+pub fn strip_markdown(text: &str) -> String {
+    let mut out = Vec::new();
+    let mut fence: Option<&str> = None;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            out.push(CODE_BLOCK_OMITTED.to_string());
+            continue;
+        }
+        if MD_RULE_REGEX.is_match(line) {
+            out.push(String::new());
+            continue;
+        }
+
+        let line = MD_QUOTE_REGEX.replace(line, "");
+        let (line, sentence) = if let Some(caps) = MD_HEADER_REGEX.captures(&line) {
+            (caps[1].to_string(), true)
+        } else if let Some(caps) = MD_LIST_REGEX.captures(&line) {
+            (caps[1].to_string(), true)
+        } else {
+            (line.into_owned(), false)
+        };
+        let mut line = strip_inline_markdown(&line);
+        if sentence {
+            end_sentence(&mut line);
+        }
+        out.push(line);
+    }
+    strip_html(&out.join("\n"))
+}
+
+/// Decodes the named entities common in web text and all numeric ones.
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(num) = entity.strip_prefix('#') {
+        let code = match num.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => num.parse().ok()?,
+        };
+        return char::from_u32(code);
+    }
+    Some(match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "ndash" => '–',
+        "mdash" => '—',
+        "hellip" => '…',
+        "lsquo" | "rsquo" => '\'',
+        "ldquo" | "rdquo" => '"',
+        "copy" => '©',
+        "eacute" => 'é',
+        _ => return None,
+    })
+}
+
+/// Reduces HTML to its text: scripts, styles and comments are dropped,
+/// `<pre>` blocks are read as "Code block omitted.", block elements break
+/// lines (headings, paragraphs, list items and table cells ending as
+/// sentences, and headings, paragraphs and block quotes leaving a blank line
+/// as a paragraph break), other tags are removed and entities decoded.
+pub fn strip_html(text: &str) -> String {
+    let text = HTML_DROP_REGEX.replace_all(text, "");
+    let text = HTML_PRE_REGEX.replace_all(&text, format!("\n\n{}\n\n", CODE_BLOCK_OMITTED).as_str());
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in HTML_TAG_REGEX.captures_iter(&text) {
+        let m = caps.get(0).unwrap();
+        out.push_str(&text[last..m.start()]);
+        last = m.end();
+        let closing = !caps[1].is_empty();
+        match caps[2].to_ascii_lowercase().as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" => {
+                if closing {
+                    end_sentence(&mut out);
+                }
+                out.push_str("\n\n");
+            }
+            "li" | "td" | "th" | "dt" | "dd" | "caption" if closing => {
+                end_sentence(&mut out);
+                out.push('\n');
+            }
+            "blockquote" | "hr" => out.push_str("\n\n"),
+            "br" | "div" | "tr" | "ul" | "ol" | "dl" | "table" | "section" | "article" | "header" | "footer"
+            | "nav" | "aside" | "main" | "figure" | "figcaption" => out.push('\n'),
+            _ => {}
+        }
+    }
+    out.push_str(&text[last..]);
+
+    let decoded = HTML_ENTITY_REGEX.replace_all(&out, |caps: &Captures| {
+        decode_entity(&caps[1]).map_or_else(|| caps[0].to_string(), String::from)
+    });
+    // Collapse the blank lines left by removed markup, keeping one as a
+    // paragraph break
+    let mut lines: Vec<&str> = Vec::new();
+    for line in decoded.lines().map(str::trim) {
+        if !line.is_empty() || lines.last().is_some_and(|l| !l.is_empty()) {
+            lines.push(line);
+        }
+    }
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_markdown() {
+        let md = "# Getting started\n\n\
+                  Install it with **cargo** and _read_ the [docs](https://docs.rs).\n\n\
+                  ```rust\nlet x = 1;\n```\n\n\
+                  - First step\n\
+                  2. Second step!\n\
+                  > Quoted `code` and snake_case_name\n\
+                  ---\n\
+                  Say [Kokoro](/kˈOkəɹO/).";
+        assert_eq!(
+            strip_markdown(md),
+            "Getting started.\n\n\
+             Install it with cargo and read the docs.\n\n\
+             Code block omitted.\n\n\
+             First step.\n\
+             Second step!\n\
+             Quoted code and snake_case_name\n\n\
+             Say [Kokoro](/kˈOkəɹO/)."
+        );
+        assert_eq!(strip_markdown("Returns `Vec<String>` or <b>None</b>."), "Returns Vec<String> or None.");
+        assert_eq!(strip_markdown("Use `&amp;` here"), "Use &amp; here");
+    }
+
+    #[test]
+    fn test_strip_html() {
+        let html = "<html><head><title>T</title></head><body>\
+                    <h1>News</h1><p>Fish &amp; chips&nbsp;cost <b>&#163;5</b>.</p>\
+                    <script>alert(1)</script><!-- note -->\
+                    <ul><li>One</li><li>Two</li></ul><pre>fn main() {}</pre></body></html>";
+        assert_eq!(
+            strip_html(html),
+            "News.\n\nFish & chips cost £5.\n\nOne.\nTwo.\n\nCode block omitted."
+        );
+    }
+}
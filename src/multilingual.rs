@@ -0,0 +1,233 @@
+//! Text in several languages, read sentence by sentence: each sentence's
+//! language is detected with whatlang and it is handed to the `G2P` of that
+//! language, built the first time it is needed.
+
+use crate::g2p::{G2P, G2PError};
+use crate::language::Language;
+use crate::languages;
+use crate::options::G2POptions;
+use crate::stream::sentences;
+use crate::token::MToken;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use whatlang::Lang;
+
+/// A run of sentences in one language.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub text: String,
+    /// Char offsets of `text` in the input.
+    pub char_span: (usize, usize),
+    /// The language the segment was read as; `None` if it was detected as
+    /// one with no pack installed, in which case it is left unread.
+    pub language: Option<Language>,
+    /// ISO 639-3 code of the detected language; `None` if detection was
+    /// sure of no sentence in the text and the default language was used.
+    pub detected: Option<&'static str>,
+    pub phonemes: Option<String>,
+    /// Tokens of the segment, with char spans into the whole input.
+    pub tokens: Vec<MToken>,
+}
+
+/// Reads each sentence in its own language (see `G2P::multilingual`).
+pub struct MultilingualG2P {
+    /// Read where detection isn't sure, and the dialect English is read in
+    /// when it is English.
+    pub default: Language,
+    /// Options for the G2P of every language.
+    pub options: G2POptions,
+    /// Written in place of the segments that are left unread.
+    pub unk: String,
+    /// Each language's G2P, built on first use outside the lock, so that
+    /// reading a built language doesn't wait on another's build.
+    g2ps: Mutex<HashMap<Language, Arc<OnceLock<Arc<G2P>>>>>,
+}
+
+impl MultilingualG2P {
+    pub fn new(default: Language) -> Self {
+        Self::with_options(default, G2POptions::default())
+    }
+
+    pub fn with_options(default: Language, options: G2POptions) -> Self {
+        Self { default, options, unk: "❓".to_string(), g2ps: Mutex::new(HashMap::new()) }
+    }
+
+    /// The language to read `text` in, and the ISO 639-3 code it was
+    /// detected as. The language is `None` if no pack reads it.
+    pub fn detect(&self, text: &str) -> (Option<Language>, Option<&'static str>) {
+        self.resolve(sure(text))
+    }
+
+    fn resolve(&self, lang: Option<Lang>) -> (Option<Language>, Option<&'static str>) {
+        match lang {
+            Some(lang) => (self.language(lang), Some(lang.code())),
+            None => (Some(self.default.clone()), None),
+        }
+    }
+
+    /// The bundled language for `lang` if its feature is enabled, otherwise
+    /// the one registered under its ISO 639-3 code.
+    fn language(&self, lang: Lang) -> Option<Language> {
+        let language = match lang {
+            Lang::Eng if self.default.is_english() => self.default.clone(),
+            Lang::Eng => Language::EnglishUS,
+            #[cfg(feature = "spanish")]
+            Lang::Spa => Language::Spanish,
+            #[cfg(feature = "french")]
+            Lang::Fra => Language::French,
+            #[cfg(feature = "german")]
+            Lang::Deu => Language::German,
+            #[cfg(feature = "portuguese")]
+            Lang::Por if self.default == Language::PortuguesePT => Language::PortuguesePT,
+            #[cfg(feature = "portuguese")]
+            Lang::Por => Language::PortugueseBR,
+            #[cfg(feature = "ja")]
+            Lang::Jpn => Language::Japanese,
+            #[cfg(feature = "zh")]
+            Lang::Cmn => Language::Mandarin,
+            #[cfg(feature = "ko")]
+            Lang::Kor => Language::Korean,
+            #[cfg(feature = "hi")]
+            Lang::Hin => Language::Hindi,
+            #[cfg(feature = "vi")]
+            Lang::Vie => Language::Vietnamese,
+            other => {
+                let code = other.code();
+                return languages::is_registered(code).then(|| Language::Other(code.to_string()));
+            }
+        };
+        Some(language)
+    }
+
+    fn g2p_for(&self, lang: &Language) -> Arc<G2P> {
+        let cell = {
+            let mut g2ps = self.g2ps.lock().unwrap();
+            Arc::clone(g2ps.entry(lang.clone()).or_default())
+        };
+        let g2p = cell.get_or_init(|| Arc::new(G2P::with_options(lang.clone(), self.options.clone())));
+        Arc::clone(g2p)
+    }
+
+    /// Phonemes for `text`, and the segments they were read from. Segments
+    /// left unread are `unk` in the phonemes.
+    ///
+    /// Sentences too short for detection to be sure of go with the sentence
+    /// before them, or at the start of the text with the first one it is
+    /// sure of; the default language is only used if it is sure of none.
+    pub fn g2p(&self, text: &str) -> Result<(String, Vec<Segment>), G2PError> {
+        let spans = sentences(text, self.g2p_for(&self.default).rules());
+        let detected: Vec<Option<Lang>> = spans.iter().map(|&(start, end)| sure(&text[start..end])).collect();
+        let mut current = detected.iter().flatten().next().copied();
+
+        // Neighbouring sentences in one language are read together, keeping
+        // their context
+        let mut runs: Vec<(usize, usize, Option<Lang>)> = Vec::new();
+        for (&(start, end), &lang) in spans.iter().zip(&detected) {
+            current = lang.or(current);
+            match runs.last_mut() {
+                Some(run) if run.2 == current => run.1 = end,
+                _ => runs.push((start, end, current)),
+            }
+        }
+
+        let mut segments = Vec::new();
+        for (start, end, lang) in runs {
+            let (language, detected) = self.resolve(lang);
+            let offset = text[..start].chars().count();
+            let segment_text = &text[start..end];
+            let (phonemes, tokens) = match &language {
+                Some(lang) => {
+                    let (ps, mut tokens) = self.g2p_for(lang).g2p(segment_text)?;
+                    for tk in &mut tokens {
+                        tk.char_span = (tk.char_span.0 + offset, tk.char_span.1 + offset);
+                    }
+                    (Some(ps), tokens)
+                }
+                None => (None, Vec::new()),
+            };
+            segments.push(Segment {
+                text: segment_text.to_string(),
+                char_span: (offset, offset + segment_text.chars().count()),
+                language,
+                detected,
+                phonemes,
+                tokens,
+            });
+        }
+
+        let phonemes: Vec<&str> = segments.iter().map(|s| s.phonemes.as_deref().unwrap_or(&self.unk)).collect();
+        Ok((phonemes.join(" "), segments))
+    }
+}
+
+/// The language of `text`, if whatlang is sure of it.
+fn sure(text: &str) -> Option<Lang> {
+    whatlang::detect(text).filter(|info| info.is_reliable()).map(|info| info.lang())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::{EnglishNumbers, LanguagePack, LanguageRules};
+    use crate::lexicon::{Lexicon, PhonemeEntry};
+    use crate::options::NumberLocale;
+
+    #[test]
+    fn test_unsupported_language() {
+        let g2p = G2P::multilingual();
+        let text = "The weather is lovely today and we are going outside. Is it? \
+            Dzisiaj jest bardzo ładna pogoda, więc pójdziemy razem na długi spacer do parku.";
+        let (ps, segments) = g2p.g2p(text).unwrap();
+        assert_eq!(segments.len(), 2);
+        // The short question goes with the sentence before it
+        assert_eq!(segments[0].text, "The weather is lovely today and we are going outside. Is it?");
+        assert_eq!(segments[0].language, Some(Language::EnglishUS));
+        assert_eq!(segments[1].detected, Some("pol"));
+        assert_eq!(segments[1].language, None);
+        assert_eq!(segments[1].char_span.0, 61);
+        assert!(ps.ends_with(" ❓"));
+    }
+
+    struct NoRules;
+
+    impl LanguageRules for NoRules {
+        fn apply_rules(&self, _word: &str, _tag: &str, _lexicon: &Lexicon) -> Option<String> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_registered_language() {
+        languages::register("ita", || LanguagePack {
+            golds: [("questo", "kwˈesto"), ("libro", "lˈibro"), ("molto", "mˈolto"), ("bello", "bˈɛllo")]
+                .into_iter()
+                .map(|(w, ps)| (w.to_string(), PhonemeEntry::Simple(ps.to_string())))
+                .collect(),
+            silvers: HashMap::new(),
+            rules: Box::new(NoRules),
+            numbers: Box::new(EnglishNumbers),
+            tokenizer: None,
+            number_locale: NumberLocale::European,
+        });
+        let g2p = MultilingualG2P::new(Language::EnglishGB);
+        let text = "I have read this book twice already. \
+            Questo libro è molto bello, e ho deciso di leggerlo di nuovo durante le vacanze estive.";
+        let (_, segments) = g2p.g2p(text).unwrap();
+        assert_eq!(segments[0].language, Some(Language::EnglishGB));
+        assert_eq!(segments[1].language, Some(Language::Other("ita".to_string())));
+        let libro = &segments[1].tokens[1];
+        assert_eq!(libro.phonemes.as_deref(), Some("lˈibro"));
+        assert_eq!(&text[libro.char_span.0..libro.char_span.1], "libro");
+    }
+
+    #[cfg(feature = "spanish")]
+    #[test]
+    fn test_spanish() {
+        let g2p = G2P::multilingual();
+        let (_, segments) = g2p.g2p(
+            "The weather is lovely today and we are going outside. \
+            Espero que hayan dormido bien y que tengan un buen día de trabajo en la oficina con todos sus compañeros.",
+        ).unwrap();
+        assert_eq!(segments[1].language, Some(Language::Spanish));
+    }
+}
@@ -0,0 +1,199 @@
+//! Optional neural OOV fallback (`neural-fallback` feature).
+//!
+//! Runs a grapheme-to-phoneme seq2seq model exported to ONNX with greedy
+//! decoding. The model takes two `int64` inputs, the grapheme ids of the word
+//! shaped `[1, n]` and the phoneme ids decoded so far shaped `[1, t]`
+//! (starting with `<s>`), and returns logits shaped `[1, t, phonemes.len()]`;
+//! the last step's best phoneme is appended until `</s>` or `max_len`. ONNX
+//! Runtime is loaded dynamically, so `libonnxruntime` must be installed (or
+//! pointed to by `ORT_DYLIB_PATH`).
+
+use crate::fallback::{Fallback, FallbackError, rate};
+use ort::session::Session;
+use ort::value::Tensor;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use thiserror::Error;
+
+const BOS: &str = "<s>";
+const EOS: &str = "</s>";
+const UNK: &str = "<unk>";
+
+#[derive(Error, Debug)]
+pub enum NeuralError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("onnx runtime error: {0}")]
+    Onnx(#[from] ort::Error),
+    #[error("vocabulary is missing '{0}'")]
+    MissingToken(&'static str),
+}
+
+/// The JSON file shipped next to the model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NeuralConfig {
+    /// Input symbols in id order: lowercase letters plus `<pad>`, `<unk>`,
+    /// `<s>` and `</s>`.
+    pub graphemes: Vec<String>,
+    /// Output symbols in id order, in the lexicon's phoneme notation, with
+    /// the same special tokens.
+    pub phonemes: Vec<String>,
+    /// Most phonemes to decode for one word.
+    pub max_len: usize,
+}
+
+impl NeuralConfig {
+    fn id(symbols: &[String], symbol: &'static str) -> Result<i64, NeuralError> {
+        symbols
+            .iter()
+            .position(|s| s == symbol)
+            .map(|i| i as i64)
+            .ok_or(NeuralError::MissingToken(symbol))
+    }
+
+    /// Checks that both vocabularies have the special tokens decoding needs.
+    pub fn validate(&self) -> Result<(), NeuralError> {
+        for symbol in [BOS, EOS, UNK] {
+            Self::id(&self.graphemes, symbol)?;
+            Self::id(&self.phonemes, symbol)?;
+        }
+        Ok(())
+    }
+
+    /// Grapheme ids for `word`, wrapped in `<s>` and `</s>`.
+    pub fn encode(&self, word: &str) -> Result<Vec<i64>, NeuralError> {
+        let unk = Self::id(&self.graphemes, UNK)?;
+        let mut ids = vec![Self::id(&self.graphemes, BOS)?];
+        for c in word.to_lowercase().chars() {
+            let id = self.graphemes.iter().position(|g| g.chars().eq([c]));
+            ids.push(id.map_or(unk, |i| i as i64));
+        }
+        ids.push(Self::id(&self.graphemes, EOS)?);
+        Ok(ids)
+    }
+
+    /// Phonemes for decoded ids, skipping special tokens.
+    pub fn decode(&self, ids: &[i64]) -> String {
+        ids.iter()
+            .filter_map(|&id| self.phonemes.get(usize::try_from(id).ok()?))
+            .filter(|p| !(p.starts_with('<') && p.ends_with('>')))
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+/// OOV fallback backed by a seq2seq ONNX model.
+pub struct NeuralFallback {
+    config: NeuralConfig,
+    session: Mutex<Session>,
+}
+
+impl NeuralFallback {
+    pub fn load(model_path: impl AsRef<Path>, config_path: impl AsRef<Path>) -> Result<Self, NeuralError> {
+        let config: NeuralConfig = serde_json::from_str(&fs::read_to_string(config_path)?)?;
+        config.validate()?;
+        let session = Session::builder()?.commit_from_file(model_path)?;
+        Ok(Self {
+            config,
+            session: Mutex::new(session),
+        })
+    }
+
+    fn decode_greedy(&self, session: &mut Session, word: &str) -> Result<Vec<i64>, NeuralError> {
+        let input = self.config.encode(word)?;
+        let bos = NeuralConfig::id(&self.config.phonemes, BOS)?;
+        let eos = NeuralConfig::id(&self.config.phonemes, EOS)?;
+        let mut decoded = vec![bos];
+        while decoded.len() <= self.config.max_len {
+            let graphemes = Tensor::from_array(([1, input.len()], input.clone()))?;
+            let prefix = Tensor::from_array(([1, decoded.len()], decoded.clone()))?;
+            let outputs = session.run(ort::inputs![graphemes, prefix])?;
+            let (_, logits) = outputs[0].try_extract_tensor::<f32>()?;
+            let vocab = self.config.phonemes.len();
+            let last = &logits[logits.len().saturating_sub(vocab)..];
+            let Some(next) = last.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1)).map(|(i, _)| i as i64) else {
+                break;
+            };
+            if next == eos {
+                break;
+            }
+            decoded.push(next);
+        }
+        Ok(decoded)
+    }
+
+    /// `phonemize` with the session already locked.
+    fn phonemize_with(&self, session: &mut Session, word: &str) -> Result<String, FallbackError> {
+        let ids = self.decode_greedy(session, word).map_err(|e| FallbackError::Model {
+            word: word.to_string(),
+            error: e.to_string(),
+        })?;
+        let phonemes = self.config.decode(&ids);
+        if phonemes.is_empty() {
+            return Err(FallbackError::NoPhonemes {
+                word: word.to_string(),
+            });
+        }
+        Ok(phonemes)
+    }
+}
+
+impl Fallback for NeuralFallback {
+    fn phonemize(&self, word: &str) -> Result<String, FallbackError> {
+        let mut session = self
+            .session
+            .lock()
+            .map_err(|e| FallbackError::MutexPoisoned(format!("{:?}", e)))?;
+        self.phonemize_with(&mut session, word)
+    }
+
+    fn phonemize_batch(&self, words: &[&str]) -> Vec<(String, u8)> {
+        let Ok(mut session) = self.session.lock() else {
+            tracing::warn!("neural fallback mutex poisoned");
+            return vec![(String::new(), 0); words.len()];
+        };
+        words.iter().map(|word| rate(word, self.phonemize_with(&mut session, word))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> NeuralConfig {
+        serde_json::from_str(
+            r#"{
+                "graphemes": ["<pad>", "<unk>", "<s>", "</s>", "a", "c", "t"],
+                "phonemes": ["<pad>", "<unk>", "<s>", "</s>", "k", "æ", "t", "ˈ"],
+                "max_len": 16
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encode() {
+        let config = config();
+        assert_eq!(config.encode("Cat").unwrap(), [2, 5, 4, 6, 3]);
+        assert_eq!(config.encode("cab").unwrap(), [2, 5, 4, 1, 3]);
+    }
+
+    #[test]
+    fn test_decode() {
+        let config = config();
+        assert_eq!(config.decode(&[2, 4, 7, 5, 6]), "kˈæt");
+        assert_eq!(config.decode(&[2, 99, 4]), "k");
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut config = config();
+        assert!(config.validate().is_ok());
+        config.phonemes.retain(|p| p != "</s>");
+        assert!(matches!(config.validate(), Err(NeuralError::MissingToken("</s>"))));
+    }
+}
@@ -0,0 +1,1078 @@
+use crate::options::{DateOrder, FractionStyle, UnicodeForm};
+use regex::Regex;
+use std::sync::LazyLock;
+use unicode_normalization::UnicodeNormalization;
+
+/// A number immediately followed by a unit symbol: "10km", "-5°C", "100%", "2.5 kg" (when glued).
+static MEASUREMENT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<num>[-−]?(?:\d+(?:,\d{3})*(?:\.\d+)?|\.\d+))(?P<unit>[^\d\s].*)$").unwrap()
+});
+
+/// Clock times: "3:30pm", "14:05", "7 a.m." (when glued), "3pm".
+static TIME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<h>\d{1,2})(?::(?P<m>\d{2}))?(?P<ampm>[AaPp]\.?[Mm]\.?)?$").unwrap()
+});
+
+/// ISO dates: "2024-01-05".
+static ISO_DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?P<y>\d{4})-(?P<m>\d{2})-(?P<d>\d{2})$").unwrap());
+
+/// Slash dates: "5/1/2024", "12/25/24". Which field is the month depends on `DateOrder`.
+static SLASH_DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?P<a>\d{1,2})/(?P<b>\d{1,2})/(?P<y>\d{4}|\d{2})$").unwrap());
+
+/// Numeric fractions: "1/2", "22/7".
+static FRACTION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?P<n>\d{1,6})/(?P<d>\d{1,6})$").unwrap());
+
+/// Numeric ranges joined by a hyphen or en dash: "5-10", "1939–1945", "2.5-3".
+static RANGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<a>\d+(?:,\d{3})*(?:\.\d+)?)[-–](?P<b>\d+(?:,\d{3})*(?:\.\d+)?)$").unwrap()
+});
+
+/// Scientific notation: "1e9", "3.5e-4", "6.02E+23".
+static SCIENTIFIC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<m>[-−]?\d+(?:\.\d+)?)[eE](?P<e>[-+−]?\d{1,3})$").unwrap()
+});
+
+/// Version strings: "1.21.3", "v2.0", "v3". Two-part numbers without a "v"
+/// prefix are ordinary decimals unless context says otherwise.
+static VERSION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(?P<v>[vV])(?P<vnum>\d+(?:\.\d+)+)|(?P<dotted>\d+(?:\.\d+){2,}))$").unwrap()
+});
+
+/// URLs: anything with a scheme or "www.", or a host ending in a common
+/// top-level domain ("example.com/docs").
+static URL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(?:(?:https?|ftp)://\S+|www\.[a-z0-9-]+(?:\.[a-z0-9-]+)+\S*|[a-z0-9-]+(?:\.[a-z0-9-]+)*\.(?:com|org|net|edu|gov|io|ai|dev|app|co|uk|de|fr|info|me|us)(?::\d+)?(?:/\S*)?)$",
+    )
+    .unwrap()
+});
+
+/// Email addresses: "john.smith@example.org".
+static EMAIL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)+$").unwrap()
+});
+
+/// Hashtags and mentions: "#MachineLearning", "@openai". "#1" is not one.
+static HANDLE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?P<sigil>[#@])(?P<body>\w*[A-Za-z]\w*)$").unwrap());
+
+/// A single emoji, including skin tone modifiers, variation selectors, ZWJ
+/// sequences ("👩‍💻") and flags ("🇺🇸").
+pub(crate) const EMOJI_PATTERN: &str = r"(?:\p{Regional_Indicator}{2}|(?:\p{Emoji_Presentation}|\p{Extended_Pictographic}\x{FE0F})[\x{FE0F}\x{1F3FB}-\x{1F3FF}]*(?:\x{200D}\p{Extended_Pictographic}[\x{FE0F}\x{1F3FB}-\x{1F3FF}]*)*)";
+
+static EMOJI_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!("^{}$", EMOJI_PATTERN)).unwrap());
+
+/// Common emoji and their Unicode short names, without modifiers.
+const EMOJI_NAMES: &[(&str, &str)] = &[
+    ("😀", "grinning face"),
+    ("😃", "grinning face with big eyes"),
+    ("😄", "grinning face with smiling eyes"),
+    ("😁", "beaming face with smiling eyes"),
+    ("😆", "grinning squinting face"),
+    ("😅", "grinning face with sweat"),
+    ("🤣", "rolling on the floor laughing"),
+    ("😂", "face with tears of joy"),
+    ("🙂", "slightly smiling face"),
+    ("🙃", "upside-down face"),
+    ("😉", "winking face"),
+    ("😊", "smiling face with smiling eyes"),
+    ("😇", "smiling face with halo"),
+    ("🥰", "smiling face with hearts"),
+    ("😍", "smiling face with heart-eyes"),
+    ("🤩", "star-struck"),
+    ("😘", "face blowing a kiss"),
+    ("😋", "face savoring food"),
+    ("😛", "face with tongue"),
+    ("😜", "winking face with tongue"),
+    ("🤪", "zany face"),
+    ("🤔", "thinking face"),
+    ("🤐", "zipper-mouth face"),
+    ("🤨", "face with raised eyebrow"),
+    ("😐", "neutral face"),
+    ("😑", "expressionless face"),
+    ("😶", "face without mouth"),
+    ("😏", "smirking face"),
+    ("😒", "unamused face"),
+    ("🙄", "face with rolling eyes"),
+    ("😬", "grimacing face"),
+    ("😌", "relieved face"),
+    ("😔", "pensive face"),
+    ("😴", "sleeping face"),
+    ("😷", "face with medical mask"),
+    ("🤒", "face with thermometer"),
+    ("🤢", "nauseated face"),
+    ("🤮", "face vomiting"),
+    ("🥵", "hot face"),
+    ("🥶", "cold face"),
+    ("🤯", "exploding head"),
+    ("🥳", "partying face"),
+    ("😎", "smiling face with sunglasses"),
+    ("🤓", "nerd face"),
+    ("😕", "confused face"),
+    ("😟", "worried face"),
+    ("😮", "face with open mouth"),
+    ("😲", "astonished face"),
+    ("😳", "flushed face"),
+    ("🥺", "pleading face"),
+    ("😢", "crying face"),
+    ("😭", "loudly crying face"),
+    ("😱", "face screaming in fear"),
+    ("😞", "disappointed face"),
+    ("😩", "weary face"),
+    ("😤", "face with steam from nose"),
+    ("😡", "enraged face"),
+    ("😠", "angry face"),
+    ("🤬", "face with symbols on mouth"),
+    ("💀", "skull"),
+    ("💩", "pile of poo"),
+    ("🤡", "clown face"),
+    ("👻", "ghost"),
+    ("👽", "alien"),
+    ("🤖", "robot"),
+    ("😺", "grinning cat"),
+    ("🙈", "see-no-evil monkey"),
+    ("💋", "kiss mark"),
+    ("💯", "hundred points"),
+    ("💥", "collision"),
+    ("💫", "dizzy"),
+    ("💦", "sweat droplets"),
+    ("💬", "speech balloon"),
+    ("💤", "zzz"),
+    ("👋", "waving hand"),
+    ("👌", "OK hand"),
+    ("✌", "victory hand"),
+    ("🤞", "crossed fingers"),
+    ("👈", "backhand index pointing left"),
+    ("👉", "backhand index pointing right"),
+    ("👆", "backhand index pointing up"),
+    ("👇", "backhand index pointing down"),
+    ("👍", "thumbs up"),
+    ("👎", "thumbs down"),
+    ("✊", "raised fist"),
+    ("👊", "oncoming fist"),
+    ("👏", "clapping hands"),
+    ("🙌", "raising hands"),
+    ("🙏", "folded hands"),
+    ("💪", "flexed biceps"),
+    ("👀", "eyes"),
+    ("🧠", "brain"),
+    ("❤", "red heart"),
+    ("🧡", "orange heart"),
+    ("💛", "yellow heart"),
+    ("💚", "green heart"),
+    ("💙", "blue heart"),
+    ("💜", "purple heart"),
+    ("🖤", "black heart"),
+    ("💔", "broken heart"),
+    ("💕", "two hearts"),
+    ("💖", "sparkling heart"),
+    ("🔥", "fire"),
+    ("✨", "sparkles"),
+    ("⭐", "star"),
+    ("🌟", "glowing star"),
+    ("☀", "sun"),
+    ("🌈", "rainbow"),
+    ("☔", "umbrella with rain drops"),
+    ("⚡", "high voltage"),
+    ("❄", "snowflake"),
+    ("🌍", "globe showing Europe-Africa"),
+    ("🌹", "rose"),
+    ("🌸", "cherry blossom"),
+    ("🍀", "four leaf clover"),
+    ("🍕", "pizza"),
+    ("🍔", "hamburger"),
+    ("🍟", "french fries"),
+    ("🍰", "shortcake"),
+    ("🎂", "birthday cake"),
+    ("🍺", "beer mug"),
+    ("🍷", "wine glass"),
+    ("☕", "hot beverage"),
+    ("🐶", "dog face"),
+    ("🐱", "cat face"),
+    ("🦄", "unicorn"),
+    ("🐍", "snake"),
+    ("🦀", "crab"),
+    ("🎉", "party popper"),
+    ("🎊", "confetti ball"),
+    ("🎁", "wrapped gift"),
+    ("🎈", "balloon"),
+    ("🏆", "trophy"),
+    ("🥇", "first place medal"),
+    ("⚽", "soccer ball"),
+    ("🎵", "musical note"),
+    ("🎶", "musical notes"),
+    ("📷", "camera"),
+    ("📱", "mobile phone"),
+    ("💻", "laptop"),
+    ("📈", "chart increasing"),
+    ("📉", "chart decreasing"),
+    ("📌", "pushpin"),
+    ("📝", "memo"),
+    ("📅", "calendar"),
+    ("🔗", "link"),
+    ("🔒", "locked"),
+    ("🔑", "key"),
+    ("💡", "light bulb"),
+    ("💰", "money bag"),
+    ("💸", "money with wings"),
+    ("🚀", "rocket"),
+    ("🚗", "automobile"),
+    ("✈", "airplane"),
+    ("🏠", "house"),
+    ("⏰", "alarm clock"),
+    ("⌛", "hourglass done"),
+    ("✅", "check mark button"),
+    ("✔", "check mark"),
+    ("❌", "cross mark"),
+    ("❓", "red question mark"),
+    ("❗", "red exclamation mark"),
+    ("⚠", "warning"),
+    ("🚫", "prohibited"),
+    ("🆗", "OK button"),
+    ("🆕", "NEW button"),
+    ("👨‍💻", "man technologist"),
+    ("👩‍💻", "woman technologist"),
+    ("🤷", "person shrugging"),
+    ("🤦", "person facepalming"),
+];
+
+/// (symbol, unit singular, unit plural, subunit singular, subunit plural)
+const CURRENCIES: &[(&str, &str, &str, &str, &str)] = &[
+    ("$", "dollar", "dollars", "cent", "cents"),
+    ("£", "pound", "pounds", "penny", "pence"),
+    ("€", "euro", "euros", "cent", "cents"),
+    ("¥", "yen", "yen", "sen", "sen"),
+    ("₹", "rupee", "rupees", "paisa", "paise"),
+];
+
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September",
+    "October", "November", "December",
+];
+
+/// (symbol, singular, plural), US spelling. GB spelling is derived in `localize`.
+const UNITS: &[(&str, &str, &str)] = &[
+    ("%", "percent", "percent"),
+    ("‰", "per mille", "per mille"),
+    ("°C", "degree Celsius", "degrees Celsius"),
+    ("°F", "degree Fahrenheit", "degrees Fahrenheit"),
+    ("°", "degree", "degrees"),
+    ("km", "kilometer", "kilometers"),
+    ("m", "meter", "meters"),
+    ("cm", "centimeter", "centimeters"),
+    ("mm", "millimeter", "millimeters"),
+    ("mi", "mile", "miles"),
+    ("ft", "foot", "feet"),
+    ("in", "inch", "inches"),
+    ("yd", "yard", "yards"),
+    ("kg", "kilogram", "kilograms"),
+    ("g", "gram", "grams"),
+    ("mg", "milligram", "milligrams"),
+    ("lb", "pound", "pounds"),
+    ("lbs", "pound", "pounds"),
+    ("oz", "ounce", "ounces"),
+    ("l", "liter", "liters"),
+    ("L", "liter", "liters"),
+    ("ml", "milliliter", "milliliters"),
+    ("mL", "milliliter", "milliliters"),
+    ("km/h", "kilometer per hour", "kilometers per hour"),
+    ("kph", "kilometer per hour", "kilometers per hour"),
+    ("mph", "mile per hour", "miles per hour"),
+    ("ms", "millisecond", "milliseconds"),
+    ("sec", "second", "seconds"),
+    ("min", "minute", "minutes"),
+    ("h", "hour", "hours"),
+    ("hr", "hour", "hours"),
+    ("hrs", "hour", "hours"),
+    ("KB", "kilobyte", "kilobytes"),
+    ("kB", "kilobyte", "kilobytes"),
+    ("MB", "megabyte", "megabytes"),
+    ("GB", "gigabyte", "gigabytes"),
+    ("TB", "terabyte", "terabytes"),
+    ("Hz", "hertz", "hertz"),
+    ("kHz", "kilohertz", "kilohertz"),
+    ("MHz", "megahertz", "megahertz"),
+    ("GHz", "gigahertz", "gigahertz"),
+    ("W", "watt", "watts"),
+    ("kW", "kilowatt", "kilowatts"),
+    ("V", "volt", "volts"),
+];
+
+/// Symbols too ambiguous to expand when they stand alone after a number ("5 in a row").
+const DETACHED_EXCLUDED: &[&str] = &["in", "m", "g", "l", "h", "W", "V"];
+
+fn localize(words: &str, british: bool) -> String {
+    if british {
+        words.replace("meter", "metre").replace("liter", "litre")
+    } else {
+        words.to_string()
+    }
+}
+
+fn is_singular(number: &str) -> bool {
+    matches!(number.trim_start_matches(['-', '−']), "1")
+}
+
+/// Prepares raw input for tokenization: applies `form`, folds curly
+/// apostrophes and quotes to ASCII, and drops zero-width characters. Zero-width
+/// joiners inside emoji sequences ("👩‍💻") are kept.
+pub fn clean_text(text: &str, form: Option<UnicodeForm>) -> String {
+    let text: String = match form {
+        Some(UnicodeForm::Nfc) => text.nfc().collect(),
+        Some(UnicodeForm::Nfkc) => text.nfkc().collect(),
+        None => text.to_string(),
+    };
+    let mut cleaned = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '‘' | '’' | 'ʼ' | '‛' | '′' => cleaned.push('\''),
+            '“' | '”' | '„' | '‟' | '″' => cleaned.push('"'),
+            '\u{200B}' | '\u{200C}' | '\u{2060}' | '\u{FEFF}' | '\u{00AD}' => {}
+            '\u{200D}' => {
+                let is_symbol = |c: &char| !c.is_ascii() && !c.is_alphanumeric();
+                if cleaned.chars().last().is_some_and(|p| is_symbol(&p)) && chars.peek().is_some_and(is_symbol) {
+                    cleaned.push(c);
+                }
+            }
+            _ => cleaned.push(c),
+        }
+    }
+    cleaned
+}
+
+/// Spoken form of a unit symbol given the number it quantifies.
+pub fn unit_words(symbol: &str, number: &str, british: bool) -> Option<String> {
+    let &(_, singular, plural) = UNITS.iter().find(|(s, _, _)| *s == symbol)?;
+    let words = if is_singular(number) { singular } else { plural };
+    Some(localize(words, british))
+}
+
+/// Like `unit_words`, but only for symbols that are safe to read as a unit when
+/// they appear as a separate word after a number ("10 km", but not "5 in").
+pub fn detached_unit_words(symbol: &str, number: &str, british: bool) -> Option<String> {
+    if DETACHED_EXCLUDED.contains(&symbol) {
+        return None;
+    }
+    unit_words(symbol, number, british)
+}
+
+/// Expands a number glued to a unit symbol ("10km", "-5°C", "100%") into words.
+/// `verbalize` turns the numeric part into words.
+pub fn expand_measurement(
+    word: &str,
+    british: bool,
+    verbalize: impl Fn(&str) -> String,
+) -> Option<String> {
+    let caps = MEASUREMENT_REGEX.captures(word)?;
+    let number = caps["num"].replace('−', "-");
+    let unit = unit_words(&caps["unit"], &number, british)?;
+    Some(format!("{} {}", verbalize(&number), unit))
+}
+
+/// Spoken form of an am/pm marker. Spelled as capitals so the lexicon reads
+/// the letter names rather than the word "am".
+pub fn meridiem(word: &str) -> Option<&'static str> {
+    match word.to_lowercase().replace('.', "").as_str() {
+        "am" => Some("AM"),
+        "pm" => Some("PM"),
+        _ => None,
+    }
+}
+
+/// Expands a clock time: "3:30pm" → "three thirty PM", "14:05" → "fourteen oh five",
+/// "3pm" → "three PM". A bare number without minutes or am/pm is not a time.
+pub fn expand_time(word: &str, verbalize: impl Fn(&str) -> String) -> Option<String> {
+    let caps = TIME_REGEX.captures(word)?;
+    let ampm = caps.name("ampm").and_then(|m| meridiem(m.as_str()));
+    let minutes = caps.name("m");
+    if ampm.is_none() && minutes.is_none() {
+        return None;
+    }
+
+    let hour: u32 = caps["h"].parse().ok()?;
+    let minute: u32 = minutes.map_or(Some(0), |m| m.as_str().parse().ok())?;
+    let valid_hour = if ampm.is_some() { (1..=12).contains(&hour) } else { hour <= 23 };
+    if !valid_hour || minute > 59 {
+        return None;
+    }
+
+    let mut words = vec![verbalize(&hour.to_string())];
+    match minute {
+        0 if ampm.is_some() => {}
+        0 if hour == 0 || hour > 12 => words.push("hundred".to_string()),
+        0 => words.push("o'clock".to_string()),
+        1..=9 => words.push(format!("oh {}", verbalize(&minute.to_string()))),
+        _ => words.push(verbalize(&minute.to_string())),
+    }
+    if let Some(ampm) = ampm {
+        words.push(ampm.to_string());
+    }
+    Some(words.join(" "))
+}
+
+/// Full month name for a capitalized month name or abbreviation ("Jan", "Sept", "March").
+pub fn month_name(word: &str) -> Option<&'static str> {
+    if !word.chars().next().is_some_and(|c| c.is_uppercase()) {
+        return None;
+    }
+    let lower = word.to_lowercase();
+    if lower == "sept" {
+        return Some(MONTHS[8]);
+    }
+    MONTHS.iter().copied().find(|m| {
+        let m = m.to_lowercase();
+        m == lower || (lower.len() == 3 && m.starts_with(&lower))
+    })
+}
+
+fn days_in_month(month: u32) -> u32 {
+    match month {
+        2 => 29,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Expands an ISO ("2024-01-05") or slash ("5/1/2024") date to spoken form.
+/// `number` reads the year, `ordinal` reads the day.
+pub fn expand_date(
+    word: &str,
+    order: DateOrder,
+    number: impl Fn(&str) -> String,
+    ordinal: impl Fn(u32) -> String,
+) -> Option<String> {
+    let (year, month, day) = if let Some(caps) = ISO_DATE_REGEX.captures(word) {
+        (caps["y"].to_string(), caps["m"].parse().ok()?, caps["d"].parse().ok()?)
+    } else if let Some(caps) = SLASH_DATE_REGEX.captures(word) {
+        let (a, b): (u32, u32) = (caps["a"].parse().ok()?, caps["b"].parse().ok()?);
+        match order {
+            DateOrder::MonthFirst => (caps["y"].to_string(), a, b),
+            DateOrder::DayFirst => (caps["y"].to_string(), b, a),
+        }
+    } else {
+        return None;
+    };
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(month) {
+        return None;
+    }
+
+    let month = MONTHS[month as usize - 1];
+    let year = match year.strip_prefix('0') {
+        Some(digit) if year.len() == 2 => format!("oh {}", number(digit)),
+        _ => number(&year),
+    };
+    Some(match order {
+        DateOrder::MonthFirst => format!("{} {}, {}", month, ordinal(day), year),
+        DateOrder::DayFirst => format!("the {} of {}, {}", ordinal(day), month, year),
+    })
+}
+
+/// Expands a numeric fraction: "3/4" → "three quarters" (`Natural`) or
+/// "three over four" (`Over`). `number` reads the numerator and denominator,
+/// `ordinal` the denominator in natural style.
+pub fn expand_fraction(
+    word: &str,
+    style: FractionStyle,
+    number: impl Fn(&str) -> String,
+    ordinal: impl Fn(u32) -> String,
+) -> Option<String> {
+    // Idioms that look like fractions but aren't read as one
+    if word == "24/7" {
+        return Some(format!("{} {}", number("24"), number("7")));
+    }
+
+    let caps = FRACTION_REGEX.captures(word)?;
+    let (n, d): (u32, u32) = (caps["n"].parse().ok()?, caps["d"].parse().ok()?);
+    if d == 0 {
+        return None;
+    }
+    let numerator = number(&n.to_string());
+    if style == FractionStyle::Over || !(2..=10).contains(&d) {
+        return Some(format!("{} over {}", numerator, number(&d.to_string())));
+    }
+
+    let denominator = match (d, n == 1) {
+        (2, true) => "half".to_string(),
+        (2, false) => "halves".to_string(),
+        (4, true) => "quarter".to_string(),
+        (4, false) => "quarters".to_string(),
+        (_, true) => ordinal(d),
+        (_, false) => format!("{}s", ordinal(d)),
+    };
+    Some(format!("{} {}", numerator, denominator))
+}
+
+/// Expands a numeric range: "5-10" → "five to ten". `number` reads each end,
+/// so year-like ends ("1939–1945") get the year reading. Phone-number shapes
+/// ("555-0190", "555-1234") and ends with a leading zero aren't ranges.
+pub fn expand_range(word: &str, number: impl Fn(&str) -> String) -> Option<String> {
+    let caps = RANGE_REGEX.captures(word)?;
+    let (a, b) = (&caps["a"], &caps["b"]);
+    let digits = |s: &str, n: usize| s.len() == n && s.chars().all(|c| c.is_ascii_digit());
+    if (b.len() > 1 && b.starts_with('0') && !b.starts_with("0.")) || (digits(a, 3) && digits(b, 4)) {
+        return None;
+    }
+    Some(format!("{} to {}", number(&caps["a"]), number(&caps["b"])))
+}
+
+/// Expands scientific notation: "3.5e-4" → "three point five times ten to the minus four".
+pub fn expand_scientific(word: &str, number: impl Fn(&str) -> String) -> Option<String> {
+    let caps = SCIENTIFIC_REGEX.captures(word)?;
+    let exponent = caps["e"].trim_start_matches('+');
+    Some(format!(
+        "{} times ten to the {}",
+        number(&caps["m"]),
+        number(exponent)
+    ))
+}
+
+/// Reads dotted version components: "2.0" → "two point oh".
+pub fn version_words(version: &str, number: impl Fn(&str) -> String) -> Option<String> {
+    let parts: Vec<&str> = version.split('.').collect();
+    if parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    let words: Vec<String> = parts
+        .iter()
+        .map(|p| if p.trim_start_matches('0').is_empty() { "oh".to_string() } else { number(p) })
+        .collect();
+    Some(words.join(" point "))
+}
+
+/// Expands a version string: "1.21.3" → "one point twenty-one point three",
+/// "v2.0" → "version two point oh". A "v" needs a dotted number after it, so
+/// "V8" stays a name.
+pub fn expand_version(word: &str, number: impl Fn(&str) -> String) -> Option<String> {
+    let caps = VERSION_REGEX.captures(word)?;
+    if let Some(version) = caps.name("vnum") {
+        let spoken = version_words(version.as_str(), number)?;
+        Some(format!("version {}", spoken))
+    } else {
+        version_words(&caps["dotted"], number)
+    }
+}
+
+/// True for words that announce a following version number ("version 2.0").
+pub fn is_version_word(word: &str) -> bool {
+    matches!(
+        word.to_lowercase().as_str(),
+        "version" | "v" | "ver" | "release" | "firmware"
+    )
+}
+
+/// True for dotted initialisms read letter by letter: "U.S.", "Ph.D.", "A.I",
+/// "e.g.". Every dot-separated part is one or two letters.
+pub fn is_initialism(word: &str) -> bool {
+    if !word.contains('.') {
+        return false;
+    }
+    let parts: Vec<&str> = word.strip_suffix('.').unwrap_or(word).split('.').collect();
+    parts.len() >= 2
+        && parts
+            .iter()
+            .all(|p| (1..=2).contains(&p.chars().count()) && p.chars().all(char::is_alphabetic))
+}
+
+/// Spoken name of a separator inside a URL or email address.
+fn address_symbol(c: char) -> Option<&'static str> {
+    Some(match c {
+        '.' => "dot",
+        '/' => "slash",
+        ':' => "colon",
+        '-' => "dash",
+        '_' => "underscore",
+        '@' => "at",
+        '?' => "question mark",
+        '=' => "equals",
+        '&' => "and",
+        '#' => "hash",
+        '~' => "tilde",
+        '%' => "percent",
+        '+' => "plus",
+        _ => return None,
+    })
+}
+
+/// Reads an address piece by piece. Alphanumeric runs are kept as words,
+/// except two-letter domain labels ("io" in "github.io") which are spelled out.
+fn address_words(address: &str) -> String {
+    let mut pieces: Vec<(String, Option<&str>)> = Vec::new();
+    for c in address.chars() {
+        match address_symbol(c) {
+            Some(name) => pieces.push((c.to_string(), Some(name))),
+            None => match pieces.last_mut() {
+                Some((run, None)) => run.push(c),
+                _ => pieces.push((c.to_string(), None)),
+            },
+        }
+    }
+
+    let mut words = Vec::new();
+    for (k, (text, name)) in pieces.iter().enumerate() {
+        if let Some(name) = name {
+            words.push(name.to_string());
+            continue;
+        }
+        let after_dot = k > 0 && pieces[k - 1].0 == ".";
+        let ends_label = pieces.get(k + 1).is_none_or(|(t, _)| matches!(t.as_str(), "." | "/" | ":"));
+        if after_dot && ends_label && text.len() == 2 && text.chars().all(|c| c.is_ascii_alphabetic()) {
+            let letters: Vec<String> = text.to_uppercase().chars().map(String::from).collect();
+            words.push(letters.join(" "));
+        } else {
+            words.push(text.clone());
+        }
+    }
+    words.join(" ")
+}
+
+/// Expands a URL: "https://example.com/docs" → "example dot com slash docs".
+/// The scheme is dropped; "www" is spelled out.
+pub fn expand_url(word: &str) -> Option<String> {
+    if !URL_REGEX.is_match(word) {
+        return None;
+    }
+    let rest = word.split_once("://").map_or(word, |(_, rest)| rest);
+    let spoken = address_words(rest);
+    Some(match spoken.strip_prefix("www ") {
+        Some(tail) => format!("W W W {}", tail),
+        None => spoken,
+    })
+}
+
+/// Expands an email address: "john@example.org" → "john at example dot org".
+pub fn expand_email(word: &str) -> Option<String> {
+    if !EMAIL_REGEX.is_match(word) {
+        return None;
+    }
+    Some(address_words(word))
+}
+
+/// Splits a camel- or snake-case handle into words: "MachineLearning" →
+/// ["Machine", "Learning"], "HTMLParser" → ["HTML", "Parser"], "web3_dev" →
+/// ["web", "3", "dev"].
+pub fn split_handle(body: &str) -> Vec<String> {
+    let chars: Vec<char> = body.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for (k, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if let Some(prev) = current.chars().last() {
+            let next_lower = chars.get(k + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = (prev.is_lowercase() && c.is_uppercase())
+                || (prev.is_uppercase() && c.is_uppercase() && next_lower)
+                || (prev.is_ascii_digit() != c.is_ascii_digit());
+            if boundary {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+/// Splits a run of lowercase letters into the fewest dictionary words of two
+/// or more letters ("throwbackthursday" → ["throwback", "thursday"]).
+/// `lookup` returns the dictionary spelling of a word, if it is one.
+pub fn segment_words(word: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<Vec<String>> {
+    let n = word.len();
+    if !word.is_ascii() {
+        return None;
+    }
+    // best[k]: (word count, start of last word, its spelling) for word[..k]
+    let mut best: Vec<Option<(usize, usize, String)>> = vec![None; n + 1];
+    best[0] = Some((0, 0, String::new()));
+    for end in 2..=n {
+        for start in end.saturating_sub(20)..=end - 2 {
+            let Some((count, ..)) = best[start] else { continue };
+            if best[end].as_ref().is_some_and(|(c, ..)| *c <= count + 1) {
+                continue;
+            }
+            if let Some(spelling) = lookup(&word[start..end]) {
+                best[end] = Some((count + 1, start, spelling));
+            }
+        }
+    }
+
+    let mut words = Vec::new();
+    let mut end = n;
+    while end > 0 {
+        let (_, start, spelling) = best[end].clone()?;
+        words.push(spelling);
+        end = start;
+    }
+    words.reverse();
+    Some(words)
+}
+
+/// Expands a hashtag or mention: "#MachineLearning" → "hashtag machine learning",
+/// "@openai" → "at open AI". Lowercase runs the dictionary doesn't know are
+/// segmented with `lookup` (see `segment_words`). Without `speak_sigil` the
+/// "#"/"@" is dropped.
+pub fn expand_handle(word: &str, speak_sigil: bool, lookup: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let caps = HANDLE_REGEX.captures(word)?;
+    let mut words = Vec::new();
+    if speak_sigil {
+        words.push(if &caps["sigil"] == "#" { "hashtag" } else { "at" }.to_string());
+    }
+    for piece in split_handle(&caps["body"]) {
+        let lower = piece.to_lowercase();
+        if piece == lower && piece.len() > 3 && lookup(&piece).is_none() {
+            match segment_words(&piece, &lookup) {
+                Some(segments) => words.extend(segments),
+                None => words.push(piece),
+            }
+        } else if piece.chars().count() > 1 && piece == piece.to_uppercase() {
+            words.push(piece);
+        } else {
+            words.push(lower);
+        }
+    }
+    Some(words.join(" "))
+}
+
+/// True for a single emoji (see `EMOJI_PATTERN`).
+pub fn is_emoji(word: &str) -> bool {
+    EMOJI_REGEX.is_match(word)
+}
+
+/// Spoken description of an emoji: "🎉" → "party popper emoji". Skin tones and
+/// variation selectors are ignored; flags read their country letters and
+/// emoji missing from the table are just "emoji".
+pub fn describe_emoji(emoji: &str) -> String {
+    let base: String = emoji
+        .chars()
+        .filter(|c| !matches!(c, '\u{FE0F}' | '\u{1F3FB}'..='\u{1F3FF}'))
+        .collect();
+    if let Some((_, name)) = EMOJI_NAMES.iter().find(|(e, _)| *e == base) {
+        return format!("{} emoji", name);
+    }
+    let letters: Option<Vec<String>> = base
+        .chars()
+        .map(|c| {
+            let offset = (c as u32).checked_sub(0x1F1E6).filter(|&o| o < 26)?;
+            char::from_u32('A' as u32 + offset).map(String::from)
+        })
+        .collect();
+    match letters {
+        Some(letters) if letters.len() == 2 => format!("{} flag", letters.join(" ")),
+        _ => "emoji".to_string(),
+    }
+}
+
+/// True for symbols `expand_currency` can read.
+pub fn is_currency_symbol(word: &str) -> bool {
+    CURRENCIES.iter().any(|(s, ..)| *s == word)
+}
+
+/// Reads an amount ("123.45", already in plain locale-free form) in the given
+/// currency: "one hundred twenty-three dollars and forty-five cents".
+/// Amounts with more than two decimals are read as a decimal number of units.
+pub fn expand_currency(amount: &str, symbol: &str, number: impl Fn(&str) -> String) -> Option<String> {
+    let &(_, unit, units, subunit, subunits) = CURRENCIES.iter().find(|(s, ..)| *s == symbol)?;
+    let (whole, frac) = amount.split_once('.').unwrap_or((amount, ""));
+    if frac.len() > 2 {
+        return Some(format!("{} {}", number(amount), units));
+    }
+
+    let whole = whole.trim_start_matches('0');
+    let cents: u32 = if frac.is_empty() { 0 } else { format!("{:0<2}", frac).parse().ok()? };
+    let mut words = Vec::new();
+    if !whole.is_empty() || cents == 0 {
+        let whole = if whole.is_empty() { "0" } else { whole };
+        words.push(format!("{} {}", number(whole), if whole == "1" { unit } else { units }));
+    }
+    if cents > 0 {
+        words.push(format!(
+            "{} {}",
+            number(&cents.to_string()),
+            if cents == 1 { subunit } else { subunits }
+        ));
+    }
+    Some(words.join(" and "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(n: &str) -> String {
+        format!("<{}>", n)
+    }
+
+    #[test]
+    fn test_expand_measurement() {
+        assert_eq!(expand_measurement("100%", false, digits).as_deref(), Some("<100> percent"));
+        assert_eq!(
+            expand_measurement("-5°C", false, digits).as_deref(),
+            Some("<-5> degrees Celsius")
+        );
+        assert_eq!(
+            expand_measurement("−1°C", false, digits).as_deref(),
+            Some("<-1> degree Celsius")
+        );
+        assert_eq!(expand_measurement("10km", false, digits).as_deref(), Some("<10> kilometers"));
+        assert_eq!(expand_measurement("10km", true, digits).as_deref(), Some("<10> kilometres"));
+        assert_eq!(expand_measurement("1kg", false, digits).as_deref(), Some("<1> kilogram"));
+        assert_eq!(expand_measurement("2.5l", true, digits).as_deref(), Some("<2.5> litres"));
+        assert_eq!(expand_measurement("1990s", false, digits), None);
+        assert_eq!(expand_measurement("10xyz", false, digits), None);
+        assert_eq!(expand_measurement("km", false, digits), None);
+    }
+
+    #[test]
+    fn test_expand_time() {
+        assert_eq!(expand_time("3:30pm", digits).as_deref(), Some("<3> <30> PM"));
+        assert_eq!(expand_time("14:05", digits).as_deref(), Some("<14> oh <5>"));
+        assert_eq!(expand_time("3pm", digits).as_deref(), Some("<3> PM"));
+        assert_eq!(expand_time("7a.m.", digits).as_deref(), Some("<7> AM"));
+        assert_eq!(expand_time("12:00", digits).as_deref(), Some("<12> o'clock"));
+        assert_eq!(expand_time("18:00", digits).as_deref(), Some("<18> hundred"));
+        assert_eq!(expand_time("09:15", digits).as_deref(), Some("<9> <15>"));
+        assert_eq!(expand_time("25:00", digits), None);
+        assert_eq!(expand_time("13pm", digits), None);
+        assert_eq!(expand_time("3:75", digits), None);
+        assert_eq!(expand_time("15", digits), None);
+    }
+
+    #[test]
+    fn test_expand_date() {
+        let ordinal = |d: u32| format!("<{}th>", d);
+        assert_eq!(
+            expand_date("2024-01-05", DateOrder::MonthFirst, digits, ordinal).as_deref(),
+            Some("January <5th>, <2024>")
+        );
+        assert_eq!(
+            expand_date("5/1/2024", DateOrder::MonthFirst, digits, ordinal).as_deref(),
+            Some("May <1th>, <2024>")
+        );
+        assert_eq!(
+            expand_date("5/1/2024", DateOrder::DayFirst, digits, ordinal).as_deref(),
+            Some("the <5th> of January, <2024>")
+        );
+        assert_eq!(
+            expand_date("12/25/05", DateOrder::MonthFirst, digits, ordinal).as_deref(),
+            Some("December <25th>, oh <5>")
+        );
+        assert_eq!(expand_date("13/25/2024", DateOrder::MonthFirst, digits, ordinal), None);
+        assert_eq!(expand_date("2024-02-30", DateOrder::MonthFirst, digits, ordinal), None);
+        assert_eq!(expand_date("1/2", DateOrder::MonthFirst, digits, ordinal), None);
+    }
+
+    #[test]
+    fn test_expand_fraction() {
+        let ordinal = |d: u32| match d {
+            3 => "third".to_string(),
+            8 => "eighth".to_string(),
+            _ => format!("{}th", d),
+        };
+        let natural = FractionStyle::Natural;
+        assert_eq!(expand_fraction("1/2", natural, digits, ordinal).as_deref(), Some("<1> half"));
+        assert_eq!(
+            expand_fraction("3/4", natural, digits, ordinal).as_deref(),
+            Some("<3> quarters")
+        );
+        assert_eq!(
+            expand_fraction("2/3", natural, digits, ordinal).as_deref(),
+            Some("<2> thirds")
+        );
+        assert_eq!(
+            expand_fraction("1/8", natural, digits, ordinal).as_deref(),
+            Some("<1> eighth")
+        );
+        assert_eq!(
+            expand_fraction("22/7", natural, digits, ordinal).as_deref(),
+            Some("<22> 7ths")
+        );
+        assert_eq!(
+            expand_fraction("3/16", natural, digits, ordinal).as_deref(),
+            Some("<3> over <16>")
+        );
+        assert_eq!(
+            expand_fraction("3/4", FractionStyle::Over, digits, ordinal).as_deref(),
+            Some("<3> over <4>")
+        );
+        assert_eq!(expand_fraction("1/0", natural, digits, ordinal), None);
+        assert_eq!(expand_fraction("a/b", natural, digits, ordinal), None);
+    }
+
+    #[test]
+    fn test_expand_range() {
+        assert_eq!(expand_range("5-10", digits).as_deref(), Some("<5> to <10>"));
+        assert_eq!(expand_range("1939–1945", digits).as_deref(), Some("<1939> to <1945>"));
+        assert_eq!(expand_range("2.5-3", digits).as_deref(), Some("<2.5> to <3>"));
+        assert_eq!(expand_range("-5", digits), None);
+        assert_eq!(expand_range("5-", digits), None);
+        assert_eq!(expand_range("415-555-0190", digits), None);
+        assert_eq!(expand_range("555-0190", digits), None);
+        assert_eq!(expand_range("555-1234", digits), None);
+        assert_eq!(expand_range("1-05", digits), None);
+        assert_eq!(expand_range("0-10", digits).as_deref(), Some("<0> to <10>"));
+    }
+
+    #[test]
+    fn test_expand_scientific() {
+        assert_eq!(
+            expand_scientific("1e9", digits).as_deref(),
+            Some("<1> times ten to the <9>")
+        );
+        assert_eq!(
+            expand_scientific("3.5e-4", digits).as_deref(),
+            Some("<3.5> times ten to the <-4>")
+        );
+        assert_eq!(
+            expand_scientific("6.02E+23", digits).as_deref(),
+            Some("<6.02> times ten to the <23>")
+        );
+        assert_eq!(expand_scientific("e9", digits), None);
+        assert_eq!(expand_scientific("1e", digits), None);
+    }
+
+    #[test]
+    fn test_expand_version() {
+        assert_eq!(
+            expand_version("1.21.3", digits).as_deref(),
+            Some("<1> point <21> point <3>")
+        );
+        assert_eq!(
+            expand_version("v2.0", digits).as_deref(),
+            Some("version <2> point oh")
+        );
+        assert_eq!(expand_version("2.0", digits), None);
+        assert_eq!(expand_version("V8", digits), None);
+        assert_eq!(expand_version("v2", digits), None);
+        assert_eq!(expand_version("1..2.3", digits), None);
+        assert_eq!(version_words("2.0", digits).as_deref(), Some("<2> point oh"));
+    }
+
+    #[test]
+    fn test_expand_url() {
+        assert_eq!(
+            expand_url("https://example.com/docs").as_deref(),
+            Some("example dot com slash docs")
+        );
+        assert_eq!(expand_url("www.rust-lang.org").as_deref(), Some("W W W dot rust dash lang dot org"));
+        assert_eq!(expand_url("github.io").as_deref(), Some("github dot I O"));
+        assert_eq!(expand_url("bbc.co.uk").as_deref(), Some("bbc dot C O dot U K"));
+        assert_eq!(expand_url("file.txt"), None);
+        assert_eq!(expand_url("e.g"), None);
+    }
+
+    #[test]
+    fn test_expand_email() {
+        assert_eq!(
+            expand_email("john@example.org").as_deref(),
+            Some("john at example dot org")
+        );
+        assert_eq!(
+            expand_email("jane_doe@mail.example.io").as_deref(),
+            Some("jane underscore doe at mail dot example dot I O")
+        );
+        assert_eq!(expand_email("@openai"), None);
+    }
+
+    #[test]
+    fn test_split_handle() {
+        assert_eq!(split_handle("MachineLearning"), ["Machine", "Learning"]);
+        assert_eq!(split_handle("HTMLParser"), ["HTML", "Parser"]);
+        assert_eq!(split_handle("web3_dev"), ["web", "3", "dev"]);
+        assert_eq!(split_handle("rust"), ["rust"]);
+    }
+
+    #[test]
+    fn test_expand_handle() {
+        let lookup = |w: &str| match w {
+            "open" | "throwback" | "thursday" | "throw" | "back" => Some(w.to_string()),
+            "ai" => Some("AI".to_string()),
+            _ => None,
+        };
+        assert_eq!(
+            expand_handle("#MachineLearning", true, lookup).as_deref(),
+            Some("hashtag machine learning")
+        );
+        assert_eq!(expand_handle("@openai", true, lookup).as_deref(), Some("at open AI"));
+        assert_eq!(
+            expand_handle("#throwbackthursday", false, lookup).as_deref(),
+            Some("throwback thursday")
+        );
+        assert_eq!(expand_handle("@xyzzy", true, lookup).as_deref(), Some("at xyzzy"));
+        assert_eq!(expand_handle("#1", true, lookup), None);
+    }
+
+    #[test]
+    fn test_clean_text() {
+        let nfc = Some(UnicodeForm::Nfc);
+        assert_eq!(clean_text("don’t “quote” ‘this’", nfc), "don't \"quote\" 'this'");
+        assert_eq!(clean_text("cafe\u{301}", nfc), "café");
+        assert_eq!(clean_text("zero\u{200B}width\u{FEFF}", nfc), "zerowidth");
+        assert_eq!(clean_text("a\u{200D}b 👩\u{200D}💻", nfc), "ab 👩\u{200D}💻");
+        assert_eq!(clean_text("ﬁne x²", Some(UnicodeForm::Nfkc)), "fine x2");
+        assert_eq!(clean_text("ﬁne", None), "ﬁne");
+    }
+
+    #[test]
+    fn test_emoji() {
+        for emoji in ["🎉", "👍🏽", "❤\u{FE0F}", "🇺🇸", "👩\u{200D}💻"] {
+            assert!(is_emoji(emoji), "{}", emoji);
+        }
+        for word in ["©", "a", "#", "❤"] {
+            assert!(!is_emoji(word), "{}", word);
+        }
+        assert_eq!(describe_emoji("🎉"), "party popper emoji");
+        assert_eq!(describe_emoji("👍🏽"), "thumbs up emoji");
+        assert_eq!(describe_emoji("❤\u{FE0F}"), "red heart emoji");
+        assert_eq!(describe_emoji("🇺🇸"), "U S flag");
+        assert_eq!(describe_emoji("🫠"), "emoji");
+    }
+
+    #[test]
+    fn test_is_initialism() {
+        for word in ["U.S.", "U.S", "Ph.D.", "A.I.", "e.g.", "U.S.A."] {
+            assert!(is_initialism(word), "{}", word);
+        }
+        for word in ["U", "U.", "etc.", "U..S", "www.example.com", "1.2"] {
+            assert!(!is_initialism(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_expand_currency() {
+        assert_eq!(
+            expand_currency("123.45", "$", digits).as_deref(),
+            Some("<123> dollars and <45> cents")
+        );
+        assert_eq!(expand_currency("1", "$", digits).as_deref(), Some("<1> dollar"));
+        assert_eq!(expand_currency("0.5", "£", digits).as_deref(), Some("<50> pence"));
+        assert_eq!(expand_currency("3.01", "£", digits).as_deref(), Some("<3> pounds and <1> penny"));
+        assert_eq!(expand_currency("2.125", "€", digits).as_deref(), Some("<2.125> euros"));
+        assert_eq!(expand_currency("5", "#", digits), None);
+    }
+
+    #[test]
+    fn test_month_name() {
+        assert_eq!(month_name("Jan"), Some("January"));
+        assert_eq!(month_name("Sept"), Some("September"));
+        assert_eq!(month_name("May"), Some("May"));
+        assert_eq!(month_name("may"), None);
+        assert_eq!(month_name("Ma"), None);
+    }
+
+    #[test]
+    fn test_detached_unit_words() {
+        assert_eq!(detached_unit_words("km", "10", false).as_deref(), Some("kilometers"));
+        assert_eq!(detached_unit_words("ft", "1", false).as_deref(), Some("foot"));
+        assert_eq!(detached_unit_words("in", "5", false), None);
+    }
+}
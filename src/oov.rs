@@ -0,0 +1,106 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// How G2P read a word the lexicon doesn't have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OovSource {
+    /// The OOV fallback produced phonemes.
+    Fallback,
+    /// Spelled out character by character.
+    Spelled,
+    /// Replaced by the unknown marker.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OovEntry {
+    pub word: String,
+    pub count: usize,
+    /// The phonemes chosen the last time the word was seen.
+    pub phonemes: String,
+    pub source: OovSource,
+}
+
+/// Collects the words G2P resolved without the lexicon, with how often each
+/// was seen, so production traffic can be mined for new dictionary entries.
+/// Share one with `G2P::set_oov_recorder` and read it while G2P runs.
+#[derive(Debug, Default)]
+pub struct OovRecorder {
+    entries: Mutex<HashMap<String, OovEntry>>,
+}
+
+impl OovRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, word: &str, phonemes: &str, source: OovSource) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        let entry = entries.entry(word.to_string()).or_insert_with(|| OovEntry {
+            word: word.to_string(),
+            count: 0,
+            phonemes: String::new(),
+            source,
+        });
+        entry.count += 1;
+        entry.phonemes = phonemes.to_string();
+        entry.source = source;
+    }
+
+    /// Recorded words, most frequent first.
+    pub fn entries(&self) -> Vec<OovEntry> {
+        let mut entries: Vec<OovEntry> = self
+            .entries
+            .lock()
+            .map(|entries| entries.values().cloned().collect())
+            .unwrap_or_default();
+        entries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+        entries
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+
+    /// The report as a JSON array of entries, most frequent first.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.entries())
+    }
+
+    pub fn write_json(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_json()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder() {
+        let recorder = OovRecorder::new();
+        recorder.record("Frodo", "fɹˈo‍ʊdo‍ʊ", OovSource::Fallback);
+        recorder.record("Sam", "sˈæm", OovSource::Fallback);
+        recorder.record("Frodo", "fɹˈo‍ʊdo‍ʊ", OovSource::Fallback);
+
+        let entries = recorder.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[0].word.as_str(), entries[0].count), ("Frodo", 2));
+
+        let json: serde_json::Value = serde_json::from_str(&recorder.to_json().unwrap()).unwrap();
+        assert_eq!(json[1]["word"], "Sam");
+        assert_eq!(json[1]["source"], "fallback");
+
+        recorder.clear();
+        assert!(recorder.entries().is_empty());
+    }
+}
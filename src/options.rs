@@ -0,0 +1,271 @@
+use crate::stress::Stress;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Day/month ordering for numeric dates, both when parsing "5/1/2024" and
+/// when speaking the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateOrder {
+    /// US: 5/1/2024 is May 1st, read "May first, twenty twenty-four".
+    MonthFirst,
+    /// GB: 5/1/2024 is 5 January, read "the fifth of January, twenty twenty-four".
+    DayFirst,
+}
+
+/// How numeric fractions like "3/4" are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FractionStyle {
+    /// "one half", "three quarters", "two thirds"; falls back to `Over` for
+    /// denominators above ten.
+    Natural,
+    /// "three over four".
+    Over,
+}
+
+/// How plain integers are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberStyle {
+    /// "one billion two hundred and thirty-four million ..."
+    Cardinal,
+    /// "one two three four", as for phone numbers and codes.
+    Digits,
+    /// Digits for strings with a leading zero, phone-length numbers right
+    /// after a "+" ("+14155550190") and runs of digit groups ("415 555 0190");
+    /// cardinal otherwise.
+    Auto,
+}
+
+/// Wording for the sign of negative numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NegativeStyle {
+    /// "minus five"
+    Minus,
+    /// "negative five"
+    Negative,
+}
+
+/// Digit grouping and decimal mark conventions of the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberLocale {
+    /// 1,234,567.89
+    English,
+    /// 1.234.567,89 and space-grouped 1 234 567,89
+    European,
+}
+
+/// Unicode normalization form applied to input text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnicodeForm {
+    /// Canonical composition: "e\u{301}" → "é".
+    Nfc,
+    /// Compatibility composition, additionally folding "ﬁ" → "fi", "²" → "2"
+    /// and full-width letters/digits to ASCII.
+    Nfkc,
+}
+
+/// What to do with emoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmojiPolicy {
+    /// Silence them.
+    Strip,
+    /// Leave them unresolved, producing the unknown marker.
+    Unknown,
+    /// Read their name: "🎉" → "party popper emoji".
+    Describe,
+}
+
+/// What to do with a word nothing else could read: not in the lexicon, not
+/// derivable from it, and (with `correct_typos`) not a typo of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OovPolicy {
+    /// Guess with the OOV fallback, spelling the word out if there is none or
+    /// it has nothing to offer.
+    Fallback,
+    /// Spell the word out character by character, without the fallback.
+    SpellOut,
+    /// Write this marker in its place.
+    Mark(String),
+    /// Silence it.
+    Skip,
+    /// Fail with `G2PError::UnknownWord`, as for validating a batch.
+    Error,
+}
+
+/// What to do with words written in a non-Latin script ("世界", "Здравствуй").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptPolicy {
+    /// Silence them.
+    Skip,
+    /// Read each such word as a single unknown marker.
+    Unknown,
+    /// Romanize Cyrillic and Greek and read the result as English; other
+    /// scripts are unknown.
+    Transliterate,
+    /// Hand them to the fallback registered for their script with
+    /// `G2P::route_script`; unknown if there is none.
+    Route,
+}
+
+/// Phonetic alphabet of `G2P::g2p` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    /// The lexicon's own notation, as Kokoro and Python misaki expect.
+    Misaki,
+    /// Standard IPA, with misaki's shorthand ("A", "ʤ", "ᵻ") spelled out.
+    Ipa,
+    /// X-SAMPA: `k"{t` for "kˈæt".
+    XSampa,
+    /// Kirshenbaum (ASCII-IPA, as used by espeak): `k'&t` for "kˈæt".
+    Kirshenbaum,
+}
+
+/// Markup of the input text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputFormat {
+    /// Read as is.
+    Plain,
+    /// Strip Markdown (and inline HTML) first; see `markup::strip_markdown`.
+    Markdown,
+    /// Strip HTML tags and decode entities first; see `markup::strip_html`.
+    Html,
+}
+
+/// What `G2P::g2p` writes for each kind of pause when
+/// `G2POptions::pause_markers` is set. The defaults are the punctuation
+/// Kokoro reads as pauses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PauseMarkers {
+    /// For ",", ";" and ":".
+    pub clause: String,
+    pub period: String,
+    pub question: String,
+    pub exclamation: String,
+    /// For "…" and "...".
+    pub ellipsis: String,
+    /// For "—", "–" and a free-standing "-" or "--".
+    pub dash: String,
+    /// Added at a blank line in the input.
+    pub paragraph: String,
+}
+
+impl Default for PauseMarkers {
+    fn default() -> Self {
+        Self {
+            clause: ",".to_string(),
+            period: ".".to_string(),
+            question: "?".to_string(),
+            exclamation: "!".to_string(),
+            ellipsis: "…".to_string(),
+            dash: "—".to_string(),
+            paragraph: "…".to_string(),
+        }
+    }
+}
+
+/// How text is split into tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    /// Whitespace-separated words, split into subtokens (keeping contractions
+    /// whole).
+    Default,
+    /// spaCy's English rules, as Python misaki uses (see `spacy`):
+    /// contractions are split ("do" "n't"), the tagger sees spaCy's tokens,
+    /// and split words are looked up whole first.
+    SpacyCompat,
+}
+
+/// Behavioural switches for the G2P pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct G2POptions {
+    /// Read plausible 4-digit years as "twenty twenty-four" instead of a cardinal
+    /// where the context calls for a year ("in 2024", "2024 AD", not "2024 people").
+    pub year_style: bool,
+    /// Ordering for numeric dates. `None` follows the dialect (US month-first, GB day-first).
+    pub date_order: Option<DateOrder>,
+    /// Reading style for fractions such as "1/2" or "22/7".
+    pub fraction_style: FractionStyle,
+    /// Reading style for integers.
+    pub number_style: NumberStyle,
+    /// Wording for "-5" / "−3.2".
+    pub negative_style: NegativeStyle,
+    /// How thousands separators and decimal marks are parsed. `None` follows
+    /// the language (European for Spanish, English for English).
+    pub number_locale: Option<NumberLocale>,
+    /// Read URLs and email addresses as "example dot com slash docs".
+    pub read_addresses: bool,
+    /// Say "hashtag"/"at" for the sigil of "#tags" and "@mentions"; otherwise drop it.
+    pub speak_sigils: bool,
+    /// Normalization form for input text. `None` leaves it untouched.
+    pub unicode_form: Option<UnicodeForm>,
+    /// Handling of words in non-Latin scripts.
+    pub script_policy: ScriptPolicy,
+    /// Handling of emoji such as "🎉".
+    pub emoji_policy: EmojiPolicy,
+    /// Fill each token's `start_ts`/`end_ts` with estimated timings at this
+    /// speaking rate (1.0 is normal speed; see `timing::estimate_timestamps`).
+    /// `None` leaves them unset.
+    pub speaking_rate: Option<f64>,
+    /// Phonetic alphabet of the output. Conversions from the lexicon's
+    /// notation are in `phonology`.
+    pub output_format: OutputFormat,
+    /// Markup to strip from the input before it is read.
+    pub input_format: InputFormat,
+    /// Write pauses from punctuation and paragraph breaks as these markers
+    /// and record their length in `MToken::pause`. `None` reads punctuation
+    /// as plain spaces.
+    pub pause_markers: Option<PauseMarkers>,
+    /// Tokenization rules.
+    pub tokenizer: TokenizerKind,
+    /// Read an unknown word that is one edit from a dictionary word as that
+    /// word, taking it for a typo (see `Lexicon::nearest_word`), rather than
+    /// guessing at it with the fallback.
+    pub correct_typos: bool,
+    /// Handling of words nothing in the pipeline could read.
+    pub oov_policy: OovPolicy,
+    /// Written for words left unread. `None` uses `G2P::unk`.
+    pub unk: Option<String>,
+    /// Stress for words wherever they appear, by lowercase spelling, as the
+    /// link syntax `[word](-1)` sets it for one occurrence.
+    pub stress_overrides: HashMap<String, Stress>,
+}
+
+impl Default for G2POptions {
+    fn default() -> Self {
+        Self {
+            year_style: true,
+            date_order: None,
+            fraction_style: FractionStyle::Natural,
+            number_style: NumberStyle::Auto,
+            negative_style: NegativeStyle::Minus,
+            number_locale: None,
+            read_addresses: true,
+            speak_sigils: true,
+            unicode_form: Some(UnicodeForm::Nfc),
+            script_policy: ScriptPolicy::Unknown,
+            emoji_policy: EmojiPolicy::Strip,
+            speaking_rate: None,
+            output_format: OutputFormat::Misaki,
+            input_format: InputFormat::Plain,
+            pause_markers: None,
+            tokenizer: TokenizerKind::Default,
+            correct_typos: false,
+            oov_policy: OovPolicy::Fallback,
+            unk: None,
+            stress_overrides: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Parity checks against Python misaki (`parity` feature).
+//!
+//! A corpus is a JSON Lines file of `{"text": ..., "phonemes": ...}` cases
+//! holding Python misaki's output, as written by `parity_goldens.py`. `run`
+//! phonemizes each text and explains every mismatch by the first of a series
+//! of normalizations that makes the two outputs agree.
+
+use crate::g2p::{G2P, G2PError};
+use crate::integrations::kokoro;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+const PUNCTUATION: &str = ";:,.!?—…\"()“”";
+
+#[derive(Error, Debug)]
+pub enum ParityError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("line {line}: {source}")]
+    Json {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ParityCase {
+    pub text: String,
+    /// Python misaki's phonemes for `text`.
+    pub phonemes: String,
+    /// Line of the case in its corpus file, from 1.
+    #[serde(skip)]
+    pub line: usize,
+}
+
+/// Reads a corpus from JSON Lines, skipping blank lines.
+pub fn parse_corpus(jsonl: &str) -> Result<Vec<ParityCase>, ParityError> {
+    jsonl
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| {
+            let mut case: ParityCase =
+                serde_json::from_str(l).map_err(|source| ParityError::Json { line: i + 1, source })?;
+            case.line = i + 1;
+            Ok(case)
+        })
+        .collect()
+}
+
+pub fn load_corpus(path: impl AsRef<Path>) -> Result<Vec<ParityCase>, ParityError> {
+    parse_corpus(&fs::read_to_string(path)?)
+}
+
+/// Why an output differs from Python misaki's, from the most superficial
+/// difference to the most serious.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MismatchReason {
+    /// Same phonemes, spaced differently.
+    Whitespace,
+    /// Tied pairs where misaki writes its shorthand ("e‍ɪ" for "A").
+    Notation,
+    /// Punctuation kept in one and not the other.
+    Punctuation,
+    /// Same sounds, different stress marks.
+    Stress,
+    /// A different number of words, from tokenization or normalization.
+    WordCount,
+    /// Words read differently.
+    Phonemes,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mismatch {
+    pub line: usize,
+    pub text: String,
+    pub expected: String,
+    pub actual: String,
+    pub reason: MismatchReason,
+    /// `(expected, actual)` for each word read differently, when the word
+    /// counts agree.
+    pub words: Vec<(String, String)>,
+}
+
+fn words(phonemes: &str) -> Vec<&str> {
+    phonemes.split_whitespace().collect()
+}
+
+fn strip_punctuation(phonemes: &str) -> String {
+    phonemes.chars().map(|c| if PUNCTUATION.contains(c) { ' ' } else { c }).collect()
+}
+
+fn strip_stress(phonemes: &str) -> String {
+    phonemes.replace(['ˈ', 'ˌ'], "")
+}
+
+/// Why `actual` differs from `expected`, with the differing word pairs;
+/// `None` if they are identical.
+pub fn classify(expected: &str, actual: &str) -> Option<(MismatchReason, Vec<(String, String)>)> {
+    if expected == actual {
+        return None;
+    }
+    let spaced = |s: &str| words(s).join(" ");
+    let (mut e, mut a) = (spaced(expected), spaced(actual));
+    if e == a {
+        return Some((MismatchReason::Whitespace, Vec::new()));
+    }
+    // Each step normalizes on top of the previous ones
+    let steps = [
+        (MismatchReason::Notation, kokoro::normalize as fn(&str) -> String),
+        (MismatchReason::Punctuation, strip_punctuation),
+        (MismatchReason::Stress, strip_stress),
+    ];
+    let mut diff_words = None;
+    for (reason, normalize) in steps {
+        (e, a) = (spaced(&normalize(&e)), spaced(&normalize(&a)));
+        if reason == MismatchReason::Punctuation {
+            // Word pairs are compared with stress marks still in place
+            diff_words = Some((e.clone(), a.clone()));
+        }
+        if e == a {
+            return Some((reason, Vec::new()));
+        }
+    }
+    let (e, a) = diff_words.unwrap();
+    let (e, a) = (words(&e), words(&a));
+    if e.len() != a.len() {
+        return Some((MismatchReason::WordCount, Vec::new()));
+    }
+    let pairs = e
+        .iter()
+        .zip(&a)
+        .filter(|(e, a)| e != a)
+        .map(|(e, a)| (e.to_string(), a.to_string()))
+        .collect();
+    Some((MismatchReason::Phonemes, pairs))
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParityReport {
+    pub cases: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl ParityReport {
+    pub fn matched(&self) -> usize {
+        self.cases - self.mismatches.len()
+    }
+
+    pub fn counts(&self) -> BTreeMap<MismatchReason, usize> {
+        let mut counts = BTreeMap::new();
+        for m in &self.mismatches {
+            *counts.entry(m.reason).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+impl fmt::Display for ParityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for m in &self.mismatches {
+            writeln!(f, "line {} [{:?}] {}", m.line, m.reason, m.text)?;
+            writeln!(f, "  - {}", m.expected)?;
+            writeln!(f, "  + {}", m.actual)?;
+            for (e, a) in &m.words {
+                writeln!(f, "    {} → {}", e, a)?;
+            }
+        }
+        write!(f, "{}/{} match", self.matched(), self.cases)?;
+        for (reason, n) in self.counts() {
+            write!(f, ", {} {:?}", n, reason)?;
+        }
+        Ok(())
+    }
+}
+
+/// Phonemizes every case with `g2p` and collects the mismatches. Python
+/// misaki keeps punctuation in its output, so `g2p` should usually have
+/// `G2POptions::pause_markers` set to the defaults.
+pub fn run(g2p: &G2P, cases: &[ParityCase]) -> Result<ParityReport, G2PError> {
+    let mut report = ParityReport {
+        cases: cases.len(),
+        mismatches: Vec::new(),
+    };
+    for case in cases {
+        let (actual, _) = g2p.g2p(&case.text)?;
+        let actual = actual.trim().to_string();
+        if let Some((reason, words)) = classify(case.phonemes.trim(), &actual) {
+            report.mismatches.push(Mismatch {
+                line: case.line,
+                text: case.text.clone(),
+                expected: case.phonemes.clone(),
+                actual,
+                reason,
+                words,
+            });
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        use MismatchReason::*;
+        assert_eq!(classify("hˈA", "hˈA"), None);
+        assert_eq!(classify("hˈA wˈɜɹld", "hˈA  wˈɜɹld"), Some((Whitespace, vec![])));
+        assert_eq!(classify("hˈA", "hˈe\u{200D}ɪ"), Some((Notation, vec![])));
+        assert_eq!(classify("hˈA, wˈɜɹld.", "hˈA wˈɜɹld"), Some((Punctuation, vec![])));
+        assert_eq!(classify("hˈA wˈɜɹld", "hˌA wɜɹld"), Some((Stress, vec![])));
+        assert_eq!(classify("twˈɛnti wˈʌn", "twˌɛntiwˈʌn"), Some((WordCount, vec![])));
+        assert_eq!(
+            classify("ðə kˈæt, sˈæt", "ðə kˈɑt sˈæt"),
+            Some((Phonemes, vec![("kˈæt".to_string(), "kˈɑt".to_string())]))
+        );
+    }
+
+    #[test]
+    fn test_parse_corpus() {
+        let corpus = "{\"text\": \"Hi\", \"phonemes\": \"hˈI\"}\n\n{\"text\": \"Bye\", \"phonemes\": \"bˈI\"}\n";
+        let cases = parse_corpus(corpus).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!((cases[1].text.as_str(), cases[1].line), ("Bye", 3));
+        assert!(matches!(parse_corpus("{\"text\": 1}"), Err(ParityError::Json { line: 1, .. })));
+    }
+}
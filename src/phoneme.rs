@@ -0,0 +1,354 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// One symbol of the misaki phoneme inventory, including the marks that
+/// modify neighbouring phonemes and the punctuation G2P output keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phoneme {
+    // Misaki's single-letter diphthongs
+    /// `A`, eɪ as in "face".
+    Ei,
+    /// `I`, aɪ as in "price".
+    Ai,
+    /// `O`, US oʊ as in "goat".
+    Ou,
+    /// `Q`, GB əʊ as in "goat".
+    Eu,
+    /// `W`, aʊ as in "mouth".
+    Au,
+    /// `Y`, ɔɪ as in "choice".
+    Oi,
+    // Vowels
+    /// `a`
+    OpenA,
+    /// `e`
+    E,
+    /// `i`
+    I,
+    /// `o`
+    O,
+    /// `u`
+    U,
+    /// `æ`
+    Ash,
+    /// `ɐ`
+    TurnedA,
+    /// `ɑ`
+    ScriptA,
+    /// `ɒ`
+    TurnedScriptA,
+    /// `ɔ`
+    OpenO,
+    /// `ə`
+    Schwa,
+    /// `ɚ`
+    RhoticSchwa,
+    /// `ɛ`
+    OpenE,
+    /// `ɜ`
+    ReversedOpenE,
+    /// `ɪ`
+    SmallCapitalI,
+    /// `ʊ`
+    Upsilon,
+    /// `ʌ`
+    TurnedV,
+    /// `ᵊ`, a schwa that may be dropped.
+    ReducedSchwa,
+    /// `ᵻ`, between ɪ and ə.
+    BarredSmallI,
+    // Consonants
+    B,
+    D,
+    F,
+    H,
+    J,
+    K,
+    L,
+    M,
+    N,
+    P,
+    R,
+    S,
+    T,
+    V,
+    W,
+    X,
+    Z,
+    /// `ð`
+    Eth,
+    /// `ŋ`
+    Eng,
+    /// `ɡ`
+    G,
+    /// `ɬ`
+    BeltedL,
+    /// `ɹ`
+    TurnedR,
+    /// `ɾ`
+    Flap,
+    /// `ʃ`
+    Esh,
+    /// `ʒ`
+    Ezh,
+    /// `ʔ`
+    GlottalStop,
+    /// `ʤ`
+    Dezh,
+    /// `ʧ`
+    Tesh,
+    /// `θ`
+    Theta,
+    /// `ç`
+    CCedilla,
+    // Marks
+    /// `ˈ`, before the stressed vowel.
+    PrimaryStress,
+    /// `ˌ`
+    SecondaryStress,
+    /// `ː`
+    Long,
+    /// Zero-width joiner tying a diphthong or affricate ("e‍ɪ", "d‍ʒ").
+    Tie,
+    /// Combining `◌̩`
+    Syllabic,
+    /// Combining `◌̃`
+    Nasalized,
+    /// `ʲ`, after a palatalized consonant.
+    Palatalized,
+    // Separators
+    Space,
+    /// One of `;:,.!?—…"()“”`.
+    Punctuation(char),
+}
+
+const SYMBOLS: &[(char, Phoneme)] = &[
+    ('A', Phoneme::Ei),
+    ('I', Phoneme::Ai),
+    ('O', Phoneme::Ou),
+    ('Q', Phoneme::Eu),
+    ('W', Phoneme::Au),
+    ('Y', Phoneme::Oi),
+    ('a', Phoneme::OpenA),
+    ('e', Phoneme::E),
+    ('i', Phoneme::I),
+    ('o', Phoneme::O),
+    ('u', Phoneme::U),
+    ('æ', Phoneme::Ash),
+    ('ɐ', Phoneme::TurnedA),
+    ('ɑ', Phoneme::ScriptA),
+    ('ɒ', Phoneme::TurnedScriptA),
+    ('ɔ', Phoneme::OpenO),
+    ('ə', Phoneme::Schwa),
+    ('ɚ', Phoneme::RhoticSchwa),
+    ('ɛ', Phoneme::OpenE),
+    ('ɜ', Phoneme::ReversedOpenE),
+    ('ɪ', Phoneme::SmallCapitalI),
+    ('ʊ', Phoneme::Upsilon),
+    ('ʌ', Phoneme::TurnedV),
+    ('ᵊ', Phoneme::ReducedSchwa),
+    ('ᵻ', Phoneme::BarredSmallI),
+    ('b', Phoneme::B),
+    ('d', Phoneme::D),
+    ('f', Phoneme::F),
+    ('h', Phoneme::H),
+    ('j', Phoneme::J),
+    ('k', Phoneme::K),
+    ('l', Phoneme::L),
+    ('m', Phoneme::M),
+    ('n', Phoneme::N),
+    ('p', Phoneme::P),
+    ('r', Phoneme::R),
+    ('s', Phoneme::S),
+    ('t', Phoneme::T),
+    ('v', Phoneme::V),
+    ('w', Phoneme::W),
+    ('x', Phoneme::X),
+    ('z', Phoneme::Z),
+    ('ð', Phoneme::Eth),
+    ('ŋ', Phoneme::Eng),
+    ('ɡ', Phoneme::G),
+    ('ɬ', Phoneme::BeltedL),
+    ('ɹ', Phoneme::TurnedR),
+    ('ɾ', Phoneme::Flap),
+    ('ʃ', Phoneme::Esh),
+    ('ʒ', Phoneme::Ezh),
+    ('ʔ', Phoneme::GlottalStop),
+    ('ʤ', Phoneme::Dezh),
+    ('ʧ', Phoneme::Tesh),
+    ('θ', Phoneme::Theta),
+    ('ç', Phoneme::CCedilla),
+    ('ˈ', Phoneme::PrimaryStress),
+    ('ˌ', Phoneme::SecondaryStress),
+    ('ː', Phoneme::Long),
+    ('\u{200D}', Phoneme::Tie),
+    ('\u{0329}', Phoneme::Syllabic),
+    ('\u{0303}', Phoneme::Nasalized),
+    ('ʲ', Phoneme::Palatalized),
+    (' ', Phoneme::Space),
+];
+
+const PUNCTUATION: &str = ";:,.!?—…\"()“”";
+
+impl Phoneme {
+    pub fn from_symbol(c: char) -> Option<Phoneme> {
+        if PUNCTUATION.contains(c) {
+            return Some(Phoneme::Punctuation(c));
+        }
+        SYMBOLS.iter().find(|(symbol, _)| *symbol == c).map(|(_, p)| *p)
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Phoneme::Punctuation(c) => c,
+            _ => SYMBOLS.iter().find(|(_, p)| *p == self).map(|(c, _)| *c).unwrap_or(' '),
+        }
+    }
+
+    pub fn is_vowel(self) -> bool {
+        use Phoneme::*;
+        matches!(
+            self,
+            Ei | Ai
+                | Ou
+                | Eu
+                | Au
+                | Oi
+                | OpenA
+                | E
+                | I
+                | O
+                | U
+                | Ash
+                | TurnedA
+                | ScriptA
+                | TurnedScriptA
+                | OpenO
+                | Schwa
+                | RhoticSchwa
+                | OpenE
+                | ReversedOpenE
+                | SmallCapitalI
+                | Upsilon
+                | TurnedV
+                | ReducedSchwa
+                | BarredSmallI
+        )
+    }
+
+    /// Stress, length, ties and diacritics, which modify a neighbouring
+    /// phoneme rather than being sounds of their own.
+    pub fn is_mark(self) -> bool {
+        matches!(
+            self,
+            Phoneme::PrimaryStress
+                | Phoneme::SecondaryStress
+                | Phoneme::Long
+                | Phoneme::Tie
+                | Phoneme::Syllabic
+                | Phoneme::Nasalized
+                | Phoneme::Palatalized
+        )
+    }
+}
+
+impl fmt::Display for Phoneme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+#[error("'{symbol}' at char {position} is not in the phoneme inventory")]
+pub struct PhonemeError {
+    pub symbol: char,
+    pub position: usize,
+}
+
+/// A phoneme string checked against the misaki inventory.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PhonemeString(Vec<Phoneme>);
+
+impl PhonemeString {
+    /// Fails at the first symbol outside the inventory.
+    pub fn parse(s: &str) -> Result<Self, PhonemeError> {
+        s.chars()
+            .enumerate()
+            .map(|(position, symbol)| Phoneme::from_symbol(symbol).ok_or(PhonemeError { symbol, position }))
+            .collect::<Result<Vec<_>, _>>()
+            .map(PhonemeString)
+    }
+
+    pub fn phonemes(&self) -> &[Phoneme] {
+        &self.0
+    }
+}
+
+impl Deref for PhonemeString {
+    type Target = [Phoneme];
+
+    fn deref(&self) -> &[Phoneme] {
+        &self.0
+    }
+}
+
+impl FromStr for PhonemeString {
+    type Err = PhonemeError;
+
+    fn from_str(s: &str) -> Result<Self, PhonemeError> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for PhonemeString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|p| write!(f, "{}", p))
+    }
+}
+
+impl Serialize for PhonemeString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PhonemeString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::parse(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let ps = PhonemeString::parse("ɐbˈe‍ɪt, kˈæt").unwrap();
+        assert_eq!(ps[..3], [Phoneme::TurnedA, Phoneme::B, Phoneme::PrimaryStress]);
+        assert_eq!(ps.iter().filter(|p| p.is_vowel()).count(), 4);
+        assert!(ps.contains(&Phoneme::Punctuation(',')));
+        assert_eq!(ps.to_string(), "ɐbˈe‍ɪt, kˈæt");
+
+        assert_eq!(
+            PhonemeString::parse("kˈæt❓"),
+            Err(PhonemeError {
+                symbol: '❓',
+                position: 4
+            })
+        );
+        assert!(serde_json::from_str::<PhonemeString>("\"kˈæg\"").is_err());
+    }
+
+    #[test]
+    fn test_inventory_round_trip() {
+        for &(c, p) in SYMBOLS {
+            assert_eq!(Phoneme::from_symbol(c), Some(p));
+            assert_eq!(p.symbol(), c);
+        }
+    }
+}
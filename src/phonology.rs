@@ -0,0 +1,468 @@
+//! Conversions between the lexicon's phoneme notation and other phonetic
+//! alphabets.
+
+use crate::options::OutputFormat;
+
+/// Misaki shorthand to standard IPA. Ties are dropped.
+const IPA: &[(char, &str)] = &[
+    ('A', "eɪ"),
+    ('I', "aɪ"),
+    ('O', "oʊ"),
+    ('Q', "əʊ"),
+    ('W', "aʊ"),
+    ('Y', "ɔɪ"),
+    ('ʤ', "dʒ"),
+    ('ʧ', "tʃ"),
+    ('ᵻ', "ɨ"),
+    ('\u{200D}', ""),
+];
+
+/// IPA to X-SAMPA. Symbols missing here are the same in both.
+const X_SAMPA: &[(char, &str)] = &[
+    ('ˈ', "\""),
+    ('ˌ', "%"),
+    ('ː', ":"),
+    ('æ', "{"),
+    ('ɑ', "A"),
+    ('ɒ', "Q"),
+    ('ɔ', "O"),
+    ('ə', "@"),
+    ('ᵊ', "@"),
+    ('ɚ', "@`"),
+    ('ɛ', "E"),
+    ('ɜ', "3"),
+    ('ɝ', "3`"),
+    ('ɪ', "I"),
+    ('ʊ', "U"),
+    ('ʌ', "V"),
+    ('ɐ', "6"),
+    ('ɨ', "1"),
+    ('ð', "D"),
+    ('θ', "T"),
+    ('ŋ', "N"),
+    ('ɹ', "r\\"),
+    ('ɾ', "4"),
+    ('ʃ', "S"),
+    ('ʒ', "Z"),
+    ('ʔ', "?"),
+    ('ɡ', "g"),
+    ('ɫ', "5"),
+    ('ɬ', "K"),
+    ('ç', "C"),
+    ('\u{0329}', "="),
+    ('\u{0303}', "~"),
+];
+
+/// IPA to Kirshenbaum. Symbols missing here are the same in both.
+const KIRSHENBAUM: &[(char, &str)] = &[
+    ('ˈ', "'"),
+    ('ˌ', ","),
+    ('ː', ":"),
+    ('æ', "&"),
+    ('ɑ', "A"),
+    ('ɒ', "A."),
+    ('ɔ', "O"),
+    ('ə', "@"),
+    ('ᵊ', "@"),
+    ('ɚ', "R"),
+    ('ɛ', "E"),
+    ('ɜ', "V\""),
+    ('ɪ', "I"),
+    ('ʊ', "U"),
+    ('ʌ', "V"),
+    ('ɐ', "a#"),
+    ('ɨ', "i\""),
+    ('ð', "D"),
+    ('θ', "T"),
+    ('ŋ', "N"),
+    ('ɹ', "r"),
+    ('ɾ', "*"),
+    ('ʃ', "S"),
+    ('ʒ', "Z"),
+    ('ʔ', "?"),
+    ('ɡ', "g"),
+    ('ɫ', "l~"),
+    ('ɬ', "s<lat>"),
+    ('ç', "C"),
+    ('\u{0329}', "-"),
+    ('\u{0303}', "~"),
+];
+
+fn map_chars(phonemes: &str, table: &[(char, &str)]) -> String {
+    let mut out = String::with_capacity(phonemes.len());
+    for c in phonemes.chars() {
+        match table.iter().find(|(from, _)| *from == c) {
+            Some((_, to)) => out.push_str(to),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Spells out misaki's shorthand in standard IPA: "hˈO" → "hˈoʊ".
+pub fn to_ipa(phonemes: &str) -> String {
+    map_chars(phonemes, IPA)
+}
+
+/// Converts lexicon phonemes to X-SAMPA: "kˈæt" → `k"{t`.
+pub fn to_x_sampa(phonemes: &str) -> String {
+    map_chars(&to_ipa(phonemes), X_SAMPA)
+}
+
+/// Converts lexicon phonemes to Kirshenbaum: "kˈæt" → `k'&t`.
+pub fn to_kirshenbaum(phonemes: &str) -> String {
+    map_chars(&to_ipa(phonemes), KIRSHENBAUM)
+}
+
+/// Converts lexicon phonemes to `format`.
+pub fn convert(phonemes: &str, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Misaki => phonemes.to_string(),
+        OutputFormat::Ipa => to_ipa(phonemes),
+        OutputFormat::XSampa => to_x_sampa(phonemes),
+        OutputFormat::Kirshenbaum => to_kirshenbaum(phonemes),
+    }
+}
+
+/// Lexicon phonemes (after stripping ties) to ARPAbet phones, with whether
+/// the phone is a vowel and so carries a stress digit. Matched longest first.
+const ARPABET: &[(&str, &str, bool)] = &[
+    ("eɪ", "EY", true),
+    ("A", "EY", true),
+    ("aɪ", "AY", true),
+    ("I", "AY", true),
+    ("oʊ", "OW", true),
+    ("əʊ", "OW", true),
+    ("O", "OW", true),
+    ("Q", "OW", true),
+    ("o", "OW", true),
+    ("aʊ", "AW", true),
+    ("W", "AW", true),
+    ("ɔɪ", "OY", true),
+    ("Y", "OY", true),
+    ("ɑː", "AA", true),
+    ("ɑ", "AA", true),
+    ("ɒː", "AA", true),
+    ("ɒ", "AA", true),
+    ("æ", "AE", true),
+    ("a", "AE", true),
+    ("ʌ", "AH", true),
+    ("ə", "AH", true),
+    ("ɐ", "AH", true),
+    ("ᵊ", "AH", true),
+    ("ɔː", "AO", true),
+    ("ɔ", "AO", true),
+    ("ɛ", "EH", true),
+    ("e", "EH", true),
+    ("ɜːɹ", "ER", true),
+    ("ɜɹ", "ER", true),
+    ("ɜː", "ER", true),
+    ("ɜ", "ER", true),
+    ("ɚɹ", "ER", true),
+    ("ɚ", "ER", true),
+    ("ɪ", "IH", true),
+    ("ᵻ", "IH", true),
+    ("iː", "IY", true),
+    ("i", "IY", true),
+    ("ʊ", "UH", true),
+    ("uː", "UW", true),
+    ("u", "UW", true),
+    ("b", "B", false),
+    ("tʃ", "CH", false),
+    ("ʧ", "CH", false),
+    ("d", "D", false),
+    ("dʒ", "JH", false),
+    ("ʤ", "JH", false),
+    ("ð", "DH", false),
+    ("f", "F", false),
+    ("ɡ", "G", false),
+    ("g", "G", false),
+    ("h", "HH", false),
+    ("k", "K", false),
+    ("x", "K", false),
+    ("l", "L", false),
+    ("ɫ", "L", false),
+    ("ɬ", "L", false),
+    ("m", "M", false),
+    ("n", "N", false),
+    ("ŋ", "NG", false),
+    ("p", "P", false),
+    ("ɹ", "R", false),
+    ("r", "R", false),
+    ("s", "S", false),
+    ("ʃ", "SH", false),
+    ("t", "T", false),
+    ("ɾ", "T", false),
+    ("ʔ", "T", false),
+    ("θ", "TH", false),
+    ("v", "V", false),
+    ("w", "W", false),
+    ("j", "Y", false),
+    ("z", "Z", false),
+    ("ʒ", "ZH", false),
+];
+
+/// ARPAbet phones to lexicon phonemes (US), for `from_arpabet`. `AH` and `ER`
+/// depend on stress and are handled separately.
+const FROM_ARPABET: &[(&str, &str)] = &[
+    ("AA", "ɑː"),
+    ("AE", "æ"),
+    ("AO", "ɔː"),
+    ("AW", "a‍ʊ"),
+    ("AY", "a‍ɪ"),
+    ("EH", "ɛ"),
+    ("EY", "e‍ɪ"),
+    ("IH", "ɪ"),
+    ("IY", "i"),
+    ("OW", "o‍ʊ"),
+    ("OY", "ɔ‍ɪ"),
+    ("UH", "ʊ"),
+    ("UW", "u"),
+    ("B", "b"),
+    ("CH", "t‍ʃ"),
+    ("D", "d"),
+    ("DH", "ð"),
+    ("F", "f"),
+    ("G", "ɡ"),
+    ("HH", "h"),
+    ("JH", "d‍ʒ"),
+    ("K", "k"),
+    ("L", "l"),
+    ("M", "m"),
+    ("N", "n"),
+    ("NG", "ŋ"),
+    ("P", "p"),
+    ("R", "ɹ"),
+    ("S", "s"),
+    ("SH", "ʃ"),
+    ("T", "t"),
+    ("TH", "θ"),
+    ("V", "v"),
+    ("W", "w"),
+    ("Y", "j"),
+    ("Z", "z"),
+    ("ZH", "ʒ"),
+];
+
+/// Separator between words in ARPAbet strings.
+pub const ARPABET_WORD_SEPARATOR: &str = "|";
+
+/// Longest entry of `ARPABET` that `s` starts with.
+fn longest_arpabet(s: &str) -> Option<&'static (&'static str, &'static str, bool)> {
+    ARPABET
+        .iter()
+        .filter(|(from, _, _)| s.starts_with(from))
+        .max_by_key(|(from, _, _)| from.len())
+}
+
+/// Converts lexicon phonemes to CMUdict-style ARPAbet, with stress digits on
+/// vowels (`1` primary, `2` secondary, `0` unstressed): "kˈæt" → "K AE1 T".
+/// Words are separated by `|`. The conversion is lossy: distinctions ARPAbet
+/// lacks (flaps, glottal stops, GB vowels) are folded into the nearest phone,
+/// and symbols with no counterpart are dropped.
+pub fn to_arpabet(phonemes: &str) -> String {
+    let words: Vec<String> = phonemes
+        .split_whitespace()
+        .map(|word| {
+            // Ties and diacritics (syllabic, nasal) don't change the phone
+            let word: String = word.chars().filter(|c| !matches!(c, '\u{200D}' | '\u{0329}' | '\u{0303}')).collect();
+            let mut phones = Vec::new();
+            let mut stress = None;
+            let mut rest = word.as_str();
+            while let Some(c) = rest.chars().next() {
+                match c {
+                    'ˈ' => stress = Some('1'),
+                    'ˌ' => stress = Some('2'),
+                    _ => {
+                        if let Some(&(from, phone, vowel)) = longest_arpabet(rest) {
+                            if vowel {
+                                phones.push(format!("{}{}", phone, stress.take().unwrap_or('0')));
+                            } else {
+                                phones.push(phone.to_string());
+                            }
+                            rest = &rest[from.len()..];
+                            continue;
+                        }
+                    }
+                }
+                rest = &rest[c.len_utf8()..];
+            }
+            phones.join(" ")
+        })
+        .filter(|phones| !phones.is_empty())
+        .collect();
+    words.join(&format!(" {} ", ARPABET_WORD_SEPARATOR))
+}
+
+/// Converts ARPAbet, with or without stress digits, to lexicon phonemes in
+/// the US notation: "K AE1 T" → "kˈæt". `|` separates words; unknown phones
+/// are dropped.
+pub fn from_arpabet(arpabet: &str) -> String {
+    let mut phonemes = String::new();
+    for phone in arpabet.split_whitespace() {
+        if phone == ARPABET_WORD_SEPARATOR {
+            phonemes.push(' ');
+            continue;
+        }
+        let phone = phone.to_uppercase();
+        let (base, stress) = match phone.strip_suffix(['0', '1', '2']) {
+            Some(base) => (base, phone.chars().last()),
+            None => (phone.as_str(), None),
+        };
+        let stressed = matches!(stress, Some('1' | '2'));
+        let ipa = match base {
+            "AH" if stressed => "ʌ",
+            "AH" => "ə",
+            "ER" if stressed => "ɜɹ",
+            "ER" => "ɚ",
+            _ => match FROM_ARPABET.iter().find(|(arpa, _)| *arpa == base) {
+                Some((_, ipa)) => ipa,
+                None => continue,
+            },
+        };
+        match stress {
+            Some('1') => phonemes.push('ˈ'),
+            Some('2') => phonemes.push('ˌ'),
+            _ => {}
+        }
+        phonemes.push_str(ipa);
+    }
+    phonemes
+}
+
+/// The 15 visemes of the Oculus/ARKit lip-sync scheme, numbered as there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Viseme {
+    Sil = 0,
+    Pp = 1,
+    Ff = 2,
+    Th = 3,
+    Dd = 4,
+    Kk = 5,
+    Ch = 6,
+    Ss = 7,
+    Nn = 8,
+    Rr = 9,
+    Aa = 10,
+    E = 11,
+    I = 12,
+    O = 13,
+    U = 14,
+}
+
+impl Viseme {
+    /// The scheme's name for the viseme ("sil", "PP", "aa", ...).
+    pub fn name(self) -> &'static str {
+        match self {
+            Viseme::Sil => "sil",
+            Viseme::Pp => "PP",
+            Viseme::Ff => "FF",
+            Viseme::Th => "TH",
+            Viseme::Dd => "DD",
+            Viseme::Kk => "kk",
+            Viseme::Ch => "CH",
+            Viseme::Ss => "SS",
+            Viseme::Nn => "nn",
+            Viseme::Rr => "RR",
+            Viseme::Aa => "aa",
+            Viseme::E => "E",
+            Viseme::I => "I",
+            Viseme::O => "O",
+            Viseme::U => "U",
+        }
+    }
+
+    /// Mouth shape for one phoneme symbol; `None` for marks that don't move
+    /// the mouth on their own (stress, length, ties, diacritics).
+    pub fn for_phoneme(c: char) -> Option<Viseme> {
+        Some(match c {
+            'p' | 'b' | 'm' => Viseme::Pp,
+            'f' | 'v' => Viseme::Ff,
+            'θ' | 'ð' => Viseme::Th,
+            't' | 'd' | 'ɾ' | 'ʔ' => Viseme::Dd,
+            'k' | 'g' | 'ɡ' | 'ŋ' | 'x' | 'h' => Viseme::Kk,
+            'ʃ' | 'ʒ' | 'ʧ' | 'ʤ' | 'ç' => Viseme::Ch,
+            's' | 'z' => Viseme::Ss,
+            'n' | 'l' | 'ɫ' | 'ɬ' => Viseme::Nn,
+            'ɹ' | 'r' | 'ɚ' | 'ɜ' => Viseme::Rr,
+            'ɑ' | 'a' | 'æ' | 'ɐ' | 'ʌ' | 'I' | 'W' => Viseme::Aa,
+            'ɛ' | 'e' | 'ə' | 'ᵊ' | 'A' => Viseme::E,
+            'ɪ' | 'i' | 'ᵻ' | 'j' => Viseme::I,
+            'ɔ' | 'ɒ' | 'o' | 'O' | 'Q' | 'Y' => Viseme::O,
+            'ʊ' | 'u' | 'w' => Viseme::U,
+            'ˈ' | 'ˌ' | 'ː' | '\u{200D}' | '\u{0329}' | '\u{0303}' => return None,
+            _ => Viseme::Sil,
+        })
+    }
+}
+
+/// Visemes for lexicon phonemes, each with the char index of the phoneme it
+/// starts at, so they line up with phoneme timings. Tied diphthongs give one
+/// viseme per element; whitespace, punctuation and unknown symbols are
+/// silence, with runs of silence merged.
+pub fn to_visemes(phonemes: &str) -> Vec<(Viseme, usize)> {
+    let mut visemes: Vec<(Viseme, usize)> = Vec::new();
+    for (i, c) in phonemes.chars().enumerate() {
+        let Some(viseme) = Viseme::for_phoneme(c) else {
+            continue;
+        };
+        if viseme == Viseme::Sil && visemes.last().is_some_and(|(v, _)| *v == Viseme::Sil) {
+            continue;
+        }
+        visemes.push((viseme, i));
+    }
+    visemes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_formats() {
+        assert_eq!(to_ipa("hˈO ʤˈʌmp"), "hˈoʊ dʒˈʌmp");
+        assert_eq!(to_ipa("ɐbˈe‍ɪt"), "ɐbˈeɪt");
+        assert_eq!(to_x_sampa("kˈæt"), "k\"{t");
+        assert_eq!(to_x_sampa("ðə ɹˈɛd"), "D@ r\\\"Ed");
+        assert_eq!(to_kirshenbaum("kˈæt"), "k'&t");
+        assert_eq!(convert("θˈIŋk", OutputFormat::Kirshenbaum), "T'aINk");
+        assert_eq!(convert("θˈIŋk", OutputFormat::Misaki), "θˈIŋk");
+    }
+
+    #[test]
+    fn test_to_visemes() {
+        assert_eq!(
+            to_visemes("kˈæt, ʃi"),
+            [
+                (Viseme::Kk, 0),
+                (Viseme::Aa, 2),
+                (Viseme::Dd, 3),
+                (Viseme::Sil, 4),
+                (Viseme::Ch, 6),
+                (Viseme::I, 7),
+            ]
+        );
+        let names: Vec<&str> = to_visemes("ˈa‍ɪ").iter().map(|(v, _)| v.name()).collect();
+        assert_eq!(names, ["aa", "I"]);
+    }
+
+    #[test]
+    fn test_to_arpabet() {
+        assert_eq!(to_arpabet("kˈæt"), "K AE1 T");
+        assert_eq!(to_arpabet("ɐbˈe‍ɪt"), "AH0 B EY1 T");
+        assert_eq!(to_arpabet("ˈɑː‍ɹdvɑː‍ɹk"), "AA1 R D V AA0 R K");
+        assert_eq!(to_arpabet("həlˈO wˈɜɹld"), "HH AH0 L OW1 | W ER1 L D");
+        assert_eq!(to_arpabet("ˌæbɚɹˈe‍ɪʃən"), "AE2 B ER0 EY1 SH AH0 N");
+    }
+
+    #[test]
+    fn test_from_arpabet() {
+        assert_eq!(from_arpabet("K AE1 T"), "kˈæt");
+        assert_eq!(from_arpabet("HH AH0 L OW1 | W ER1 L D"), "həlˈo‍ʊ wˈɜɹld");
+        assert_eq!(from_arpabet("jh ah1 m p"), "d‍ʒˈʌmp");
+
+        for arpabet in ["AH0 B EY1 T", "AE2 B ER0 EY1 SH AH0 N", "CH IY1 Z | AY1"] {
+            assert_eq!(to_arpabet(&from_arpabet(arpabet)), arpabet);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! The stages `G2P::g2p` runs tokens through, and the `Stage` trait for
+//! adding your own between them (see `G2PBuilder::stage`): bleeping words,
+//! normalizers for a domain's jargon, or anything else that edits tokens.
+//!
+//! The built-in stages always all run, in the order of `BuiltinStage::ALL`;
+//! they can't be removed, replaced or reordered, only added to. A custom
+//! stage can still undo or override one: a token it gives phonemes before
+//! `BuiltinStage::Lexicon` is read as they say, and one after `Accent` sees
+//! the accented phonemes, before `output_format` converts them.
+
+use crate::lexicon::Lexicon;
+use crate::options::G2POptions;
+use crate::token::MToken;
+
+/// A built-in stage of the pipeline. They run in the order of `ALL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinStage {
+    /// Splits the preprocessed text into tokens (see `G2P::tokenize`),
+    /// applying the features of links such as `[word](/phonemes/)`.
+    Tokenize,
+    /// Tags tokens with the tagger.
+    Tag,
+    /// Splits tokens into subtokens at symbols, digits and hyphens ("$5",
+    /// "twenty-one"), tagged as the word they make up.
+    Retokenize,
+    /// Silences the periods of abbreviations, flags numbers ("$5", "5th"),
+    /// handles emoji, and looks up split words whole.
+    Normalize,
+    /// Reads what can be read without guessing: aliases, numbers, other
+    /// scripts, heteronyms, the lexicon and the language's rules.
+    Lexicon,
+    /// Reads the words the lexicon couldn't, as `G2POptions::oov_policy`
+    /// says, then merges split words back into one token.
+    Fallback,
+    /// Applies the dialect's accent and the `G2PBuilder::post_process` hooks.
+    Accent,
+    /// Sets intonation, pause markers and timings.
+    Prosody,
+}
+
+impl BuiltinStage {
+    pub const ALL: [BuiltinStage; 8] = [
+        BuiltinStage::Tokenize,
+        BuiltinStage::Tag,
+        BuiltinStage::Retokenize,
+        BuiltinStage::Normalize,
+        BuiltinStage::Lexicon,
+        BuiltinStage::Fallback,
+        BuiltinStage::Accent,
+        BuiltinStage::Prosody,
+    ];
+}
+
+/// What a stage can see besides the tokens.
+pub struct PipelineCtx<'a> {
+    /// The preprocessed text, which tokens' `char_span` index.
+    pub text: &'a str,
+    pub lexicon: &'a Lexicon,
+    pub options: &'a G2POptions,
+    /// What is written for a token without phonemes.
+    pub unk: &'a str,
+}
+
+/// A step added to the pipeline. It may change tokens, drop them or add
+/// new ones; a token given phonemes before `BuiltinStage::Lexicon` is read
+/// as they say. Closures `|tokens: &mut Vec<MToken>, ctx: &PipelineCtx|`
+/// implement it.
+pub trait Stage: Send + Sync {
+    fn process(&self, tokens: &mut Vec<MToken>, ctx: &PipelineCtx);
+}
+
+impl<F> Stage for F
+where
+    F: Fn(&mut Vec<MToken>, &PipelineCtx) + Send + Sync,
+{
+    fn process(&self, tokens: &mut Vec<MToken>, ctx: &PipelineCtx) {
+        self(tokens, ctx)
+    }
+}
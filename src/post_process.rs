@@ -0,0 +1,21 @@
+//! Hooks that rewrite each token's phonemes once the pipeline has resolved
+//! them (see `G2PBuilder::post_process`): accent tweaks, masking words, or
+//! symbols a particular TTS engine wants in place of the lexicon's.
+
+use crate::token::MToken;
+
+/// Rewrites the phonemes of a token, in the lexicon's notation: `phonemes`
+/// is what the pipeline (and any hook before this one) made of `token`.
+/// Closures `|tk: &MToken, ps: &str| -> String` implement it.
+pub trait PostProcess: Send + Sync {
+    fn process(&self, token: &MToken, phonemes: &str) -> String;
+}
+
+impl<F> PostProcess for F
+where
+    F: Fn(&MToken, &str) -> String + Send + Sync,
+{
+    fn process(&self, token: &MToken, phonemes: &str) -> String {
+        self(token, phonemes)
+    }
+}
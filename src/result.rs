@@ -0,0 +1,124 @@
+use crate::lexicon::Rating;
+use crate::token::MToken;
+use serde::{Deserialize, Serialize};
+
+/// Everything `G2P::analyze` worked out about a text, in a form that can be
+/// sent over JSON to a synthesis worker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct G2PResult {
+    /// The input text.
+    pub text: String,
+    pub phonemes: String,
+    pub tokens: Vec<MToken>,
+    /// `phonemes` split at token boundaries into pieces that fit one Kokoro
+    /// call (see `chunk`).
+    pub chunks: Vec<String>,
+    pub warnings: Vec<G2PWarning>,
+}
+
+/// A sentence of a document read by `G2P::g2p_stream`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SentenceResult {
+    pub text: String,
+    /// Char offsets of `text` in the whole input.
+    pub char_span: (usize, usize),
+    pub phonemes: String,
+    /// Tokens of the sentence, with char spans into the whole input.
+    pub tokens: Vec<MToken>,
+}
+
+/// Something in the text G2P could only guess at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum G2PWarning {
+    /// Not in the lexicon: read by the OOV fallback or spelled out.
+    Guessed { word: String, char_span: (usize, usize) },
+    /// Left as the unknown marker.
+    Unknown { word: String, char_span: (usize, usize) },
+    /// Taken for a typo and read as `correction`.
+    Corrected { word: String, correction: String, char_span: (usize, usize) },
+}
+
+/// Warnings for the tokens G2P couldn't find in the lexicon.
+pub fn warnings(tokens: &[MToken]) -> Vec<G2PWarning> {
+    tokens
+        .iter()
+        .filter_map(|tk| {
+            let (word, char_span) = (tk.text.clone(), tk.char_span);
+            match tk.underscore().rating? {
+                Rating::Fallback => Some(G2PWarning::Guessed { word, char_span }),
+                Rating::Corrected => {
+                    let correction = tk.underscore().correction.clone()?;
+                    Some(G2PWarning::Corrected { word, correction, char_span })
+                }
+                Rating::Unknown => Some(G2PWarning::Unknown { word, char_span }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// How good a place the end of a token is to split: sentence ends beat
+/// clause punctuation, which beats a plain word gap.
+fn break_strength(tk: &MToken) -> u8 {
+    match tk.text.chars().last() {
+        Some('.' | '!' | '?' | '…') => 2,
+        Some(',' | ';' | ':' | '—') => 1,
+        _ => 0,
+    }
+}
+
+/// Joins the tokens' phonemes (`unk` for tokens without) into chunks of at
+/// most `max_len` chars, splitting only between tokens and at the strongest
+/// break available. A single token longer than `max_len` gets a chunk of its
+/// own.
+pub fn chunk(tokens: &[MToken], unk: &str, max_len: usize) -> Vec<String> {
+    let pieces: Vec<String> = tokens
+        .iter()
+        .map(|tk| tk.phonemes.as_deref().unwrap_or(unk).to_string() + tk.phoneme_space())
+        .collect();
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < pieces.len() {
+        let mut end = start;
+        let mut len = 0;
+        while end < pieces.len() {
+            let piece_len = pieces[end].trim_end().chars().count();
+            if end > start && len + piece_len > max_len {
+                break;
+            }
+            len += pieces[end].chars().count();
+            end += 1;
+        }
+        if end < pieces.len() {
+            end = (start + 1..=end).max_by_key(|&i| break_strength(&tokens[i - 1])).unwrap_or(end);
+        }
+        let chunk = pieces[start..end].concat().trim().to_string();
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+        start = end;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk() {
+        let tokens = [
+            MToken::read_as("One", "wˈʌn"),
+            MToken::read_as("two", "tˈu"),
+            MToken::read_as(".", "."),
+            MToken::read_as("Three", "θɹˈi"),
+            MToken::read_as(",", ","),
+            MToken::read_as("four", "fˈɔɹ"),
+        ];
+        assert_eq!(chunk(&tokens, "❓", 100), ["wˈʌn tˈu . θɹˈi , fˈɔɹ"]);
+        // Splits after the period rather than at the last word gap that fits
+        assert_eq!(chunk(&tokens, "❓", 16), ["wˈʌn tˈu .", "θɹˈi , fˈɔɹ"]);
+        assert_eq!(chunk(&tokens, "❓", 2), ["wˈʌn", "tˈu", ".", "θɹˈi", ",", "fˈɔɹ"]);
+    }
+}
@@ -0,0 +1,180 @@
+/// Writing systems the G2P distinguishes when deciding how to read a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Han,
+    /// Hiragana and katakana.
+    Kana,
+    Hangul,
+    Arabic,
+    Hebrew,
+    Devanagari,
+    Thai,
+    Other,
+}
+
+impl Script {
+    /// Script of a letter, or `None` for characters shared across scripts
+    /// (digits, punctuation, symbols, marks).
+    pub fn of(c: char) -> Option<Script> {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        Some(match c as u32 {
+            0x0041..=0x024F | 0x1E00..=0x1EFF | 0x2C60..=0x2C7F | 0xA720..=0xA7FF => Script::Latin,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x0400..=0x052F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => Script::Cyrillic,
+            0x0590..=0x05FF | 0xFB1D..=0xFB4F => Script::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0x08A0..=0x08FF | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => {
+                Script::Arabic
+            }
+            0x0900..=0x097F => Script::Devanagari,
+            0x0E00..=0x0E7F => Script::Thai,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Kana,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x2FA1F => Script::Han,
+            _ => Script::Other,
+        })
+    }
+
+    /// The script of a word: its first non-Latin letter's script if it has
+    /// one, `Latin` if all its letters are Latin, and `None` without letters.
+    pub fn detect(word: &str) -> Option<Script> {
+        let mut scripts = word.chars().filter_map(Script::of);
+        let first = scripts.next()?;
+        if first != Script::Latin {
+            return Some(first);
+        }
+        Some(scripts.find(|&s| s != Script::Latin).unwrap_or(Script::Latin))
+    }
+}
+
+/// Romanization of a lowercase Cyrillic letter.
+fn cyrillic_to_latin(c: char) -> Option<&'static str> {
+    Some(match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' | 'ґ' => "g",
+        'д' => "d",
+        'е' => "e",
+        'ё' => "yo",
+        'є' => "ye",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ў' => "w",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ъ' | 'ь' => "",
+        'ы' => "y",
+        'э' => "e",
+        'ю' => "yu",
+        'я' => "ya",
+        _ => return None,
+    })
+}
+
+/// Romanization of a lowercase Greek letter.
+fn greek_to_latin(c: char) -> Option<&'static str> {
+    Some(match c {
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' => "i",
+        'θ' => "th",
+        'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' | 'ώ' => "o",
+        _ => return None,
+    })
+}
+
+/// Romanizes a Cyrillic or Greek word ("Здравствуй" → "Zdravstvuy"). Returns
+/// `None` for other scripts or if a letter has no romanization.
+pub fn to_latin(word: &str) -> Option<String> {
+    let table = match Script::detect(word)? {
+        Script::Cyrillic => cyrillic_to_latin,
+        Script::Greek => greek_to_latin,
+        _ => return None,
+    };
+    let mut latin = String::with_capacity(word.len());
+    for c in word.chars() {
+        if Script::of(c).is_none_or(|s| s == Script::Latin) {
+            latin.push(c);
+            continue;
+        }
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        let roman = table(lower)?;
+        if c.is_uppercase() {
+            let mut chars = roman.chars();
+            if let Some(first) = chars.next() {
+                latin.extend(first.to_uppercase());
+                latin.push_str(chars.as_str());
+            }
+        } else {
+            latin.push_str(roman);
+        }
+    }
+    Some(latin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Script::detect("hello"), Some(Script::Latin));
+        assert_eq!(Script::detect("café"), Some(Script::Latin));
+        assert_eq!(Script::detect("世界"), Some(Script::Han));
+        assert_eq!(Script::detect("こんにちは"), Some(Script::Kana));
+        assert_eq!(Script::detect("Здравствуй"), Some(Script::Cyrillic));
+        assert_eq!(Script::detect("مرحبا"), Some(Script::Arabic));
+        assert_eq!(Script::detect("abcд"), Some(Script::Cyrillic));
+        assert_eq!(Script::detect("123"), None);
+    }
+
+    #[test]
+    fn test_to_latin() {
+        assert_eq!(to_latin("Здравствуй").as_deref(), Some("Zdravstvuy"));
+        assert_eq!(to_latin("Щука").as_deref(), Some("Shchuka"));
+        assert_eq!(to_latin("Αθήνα").as_deref(), Some("Athina"));
+        assert_eq!(to_latin("世界"), None);
+        assert_eq!(to_latin("hello"), None);
+    }
+}
@@ -0,0 +1,224 @@
+//! Phonemization over HTTP, for a sidecar shared by several TTS workers:
+//!
+//! - `POST /g2p` takes `{"text": ..., "language": "en-gb", "options":
+//!   {...}}` (all but `text` optional) and answers with a `G2PResult`.
+//! - `GET /health` answers "ok".
+//! - `GET /metrics` gives request counts in the Prometheus text format.
+//!
+//! Serve `router` with `axum::serve`, or run `misaki serve` (features `cli`
+//! and `server`).
+
+use crate::async_g2p::AsyncG2P;
+use crate::g2p::{G2P, G2PError};
+use crate::language::Language;
+use crate::options::{G2POptions, InputFormat, OutputFormat};
+use crate::result::G2PResult;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// The body of `POST /g2p`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct G2PRequest {
+    pub text: String,
+    /// A code `Language::from_code` reads; the server's default if unset.
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub options: RequestOptions,
+}
+
+/// The options a request may set; the rest are the defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RequestOptions {
+    pub output_format: Option<OutputFormat>,
+    pub input_format: Option<InputFormat>,
+    pub correct_typos: bool,
+}
+
+impl RequestOptions {
+    fn to_options(self) -> G2POptions {
+        let defaults = G2POptions::default();
+        G2POptions {
+            output_format: self.output_format.unwrap_or(defaults.output_format),
+            input_format: self.input_format.unwrap_or(defaults.input_format),
+            correct_typos: self.correct_typos,
+            ..defaults
+        }
+    }
+}
+
+#[derive(Default)]
+struct Metrics {
+    requests: AtomicU64,
+    /// Requests answered with an error status.
+    errors: AtomicU64,
+    tokens: AtomicU64,
+}
+
+struct Server {
+    default: Language,
+    /// G2Ps by language, built the first time they are asked for and
+    /// sharing the workers of `workers`; a request's options are passed per
+    /// call. The lock is held only to find a G2P's cell; requests for one
+    /// being built wait on the cell.
+    g2ps: Mutex<HashMap<Language, Arc<OnceCell<AsyncG2P>>>>,
+    workers: AsyncG2P,
+    metrics: Metrics,
+}
+
+/// The server's routes, reading `default` where a request names no
+/// language, with at most `workers` requests phonemized at once; the rest
+/// wait their turn.
+pub fn router(default: Language, workers: usize) -> Router {
+    let server = Server {
+        workers: AsyncG2P::new(G2P::new(default.clone()), workers),
+        default,
+        g2ps: Mutex::new(HashMap::new()),
+        metrics: Metrics::default(),
+    };
+    Router::new()
+        .route("/g2p", post(g2p))
+        .route("/health", get(|| async { "ok" }))
+        .route("/metrics", get(metrics))
+        .with_state(Arc::new(server))
+}
+
+/// An error response: the status and `{"error": message}`.
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl Server {
+    async fn g2p_for(&self, lang: Language) -> AsyncG2P {
+        if lang == self.default {
+            return self.workers.clone();
+        }
+        let cell = {
+            let mut g2ps = self.g2ps.lock().unwrap();
+            Arc::clone(g2ps.entry(lang.clone()).or_default())
+        };
+        let g2p = cell.get_or_init(|| self.workers.build_sharing_workers(move || G2P::new(lang))).await;
+        g2p.clone()
+    }
+
+    /// G2Ps built so far, the default's included.
+    fn g2p_count(&self) -> usize {
+        let g2ps = self.g2ps.lock().unwrap();
+        g2ps.values().filter(|cell| cell.initialized()).count() + 1
+    }
+
+    async fn read(&self, request: G2PRequest) -> Result<G2PResult, ApiError> {
+        let lang = match &request.language {
+            Some(code) => Language::from_code(code)
+                .ok_or_else(|| ApiError(StatusCode::BAD_REQUEST, format!("unknown language '{}'", code)))?,
+            None => self.default.clone(),
+        };
+        let g2p = self.g2p_for(lang).await;
+        g2p.analyze_with(request.text, request.options.to_options()).await.map_err(|e| match e {
+            G2PError::UnknownWord { .. } => ApiError(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+            e => ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })
+    }
+}
+
+async fn g2p(State(server): State<Arc<Server>>, Json(request): Json<G2PRequest>) -> Result<Json<G2PResult>, ApiError> {
+    server.metrics.requests.fetch_add(1, Ordering::Relaxed);
+    let result = server.read(request).await;
+    match &result {
+        Ok(result) => server.metrics.tokens.fetch_add(result.tokens.len() as u64, Ordering::Relaxed),
+        Err(_) => server.metrics.errors.fetch_add(1, Ordering::Relaxed),
+    };
+    result.map(Json)
+}
+
+async fn metrics(State(server): State<Arc<Server>>) -> String {
+    let m = &server.metrics;
+    let lines = [
+        ("misaki_requests_total", "counter", "POST /g2p requests.", m.requests.load(Ordering::Relaxed)),
+        ("misaki_request_errors_total", "counter", "POST /g2p requests that failed.", m.errors.load(Ordering::Relaxed)),
+        ("misaki_tokens_total", "counter", "Tokens phonemized.", m.tokens.load(Ordering::Relaxed)),
+        ("misaki_workers_available", "gauge", "Workers free to take a request.", server.workers.available() as u64),
+        ("misaki_g2ps", "gauge", "G2Ps built, one to a language.", server.g2p_count() as u64),
+    ];
+    lines
+        .iter()
+        .map(|(name, kind, help, value)| format!("# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    /// Status line and body of a request to `addr`.
+    fn request(addr: std::net::SocketAddr, method: &str, path: &str, body: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap().to_string(), body.to_string())
+    }
+
+    #[test]
+    fn test_server() {
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_io().build().unwrap();
+        let listener = runtime.block_on(tokio::net::TcpListener::bind("127.0.0.1:0")).unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(async move { axum::serve(listener, router(Language::EnglishUS, 2)).await });
+
+        let (status, body) = request(addr, "GET", "/health", "");
+        assert!(status.contains("200"), "{}", status);
+        assert_eq!(body, "ok");
+
+        let (status, body) = request(addr, "POST", "/g2p", r#"{"text": "The cat."}"#);
+        assert!(status.contains("200"), "{}", status);
+        let result: G2PResult = serde_json::from_str(&body).unwrap();
+        assert_eq!(result.phonemes, G2P::new(Language::EnglishUS).g2p("The cat.").unwrap().0);
+
+        let (_, body) = request(
+            addr,
+            "POST",
+            "/g2p",
+            r#"{"text": "cat", "language": "en-gb", "options": {"output_format": "x_sampa"}}"#,
+        );
+        let result: G2PResult = serde_json::from_str(&body).unwrap();
+        assert_eq!(result.phonemes, r#"k"at"#);
+
+        // Options are per request; the default language's G2P reads them
+        let (_, body) = request(addr, "POST", "/g2p", r#"{"text": "cat", "options": {"output_format": "x_sampa"}}"#);
+        let result: G2PResult = serde_json::from_str(&body).unwrap();
+        assert_eq!(result.phonemes, r#"k"{t"#);
+
+        let (status, body) = request(addr, "POST", "/g2p", r#"{"text": "cat", "language": "xx"}"#);
+        assert!(status.contains("400"), "{}", status);
+        assert!(body.contains("unknown language"));
+
+        let (_, body) = request(addr, "GET", "/metrics", "");
+        assert!(body.contains("misaki_requests_total 4\n"), "{}", body);
+        assert!(body.contains("misaki_request_errors_total 1\n"), "{}", body);
+        // The default and en-gb, however many options they were read with
+        assert!(body.contains("misaki_g2ps 2\n"), "{}", body);
+    }
+}
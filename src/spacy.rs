@@ -0,0 +1,237 @@
+//! A rule tokenizer reproducing spaCy's English tokenization, which Python
+//! misaki tags and phonemizes from: prefixes and suffixes are peeled off each
+//! whitespace-separated chunk, what remains is split at infixes, and a table
+//! of exceptions keeps abbreviations whole and splits contractions
+//! ("don't" → "do" "n't").
+
+/// Punctuation split off the front of a chunk.
+const PREFIXES: &str = "§%=—–…,:;!?¿¡()[]{}<>_#*&'\"”“`‘´’‚„»«$£€¥₹";
+/// Punctuation split off the end of a chunk ("." has rules of its own).
+const SUFFIXES: &str = "…,:;!?¿¡()[]{}<>_#*&'\"”“`‘´’‚„»«—–%";
+const QUOTES: &str = "'\"”“`‘´’‚„»«";
+
+/// Kept whole, period included.
+const ABBREVIATIONS: &[&str] = &[
+    "Mr.", "Mrs.", "Ms.", "Dr.", "St.", "Jr.", "Sr.", "Mt.", "Prof.", "Gen.", "Gov.", "Rep.", "Sen.", "Rev.", "Inc.",
+    "Ltd.", "Co.", "Corp.", "Jan.", "Feb.", "Mar.", "Apr.", "Jun.", "Jul.", "Aug.", "Sep.", "Sept.", "Oct.", "Nov.",
+    "Dec.", "e.g.", "i.e.", "etc.", "vs.", "a.m.", "p.m.",
+];
+
+/// Verbs spaCy splits "n't" from ("ca" for "can't", "wo" for "won't").
+const NT_STEMS: &[&str] = &[
+    "ai", "are", "ca", "could", "did", "does", "do", "had", "has", "have", "is", "might", "must", "need", "sha",
+    "should", "was", "were", "wo", "would",
+];
+
+/// Clitics and the words spaCy splits them from.
+const CLITICS: &[(&str, &[&str])] = &[
+    ("'m", &["i"]),
+    ("'re", &["you", "we", "they", "who", "what"]),
+    ("'ll", &["i", "you", "he", "she", "it", "we", "they", "that", "there", "this", "who", "what"]),
+    ("'ve", &["i", "you", "we", "they", "who", "what", "could", "would", "should", "might", "must"]),
+    ("'d", &["i", "you", "he", "she", "it", "we", "they", "that", "there", "who", "what"]),
+];
+
+/// Words spaCy splits without an apostrophe.
+const FUSED: &[(&str, usize)] = &[("cannot", 3), ("gonna", 3), ("gotta", 3)];
+
+/// Byte offsets where an exception splits `word`, or `None` if it is not an
+/// exception. Abbreviations are exceptions with no splits.
+fn exception(word: &str) -> Option<Vec<usize>> {
+    if ABBREVIATIONS.contains(&word) || (word.len() == 2 && word.ends_with('.') && word.starts_with(|c: char| c.is_ascii_lowercase())) {
+        return Some(Vec::new());
+    }
+    let lower = word.to_lowercase().replace('’', "'");
+    // Case folding and apostrophe folding keep one char per char for ASCII
+    // words; splits are found in `lower` and mapped back to `word`'s bytes
+    if !lower.is_ascii() || lower.len() != word.chars().count() {
+        return None;
+    }
+    let to_bytes = |splits: Vec<usize>| splits.into_iter().map(|at| word.char_indices().nth(at).map_or(word.len(), |(i, _)| i)).collect();
+    if let Some(&(_, at)) = FUSED.iter().find(|(w, _)| *w == lower) {
+        return Some(to_bytes(vec![at]));
+    }
+    let mut splits = Vec::new();
+    let mut stem = lower.as_str();
+    while let Some((clitic, hosts)) = CLITICS.iter().find(|(c, _)| stem.ends_with(c)) {
+        let host = &stem[..stem.len() - clitic.len()];
+        // Clitics stack: "couldn't've", "they'd've"
+        if !(hosts.contains(&host) || host.ends_with("n't") || host.ends_with("'d")) {
+            break;
+        }
+        splits.push(host.len());
+        stem = host;
+    }
+    if let Some(host) = stem.strip_suffix("n't")
+        && NT_STEMS.contains(&host)
+    {
+        splits.push(host.len());
+    }
+    if splits.is_empty() {
+        return None;
+    }
+    splits.reverse();
+    Some(to_bytes(splits))
+}
+
+fn prefix_len(s: &str) -> usize {
+    if s.starts_with("..") {
+        return s.len() - s.trim_start_matches('.').len();
+    }
+    match s.chars().next() {
+        Some('+') if !s[1..].starts_with(|c: char| c.is_ascii_digit()) => 1,
+        Some(c) if PREFIXES.contains(c) => c.len_utf8(),
+        _ => 0,
+    }
+}
+
+fn suffix_len(s: &str) -> usize {
+    if s.ends_with("..") {
+        return s.len() - s.trim_end_matches('.').len();
+    }
+    for possessive in ["'s", "'S", "’s", "’S"] {
+        if s.len() > possessive.len() && s.ends_with(possessive) {
+            return possessive.len();
+        }
+    }
+    let mut rev = s.chars().rev();
+    match (rev.next(), rev.next(), rev.next()) {
+        (Some('.'), Some(p), p2) => {
+            let after_word = p.is_ascii_digit() || p.is_lowercase() || "%²-+".contains(p) || QUOTES.contains(p) || PREFIXES.contains(p);
+            let after_caps = p.is_uppercase() && p2.is_some_and(char::is_uppercase);
+            if after_word || after_caps { 1 } else { 0 }
+        }
+        (Some(c), ..) if SUFFIXES.contains(c) => c.len_utf8(),
+        _ => 0,
+    }
+}
+
+/// Splits `s` at spaCy's infixes: ellipses, hyphens between letters,
+/// operators between digits, and ",", ".", ":", "/" etc. between letters.
+/// Returns `(start, end)` byte ranges relative to `s`.
+fn split_infixes(s: &str) -> Vec<(usize, usize)> {
+    let chars: Vec<(usize, char)> = s.char_indices().collect();
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let (b, c) = chars[i];
+        let prev = i.checked_sub(1).map(|j| chars[j].1);
+        let next = chars.get(i + 1).map(|&(_, c)| c);
+        let is_alpha = |c: Option<char>| c.is_some_and(char::is_alphabetic);
+        let is_alnum = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+        let is_digit = |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit());
+        let single = c == '…'
+            || ("+-*^".contains(c) && is_digit(prev) && (is_digit(next) || next == Some('-')))
+            || (c == '.'
+                && prev.is_some_and(|p| p.is_lowercase() || QUOTES.contains(p))
+                && next.is_some_and(|n| n.is_uppercase() || QUOTES.contains(n)))
+            || (c == ',' && is_alpha(prev) && is_alpha(next))
+            || (":<>=/".contains(c) && is_alnum(prev) && is_alpha(next));
+        let len = if single {
+            1
+        } else if c == '.' && next == Some('.') {
+            chars[i..].iter().take_while(|&&(_, c)| c == '.').count()
+        } else if "-–—~".contains(c) && is_alnum(prev) {
+            // Hyphen runs ("--") count as one infix
+            let run = chars[i..].iter().take_while(|&&(_, c)| "-–—~".contains(c)).count();
+            if is_alpha(chars.get(i + run).map(|&(_, c)| c)) { run } else { 0 }
+        } else {
+            0
+        };
+        if len > 0 && i > 0 {
+            let end = chars.get(i + len).map_or(s.len(), |&(b, _)| b);
+            pieces.push((start, b));
+            pieces.push((b, end));
+            start = end;
+            i += len;
+        } else {
+            i += len.max(1);
+        }
+    }
+    if start < s.len() {
+        pieces.push((start, s.len()));
+    }
+    pieces
+}
+
+/// Tokenizes one whitespace-free chunk into byte ranges relative to it.
+fn split_chunk(chunk: &str) -> Vec<(usize, usize)> {
+    let with_exception = |start: usize, s: &str, out: &mut Vec<(usize, usize)>| match exception(s) {
+        Some(splits) => {
+            let mut last = 0;
+            for at in splits.into_iter().chain([s.len()]) {
+                out.push((start + last, start + at));
+                last = at;
+            }
+            true
+        }
+        None => false,
+    };
+
+    let mut tokens = Vec::new();
+    if with_exception(0, chunk, &mut tokens) {
+        return tokens;
+    }
+    let (mut start, mut end) = (0, chunk.len());
+    let mut prefixes = Vec::new();
+    let mut suffixes = Vec::new();
+    while start < end {
+        let s = &chunk[start..end];
+        if exception(s).is_some() {
+            break;
+        }
+        let pre = prefix_len(s);
+        if pre > 0 && pre < s.len() {
+            prefixes.push((start, start + pre));
+            start += pre;
+            continue;
+        }
+        let suf = suffix_len(s);
+        if suf > 0 && suf < s.len() {
+            suffixes.push((end - suf, end));
+            end -= suf;
+            continue;
+        }
+        break;
+    }
+    tokens.extend(prefixes);
+    if start < end && !with_exception(start, &chunk[start..end], &mut tokens) {
+        tokens.extend(split_infixes(&chunk[start..end]).into_iter().map(|(s, e)| (start + s, start + e)));
+    }
+    tokens.extend(suffixes.into_iter().rev());
+    tokens
+}
+
+/// Splits `text` into spaCy's English tokens, as byte ranges.
+pub fn tokenize(text: &str) -> Vec<(usize, usize)> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    for chunk in text.split_whitespace() {
+        let start = offset + text[offset..].find(chunk).unwrap_or(0);
+        tokens.extend(split_chunk(chunk).into_iter().map(|(s, e)| (start + s, start + e)));
+        offset = start + chunk.len();
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<&str> {
+        tokenize(text).into_iter().map(|(s, e)| &text[s..e]).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(words("I don't think he'll go."), ["I", "do", "n't", "think", "he", "'ll", "go", "."]);
+        assert_eq!(words("I don’t think he’ll go."), ["I", "do", "n’t", "think", "he", "’ll", "go", "."]);
+        assert_eq!(words("\"Can't,\" she said."), ["\"", "Ca", "n't", ",", "\"", "she", "said", "."]);
+        assert_eq!(words("Mr. Smith's well-known U.S. trip"), ["Mr.", "Smith", "'s", "well", "-", "known", "U.S.", "trip"]);
+        assert_eq!(words("Wait...what? (yes)"), ["Wait", "...", "what", "?", "(", "yes", ")"]);
+        assert_eq!(words("It costs $5.50, I'm gonna pay."), ["It", "costs", "$", "5.50", ",", "I", "'m", "gon", "na", "pay", "."]);
+        assert_eq!(words("couldn't've 3-4 and/or"), ["could", "n't", "'ve", "3", "-", "4", "and", "/", "or"]);
+        assert_eq!(words("They'd've"), ["They", "'d", "'ve"]);
+    }
+}
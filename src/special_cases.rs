@@ -0,0 +1,169 @@
+//! Words read by their tag and neighbours rather than by one dictionary
+//! entry ("a", "the", "to", "used"), as a table of rules: see
+//! `data/special_cases.json` for the bundled English one.
+//!
+//! Each entry lists spellings and the rules for them. The first rule whose
+//! `when` holds decides, except that a `gold` rule whose entry is missing
+//! passes on to the next; a word no rule decides has no special case.
+
+use crate::lexicon::{LexiconError, Lexicon, TokenContext};
+use crate::stress::Stress;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Special-case rules by spelling.
+#[derive(Debug, Clone, Default)]
+pub struct SpecialCases {
+    rules: HashMap<String, Vec<Rule>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Entry {
+    words: Vec<String>,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Rule {
+    #[serde(default)]
+    pub when: When,
+    pub then: Then,
+}
+
+/// Conditions on the token, all of which must hold. An empty `When`
+/// always holds.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct When {
+    /// The spelling is one of these.
+    pub word: Option<Vec<String>>,
+    /// The tag is one of these.
+    pub tag: Option<Vec<String>>,
+    pub tag_prefix: Option<String>,
+    /// `Lexicon::get_parent_tag` of the tag.
+    pub parent_tag: Option<String>,
+    pub next: Option<Next>,
+    /// "to" follows, as in "used to".
+    pub future_to: Option<bool>,
+    /// The token carries positive stress.
+    pub stressed: Option<bool>,
+    /// Conditions that must not all hold.
+    pub not: Option<Box<When>>,
+}
+
+/// What is known of the next word's first sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Next {
+    Vowel,
+    Consonant,
+    /// Either a vowel or a consonant.
+    Known,
+    /// No next word, or one without a sound to go by.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Then {
+    /// These phonemes.
+    Say(String),
+    /// The gold entry for `word`: its single reading, or the reading under
+    /// `key` if it is tagged.
+    Gold { word: String, key: Option<String> },
+    /// `word` looked up as if it were written instead, with tag `tag`.
+    Lookup { word: String, tag: String },
+    /// Spelled out letter by letter.
+    Letters,
+    /// No special case.
+    Nothing,
+}
+
+impl When {
+    fn holds(&self, word: &str, tag: &str, stress: Option<Stress>, ctx: Option<&TokenContext>) -> bool {
+        let future_vowel = ctx.and_then(|c| c.future_vowel);
+        self.word.as_ref().is_none_or(|ws| ws.iter().any(|w| w == word))
+            && self.tag.as_ref().is_none_or(|ts| ts.iter().any(|t| t == tag))
+            && self.tag_prefix.as_ref().is_none_or(|p| tag.starts_with(p.as_str()))
+            && self.parent_tag.as_ref().is_none_or(|p| Lexicon::get_parent_tag(tag) == p)
+            && self.next.is_none_or(|next| match next {
+                Next::Vowel => future_vowel == Some(true),
+                Next::Consonant => future_vowel == Some(false),
+                Next::Known => future_vowel.is_some(),
+                Next::Unknown => future_vowel.is_none(),
+            })
+            && self.future_to.is_none_or(|to| ctx.is_some_and(|c| c.future_to) == to)
+            && self.stressed.is_none_or(|s| stress.is_some_and(|st| f64::from(st) > 0.0) == s)
+            && self.not.as_ref().is_none_or(|not| !not.holds(word, tag, stress, ctx))
+    }
+}
+
+impl SpecialCases {
+    /// The bundled English table.
+    pub fn english() -> Self {
+        Self::from_json(include_str!("../data/special_cases.json")).expect("bundled special_cases.json")
+    }
+
+    /// Reads a table in the schema of `data/special_cases.json`. A spelling
+    /// listed by several entries takes the rules of all of them, in order.
+    pub fn from_json(json: &str) -> Result<Self, LexiconError> {
+        let entries: Vec<Entry> = serde_json::from_str(json)?;
+        let mut rules: HashMap<String, Vec<Rule>> = HashMap::new();
+        for entry in entries {
+            for word in entry.words {
+                rules.entry(word).or_default().extend(entry.rules.iter().cloned());
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// What the rules whose conditions hold say to do, in order.
+    pub fn actions<'s: 'a, 'a>(
+        &'s self,
+        word: &'a str,
+        tag: &'a str,
+        stress: Option<Stress>,
+        ctx: Option<&'a TokenContext>,
+    ) -> impl Iterator<Item = &'s Then> + 'a {
+        self.rules
+            .get(word)
+            .into_iter()
+            .flatten()
+            .filter(move |rule| rule.when.holds(word, tag, stress, ctx))
+            .map(|rule| &rule.then)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        let cases = SpecialCases::from_json(
+            r#"[
+                {"words": ["read"], "rules": [
+                    {"when": {"tag": ["VBD", "VBN"]}, "then": {"say": "ɹˈɛd"}},
+                    {"when": {"not": {"next": "unknown"}}, "then": {"gold": {"word": "read", "key": "VB"}}},
+                    {"then": "nothing"}
+                ]}
+            ]"#,
+        )
+        .unwrap();
+        let first = |tag, ctx| cases.actions("read", tag, None, ctx).next().cloned();
+        let ctx = TokenContext { future_vowel: Some(false), ..Default::default() };
+
+        assert_eq!(first("VBD", None), Some(Then::Say("ɹˈɛd".to_string())));
+        assert_eq!(first("VB", None), Some(Then::Nothing));
+        assert_eq!(
+            first("VB", Some(&ctx)),
+            Some(Then::Gold { word: "read".to_string(), key: Some("VB".to_string()) })
+        );
+        assert_eq!(cases.actions("Read", "VBD", None, None).count(), 0);
+        assert!(SpecialCases::from_json(r#"[{"words": ["x"], "rules": [{"when": {"tags": []}, "then": "letters"}]}]"#).is_err());
+    }
+}
@@ -0,0 +1,242 @@
+//! Phonemizing a document sentence by sentence as it is read (see
+//! `G2P::g2p_stream`), holding at most a chunk of text and one sentence at a
+//! time.
+
+use crate::g2p::{G2P, G2PError};
+use crate::languages::LanguageRules;
+use crate::result::SentenceResult;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read};
+
+/// Bytes read at a time, at most.
+const CHUNK: u64 = 8 * 1024;
+
+/// Bytes of a sentence held waiting for its end; a longer one is cut.
+const MAX_SENTENCE: usize = 64 * 1024;
+
+/// Byte spans of the sentences in `text`, without the whitespace around
+/// them. A sentence ends at a blank line, at CJK and Devanagari full stops,
+/// and at ".", "!", "?" and "…" followed by whitespace; a single line break
+/// doesn't end one, so a sentence wrapped over lines is read whole. Nor does
+/// a period before a lowercase word, or after one of the language's titles
+/// before a capital ("Dr. Smith"), as `G2P` reads them mid-sentence.
+#[cfg(feature = "auto-detect")]
+pub(crate) fn sentences(text: &str, rules: &dyn LanguageRules) -> Vec<(usize, usize)> {
+    split_sentences(text, rules).into_iter().map(|(start, end, _)| (start, end)).collect()
+}
+
+/// True if the period at byte `i` of `text` ends the sentence from `begin`,
+/// as `G2P` reads it: not after an abbreviation it reads mid-sentence, and
+/// not yet after an abbreviation whose next word hasn't been seen.
+fn period_ends(text: &str, begin: usize, i: usize, rules: &dyn LanguageRules) -> bool {
+    let word = text[begin..i]
+        .rsplit(char::is_whitespace)
+        .next()
+        .unwrap_or("")
+        .trim_start_matches(['(', '[', '"', '\'', '“', '‘', '«']);
+    let abbreviation = rules.expand_abbreviation(word, None, Some("NNP"));
+    match text[i + 1..].trim_start().chars().next() {
+        Some(c) if c.is_lowercase() => false,
+        Some(_) => !abbreviation.is_some_and(|a| a.before_name),
+        None => abbreviation.is_none(),
+    }
+}
+
+/// `sentences`, each with whether its end was seen, rather than the text
+/// running out on it.
+fn split_sentences(text: &str, rules: &dyn LanguageRules) -> Vec<(usize, usize, bool)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if start.is_none() && c.is_whitespace() {
+            continue;
+        }
+        let begin = *start.get_or_insert(i);
+        let next = chars.peek().map(|&(_, n)| n);
+        let ended = match c {
+            '。' | '！' | '？' | '।' => true,
+            '.' => next.is_some_and(char::is_whitespace) && period_ends(text, begin, i, rules),
+            '!' | '?' | '…' => next.is_some_and(char::is_whitespace),
+            '\n' => text[i + 1..].trim_start_matches(|c: char| c != '\n' && c.is_whitespace()).starts_with('\n'),
+            _ => false,
+        };
+        if ended || next.is_none() {
+            let end = begin + text[begin..i + c.len_utf8()].trim_end().len();
+            spans.push((begin, end, ended));
+            start = None;
+        }
+    }
+    spans
+}
+
+/// The sentences of a reader, phonemized one at a time (see
+/// `G2P::g2p_stream`).
+pub struct SentenceStream<'g, R> {
+    g2p: &'g G2P,
+    reader: R,
+    chunk: u64,
+    /// Bytes read but not yet decoded: the start of a char cut by a chunk.
+    bytes: Vec<u8>,
+    /// Decoded text not yet split into sentences.
+    text: String,
+    /// Chars of the input before `text`.
+    offset: usize,
+    /// Sentences split off but not yet phonemized, with their char offsets.
+    ready: VecDeque<(String, usize)>,
+    done: bool,
+}
+
+impl<'g, R: BufRead> SentenceStream<'g, R> {
+    pub(crate) fn new(g2p: &'g G2P, reader: R) -> Self {
+        Self {
+            g2p,
+            reader,
+            chunk: CHUNK,
+            bytes: Vec::new(),
+            text: String::new(),
+            offset: 0,
+            ready: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Reads up to a line or a chunk, whichever is shorter, into `text`.
+    /// Returns false at the end of the input.
+    fn fill(&mut self) -> io::Result<bool> {
+        let n = (&mut self.reader).take(self.chunk).read_until(b'\n', &mut self.bytes)?;
+        let valid = match std::str::from_utf8(&self.bytes) {
+            Ok(text) => text.len(),
+            // A char cut by the chunk is finished by the next one
+            Err(e) if e.error_len().is_none() && n > 0 => e.valid_up_to(),
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+        self.text.push_str(std::str::from_utf8(&self.bytes[..valid]).expect("checked above"));
+        self.bytes.drain(..valid);
+        Ok(n > 0)
+    }
+
+    /// Moves the sentences of `text` to `ready`. Unless `all`, the last one
+    /// stays until its end is seen, as more of it may be still to come.
+    fn split(&mut self, all: bool) {
+        let spans = split_sentences(&self.text, self.g2p.rules());
+        let ended = spans.last().is_some_and(|&(_, _, ended)| ended);
+        let keep = !all && !ended && self.text.len() < MAX_SENTENCE;
+        let complete = if keep { spans.len().saturating_sub(1) } else { spans.len() };
+        let mut consumed = 0;
+        let mut offset = self.offset;
+        for &(start, end, _) in &spans[..complete] {
+            offset += self.text[consumed..start].chars().count();
+            let sentence = &self.text[start..end];
+            self.ready.push_back((sentence.to_string(), offset));
+            offset += sentence.chars().count();
+            consumed = end;
+        }
+        // Drop what is left up to the held sentence, or all of it
+        let rest = spans.get(complete).map_or(self.text.len(), |&(start, _, _)| start);
+        offset += self.text[consumed..rest].chars().count();
+        self.text.drain(..rest);
+        self.offset = offset;
+    }
+
+    fn read(&self, text: String, offset: usize) -> Result<SentenceResult, G2PError> {
+        let (phonemes, mut tokens) = self.g2p.g2p(&text)?;
+        for tk in &mut tokens {
+            tk.char_span = (tk.char_span.0 + offset, tk.char_span.1 + offset);
+        }
+        let char_span = (offset, offset + text.chars().count());
+        Ok(SentenceResult { text, char_span, phonemes, tokens })
+    }
+}
+
+impl<R: BufRead> Iterator for SentenceStream<'_, R> {
+    type Item = Result<SentenceResult, G2PError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((text, offset)) = self.ready.pop_front() {
+                return Some(self.read(text, offset));
+            }
+            if self.done {
+                return None;
+            }
+            match self.fill() {
+                Ok(more) => {
+                    self.done = !more;
+                    self.split(!more);
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language;
+    use crate::languages::english::English;
+
+    #[test]
+    fn test_sentences() {
+        let split = |text| split_sentences(text, &English);
+        let text = "Hello there. How are you?\nFine…  日本語です。次";
+        let spans: Vec<&str> = split(text).into_iter().map(|(s, e, _)| &text[s..e]).collect();
+        assert_eq!(spans, ["Hello there.", "How are you?", "Fine…", "日本語です。", "次"]);
+        assert_eq!(split("3.5 is a number"), [(0, 15, false)]);
+        assert_eq!(split("Hi. There"), [(0, 3, true), (4, 9, false)]);
+
+        // Only a blank line ends a sentence at a line break
+        let text = "A sentence wrapped\nover lines.\nA heading\n \nText";
+        let spans: Vec<&str> = split(text).into_iter().map(|(s, e, _)| &text[s..e]).collect();
+        assert_eq!(spans, ["A sentence wrapped\nover lines.", "A heading", "Text"]);
+
+        // Abbreviations read mid-sentence don't end one
+        let text = "Dr. Smith met Mr. Jones, e.g. at lunch. Bread etc. Then more.";
+        let spans: Vec<&str> = split(text).into_iter().map(|(s, e, _)| &text[s..e]).collect();
+        assert_eq!(spans, ["Dr. Smith met Mr. Jones, e.g. at lunch.", "Bread etc.", "Then more."]);
+        // Until the word after a title is seen, the sentence may go on
+        assert_eq!(split("We met Dr. "), [(0, 10, false)]);
+    }
+
+    #[test]
+    fn test_g2p_stream() {
+        let g2p = G2P::new(Language::EnglishUS);
+        let text = "The cat sat. It was 3.5 feet\nfrom the door!\n\n  Café time";
+        let results: Vec<SentenceResult> = g2p.g2p_stream(text.as_bytes()).collect::<Result<_, _>>().unwrap();
+
+        let sentences: Vec<&str> = results.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(sentences, ["The cat sat.", "It was 3.5 feet\nfrom the door!", "Café time"]);
+        for r in &results {
+            let (start, end) = r.char_span;
+            assert_eq!(text.chars().skip(start).take(end - start).collect::<String>(), r.text);
+            assert_eq!(r.phonemes, g2p.g2p(&r.text).unwrap().0);
+        }
+        assert_eq!(results[2].tokens[0].char_span.0, results[2].char_span.0);
+
+        // Chunks that cut a char or a sentence read the same
+        let stream = SentenceStream { chunk: 4, ..SentenceStream::new(&g2p, text.as_bytes()) };
+        let chunked: Vec<String> = stream.map(|r| r.unwrap().text).collect();
+        assert_eq!(chunked, sentences);
+
+        // Read with their neighbours, abbreviations read as in `g2p`
+        let text = "We went to St. Paul yesterday. Dr. Smith came too.";
+        let results: Vec<SentenceResult> = g2p.g2p_stream(text.as_bytes()).collect::<Result<_, _>>().unwrap();
+        let sentences: Vec<&str> = results.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(sentences, ["We went to St. Paul yesterday.", "Dr. Smith came too."]);
+        for r in &results {
+            assert_eq!(r.phonemes, g2p.g2p(&r.text).unwrap().0);
+        }
+        let (saint, _) = g2p.g2p("We went to Saint Paul yesterday.").unwrap();
+        assert_eq!(results[0].phonemes, saint);
+
+        let invalid: &[u8] = b"The cat.\n\xff\xfe\n";
+        let mut stream = g2p.g2p_stream(invalid);
+        assert!(stream.next().unwrap().is_ok());
+        assert!(matches!(stream.next(), Some(Err(G2PError::Io(_)))));
+        assert!(stream.next().is_none());
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+/// How to adjust a word's stress marks, as in Python misaki's numeric
+/// stress values (`-2`, `-1`, `0`, `1`, `2`). Converts to and from `f64`,
+/// which is also how it (de)serializes and how the `[word](+1)` link syntax
+/// writes it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "f64", into = "f64")]
+pub enum Stress {
+    /// Strips all stress marks (`-2`).
+    Remove,
+    /// Turns primary stress into secondary (`-1`).
+    Demote,
+    /// Turns primary stress into secondary, and gives unstressed words
+    /// secondary stress (`0`).
+    Secondary,
+    /// Turns secondary stress into primary, and gives unstressed words
+    /// secondary stress (`1`).
+    Primary,
+    /// Turns secondary stress into primary, and gives unstressed words
+    /// primary stress (`2`).
+    Promote,
+    /// Any other value, interpreted by the same thresholds (`-0.5`, `0.5`).
+    Weight(f64),
+}
+
+impl From<f64> for Stress {
+    fn from(value: f64) -> Self {
+        if value == -2.0 {
+            Stress::Remove
+        } else if value == -1.0 {
+            Stress::Demote
+        } else if value == 0.0 {
+            Stress::Secondary
+        } else if value == 1.0 {
+            Stress::Primary
+        } else if value == 2.0 {
+            Stress::Promote
+        } else {
+            Stress::Weight(value)
+        }
+    }
+}
+
+impl From<Stress> for f64 {
+    fn from(stress: Stress) -> Self {
+        match stress {
+            Stress::Remove => -2.0,
+            Stress::Demote => -1.0,
+            Stress::Secondary => 0.0,
+            Stress::Primary => 1.0,
+            Stress::Promote => 2.0,
+            Stress::Weight(value) => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_float_round_trip() {
+        for value in [-2.0, -1.0, -0.5, 0.0, 0.5, 1.0, 2.0] {
+            assert_eq!(f64::from(Stress::from(value)), value);
+        }
+        assert_eq!(Stress::from(-1.0), Stress::Demote);
+        assert_eq!(Stress::from(0.5), Stress::Weight(0.5));
+        assert_eq!(serde_json::to_string(&Stress::Promote).unwrap(), "2.0");
+        assert_eq!(serde_json::from_str::<Stress>("-2").unwrap(), Stress::Remove);
+    }
+}
@@ -1,6 +1,16 @@
+use crate::tagger_format;
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
+
+/// A word is pinned to a tag when it occurs at least this often in training
+/// data and takes that tag at least this often.
+const TAGDICT_MIN_COUNT: usize = 20;
+const TAGDICT_MIN_SHARE: f64 = 0.97;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AveragedPerceptron {
@@ -15,42 +25,187 @@ impl AveragedPerceptron {
         Self { feature_weights, classes }
     }
 
-    pub fn predict(&self, word_features: HashMap<String, usize>) -> (&str, f32) {
+    /// The score of each of `classes`, in order. Features are summed in name
+    /// order so the same features always give the same scores.
+    pub fn scores(&self, word_features: &HashMap<String, usize>) -> Vec<f32> {
+        let mut sorted: Vec<(&String, &usize)> = word_features.iter().collect();
+        sorted.sort_unstable();
         let mut scores: HashMap<&str, f32> = HashMap::new();
-        for (feature, value) in word_features {
-            if let Some(weights) = self.feature_weights.get(&feature) {
-                if value != 0 {
-                    for (label, weight) in weights {
-                        *scores.entry(label.as_str()).or_insert(0.0) += weight * (value as f32);
-                    }
+        for (feature, &value) in sorted {
+            if let Some(weights) = self.feature_weights.get(feature)
+                && value != 0
+            {
+                for (label, weight) in weights {
+                    *scores.entry(label.as_str()).or_insert(0.0) += weight * (value as f32);
                 }
             }
         }
+        self.classes.iter().map(|c| scores.get(c.as_str()).copied().unwrap_or(0.0)).collect()
+    }
 
-        let class = self.classes.iter()
-            .max_by(|a, b| {
-                let sa = scores.get(a.as_str()).unwrap_or(&0.0);
-                let sb = scores.get(b.as_str()).unwrap_or(&0.0);
-                sa.partial_cmp(sb).unwrap()
+    /// The best class and its softmax probability.
+    pub fn predict(&self, word_features: HashMap<String, usize>, tie_break: TieBreak) -> (&str, f32) {
+        let scores = self.scores(&word_features);
+        let best = (0..self.classes.len())
+            .reduce(|best, i| {
+                let later_wins = tie_break == TieBreak::ClassOrder || self.classes[i] > self.classes[best];
+                let tied = scores[i] == scores[best] && later_wins;
+                if scores[i] > scores[best] || tied { i } else { best }
             })
             .unwrap();
+        (self.classes[best].as_str(), softmax(&scores)[best])
+    }
+}
+
+fn softmax(scores: &[f32]) -> Vec<f32> {
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = scores.iter().map(|s| (s - max).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+    exps.into_iter().map(|e| e / sum).collect()
+}
+
+/// Running totals for averaging the weights over every update, kept per
+/// `(feature, class)` as of the update count in `stamps`.
+#[derive(Default)]
+struct Averager {
+    updates: usize,
+    totals: HashMap<(String, String), f32>,
+    stamps: HashMap<(String, String), usize>,
+}
 
-        let max_score = *scores.get(class.as_str()).unwrap_or(&0.0);
-        // Softmax-ish or just return the max score? postagger used softmax.
-        // For now, let's keep it simple as postagger.rs did.
-        (class.as_str(), max_score)
+impl Averager {
+    fn set(&mut self, model: &mut AveragedPerceptron, feature: &str, class: &str, delta: f32) {
+        let weights = model.feature_weights.entry(feature.to_string()).or_default();
+        let weight = weights.entry(class.to_string()).or_insert(0.0);
+        let param = (feature.to_string(), class.to_string());
+        let stamp = self.stamps.insert(param.clone(), self.updates).unwrap_or(0);
+        *self.totals.entry(param).or_insert(0.0) += (self.updates - stamp) as f32 * *weight;
+        *weight += delta;
+    }
+
+    /// Counts one prediction, moving weight from `guess` to `truth` if it was wrong.
+    fn update(&mut self, model: &mut AveragedPerceptron, truth: &str, guess: &str, features: &HashMap<String, usize>) {
+        self.updates += 1;
+        if truth == guess {
+            return;
+        }
+        for feature in features.keys() {
+            self.set(model, feature, truth, 1.0);
+            self.set(model, feature, guess, -1.0);
+        }
+    }
+
+    /// Replaces each weight with its average over all updates, rounded like
+    /// the bundled model's.
+    fn average(&self, model: &mut AveragedPerceptron) {
+        if self.updates == 0 {
+            return;
+        }
+        for (feature, weights) in &mut model.feature_weights {
+            weights.retain(|class, weight| {
+                let param = (feature.clone(), class.clone());
+                let stamp = self.stamps.get(&param).copied().unwrap_or(0);
+                let total = self.totals.get(&param).copied().unwrap_or(0.0) + (self.updates - stamp) as f32 * *weight;
+                *weight = (total / self.updates as f32 * 1000.0).round() / 1000.0;
+                *weight != 0.0
+            });
+        }
+        model.feature_weights.retain(|_, weights| !weights.is_empty());
     }
 }
 
 pub struct Tag<'a> {
     pub word: &'a str,
     pub tag: String,
+    /// Probability the tag is right, 1 for words in the tag dictionary.
     pub conf: f32,
+    /// `conf` is below `TaggerOptions::min_confidence`; G2P then reads
+    /// heteronyms by their DEFAULT entry rather than by the tag.
+    pub uncertain: bool,
+}
+
+/// A tag from `PerceptronTagger::tag_stream`, which owns its word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggedWord {
+    pub word: String,
+    pub tag: String,
+    pub conf: f32,
+    pub uncertain: bool,
+}
+
+/// Part-of-speech tagger G2P uses to choose between a word's readings
+/// ("lead" the verb or the metal). Tags are Penn Treebank tags, as spaCy's
+/// `tag_`; words left without a tag are read as nouns.
+///
+/// Besides `PerceptronTagger`, any `Fn(&[&str]) -> Vec<String>` is a tagger,
+/// which lets tags come from a model running elsewhere.
+pub trait Tagger: Send + Sync {
+    /// One tag per word, in order.
+    fn tag<'a>(&self, words: &[&'a str]) -> Vec<Tag<'a>>;
+}
+
+impl<F> Tagger for F
+where
+    F: Fn(&[&str]) -> Vec<String> + Send + Sync,
+{
+    fn tag<'a>(&self, words: &[&'a str]) -> Vec<Tag<'a>> {
+        words
+            .iter()
+            .zip(self(words))
+            .map(|(&word, tag)| Tag { word, tag, conf: 1.0, uncertain: false })
+            .collect()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TaggerError {
+    #[error("not a tagger model, or from an incompatible version")]
+    Format,
+    #[error("corrupt tagger model: {0}")]
+    Decode(#[from] bincode::Error),
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// How the tagger chooses between equally scored tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// The greatest tag name, as NLTK's `max(classes, key=lambda c: (scores[c], c))`.
+    #[default]
+    Python,
+    /// The last in the model's class order, as ties were broken before
+    /// `Python` was added.
+    ClassOrder,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaggerOptions {
+    /// Sequences of tags kept while tagging a sentence. 1 tags each word
+    /// greedily given the tags before it; wider beams can revise a tag that
+    /// only fits poorly with the words after it.
+    pub beam_width: usize,
+    /// Tags less likely than this to be right are marked `uncertain`.
+    pub min_confidence: f32,
+    /// Which of equally scored tags wins: the greatest name (`Python`) or the last in class order (`ClassOrder`).
+    pub tie_break: TieBreak,
+}
+
+impl Default for TaggerOptions {
+    fn default() -> Self {
+        Self {
+            beam_width: 1,
+            min_confidence: 0.0,
+            tie_break: TieBreak::default(),
+        }
+    }
 }
 
 pub struct PerceptronTagger {
     model: AveragedPerceptron,
     tags: HashMap<String, String>,
+    options: TaggerOptions,
 }
 
 impl PerceptronTagger {
@@ -59,30 +214,119 @@ impl PerceptronTagger {
         Self {
             model: AveragedPerceptron::new(weights_json, classes_txt),
             tags,
+            options: TaggerOptions::default(),
         }
     }
 
-    pub fn tag<'a>(&'a self, words: &[&'a str]) -> Vec<Tag<'a>> {
+    pub fn with_options(mut self, options: TaggerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The bundled English model, converted to the binary form at build time.
+    pub fn english() -> Self {
+        Self::from_bytes(include_bytes!(concat!(env!("OUT_DIR"), "/tagger.bin"))).expect("bundled tagger model")
+    }
+
+    /// Encodes the model in a compact binary form, much smaller and faster to
+    /// load than the JSON files `new` reads.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        tagger_format::encode(&self.model.feature_weights, &self.model.classes, &self.tags)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TaggerError> {
+        let payload = bytes.strip_prefix(tagger_format::MAGIC.as_slice()).ok_or(TaggerError::Format)?;
+        let (feature_weights, classes, tags) = tagger_format::decode(payload)?;
+        Ok(Self {
+            model: AveragedPerceptron { feature_weights, classes },
+            tags,
+            options: TaggerOptions::default(),
+        })
+    }
+
+    /// Tags `words`. A model without the capitalization features, like the
+    /// bundled one, takes every all-caps word for a proper noun, so an
+    /// all-caps heading ("NASA LAUNCHES NEW ROCKET") is tagged as its
+    /// lowercase words would be, but for those in the tag dictionary ("I").
+    pub fn tag<'a>(&self, words: &[&'a str]) -> Vec<Tag<'a>> {
+        let lowered: Vec<String> = if self.is_heading(words) {
+            words.iter().map(|&w| if self.tags.contains_key(w) { w.to_string() } else { w.to_lowercase() }).collect()
+        } else {
+            Vec::new()
+        };
+        let read: Vec<&str> = if lowered.is_empty() { words.to_vec() } else { lowered.iter().map(String::as_str).collect() };
+        let tags = if self.options.beam_width > 1 {
+            self.tag_beam(&read, self.options.beam_width)
+        } else {
+            self.tag_greedy(&read)
+        };
+        tags.into_iter()
+            .zip(words)
+            .map(|(tag, &word)| Tag { word, uncertain: tag.conf < self.options.min_confidence, ..tag })
+            .collect()
+    }
+
+    /// True if the model has no capitalization features and `words` are all
+    /// caps: every word with letters is, and at least two have more than one.
+    fn is_heading(&self, words: &[&str]) -> bool {
+        if self.model.feature_weights.contains_key("i case upper") {
+            return false;
+        }
+        let mut long = 0;
+        for &word in words {
+            match Self::casing(word) {
+                Some("upper") => long += 1,
+                Some(_) if word.chars().filter(|c| c.is_alphabetic()).any(|c| !c.is_uppercase()) => return false,
+                _ => {}
+            }
+        }
+        long >= 2
+    }
+
+    /// Fixes the tag of `word` (case-sensitive), bypassing the model, as for
+    /// the unambiguous words bundled with it.
+    pub fn add_unambiguous(&mut self, word: &str, tag: &str) {
+        self.tags.insert(word.to_string(), tag.to_string());
+    }
+
+    /// Loads a JSON object of word → tag, in the schema of the bundled
+    /// `tags.json`, over the unambiguous words. Existing words are replaced.
+    pub fn load_unambiguous(&mut self, path: impl AsRef<Path>) -> Result<(), TaggerError> {
+        let data = fs::read_to_string(path)?;
+        let tags: HashMap<String, String> = serde_json::from_str(&data)?;
+        self.tags.extend(tags);
+        Ok(())
+    }
+
+    /// Tags each sentence on its own.
+    pub fn tag_sentences<'a>(&self, sentences: &[Vec<&'a str>]) -> Vec<Vec<Tag<'a>>> {
+        sentences.iter().map(|words| self.tag(words)).collect()
+    }
+
+    /// Tags a document word by word as `words` yields them, holding only the
+    /// two words before and after the current one. The tags are those `tag`
+    /// gives the whole document, read greedily (`beam_width` is ignored), but
+    /// for all-caps headings, which it can't see whole.
+    pub fn tag_stream<I>(&self, words: I) -> TagStream<'_, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        TagStream {
+            tagger: self,
+            words: words.into_iter(),
+            window: VecDeque::new(),
+            tagged: 0,
+            prev: "-START-".to_string(),
+            prev2: "-START2-".to_string(),
+        }
+    }
+
+    fn tag_greedy<'a>(&self, words: &[&'a str]) -> Vec<Tag<'a>> {
         let mut prev = "-START-";
         let mut prev2 = "-START2-";
         let mut output = Vec::new();
-
-        let mut context = Vec::new();
-        context.push("-START-");
-        context.push("-START2-");
-        for &token in words {
-            context.push(if token.contains("'-'") && !token.starts_with('-') {
-                "!HYPHEN"
-            } else if token.parse::<usize>().is_ok() && token.len() == 4 {
-                "!YEAR"
-            } else if token.chars().next().map_or(false, |c| c.is_ascii_digit()) {
-                "!DIGITS"
-            } else {
-                token
-            });
-        }
-        context.push("-END-");
-        context.push("-END2-");
+        let context = Self::context(words);
 
         for (i, &token) in words.iter().enumerate() {
             if let Some(tag) = self.tags.get(token) {
@@ -90,16 +334,18 @@ impl PerceptronTagger {
                     word: token,
                     tag: tag.clone(),
                     conf: 1.0,
+                    uncertain: false,
                 });
                 prev2 = prev;
                 prev = tag;
             } else {
-                let features = Self::get_features(i + 2, token, &context, prev, prev2);
-                let (tag, conf) = self.model.predict(features);
+                let features = Self::get_features(i + 2, words, &context, prev, prev2);
+                let (tag, conf) = self.model.predict(features, self.options.tie_break);
                 output.push(Tag {
                     word: token,
                     tag: tag.to_string(),
                     conf,
+                    uncertain: false,
                 });
                 prev2 = prev;
                 prev = tag;
@@ -108,7 +354,171 @@ impl PerceptronTagger {
         output
     }
 
-    fn get_features(i: usize, word: &str, context: &[&str], prev: &str, prev2: &str) -> HashMap<String, usize> {
+    /// Beam search over tag sequences, scored by the sum of their tags'
+    /// scores (confidences are still each tag's probability). Of sequences
+    /// ending in the same two tags, which are all the features look back on,
+    /// only the best is kept.
+    fn tag_beam<'a>(&self, words: &[&'a str], width: usize) -> Vec<Tag<'a>> {
+        // Each sequence's tags, their probabilities, and its total score
+        let mut beam: Vec<(Vec<&str>, Vec<f32>, f32)> = vec![(Vec::new(), Vec::new(), 0.0)];
+        let context = Self::context(words);
+        for (i, &word) in words.iter().enumerate() {
+            // (sequence, tag, tag probability, total)
+            let mut candidates: Vec<(usize, &str, f32, f32)> = Vec::new();
+            for (b, (tags, _, total)) in beam.iter().enumerate() {
+                if let Some(tag) = self.tags.get(word) {
+                    candidates.push((b, tag, 1.0, *total));
+                    continue;
+                }
+                let prev = tags.last().copied().unwrap_or("-START-");
+                let prev2 = match tags.len() {
+                    0 => "-START2-",
+                    1 => "-START-",
+                    n => tags[n - 2],
+                };
+                let features = Self::get_features(i + 2, words, &context, prev, prev2);
+                let scores = self.model.scores(&features);
+                let probs = softmax(&scores);
+                candidates.extend(
+                    self.model.classes.iter().zip(scores).zip(probs).map(|((c, s), p)| (b, c.as_str(), p, total + s)),
+                );
+            }
+            candidates.sort_by(|a, b| b.3.total_cmp(&a.3));
+
+            let mut states = HashSet::new();
+            let mut next = Vec::with_capacity(width);
+            for (b, tag, score, total) in candidates {
+                if next.len() == width {
+                    break;
+                }
+                if states.insert((beam[b].0.last().copied(), tag)) {
+                    let (mut tags, mut scores) = (beam[b].0.clone(), beam[b].1.clone());
+                    tags.push(tag);
+                    scores.push(score);
+                    next.push((tags, scores, total));
+                }
+            }
+            beam = next;
+        }
+        let (tags, scores, _) = beam.swap_remove(0);
+        words
+            .iter()
+            .zip(tags.into_iter().zip(scores))
+            .map(|(&word, (tag, conf))| Tag { word, tag: tag.to_string(), conf, uncertain: false })
+            .collect()
+    }
+
+    /// Trains the model further on tagged sentences, `(words, tags)` pairs
+    /// of equal length, with the averaged perceptron update. Starting from
+    /// `PerceptronTagger::english()` fine-tunes the bundled model; starting
+    /// from `PerceptronTagger::new("{}", "", "{}")` trains one from scratch.
+    /// Training also learns the capitalization features ("i case title") the
+    /// bundled weights lack. Words that nearly always take one tag in
+    /// `sentences` are added to the tag dictionary. Sentences are visited in
+    /// a fixed shuffled order each iteration, so training is reproducible.
+    pub fn train(&mut self, sentences: &[(Vec<String>, Vec<String>)], iterations: usize) {
+        let mut counts: HashMap<&str, HashMap<&str, usize>> = HashMap::new();
+        for (words, tags) in sentences {
+            assert_eq!(words.len(), tags.len(), "every word needs a tag");
+            for (word, tag) in words.iter().zip(tags) {
+                *counts.entry(word).or_default().entry(tag).or_insert(0) += 1;
+                if !self.model.classes.contains(tag) {
+                    self.model.classes.push(tag.clone());
+                }
+            }
+        }
+        for (word, tag_counts) in counts {
+            let n: usize = tag_counts.values().sum();
+            let (tag, &mode) = tag_counts.iter().max_by_key(|&(tag, n)| (n, std::cmp::Reverse(*tag))).unwrap();
+            if n >= TAGDICT_MIN_COUNT && mode as f64 / n as f64 >= TAGDICT_MIN_SHARE {
+                self.tags.insert(word.to_string(), tag.to_string());
+            }
+        }
+
+        let mut averager = Averager::default();
+        let mut order: Vec<usize> = (0..sentences.len()).collect();
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..iterations {
+            for &s in &order {
+                let (words, tags) = &sentences[s];
+                let words: Vec<&str> = words.iter().map(String::as_str).collect();
+                let context = Self::context(&words);
+                let (mut prev, mut prev2) = ("-START-".to_string(), "-START2-".to_string());
+                for (i, &word) in words.iter().enumerate() {
+                    let guess = match self.tags.get(word) {
+                        Some(tag) => tag.clone(),
+                        None => {
+                            let features = Self::get_features(i + 2, &words, &context, &prev, &prev2);
+                            let guess = self.model.predict(features.clone(), self.options.tie_break).0.to_string();
+                            averager.update(&mut self.model, &tags[i], &guess, &features);
+                            guess
+                        }
+                    };
+                    prev2 = std::mem::replace(&mut prev, guess);
+                }
+            }
+            // Fisher-Yates with xorshift
+            for i in (1..order.len()).rev() {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                order.swap(i, (seed % (i as u64 + 1)) as usize);
+            }
+        }
+        averager.average(&mut self.model);
+    }
+
+    /// Writes the model as the `weights.json`, `classes.txt` and `tags.json`
+    /// that `new` reads.
+    pub fn save(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::write(dir.join("weights.json"), serde_json::to_string(&self.model.feature_weights)?)?;
+        fs::write(dir.join("classes.txt"), self.model.classes.join("\n"))?;
+        fs::write(dir.join("tags.json"), serde_json::to_string(&self.tags)?)
+    }
+
+    /// A word as the model sees it, as NLTK's `AveragedPerceptron.normalize`:
+    /// hyphenated words, years and other numbers are classes of their own and
+    /// everything else is lowercased.
+    fn normalize(word: &str) -> String {
+        if word.contains('-') && !word.starts_with('-') {
+            "!HYPHEN".to_string()
+        } else if word.chars().count() == 4 && word.chars().all(char::is_numeric) {
+            "!YEAR".to_string()
+        } else if word.starts_with(char::is_numeric) {
+            "!DIGITS".to_string()
+        } else {
+            word.to_lowercase()
+        }
+    }
+
+    /// The words as the model sees them, padded with sentence boundaries.
+    fn context(words: &[&str]) -> Vec<String> {
+        let mut context = vec!["-START-".to_string(), "-START2-".to_string()];
+        context.extend(words.iter().map(|w| Self::normalize(w)));
+        context.extend(["-END-".to_string(), "-END2-".to_string()]);
+        context
+    }
+
+    /// How a word is capitalized, or `None` if it has no letters.
+    fn casing(word: &str) -> Option<&'static str> {
+        let mut letters = word.chars().filter(|c| c.is_alphabetic());
+        let first = letters.next()?;
+        let rest: Vec<char> = letters.collect();
+        Some(if first.is_uppercase() && !rest.is_empty() && rest.iter().all(|c| c.is_uppercase()) {
+            "upper"
+        } else if first.is_uppercase() && rest.iter().all(|c| c.is_lowercase()) {
+            "title"
+        } else if first.is_lowercase() && rest.iter().all(|c| c.is_lowercase()) {
+            "lower"
+        } else {
+            "mixed"
+        })
+    }
+
+    /// Features of the word at `context[i]`, which is `words[i - 2]`.
+    fn get_features(i: usize, words: &[&str], context: &[String], prev: &str, prev2: &str) -> HashMap<String, usize> {
+        let word = words[i - 2];
         let mut features = HashMap::new();
         features.insert("bias".to_string(), 1);
 
@@ -134,6 +544,275 @@ impl PerceptronTagger {
         let i_minus_1_suffix: String = context[i - 1].chars().rev().take(3).collect::<String>().chars().rev().collect();
         features.insert(format!("i-1 suffix {}", i_minus_1_suffix), 1);
 
+        // Context words are lowercased, so capitalization gets features of its
+        // own: an all-caps heading says less about proper nouns than title
+        // case does, and title case says little at the start of a sentence.
+        // The bundled weights predate these and carry none, so they only
+        // count in models fine-tuned or trained with `train()`; `tag` reads
+        // all-caps headings in lowercase for the others
+        if let Some(case) = Self::casing(word) {
+            features.insert(format!("i case {}", case), 1);
+            if i == 2 {
+                features.insert(format!("i case+first {}", case), 1);
+            }
+        }
+        if let Some(case) = (i > 2).then(|| words[i - 3]).and_then(Self::casing) {
+            features.insert(format!("i-1 case {}", case), 1);
+        }
+        if let Some(case) = words.get(i - 1).copied().and_then(Self::casing) {
+            features.insert(format!("i+1 case {}", case), 1);
+        }
+
         features
     }
 }
+
+impl Tagger for PerceptronTagger {
+    fn tag<'a>(&self, words: &[&'a str]) -> Vec<Tag<'a>> {
+        PerceptronTagger::tag(self, words)
+    }
+}
+
+pub struct TagStream<'t, I> {
+    tagger: &'t PerceptronTagger,
+    words: I,
+    /// Up to two tagged words, the word to tag next and up to two after it.
+    window: VecDeque<String>,
+    /// How many words at the front of `window` are tagged.
+    tagged: usize,
+    prev: String,
+    prev2: String,
+}
+
+impl<I> Iterator for TagStream<'_, I>
+where
+    I: Iterator,
+    I::Item: Into<String>,
+{
+    type Item = TaggedWord;
+
+    fn next(&mut self) -> Option<TaggedWord> {
+        while self.window.len() < self.tagged + 3 {
+            match self.words.next() {
+                Some(word) => self.window.push_back(word.into()),
+                None => break,
+            }
+        }
+        if self.window.len() == self.tagged {
+            return None;
+        }
+
+        let words: Vec<&str> = self.window.iter().map(String::as_str).collect();
+        let word = words[self.tagged];
+        let (tag, conf) = match self.tagger.tags.get(word) {
+            Some(tag) => (tag.clone(), 1.0),
+            None => {
+                // The window holds fewer than two words before this one only
+                // at the start of the document, where the padding belongs
+                let context = PerceptronTagger::context(&words);
+                let features = PerceptronTagger::get_features(self.tagged + 2, &words, &context, &self.prev, &self.prev2);
+                let (tag, conf) = self.tagger.model.predict(features, self.tagger.options.tie_break);
+                (tag.to_string(), conf)
+            }
+        };
+        let tagged = TaggedWord {
+            word: word.to_string(),
+            tag: tag.clone(),
+            conf,
+            uncertain: conf < self.tagger.options.min_confidence,
+        };
+
+        self.prev2 = std::mem::replace(&mut self.prev, tag);
+        self.tagged += 1;
+        if self.tagged > 2 {
+            self.window.pop_front();
+            self.tagged -= 1;
+        }
+        Some(tagged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentence(tagged: &str) -> (Vec<String>, Vec<String>) {
+        tagged
+            .split_whitespace()
+            .map(|wt| {
+                let (w, t) = wt.rsplit_once('/').unwrap();
+                (w.to_string(), t.to_string())
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn test_train() {
+        let corpus = [
+            "the/DT dog/NN barks/VBZ ./.",
+            "a/DT cat/NN sleeps/VBZ ./.",
+            "the/DT dogs/NNS bark/VBP loudly/RB ./.",
+            "cats/NNS sleep/VBP ./.",
+        ];
+        let sentences: Vec<_> = corpus.iter().map(|s| sentence(s)).collect();
+        let mut tagger = PerceptronTagger::new("{}", "", "{}");
+        tagger.train(&sentences, 10);
+        let tags = |tagger: &PerceptronTagger, text: &str| -> Vec<String> {
+            let words: Vec<&str> = text.split_whitespace().collect();
+            tagger.tag(&words).into_iter().map(|t| t.tag).collect()
+        };
+        assert_eq!(tags(&tagger, "the dogs bark loudly ."), ["DT", "NNS", "VBP", "RB", "."]);
+        // Too rare for the tag dictionary
+        assert!(tagger.tags.is_empty());
+
+        let dir = std::env::temp_dir().join(format!("misaki_tagger_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        tagger.save(&dir).unwrap();
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        let loaded = PerceptronTagger::new(&read("weights.json"), &read("classes.txt"), &read("tags.json"));
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(tags(&loaded, "a cat sleeps ."), tags(&tagger, "a cat sleeps ."));
+
+        let mut tagger = PerceptronTagger::new("{}", "", "{}");
+        tagger.train(&vec![sentences[0].clone(); TAGDICT_MIN_COUNT], 1);
+        assert_eq!(tagger.tags.get("dog").map(String::as_str), Some("NN"));
+    }
+    #[test]
+    fn test_bytes() {
+        let read = |name: &str| fs::read_to_string(format!("src/resources/tagger/{}", name)).unwrap();
+        let json = PerceptronTagger::new(&read("weights.json"), &read("classes.txt"), &read("tags.json"));
+        let bytes = json.to_bytes();
+        assert!(bytes.len() < read("weights.json").len() / 3);
+
+        let binary = PerceptronTagger::from_bytes(&bytes).unwrap();
+        assert_eq!(binary.to_bytes(), bytes);
+        let words = ["I", "will", "lead", "the", "object", "of", "my", "desire", "in", "1999", "."];
+        let tag = |t: &PerceptronTagger| t.tag(&words).into_iter().map(|t| t.tag).collect::<Vec<_>>();
+        assert_eq!(tag(&binary), tag(&json));
+        assert_eq!(tag(&PerceptronTagger::english()), tag(&json));
+
+        assert!(matches!(PerceptronTagger::from_bytes(b"{}"), Err(TaggerError::Format)));
+        assert!(matches!(PerceptronTagger::from_bytes(&bytes[..100]), Err(TaggerError::Decode(_))));
+    }
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("well-known", "!HYPHEN"),
+            ("-5", "-5"),
+            ("1999", "!YEAR"),
+            ("+199", "+199"),
+            ("19999", "!DIGITS"),
+            ("3rd", "!DIGITS"),
+            ("The", "the"),
+        ];
+        for (word, normal) in cases {
+            assert_eq!(PerceptronTagger::normalize(word), normal);
+        }
+
+        let tagger = PerceptronTagger::english();
+        let tags: Vec<String> = tagger.tag(&["A", "Well-Known", "Singer"]).into_iter().map(|t| t.tag).collect();
+        assert_eq!(tags[1], "JJ");
+    }
+    #[test]
+    fn test_beam() {
+        let words = ["I", "saw", "her", "duck", "."];
+        let tags = |tagger: PerceptronTagger| tagger.tag(&words).into_iter().map(|t| t.tag).collect::<Vec<_>>();
+        assert_eq!(tags(PerceptronTagger::english()), ["PRP", "VBD", "PRP", "NN", "."]);
+        // "her" fits "duck" better as a possessive
+        let beam = PerceptronTagger::english().with_options(TaggerOptions { beam_width: 8, ..Default::default() });
+        assert_eq!(tags(beam), ["PRP", "VBD", "PRP$", "NN", "."]);
+        let beam = PerceptronTagger::english().with_options(TaggerOptions { beam_width: 8, ..Default::default() });
+        assert!(beam.tag(&[]).is_empty());
+    }
+    #[test]
+    fn test_confidence() {
+        let options = TaggerOptions { min_confidence: 0.9, ..Default::default() };
+        let tagger = PerceptronTagger::english().with_options(options);
+        let tags = tagger.tag(&["I", "saw", "her", "duck", "."]);
+        assert!(tags.iter().all(|t| (0.0..=1.0).contains(&t.conf)));
+        // "her" could be a pronoun or a possessive
+        assert!(tags[2].conf < 0.9 && tags[2].uncertain);
+        assert!(!tags[1].uncertain && !tags[3].uncertain);
+        assert!(!PerceptronTagger::english().tag(&["I", "saw", "her", "duck", "."])[2].uncertain);
+    }
+
+    #[test]
+    fn test_case_features() {
+        let words = ["NASA", "Launches", "new", "iPhone", "42"];
+        let context = PerceptronTagger::context(&words);
+        let features = |i: usize| PerceptronTagger::get_features(i + 2, &words, &context, "-START-", "-START2-");
+        assert!(features(0).contains_key("i case upper"));
+        assert!(features(0).contains_key("i case+first upper"));
+        assert!(features(0).contains_key("i+1 case title"));
+        assert!(!features(0).keys().any(|f| f.starts_with("i-1 case")));
+        assert!(features(2).contains_key("i case lower"));
+        assert!(features(3).contains_key("i case mixed"));
+        assert!(!features(4).keys().any(|f| f.starts_with("i case")));
+
+        // Unseen words are told apart by their case alone
+        let corpus = [
+            "the/DT BLORF/NNP arrived/VBD ./.",
+            "the/DT zib/NN arrived/VBD ./.",
+            "a/DT QUUX/NNP left/VBD ./.",
+            "a/DT fep/NN left/VBD ./.",
+        ];
+        let sentences: Vec<_> = corpus.iter().map(|s| sentence(s)).collect();
+        let mut tagger = PerceptronTagger::new("{}", "", "{}");
+        tagger.train(&sentences, 10);
+        let tags: Vec<String> = tagger.tag(&["the", "WUG", "left", "."]).into_iter().map(|t| t.tag).collect();
+        assert_eq!(tags[1], "NNP");
+        let tags: Vec<String> = tagger.tag(&["the", "wug", "left", "."]).into_iter().map(|t| t.tag).collect();
+        assert_eq!(tags[1], "NN");
+
+        // The bundled model, without them, reads an all-caps heading in lowercase
+        let heading = ["BREAKING", "NEWS", ":", "NASA", "LAUNCHES", "NEW", "ROCKET", "FROM", "FLORIDA"];
+        let tags = PerceptronTagger::english().tag(&heading);
+        assert_eq!(tags[3].word, "NASA");
+        assert_eq!((tags[4].tag.as_str(), tags[7].tag.as_str()), ("VBZ", "IN"));
+        assert_eq!(PerceptronTagger::english().tag(&["I", "AM", "HERE"])[0].tag, "PRP");
+        assert_eq!(PerceptronTagger::english().tag(&["NASA", "launches"])[0].tag, "NNP");
+    }
+    #[test]
+    fn test_tie_break() {
+        let model = AveragedPerceptron::new(r#"{"bias": {"NN": 1.0, "JJ": 1.0, "DT": -1.0}}"#, "NN\nJJ\nDT");
+        let features = || HashMap::from([("bias".to_string(), 1)]);
+        assert_eq!(model.predict(features(), TieBreak::Python).0, "NN");
+        assert_eq!(model.predict(features(), TieBreak::ClassOrder).0, "JJ");
+    }
+
+    #[test]
+    fn test_tag_stream() {
+        let tagger = PerceptronTagger::english();
+        let text = "The old man the boat . I saw her duck near the well-known lead mine in 1999 .";
+        let words: Vec<&str> = text.split(' ').collect();
+        let whole: Vec<(String, f32)> = tagger.tag(&words).into_iter().map(|t| (t.tag, t.conf)).collect();
+        let streamed: Vec<(String, f32)> = tagger.tag_stream(text.split(' ')).map(|t| (t.tag, t.conf)).collect();
+        assert_eq!(streamed, whole);
+        assert_eq!(tagger.tag_stream(["Hi"]).count(), 1);
+        assert_eq!(tagger.tag_stream(Vec::<String>::new()).count(), 0);
+
+        let sentences = vec![words[..6].to_vec(), words[6..].to_vec()];
+        let tagged = tagger.tag_sentences(&sentences);
+        assert_eq!(tagged.len(), 2);
+        assert_eq!(tagged[1].iter().map(|t| &t.tag).collect::<Vec<_>>(), tagger.tag(&words[6..]).iter().map(|t| &t.tag).collect::<Vec<_>>());
+    }
+    #[test]
+    fn test_unambiguous() {
+        let mut tagger = PerceptronTagger::english();
+        let words = ["The", "python", "bit", "him", ".", "Run", "the", "stat", "test", "."];
+        let tags = |tagger: &PerceptronTagger| tagger.tag(&words).into_iter().map(|t| t.tag).collect::<Vec<_>>();
+        assert_eq!((tags(&tagger)[1].as_str(), tags(&tagger)[7].as_str()), ("JJ", "JJ"));
+        tagger.add_unambiguous("stat", "NN");
+
+        let path = std::env::temp_dir().join(format!("misaki_user_tags_{}.json", std::process::id()));
+        fs::write(&path, r#"{"python": "NN"}"#).unwrap();
+        tagger.load_unambiguous(&path).unwrap();
+        fs::write(&path, r#"{"python": 1}"#).unwrap();
+        assert!(matches!(tagger.load_unambiguous(&path), Err(TaggerError::Json(_))));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(tagger.load_unambiguous(&path), Err(TaggerError::Io(_))));
+
+        assert_eq!((tags(&tagger)[1].as_str(), tags(&tagger)[7].as_str()), ("NN", "NN"));
+        assert_eq!(tagger.tag(&words)[1].conf, 1.0);
+    }
+}
@@ -0,0 +1,85 @@
+//! The compact binary form of a `PerceptronTagger`, shared with `build.rs`,
+//! which converts the bundled JSON model at build time.
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Leads every encoded model; the last byte is the format version.
+pub const MAGIC: &[u8; 5] = b"MSKT\x01";
+
+pub type Weights = HashMap<String, HashMap<String, f32>>;
+
+/// A feature's template index, the rest of its name, and its weights by
+/// class index.
+type CompactFeature = (u16, String, Vec<(u16, i32)>);
+
+/// Class names and feature templates (a feature up to its last space, as
+/// "i-1 word ") are stored once and referred to by index. Weights are stored
+/// in thousandths, the precision training rounds them to.
+#[derive(Serialize, Deserialize)]
+struct Compact {
+    classes: Vec<String>,
+    templates: Vec<String>,
+    weights: Vec<CompactFeature>,
+    tags: Vec<(String, String)>,
+}
+
+/// Encodes a model, sorted so the same model always gives the same bytes.
+/// Weights for classes missing from `classes` are dropped, as they can never
+/// be predicted, and the rest are rounded to thousandths.
+pub fn encode(weights: &Weights, classes: &[String], tags: &HashMap<String, String>) -> Vec<u8> {
+    let index: HashMap<&str, u16> = classes.iter().enumerate().map(|(i, c)| (c.as_str(), i as u16)).collect();
+    let split = |feature: &str| feature.rfind(' ').map_or(0, |i| i + 1);
+    let mut templates: Vec<&str> = weights.keys().map(|f| &f[..split(f)]).collect();
+    templates.sort();
+    templates.dedup();
+    let mut weights: Vec<CompactFeature> = weights
+        .iter()
+        .map(|(feature, w)| {
+            let mut w: Vec<(u16, i32)> = w
+                .iter()
+                .filter_map(|(class, &weight)| Some((*index.get(class.as_str())?, (weight * 1000.0).round() as i32)))
+                .collect();
+            w.sort_by_key(|&(i, _)| i);
+            let (template, value) = feature.split_at(split(feature));
+            let template = templates.binary_search(&template).unwrap() as u16;
+            (template, value.to_string(), w)
+        })
+        .collect();
+    weights.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+    let mut tags: Vec<(String, String)> = tags.iter().map(|(w, t)| (w.clone(), t.clone())).collect();
+    tags.sort();
+
+    let mut bytes = MAGIC.to_vec();
+    bincode::DefaultOptions::new()
+        .serialize_into(
+            &mut bytes,
+            &Compact {
+                classes: classes.to_vec(),
+                templates: templates.into_iter().map(String::from).collect(),
+                weights,
+                tags,
+            },
+        )
+        .expect("tagger model serializes");
+    bytes
+}
+
+/// Decodes the bytes after `MAGIC` into weights, classes and the tag
+/// dictionary.
+pub fn decode(bytes: &[u8]) -> bincode::Result<(Weights, Vec<String>, HashMap<String, String>)> {
+    let compact: Compact = bincode::DefaultOptions::new().deserialize(bytes)?;
+    let lookup = |table: &[String], i: u16| {
+        table.get(i as usize).cloned().ok_or_else(|| Box::new(bincode::ErrorKind::Custom(format!("index {} out of range", i))))
+    };
+    let mut weights = Weights::with_capacity(compact.weights.len());
+    for (template, value, w) in compact.weights {
+        let w = w
+            .into_iter()
+            .map(|(i, weight)| Ok((lookup(&compact.classes, i)?, weight as f32 / 1000.0)))
+            .collect::<bincode::Result<_>>()?;
+        weights.insert(lookup(&compact.templates, template)? + &value, w);
+    }
+    Ok((weights, compact.classes, compact.tags.into_iter().collect()))
+}
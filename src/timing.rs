@@ -0,0 +1,106 @@
+use crate::token::MToken;
+
+// Average phoneme durations in seconds at speaking rate 1.0
+const VOWEL: f64 = 0.09;
+const LONG: f64 = 0.05;
+const STOP: f64 = 0.065;
+const AFFRICATE: f64 = 0.1;
+const FRICATIVE: f64 = 0.085;
+const SONORANT: f64 = 0.06;
+const OTHER: f64 = 0.08;
+
+// Pauses in seconds after punctuation
+const SHORT_PAUSE: f64 = 0.25;
+const DASH_PAUSE: f64 = 0.35;
+const LONG_PAUSE: f64 = 0.5;
+pub const PARAGRAPH_PAUSE: f64 = 0.8;
+
+/// Rough spoken duration of one phoneme in seconds, at speaking rate 1.0.
+/// Stress marks, ties and whitespace take no time, and `ː` lengthens the
+/// vowel before it.
+pub fn phoneme_duration(c: char) -> f64 {
+    match c {
+        'ˈ' | 'ˌ' | '\u{200D}' => 0.0,
+        'ː' => LONG,
+        // Diphthongs written as single letters ("O" for oʊ) take two slots
+        'A' | 'I' | 'O' | 'Q' | 'W' | 'Y' => 2.0 * VOWEL,
+        'a' | 'e' | 'i' | 'o' | 'u' | 'æ' | 'ɑ' | 'ɐ' | 'ɒ' | 'ɔ' | 'ə' | 'ɚ' | 'ɛ' | 'ɜ' | 'ɝ' | 'ɪ' | 'ʊ' | 'ʌ'
+        | 'ᵻ' | 'ᵊ' => VOWEL,
+        'p' | 'b' | 't' | 'd' | 'k' | 'g' | 'ɡ' | 'ʔ' | 'ɾ' => STOP,
+        'ʧ' | 'ʤ' => AFFRICATE,
+        'f' | 'v' | 'θ' | 'ð' | 's' | 'z' | 'ʃ' | 'ʒ' | 'h' | 'x' | 'ç' => FRICATIVE,
+        'm' | 'n' | 'ŋ' | 'l' | 'ɫ' | 'ɹ' | 'r' | 'w' | 'j' => SONORANT,
+        c if c.is_whitespace() || c.is_ascii_punctuation() || "—–…“”«»¡¿".contains(c) => 0.0,
+        _ => OTHER,
+    }
+}
+
+/// Silence after a token, from the punctuation ending its text. Points
+/// inside a word ("3.14", "U.S.") aren't pauses.
+pub fn pause_after(text: &str) -> f64 {
+    let body = text.trim_end_matches(|c: char| !c.is_alphanumeric());
+    if body.contains('.') {
+        return 0.0;
+    }
+    text[body.len()..]
+        .chars()
+        .map(|c| match c {
+            '.' | '!' | '?' | '…' => LONG_PAUSE,
+            '—' | '–' => DASH_PAUSE,
+            ',' | ';' | ':' => SHORT_PAUSE,
+            _ => 0.0,
+        })
+        .fold(0.0, f64::max)
+}
+
+/// Fills `start_ts`/`end_ts` (seconds from the start of the text) with
+/// estimates from average phoneme durations and punctuation pauses (or
+/// `MToken::pause`, where set). A `rate`
+/// of 2.0 speaks twice as fast as 1.0. Rough, but enough to sync captions or
+/// highlight words without a forced aligner.
+pub fn estimate_timestamps(tokens: &mut [MToken], rate: f64) {
+    let rate = if rate > 0.0 { rate } else { 1.0 };
+    let mut t = 0.0;
+    for tk in tokens {
+        let spoken: f64 = tk.phonemes.as_deref().unwrap_or("").chars().map(phoneme_duration).sum();
+        tk.start_ts = Some(t);
+        t += spoken / rate;
+        tk.end_ts = Some(t);
+        t += tk.pause.unwrap_or_else(|| pause_after(&tk.text)) / rate;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_timestamps() {
+        let mut tokens = [
+            MToken::read_as("Hello", "həlˈO"),
+            MToken::read_as(",", ","),
+            MToken::read_as("world", "wˈɜɹld"),
+        ];
+        estimate_timestamps(&mut tokens, 1.0);
+        let hello = (tokens[0].start_ts.unwrap(), tokens[0].end_ts.unwrap());
+        assert_eq!(hello.0, 0.0);
+        assert!((hello.1 - (FRICATIVE + VOWEL + SONORANT + 2.0 * VOWEL)).abs() < 1e-9);
+        // The comma takes no time itself but delays the next word
+        assert_eq!(tokens[1].start_ts, tokens[1].end_ts);
+        assert!((tokens[2].start_ts.unwrap() - hello.1 - SHORT_PAUSE).abs() < 1e-9);
+
+        let end = tokens[2].end_ts.unwrap();
+        estimate_timestamps(&mut tokens, 2.0);
+        assert!((tokens[2].end_ts.unwrap() - end / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pause_after() {
+        assert_eq!(pause_after("."), LONG_PAUSE);
+        assert_eq!(pause_after("end."), LONG_PAUSE);
+        assert_eq!(pause_after("—"), DASH_PAUSE);
+        assert_eq!(pause_after("3.14"), 0.0);
+        assert_eq!(pause_after("U.S."), 0.0);
+        assert_eq!(pause_after("e-mail"), 0.0);
+    }
+}
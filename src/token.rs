@@ -1,6 +1,9 @@
+use crate::align;
+use crate::lexicon::Rating;
+use crate::stress::Stress;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MToken {
     pub text: String,
     pub tag: String,
@@ -8,19 +11,63 @@ pub struct MToken {
     pub phonemes: Option<String>,
     pub start_ts: Option<f64>,
     pub end_ts: Option<f64>,
+    /// Char offsets `(start, end)` of the token in the preprocessed text (see
+    /// `G2P::preprocess`).
+    #[serde(default)]
+    pub char_span: (usize, usize),
+    /// Seconds of silence after the token, set with `G2POptions::pause_markers`.
+    #[serde(default)]
+    pub pause: Option<f64>,
     #[serde(rename = "_")]
     pub underscore: Option<Underscore>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// How a sentence ends, for the pitch contour of its last words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Intonation {
+    Statement,
+    Question,
+    Exclamation,
+}
+
+impl Intonation {
+    /// From a sentence's final punctuation: any `?` makes a question ("?!"
+    /// included), otherwise any `!` an exclamation.
+    pub fn from_punctuation(text: &str) -> Intonation {
+        if text.contains(['?', '¿']) {
+            Intonation::Question
+        } else if text.contains(['!', '¡']) {
+            Intonation::Exclamation
+        } else {
+            Intonation::Statement
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Underscore {
     pub is_head: bool,
     pub alias: Option<String>,
-    pub stress: Option<f64>,
+    pub stress: Option<Stress>,
+    /// Currency symbol read together with this number token ("$" for "$5").
     pub currency: Option<String>,
+    /// Forces how a number token is read. Each character is a flag:
+    /// `d` digits, `o` ordinal, `y` year, `c` plain cardinal, `$` currency
+    /// (using `currency`, or dollars if unset). Set by the `[text](#flags#)`
+    /// link syntax or by heuristics such as "$5" and "5th".
     pub num_flags: String,
     pub prespace: bool,
-    pub rating: Option<i32>,
+    /// How far to trust `phonemes`, set when G2P resolves the token.
+    pub rating: Option<Rating>,
+    /// The dictionary word an unknown word was read as, taken for a typo of
+    /// it (see `G2POptions::correct_typos`).
+    pub correction: Option<String>,
+    /// The tagger wasn't sure of `tag`, so heteronyms take their default
+    /// reading rather than the tag's.
+    pub uncertain_tag: bool,
+    /// Set on the last token of each sentence (its final punctuation, if any).
+    pub intonation: Option<Intonation>,
 }
 
 impl MToken {
@@ -32,6 +79,8 @@ impl MToken {
             phonemes: None,
             start_ts: None,
             end_ts: None,
+            char_span: (0, 0),
+            pause: None,
             underscore: Some(Underscore {
                 is_head: true,
                 num_flags: String::new(),
@@ -41,6 +90,72 @@ impl MToken {
         }
     }
 
+    /// Rebuilds the text the tokens were made from out of each token's `text`
+    /// and `whitespace`. For tokens from `G2P::g2p` that is the preprocessed
+    /// text (see `G2P::preprocess`), minus any whitespace before the first
+    /// token.
+    pub fn detokenize(tokens: &[MToken]) -> String {
+        tokens.iter().map(|tk| format!("{}{}", tk.text, tk.whitespace)).collect()
+    }
+
+    /// What follows the token's phonemes in `G2P::g2p` output: a single
+    /// space for any whitespace after it (line breaks and tabs included),
+    /// which `whitespace` keeps as written for `detokenize`.
+    pub fn phoneme_space(&self) -> &'static str {
+        if self.whitespace.is_empty() { "" } else { " " }
+    }
+
+    /// Merges the tokens of one word into a single token, like Python misaki's
+    /// `merge_tokens`. Phonemes are joined (`unk` for tokens without), with a
+    /// space before tokens marked `prespace`. The tag comes from the longest
+    /// part, uppercase letters counting double, and the rating is the worst
+    /// of the parts.
+    pub fn merge(tokens: &[MToken], unk: &str) -> MToken {
+        let (first, last) = (&tokens[0], &tokens[tokens.len() - 1]);
+        if tokens.len() == 1 {
+            return first.clone();
+        }
+        let mut phonemes = String::new();
+        for tk in tokens {
+            let ps = tk.phonemes.as_deref().unwrap_or(unk);
+            let needs_space = |c: Option<char>| c.is_some_and(|c| !c.is_whitespace());
+            if tk.underscore().prespace && needs_space(ps.chars().next()) && needs_space(phonemes.chars().last()) {
+                phonemes.push(' ');
+            }
+            phonemes.push_str(ps);
+        }
+        let stresses: Vec<Stress> = tokens.iter().filter_map(|tk| tk.underscore().stress).collect();
+        let mut num_flags: Vec<char> = tokens.iter().flat_map(|tk| tk.underscore().num_flags.chars()).collect();
+        num_flags.sort_unstable();
+        num_flags.dedup();
+        // Ties go to the first part, as with Python's `max`
+        let tag = tokens
+            .iter()
+            .rev()
+            .max_by_key(|tk| tk.text.chars().map(|c| if c.is_uppercase() { 2 } else { 1 }).sum::<usize>())
+            .map_or(String::new(), |tk| tk.tag.clone());
+
+        let mut merged = MToken::new(Self::detokenize(&tokens[..tokens.len() - 1]) + &last.text, tag, last.whitespace.clone());
+        merged.phonemes = Some(phonemes);
+        merged.start_ts = first.start_ts;
+        merged.end_ts = last.end_ts;
+        merged.char_span = (first.char_span.0, last.char_span.1);
+        let underscore = merged.underscore_mut();
+        underscore.is_head = first.underscore().is_head;
+        underscore.stress = stresses.first().copied().filter(|s| stresses.iter().all(|t| t == s));
+        underscore.currency = tokens.iter().filter_map(|tk| tk.underscore().currency.clone()).max();
+        underscore.num_flags = num_flags.into_iter().collect();
+        underscore.prespace = first.underscore().prespace;
+        underscore.rating = tokens.iter().filter_map(|tk| tk.underscore().rating).min();
+        merged
+    }
+
+    /// Fills `start_ts`/`end_ts` from external phoneme timings; see
+    /// `align::apply_alignment`.
+    pub fn apply_alignment(tokens: &mut [MToken], phoneme_timings: &[(String, f64, f64)]) {
+        align::apply_alignment(tokens, phoneme_timings);
+    }
+
     pub fn underscore_mut(&mut self) -> &mut Underscore {
         if self.underscore.is_none() {
             self.underscore = Some(Underscore::default());
@@ -57,7 +172,21 @@ impl MToken {
             num_flags: String::new(),
             prespace: false,
             rating: None,
+            correction: None,
+            uncertain_tag: false,
+            intonation: None,
         };
         self.underscore.as_ref().unwrap_or(&DEFAULT_UNDERSCORE)
     }
 }
+
+#[cfg(test)]
+impl MToken {
+    /// A noun token already read as `phonemes`, for tests of what comes after
+    /// the pipeline.
+    pub(crate) fn read_as(text: &str, phonemes: &str) -> Self {
+        let mut tk = MToken::new(text.to_string(), "NN".to_string(), " ".to_string());
+        tk.phonemes = Some(phonemes.to_string());
+        tk
+    }
+}
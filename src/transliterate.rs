@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use unicode_normalization::char::{decompose_compatible, is_combining_mark};
+
+/// Letters that don't decompose into an ASCII base plus accents.
+const SPECIAL_LETTERS: &[(char, &str)] = &[
+    ('Æ', "AE"),
+    ('æ', "ae"),
+    ('Œ', "OE"),
+    ('œ', "oe"),
+    ('ß', "ss"),
+    ('ẞ', "SS"),
+    ('Ø', "O"),
+    ('ø', "o"),
+    ('Ð', "D"),
+    ('ð', "d"),
+    ('Þ', "Th"),
+    ('þ', "th"),
+    ('Đ', "D"),
+    ('đ', "d"),
+    ('Ħ', "H"),
+    ('ħ', "h"),
+    ('ı', "i"),
+    ('ĸ', "k"),
+    ('Ł', "L"),
+    ('ł', "l"),
+    ('Ŋ', "NG"),
+    ('ŋ', "ng"),
+    ('Ŧ', "T"),
+    ('ŧ', "t"),
+    ('ƀ', "b"),
+    ('Ɓ', "B"),
+    ('Ƈ', "C"),
+    ('ƈ', "c"),
+    ('Ɗ', "D"),
+    ('Ƒ', "F"),
+    ('ƒ', "f"),
+    ('Ɠ', "G"),
+    ('Ɨ', "I"),
+    ('Ƙ', "K"),
+    ('ƙ', "k"),
+    ('ƚ', "l"),
+    ('Ɲ', "N"),
+    ('ƞ', "n"),
+    ('Ɵ', "O"),
+    ('Ƥ', "P"),
+    ('ƥ', "p"),
+    ('ƫ', "t"),
+    ('Ƭ', "T"),
+    ('ƭ', "t"),
+    ('Ʈ', "T"),
+    ('Ʋ', "V"),
+    ('Ƴ', "Y"),
+    ('ƴ', "y"),
+    ('Ƶ', "Z"),
+    ('ƶ', "z"),
+    ('Ȥ', "Z"),
+    ('ȥ', "z"),
+    ('ȴ', "l"),
+    ('ȵ', "n"),
+    ('ȶ', "t"),
+    ('ȷ', "j"),
+    ('Ⱥ', "A"),
+    ('Ȼ', "C"),
+    ('ȼ', "c"),
+    ('Ƚ', "L"),
+    ('Ⱦ', "T"),
+    ('ȿ', "s"),
+    ('ɀ', "z"),
+    ('Ƀ', "B"),
+    ('Ʉ', "U"),
+    ('Ɇ', "E"),
+    ('ɇ', "e"),
+    ('Ɉ', "J"),
+    ('ɉ', "j"),
+    ('Ɍ', "R"),
+    ('ɍ', "r"),
+    ('Ɏ', "Y"),
+    ('ɏ', "y"),
+    // Dashes read as a pause between words
+    ('—', " "),
+    ('–', " "),
+];
+
+/// Maps accented and otherwise non-ASCII Latin letters to ASCII ("é" → "e",
+/// "ß" → "ss") so words the lexicon lacks in their accented spelling can still
+/// be found. The default table covers Latin-1 Supplement and Latin Extended-A/B;
+/// `insert` adds or overrides entries.
+#[derive(Debug, Clone)]
+pub struct Transliterator {
+    table: HashMap<char, String>,
+}
+
+impl Default for Transliterator {
+    fn default() -> Self {
+        let mut table = HashMap::new();
+        // Letters with diacritics decompose into an ASCII base plus combining marks
+        for c in ('\u{00C0}'..='\u{024F}').filter(|c| c.is_alphabetic()) {
+            let mut base = String::new();
+            decompose_compatible(c, |d| {
+                if !is_combining_mark(d) {
+                    base.push(d);
+                }
+            });
+            if base.is_ascii() && !base.is_empty() {
+                table.insert(c, base);
+            }
+        }
+        for &(c, ascii) in SPECIAL_LETTERS {
+            table.insert(c, ascii.to_string());
+        }
+        Self { table }
+    }
+}
+
+impl Transliterator {
+    /// A transliterator with no entries, leaving all text unchanged.
+    pub fn empty() -> Self {
+        Self { table: HashMap::new() }
+    }
+
+    /// Adds or replaces the transliteration of `c`.
+    pub fn insert(&mut self, c: char, replacement: impl Into<String>) {
+        self.table.insert(c, replacement.into());
+    }
+
+    /// Removes the transliteration of `c`, leaving it unchanged.
+    pub fn remove(&mut self, c: char) {
+        self.table.remove(&c);
+    }
+
+    /// The transliteration of `c`, if it has one.
+    pub fn get(&self, c: char) -> Option<&str> {
+        self.table.get(&c).map(String::as_str)
+    }
+
+    /// Replaces every character that has an entry.
+    pub fn transliterate(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.table.get(&c) {
+                Some(replacement) => result.push_str(replacement),
+                None => result.push(c),
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_table() {
+        let t = Transliterator::default();
+        assert_eq!(t.transliterate("café naïve Zürich São"), "cafe naive Zurich Sao");
+        assert_eq!(t.transliterate("Łódź Ångström"), "Lodz Angstrom");
+        assert_eq!(t.transliterate("Straße Æsir smørrebrød"), "Strasse AEsir smorrebrod");
+        assert_eq!(t.transliterate("Dvořák Œuvre ĳ"), "Dvorak OEuvre ij");
+        assert_eq!(t.transliterate("北京"), "北京");
+    }
+
+    #[test]
+    fn test_custom_entries() {
+        let mut t = Transliterator::default();
+        t.insert('ø', "oe");
+        t.remove('é');
+        assert_eq!(t.transliterate("Søren café"), "Soeren café");
+
+        let mut t = Transliterator::empty();
+        assert_eq!(t.transliterate("café"), "café");
+        t.insert('é', "ay");
+        assert_eq!(t.transliterate("café"), "cafay");
+    }
+}
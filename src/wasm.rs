@@ -0,0 +1,54 @@
+//! JavaScript bindings, for phonemizing in the browser next to Kokoro's ONNX
+//! model. The lexicons and tagger are compiled in, so nothing is fetched at
+//! run time unless a page adds its own dictionary with `addDictionary`.
+//! The crate is an rlib only, so build the module as a cdylib and bind it:
+//!
+//! ```sh
+//! cargo rustc --release --lib --crate-type cdylib --target wasm32-unknown-unknown --features wasm
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/misaki_rs.wasm
+//! ```
+//!
+//! ```js
+//! import init, { G2P } from "./pkg/misaki_rs.js";
+//! await init();
+//! const g2p = new G2P("en-us");
+//! const phonemes = g2p.phonemize("Hello world");
+//! ```
+
+use crate::g2p::G2P;
+use crate::language::Language;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = G2P)]
+pub struct WasmG2P {
+    inner: G2P,
+}
+
+#[wasm_bindgen(js_class = G2P)]
+impl WasmG2P {
+    /// A G2P for a language code `Language::from_code` reads ("en-us",
+    /// "en-gb", ...).
+    #[wasm_bindgen(constructor)]
+    pub fn new(language: &str) -> Result<WasmG2P, JsError> {
+        let lang = Language::from_code(language).ok_or_else(|| JsError::new(&format!("unknown language '{}'", language)))?;
+        Ok(Self { inner: G2P::new(lang) })
+    }
+
+    /// The phonemes of `text`.
+    pub fn phonemize(&self, text: &str) -> Result<String, JsError> {
+        Ok(self.inner.g2p(text)?.0)
+    }
+
+    /// `G2P::analyze` as JSON: the phonemes with the tokens, Kokoro-sized
+    /// chunks and warnings.
+    pub fn analyze(&self, text: &str) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&self.inner.analyze(text)?)?)
+    }
+
+    /// Adds the entries of a JSON dictionary (see
+    /// `Lexicon::load_user_dictionary`), as fetched by the page.
+    #[wasm_bindgen(js_name = addDictionary)]
+    pub fn add_dictionary(&mut self, json: &str) -> Result<(), JsError> {
+        Ok(self.inner.lexicon.load_user_dictionary_json(json)?)
+    }
+}